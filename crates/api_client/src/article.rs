@@ -1,11 +1,35 @@
 use super::ApiClient;
 use crate::errors::FrontendResult;
-use http::Method;
+use chrono::{DateTime, Utc};
+use http::{Method, StatusCode};
 use ibis_database::common::{
     ResolveObjectParams,
     SuccessResponse,
-    article::{ApiConflict, Article, ArticleView, EditVersion, EditView},
-    newtypes::{ArticleId, ConflictId, InstanceId, PersonId},
+    article::{
+        AnonymousEdit,
+        ApiConflict,
+        Article,
+        ArticleList,
+        ArticleLookup,
+        ArticlePreview,
+        ArticleSort,
+        ArticleView,
+        Attachment,
+        Category,
+        CategoryView,
+        Conflict,
+        ConflictStats,
+        Contributor,
+        DuplicateArticles,
+        EditCheck,
+        EditReach,
+        EditVersion,
+        EditView,
+        ExportArticle,
+        ProtectionLevel,
+        RevisionTag,
+    },
+    newtypes::{ArticleId, AttachmentId, ConflictId, InstanceId, PersonId},
 };
 use serde::{Deserialize, Serialize};
 use url::Url;
@@ -16,6 +40,8 @@ pub struct GetArticleParams {
     pub title: Option<String>,
     pub domain: Option<String>,
     pub id: Option<ArticleId>,
+    /// If the article is a redirect, return the target article instead. Enabled by default.
+    pub follow_redirect: Option<bool>,
 }
 
 #[derive(Deserialize, Serialize, Clone, Default, Debug)]
@@ -23,6 +49,14 @@ pub struct ListArticlesParams {
     pub only_local: Option<bool>,
     pub instance_id: Option<InstanceId>,
     pub include_removed: Option<bool>,
+    /// Uses the instance default if not given
+    pub sort: Option<ArticleSort>,
+    /// Page of the results, starting at 1
+    pub page: Option<i64>,
+    /// Uses the instance default if not given, at most 200
+    pub limit: Option<i64>,
+    /// Number of articles to skip, takes precedence over `page`
+    pub offset: Option<i64>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -34,6 +68,21 @@ pub struct CreateArticleParams {
     pub instance_id: Option<InstanceId>,
 }
 
+#[derive(Deserialize, Serialize, Debug)]
+pub struct CreateRedirectParams {
+    /// Title of the redirect page. If a local article with this title exists, it is turned into a
+    /// redirect.
+    pub from_title: String,
+    pub to_article_id: ArticleId,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MoveArticleParams {
+    pub article_id: ArticleId,
+    /// New title of the article. A redirect is left behind at the old title.
+    pub new_title: String,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct EditArticleParams {
     /// Id of the article to edit
@@ -48,18 +97,22 @@ pub struct EditArticleParams {
     pub previous_version_id: EditVersion,
     /// If you are resolving a conflict, pass the id to delete conflict from the database
     pub resolve_conflict_id: Option<ConflictId>,
+    /// Mark as minor edit, like a typo fix
+    pub minor: Option<bool>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
-pub struct ProtectArticleParams {
+pub struct SetProtectionParams {
     pub article_id: ArticleId,
-    pub protected: bool,
+    pub level: ProtectionLevel,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct ForkArticleParams {
     pub article_id: ArticleId,
-    pub new_title: String,
+    /// Title for the local fork, defaults to the title of the original article. Needs to be set
+    /// if a local article with the same title already exists.
+    pub new_title: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -72,6 +125,33 @@ pub struct RemoveArticleParams {
 pub struct GetEditList {
     pub article_id: Option<ArticleId>,
     pub person_id: Option<PersonId>,
+    /// Exclude edits which are marked as minor
+    pub hide_minor: Option<bool>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GetAnonymousEdits {
+    pub article_id: ArticleId,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct GetRecentChanges {
+    pub limit: Option<i64>,
+    /// Only return edits which are newer than this
+    pub since: Option<DateTime<Utc>>,
+    /// Only include edits to articles from the local instance
+    pub only_local: Option<bool>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct GetConflictStats {
+    /// Only count conflicts created at or after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Only count conflicts created before this time
+    pub until: Option<DateTime<Utc>>,
+    /// Page of the results, starting at 1
+    pub page: Option<i64>,
+    pub limit: Option<i64>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -84,6 +164,109 @@ pub struct GetConflictParams {
     pub conflict_id: ConflictId,
 }
 
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct AddAttachmentParams {
+    pub article_id: ArticleId,
+    pub url: Url,
+    pub alt_text: Option<String>,
+    pub media_type: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct RemoveAttachmentParams {
+    pub id: AttachmentId,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct TagRevisionParams {
+    pub article_id: ArticleId,
+    pub version: EditVersion,
+    pub tag: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GetTaggedRevisions {
+    pub article_id: ArticleId,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GetEditReach {
+    pub article_id: ArticleId,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct GetPopularArticles {
+    pub limit: Option<i64>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GetBacklinks {
+    pub article_id: ArticleId,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct CompareRevisionsParams {
+    pub article_id: ArticleId,
+    pub from: EditVersion,
+    pub to: EditVersion,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct AddToCategoryParams {
+    pub article_id: ArticleId,
+    /// Name of the category, it is created if it doesn't exist yet
+    pub category: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GetCategoryParams {
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct RevertArticleParams {
+    pub article_id: ArticleId,
+    pub revert_to_version: EditVersion,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct RevertToDateParams {
+    pub article_id: ArticleId,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct ExportArticleHistory {
+    pub article_id: ArticleId,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct PreviewArticleParams {
+    pub text: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GetContributorsParams {
+    pub article_id: ArticleId,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct ExportArticleParams {
+    pub article_id: ArticleId,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct ImportArticleParams {
+    /// JSON serialized [ExportArticle]
+    pub data: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct FindDuplicates {
+    /// Minimum similarity of article text, between 0 and 1
+    pub threshold: f32,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct FollowArticleParams {
     pub id: ArticleId,
@@ -95,11 +278,36 @@ impl ApiClient {
         self.post("/api/v1/article", Some(&data)).await
     }
 
+    /// Same as [ApiClient::get_article], but if a title without domain matches articles on
+    /// multiple instances, the candidates are returned instead of an error.
+    pub async fn lookup_article(&self, data: GetArticleParams) -> FrontendResult<ArticleLookup> {
+        let (status, text, url) = self
+            .send_raw(Method::GET, "/api/v1/article", Some(data))
+            .await?;
+        // multiple choices is only used for article disambiguation
+        if status == StatusCode::MULTIPLE_CHOICES {
+            let disambiguation = serde_json::from_str(&text)?;
+            return Ok(ArticleLookup::Disambiguation(disambiguation));
+        }
+        Self::response(status, text, &url).map(|a| ArticleLookup::Article(Box::new(a)))
+    }
+
     pub async fn get_article(&self, data: GetArticleParams) -> FrontendResult<ArticleView> {
         self.send(Method::GET, "/api/v1/article", Some(data)).await
     }
 
-    pub async fn list_articles(&self, data: ListArticlesParams) -> FrontendResult<Vec<Article>> {
+    pub async fn create_redirect(
+        &self,
+        params: &CreateRedirectParams,
+    ) -> FrontendResult<ArticleView> {
+        self.post("/api/v1/article/redirect", Some(params)).await
+    }
+
+    pub async fn move_article(&self, params: &MoveArticleParams) -> FrontendResult<ArticleView> {
+        self.post("/api/v1/article/move", Some(params)).await
+    }
+
+    pub async fn list_articles(&self, data: ListArticlesParams) -> FrontendResult<ArticleList> {
         self.get("/api/v1/article/list", Some(data)).await
     }
 
@@ -110,11 +318,42 @@ impl ApiClient {
         self.patch("/api/v1/article", Some(&params)).await
     }
 
+    /// Run all checks for an edit without submitting it
+    pub async fn validate_edit(
+        &self,
+        params: &EditArticleParams,
+    ) -> FrontendResult<Vec<EditCheck>> {
+        self.post("/api/v1/article/validate_edit", Some(params))
+            .await
+    }
+
+    /// Render the text in the same way as a saved article, without storing anything
+    pub async fn preview_article(&self, text: String) -> FrontendResult<ArticlePreview> {
+        let params = PreviewArticleParams { text };
+        self.post("/api/v1/article/preview", Some(params)).await
+    }
+
     pub async fn fork_article(&self, params: &ForkArticleParams) -> FrontendResult<ArticleView> {
         self.post("/api/v1/article/fork", Some(params)).await
     }
 
-    pub async fn protect_article(&self, params: &ProtectArticleParams) -> FrontendResult<Article> {
+    /// Restore the article text as it was at the given time
+    pub async fn revert_article(
+        &self,
+        params: &RevertArticleParams,
+    ) -> FrontendResult<ArticleView> {
+        self.post("/api/v1/article/revert", Some(params)).await
+    }
+
+    pub async fn revert_article_to_date(
+        &self,
+        params: &RevertToDateParams,
+    ) -> FrontendResult<ArticleView> {
+        self.post("/api/v1/article/revert_to_date", Some(params))
+            .await
+    }
+
+    pub async fn protect_article(&self, params: &SetProtectionParams) -> FrontendResult<Article> {
         self.post("/api/v1/article/protect", Some(params)).await
     }
 
@@ -133,16 +372,54 @@ impl ApiClient {
             .await
     }
 
+    pub async fn list_edits(&self, params: &GetEditList) -> FrontendResult<Vec<EditView>> {
+        self.get("/api/v1/edit/list", Some(params)).await
+    }
+
+    /// Edits of the article made without login, with the identifier of the anonymous
+    /// contributor. Only for moderators.
+    pub async fn get_anonymous_edits(
+        &self,
+        article_id: ArticleId,
+    ) -> FrontendResult<Vec<AnonymousEdit>> {
+        let params = GetAnonymousEdits { article_id };
+        self.get("/api/v1/edit/anonymous", Some(params)).await
+    }
+
+    /// Recent edits across all articles, newest first
+    pub async fn get_recent_changes(
+        &self,
+        params: &GetRecentChanges,
+    ) -> FrontendResult<Vec<EditView>> {
+        self.get("/api/v1/edit/recent", Some(params)).await
+    }
+
     pub async fn remove_article(&self, article_id: ArticleId, remove: bool) -> FrontendResult<()> {
         let params = RemoveArticleParams { article_id, remove };
         self.post("/api/v1/article/remove", Some(&params)).await
     }
 
+    /// Removed articles, most recently removed first. Only for moderators.
+    pub async fn list_removed_articles(&self) -> FrontendResult<Vec<Article>> {
+        self.get("/api/v1/article/removed", None::<()>).await
+    }
+
     pub async fn get_conflict(&self, conflict_id: ConflictId) -> FrontendResult<ApiConflict> {
         let params = GetConflictParams { conflict_id };
         self.get("/api/v1/conflict", Some(params)).await
     }
 
+    pub async fn list_conflicts(&self) -> FrontendResult<Vec<Conflict>> {
+        self.get("/api/v1/conflict/list", None::<()>).await
+    }
+
+    pub async fn get_conflict_stats(
+        &self,
+        params: &GetConflictStats,
+    ) -> FrontendResult<Vec<ConflictStats>> {
+        self.get("/api/v1/conflict/stats", Some(params)).await
+    }
+
     pub async fn delete_conflict(&self, conflict_id: ConflictId) -> FrontendResult<()> {
         let params = DeleteConflictParams { conflict_id };
         self.send(Method::DELETE, "/api/v1/conflict", Some(params))
@@ -158,6 +435,92 @@ impl ApiClient {
         self.post("/api/v1/article/follow", Some(params)).await
     }
 
+    pub async fn add_attachment(&self, params: &AddAttachmentParams) -> FrontendResult<Attachment> {
+        self.post("/api/v1/article/attachment", Some(params)).await
+    }
+
+    pub async fn remove_attachment(
+        &self,
+        params: &RemoveAttachmentParams,
+    ) -> FrontendResult<SuccessResponse> {
+        self.send(Method::DELETE, "/api/v1/article/attachment", Some(params))
+            .await
+    }
+
+    /// Mark a version of the article with a named tag, eg `v1.0` or `reviewed`
+    pub async fn tag_revision(&self, params: &TagRevisionParams) -> FrontendResult<RevisionTag> {
+        self.post("/api/v1/article/tag", Some(params)).await
+    }
+
+    pub async fn get_tagged_revisions(
+        &self,
+        article_id: ArticleId,
+    ) -> FrontendResult<Vec<RevisionTag>> {
+        let params = GetTaggedRevisions { article_id };
+        self.get("/api/v1/article/tags", Some(params)).await
+    }
+
+    pub async fn get_edit_reach(&self, params: &GetEditReach) -> FrontendResult<EditReach> {
+        self.get("/api/v1/article/edit_reach", Some(params)).await
+    }
+
+    /// Most viewed articles, most views first
+    pub async fn get_popular_articles(
+        &self,
+        params: &GetPopularArticles,
+    ) -> FrontendResult<Vec<Article>> {
+        self.get("/api/v1/article/popular", Some(params)).await
+    }
+
+    pub async fn get_backlinks(&self, params: &GetBacklinks) -> FrontendResult<Vec<Article>> {
+        self.get("/api/v1/article/backlinks", Some(params)).await
+    }
+
+    /// Unified diff of the article text between two versions
+    pub async fn compare_revisions(
+        &self,
+        params: &CompareRevisionsParams,
+    ) -> FrontendResult<String> {
+        self.get("/api/v1/article/compare", Some(params)).await
+    }
+
+    /// Users who edited the article, with most edits first
+    pub async fn get_contributors(
+        &self,
+        article_id: ArticleId,
+    ) -> FrontendResult<Vec<Contributor>> {
+        let params = GetContributorsParams { article_id };
+        self.get("/api/v1/article/contributors", Some(params)).await
+    }
+
+    /// Article with full edit history as JSON, which can be imported on another instance
+    pub async fn export_article(&self, article_id: ArticleId) -> FrontendResult<ExportArticle> {
+        let params = ExportArticleParams { article_id };
+        self.get("/api/v1/article/export", Some(params)).await
+    }
+
+    pub async fn import_article(&self, data: &ExportArticle) -> FrontendResult<ArticleView> {
+        let params = ImportArticleParams {
+            data: serde_json::to_string(data)?,
+        };
+        self.post("/api/v1/article/import", Some(params)).await
+    }
+
+    pub async fn add_to_category(&self, params: &AddToCategoryParams) -> FrontendResult<Category> {
+        self.post("/api/v1/article/category", Some(params)).await
+    }
+
+    pub async fn get_category(&self, params: &GetCategoryParams) -> FrontendResult<CategoryView> {
+        self.get("/api/v1/category", Some(params)).await
+    }
+
+    pub async fn find_duplicates(
+        &self,
+        params: &FindDuplicates,
+    ) -> FrontendResult<Vec<DuplicateArticles>> {
+        self.get("/api/v1/article/duplicates", Some(params)).await
+    }
+
     #[cfg(debug_assertions)]
     pub async fn edit_article_without_conflict(
         &self,
@@ -174,6 +537,7 @@ impl ApiClient {
             title: None,
             domain: None,
             id: Some(params.article_id),
+            follow_redirect: Some(false),
         })
         .await
         .ok()
@@ -11,15 +11,33 @@ use std::{
 pub type FrontendResult<T> = Result<T, FrontendError>;
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
-pub struct FrontendError(String);
+pub struct FrontendError {
+    message: String,
+    not_found: bool,
+}
 
 impl FrontendError {
     pub fn new(message: impl Into<String>) -> Self {
-        Self(message.into())
+        Self {
+            message: message.into(),
+            not_found: false,
+        }
+    }
+
+    /// Error for an object which doesn't exist, as returned by the api with status 404
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            not_found: true,
+        }
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        self.not_found
     }
 
     pub fn message(self) -> String {
-        self.0
+        self.message
     }
 }
 
@@ -39,7 +57,7 @@ impl<T> FrontendResultExt<T> for FrontendResult<T> {
             Ok(o) => on_success(o),
             Err(e) => {
                 warn!("{e}");
-                ErrorPopup::set(e.0);
+                ErrorPopup::set(e.message);
             }
         }
     }
@@ -74,7 +92,7 @@ impl ErrorPopup {
 
 impl Display for FrontendError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+        write!(f, "{}", self.message)
     }
 }
 
@@ -83,36 +101,42 @@ impl Error for FrontendError {}
 #[cfg(feature = "ssr")]
 impl From<reqwest::Error> for FrontendError {
     fn from(value: reqwest::Error) -> Self {
-        Self(value.to_string())
+        Self::new(value.to_string())
     }
 }
 #[cfg(not(feature = "ssr"))]
 impl From<gloo_net::Error> for FrontendError {
     fn from(value: gloo_net::Error) -> Self {
-        Self(value.to_string())
+        Self::new(value.to_string())
     }
 }
 
 impl From<url::ParseError> for FrontendError {
     fn from(value: url::ParseError) -> Self {
-        Self(value.to_string())
+        Self::new(value.to_string())
     }
 }
 
 impl From<serde_urlencoded::ser::Error> for FrontendError {
     fn from(value: serde_urlencoded::ser::Error) -> Self {
-        Self(value.to_string())
+        Self::new(value.to_string())
+    }
+}
+
+impl From<serde_json::Error> for FrontendError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::new(value.to_string())
     }
 }
 
 impl From<ParseIntError> for FrontendError {
     fn from(value: ParseIntError) -> Self {
-        Self(value.to_string())
+        Self::new(value.to_string())
     }
 }
 
 impl From<JsValue> for FrontendError {
     fn from(value: JsValue) -> Self {
-        Self(format!("{value:?}"))
+        Self::new(format!("{value:?}"))
     }
 }
@@ -1,12 +1,23 @@
 use super::ApiClient;
 use crate::errors::FrontendResult;
+use chrono::{DateTime, Utc};
 use http::Method;
 use ibis_database::common::{
     ResolveObjectParams,
     SuccessResponse,
-    article::Article,
-    instance::{Instance, InstanceView, SiteView},
-    newtypes::InstanceId,
+    article::{EditView, SearchMode, SearchResult},
+    instance::{
+        AllowedInstance,
+        BlockedInstance,
+        FederationStats,
+        InboundActivity,
+        Instance,
+        InstanceFollowers,
+        InstanceView,
+        QueuedDelivery,
+        SiteView,
+    },
+    newtypes::{InboundActivityId, InstanceId, QueuedDeliveryId},
 };
 use serde::{Deserialize, Serialize};
 use url::Url;
@@ -14,6 +25,10 @@ use url::Url;
 #[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct SearchArticleParams {
     pub query: String,
+    /// Searches title and body if not given
+    pub search_mode: Option<SearchMode>,
+    /// If nothing is found, return articles with a similar title instead
+    pub fuzzy: Option<bool>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -22,6 +37,15 @@ pub struct GetInstanceParams {
     pub hostname: Option<String>,
 }
 
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GetInstanceFollowers {
+    pub instance_id: InstanceId,
+    /// At most 50
+    pub limit: Option<i64>,
+    /// Number of followers to skip
+    pub offset: Option<i64>,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct FollowInstanceParams {
     pub id: InstanceId,
@@ -32,6 +56,56 @@ pub struct FollowInstanceParams {
 pub struct UpdateInstanceParams {
     pub name: Option<String>,
     pub topic: Option<String>,
+    pub icon_url: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct SetRegistrationOpenParams {
+    pub open: bool,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct GetFederationStats {
+    /// Page of the per-instance delivery status, starting at 1
+    pub page: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GetInstanceActivity {
+    pub instance_id: InstanceId,
+    pub limit: Option<i64>,
+    /// Only return activity older than this, for pagination
+    pub before: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct ReplayDeadLetterParams {
+    pub id: InboundActivityId,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct RetryDeliveryParams {
+    pub id: QueuedDeliveryId,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct SyncInstanceParams {
+    pub instance_id: InstanceId,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct BlockInstanceParams {
+    pub domain: String,
+    /// Set to false to unblock the domain again
+    pub block: bool,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct AllowInstanceParams {
+    pub domain: String,
+    /// Set to false to remove the domain from the allowlist again
+    pub allow: bool,
 }
 
 impl ApiClient {
@@ -39,6 +113,13 @@ impl ApiClient {
         self.get("/api/v1/instance", Some(&params)).await
     }
 
+    pub async fn get_instance_followers(
+        &self,
+        params: &GetInstanceFollowers,
+    ) -> FrontendResult<InstanceFollowers> {
+        self.get("/api/v1/instance/followers", Some(params)).await
+    }
+
     pub async fn list_instances(&self) -> FrontendResult<Vec<InstanceView>> {
         self.get("/api/v1/instance/list", None::<i32>).await
     }
@@ -50,7 +131,7 @@ impl ApiClient {
         self.patch("/api/v1/instance", Some(params)).await
     }
 
-    pub async fn search(&self, params: &SearchArticleParams) -> FrontendResult<Vec<Article>> {
+    pub async fn search(&self, params: &SearchArticleParams) -> FrontendResult<Vec<SearchResult>> {
         self.send(Method::GET, "/api/v1/search", Some(params)).await
     }
 
@@ -73,6 +154,83 @@ impl ApiClient {
         self.post("/api/v1/instance/follow", Some(params)).await
     }
 
+    pub async fn federation_stats(
+        &self,
+        params: &GetFederationStats,
+    ) -> FrontendResult<FederationStats> {
+        self.get("/api/v1/instance/federation_stats", Some(params))
+            .await
+    }
+
+    /// Recent edits received from the given instance
+    pub async fn get_instance_activity(
+        &self,
+        params: &GetInstanceActivity,
+    ) -> FrontendResult<Vec<EditView>> {
+        self.get("/api/v1/instance/activity", Some(params)).await
+    }
+
+    pub async fn list_dead_letters(&self) -> FrontendResult<Vec<InboundActivity>> {
+        self.get("/api/v1/instance/dead_letters", None::<()>).await
+    }
+
+    pub async fn replay_dead_letter(
+        &self,
+        params: &ReplayDeadLetterParams,
+    ) -> FrontendResult<SuccessResponse> {
+        self.post("/api/v1/instance/dead_letters/replay", Some(params))
+            .await
+    }
+
+    pub async fn list_delivery_queue(&self) -> FrontendResult<Vec<QueuedDelivery>> {
+        self.get("/api/v1/instance/delivery_queue", None::<()>)
+            .await
+    }
+
+    pub async fn retry_delivery(
+        &self,
+        params: &RetryDeliveryParams,
+    ) -> FrontendResult<SuccessResponse> {
+        self.post("/api/v1/instance/delivery_queue/retry", Some(params))
+            .await
+    }
+
+    pub async fn sync_instance(
+        &self,
+        params: &SyncInstanceParams,
+    ) -> FrontendResult<SuccessResponse> {
+        self.post("/api/v1/instance/sync", Some(params)).await
+    }
+
+    pub async fn block_instance(
+        &self,
+        params: &BlockInstanceParams,
+    ) -> FrontendResult<SuccessResponse> {
+        self.post("/api/v1/instance/block", Some(params)).await
+    }
+
+    pub async fn list_blocked_instances(&self) -> FrontendResult<Vec<BlockedInstance>> {
+        self.get("/api/v1/instance/blocked", None::<()>).await
+    }
+
+    pub async fn allow_instance(
+        &self,
+        params: &AllowInstanceParams,
+    ) -> FrontendResult<SuccessResponse> {
+        self.post("/api/v1/instance/allow", Some(params)).await
+    }
+
+    pub async fn list_allowed_instances(&self) -> FrontendResult<Vec<AllowedInstance>> {
+        self.get("/api/v1/instance/allowed", None::<()>).await
+    }
+
+    /// Open or close registration of new accounts, only for admin
+    pub async fn set_registration_open(&self, open: bool) -> FrontendResult<Instance> {
+        let params = SetRegistrationOpenParams { open };
+        self.post("/api/v1/instance/registration", Some(params))
+            .await
+    }
+
     pub async fn site(&self) -> FrontendResult<SiteView> {
         self.get("/api/v1/site", None::<()>).await
     }
@@ -70,6 +70,21 @@ impl ApiClient {
     where
         P: Serialize + Debug,
         T: for<'de> Deserialize<'de>,
+    {
+        let (status, text, url) = self.send_raw(method, path, params).await?;
+        Self::response(status, text, &url)
+    }
+
+    /// Send the request and return status, body and url, without checking the status
+    #[cfg(feature = "ssr")]
+    async fn send_raw<P>(
+        &self,
+        method: Method,
+        path: &str,
+        params: Option<P>,
+    ) -> FrontendResult<(u16, String, String)>
+    where
+        P: Serialize + Debug,
     {
         use ibis_database::common::{AUTH_COOKIE, Auth};
         use leptos::prelude::use_context;
@@ -91,7 +106,7 @@ impl ApiClient {
         let status = res.status();
         let url = res.url().to_string();
         let text = res.text().await?.to_string();
-        Self::response(status.into(), text, &url)
+        Ok((status.into(), text, url))
     }
 
     #[cfg(not(feature = "ssr"))]
@@ -104,6 +119,24 @@ impl ApiClient {
     where
         P: Serialize + Debug + 'a,
         T: for<'de> Deserialize<'de>,
+    {
+        let res = self.send_raw(method, path, params);
+        async move {
+            let (status, text, url) = res.await?;
+            Self::response(status, text, &url)
+        }
+    }
+
+    /// Send the request and return status, body and url, without checking the status
+    #[cfg(not(feature = "ssr"))]
+    fn send_raw<'a, P>(
+        &'a self,
+        method: Method,
+        path: &'a str,
+        params: Option<P>,
+    ) -> impl std::future::Future<Output = FrontendResult<(u16, String, String)>> + Send + 'a
+    where
+        P: Serialize + Debug + 'a,
     {
         use gloo_net::http::*;
         use leptos::{prelude::on_cleanup, web_sys};
@@ -145,7 +178,7 @@ impl ApiClient {
             let res = req.send().await?;
             let status = res.status();
             let text = res.text().await?;
-            Self::response(status, text, &res.url())
+            Ok((status, text, res.url()))
         })
     }
 
@@ -153,6 +186,9 @@ impl ApiClient {
     where
         T: for<'de> Deserialize<'de>,
     {
+        if status == StatusCode::NOT_FOUND {
+            return Err(FrontendError::not_found(text));
+        }
         let json = serde_json::from_str(&text).map_err(|e| {
             info!(r#"Failed to deserialize api response: "{e}" from "{text}" on "{url}""#);
             FrontendError::new(&text)
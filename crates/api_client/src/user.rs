@@ -1,11 +1,12 @@
 use super::ApiClient;
 use crate::{article::GetEditList, errors::FrontendResult};
+use chrono::{DateTime, Utc};
 use ibis_database::common::{
     SuccessResponse,
-    article::EditView,
+    article::{Article, EditView},
     instance::InstanceFollow,
     newtypes::PersonId,
-    user::{LocalUserView, Person},
+    user::{LocalUser, LocalUserView, Person, UserRole},
 };
 use serde::{Deserialize, Serialize};
 use url::Url;
@@ -18,7 +19,7 @@ pub struct RegisterUserParams {
     pub confirm_password: String,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct LoginUserParams {
     pub username_or_email: String,
     pub password: String,
@@ -30,6 +31,26 @@ pub struct GetUserParams {
     pub domain: Option<String>,
 }
 
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GetUserArticles {
+    pub name: String,
+    pub domain: Option<String>,
+    /// Page of the results, starting at 1
+    pub page: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+/// Either `person_id` or `name` must be provided
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct GetUserContributions {
+    pub person_id: Option<PersonId>,
+    pub name: Option<String>,
+    pub domain: Option<String>,
+    /// Page of the results, starting at 1
+    pub page: Option<i64>,
+    pub limit: Option<i64>,
+}
+
 #[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct UpdateUserParams {
     pub display_name: Option<String>,
@@ -88,6 +109,21 @@ pub struct ChangePasswordAfterReset {
     pub confirm_password: String,
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct BanUserParams {
+    pub person_id: PersonId,
+    /// Set to false to remove the ban
+    pub ban: bool,
+    /// End of a temporary ban, permanent if not given
+    pub expires: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SetUserRoleParams {
+    pub person_id: PersonId,
+    pub role: UserRole,
+}
+
 impl ApiClient {
     pub async fn register(
         &self,
@@ -104,14 +140,48 @@ impl ApiClient {
         self.post("/api/v1/account/logout", None::<()>).await
     }
 
+    pub async fn logout_all(&self) -> FrontendResult<SuccessResponse> {
+        self.post("/api/v1/account/logout_all", None::<()>).await
+    }
+
     pub async fn get_user(&self, data: GetUserParams) -> FrontendResult<Person> {
         self.get("/api/v1/user", Some(data)).await
     }
 
+    /// Articles which were originally created by the user
+    pub async fn get_user_articles(&self, data: GetUserArticles) -> FrontendResult<Vec<Article>> {
+        self.get("/api/v1/user/articles", Some(data)).await
+    }
+
+    /// Edits made by the user across all articles, newest first
+    pub async fn get_user_contributions(
+        &self,
+        data: &GetUserContributions,
+    ) -> FrontendResult<Vec<EditView>> {
+        self.get("/api/v1/user/contributions", Some(data)).await
+    }
+
+    pub async fn set_user_role(&self, params: SetUserRoleParams) -> FrontendResult<LocalUser> {
+        self.post("/api/v1/user/role", Some(params)).await
+    }
+
+    /// Prevent the user from editing articles, only for moderators
+    pub async fn ban_user(&self, params: BanUserParams) -> FrontendResult<Person> {
+        self.post("/api/v1/user/ban", Some(params)).await
+    }
+
+    pub async fn list_admins(&self) -> FrontendResult<Vec<Person>> {
+        self.get("/api/v1/user/admins", None::<()>).await
+    }
+
     pub async fn get_follows(&self) -> FrontendResult<Vec<InstanceFollow>> {
         self.get("/api/v1/user/follows", None::<()>).await
     }
 
+    pub async fn get_watchlist(&self) -> FrontendResult<Vec<Article>> {
+        self.get("/api/v1/user/watchlist", None::<()>).await
+    }
+
     pub async fn update_user_profile(
         &self,
         data: UpdateUserParams,
@@ -1,47 +1,127 @@
-use super::{UserExt, check_is_admin};
+use super::{UserExt, check_is_admin, check_is_moderator};
 use crate::{
     api::UserExtOpt,
-    utils::{generate_article_ap_id, generate_article_version},
+    utils::{
+        client_ip,
+        diff_article_versions,
+        find_similar_texts,
+        generate_article_ap_id,
+        generate_article_version,
+        git_author,
+        git_fast_import_commit,
+        git_file_name,
+    },
 };
 use activitypub_federation::{config::Data, fetch::object_id::ObjectId};
 use anyhow::anyhow;
-use axum::{Form, Json, extract::Query};
+use axum::{
+    Form,
+    Json,
+    body::Body,
+    extract::{ConnectInfo, Query},
+    http::{
+        HeaderMap,
+        StatusCode,
+        header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+    },
+    response::{
+        IntoResponse,
+        Response,
+        sse::{Event, Sse},
+    },
+};
 use axum_macros::debug_handler;
-use chrono::Utc;
-use diffy::{Patch, apply, create_patch, merge};
+use chrono::{Duration, Utc};
+use diffy::{Patch, apply, create_patch};
+use futures::{Stream, future, stream};
 use ibis_api_client::{
     article::{
+        AddAttachmentParams,
+        AddToCategoryParams,
+        CompareRevisionsParams,
         CreateArticleParams,
+        CreateRedirectParams,
         DeleteConflictParams,
         EditArticleParams,
+        ExportArticleHistory,
+        ExportArticleParams,
+        FindDuplicates,
         FollowArticleParams,
         ForkArticleParams,
         GetArticleParams,
+        GetBacklinks,
+        GetCategoryParams,
         GetConflictParams,
+        GetConflictStats,
+        GetContributorsParams,
+        GetEditReach,
+        GetPopularArticles,
+        GetTaggedRevisions,
+        ImportArticleParams,
         ListArticlesParams,
-        ProtectArticleParams,
+        MoveArticleParams,
+        PreviewArticleParams,
         RemoveArticleParams,
+        RemoveAttachmentParams,
+        RevertArticleParams,
+        RevertToDateParams,
+        SetProtectionParams,
+        TagRevisionParams,
     },
     instance::SearchArticleParams,
 };
 use ibis_database::{
+    DbUrl,
     common::{
         ResolveObjectParams,
         SuccessResponse,
         article::{
             ApiConflict,
             Article,
+            ArticleDisambiguation,
+            ArticleList,
+            ArticleListEntry,
+            ArticleLookup,
+            ArticleMove,
+            ArticlePreview,
+            ArticleSort,
             ArticleView,
+            Attachment,
+            Category,
+            CategoryView,
             Conflict,
+            ConflictStats,
+            Contributor,
+            DisambiguationCandidate,
+            DuplicateArticles,
             Edit,
+            EditCheck,
+            EditReach,
             EditVersion,
+            ExportArticle,
+            ExportEdit,
+            ProtectionLevel,
+            RevisionTag,
+            SearchResult,
             can_edit_article,
+            can_edit_article_anonymous,
         },
         instance::Instance,
-        user::Person,
+        merge::{ConflictMarkers, three_way_merge},
+        newtypes::ArticleId,
+        user::{LocalUserView, Person},
+        utils::{extract_domain, normalize_article_title},
     },
     error::BackendResult,
-    impls::{IbisContext, article::DbArticleForm, conflict::DbConflictForm, edit::DbEditForm},
+    impls::{
+        IbisContext,
+        article::DbArticleForm,
+        article_move::DbArticleMoveForm,
+        attachment::DbAttachmentForm,
+        conflict::{ConflictOutcome, DbConflictForm},
+        edit::{DbEditForm, ViewEditParams},
+        revision_tag::DbRevisionTagForm,
+    },
 };
 use ibis_federate::{
     activities::{
@@ -50,27 +130,51 @@ use ibis_federate::{
             undo_remove_article::UndoRemoveArticle,
             update_article::UpdateArticle,
         },
+        following::{follow::Follow, undo_follow::UndoFollow},
         submit_article_update,
     },
-    objects::article::ArticleWrapper,
-    validate::{validate_article_title, validate_not_empty},
+    objects::{article::ArticleWrapper, instance::InstanceWrapper, user::PersonWrapper},
+    validate::{
+        MAX_ARTICLE_ATTACHMENTS,
+        validate_article_size,
+        validate_article_title,
+        validate_attachment_url,
+        validate_edit_summary,
+        validate_not_empty,
+        validate_text_content,
+    },
+};
+use ibis_markdown::{format_markdown, render_article_markdown_with_links};
+use moka::sync::Cache;
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::LazyLock,
 };
-use ibis_markdown::format_markdown;
 
 /// Create a new article with empty text, and federate it to followers.
 #[debug_handler]
 pub(crate) async fn create_article(
     user: UserExt,
     context: Data<IbisContext>,
-    Form(params): Form<CreateArticleParams>,
+    Form(mut params): Form<CreateArticleParams>,
 ) -> BackendResult<Json<ArticleView>> {
+    params.title = normalize_article_title(&params.title);
     validate_article_title(&params.title)?;
     validate_not_empty(&params.text)?;
+    validate_text_content(&params.text)?;
+    validate_article_size(&params.text, &context.conf.options)?;
+    validate_edit_summary(&params.summary, &context.conf.options)?;
+    check_is_editor(&user, &context)?;
+    check_new_account_cooldown(&user, &context)?;
 
     let instance = match params.instance_id {
         Some(id) => Instance::read(id, &context)?,
         None => Instance::read_local(&context)?,
     };
+    // titles which only differ in case would get a different ap_id, so check explicitly
+    if instance.local && Article::read_view((&params.title, None), Some(&user), &context).is_ok() {
+        return Err(anyhow!("A local article with this title already exists").into());
+    }
     let ap_id = generate_article_ap_id(&params.title, &instance)?;
     let form = DbArticleForm {
         title: params.title,
@@ -78,9 +182,10 @@ pub(crate) async fn create_article(
         ap_id,
         instance_id: instance.id,
         local: instance.local,
-        protected: false,
+        protection: ProtectionLevel::Unprotected,
         updated: Utc::now(),
         pending: !instance.local,
+        forked_by: None,
     };
     let article = Article::create(form, user.person.id, &context).await?;
 
@@ -89,9 +194,11 @@ pub(crate) async fn create_article(
     submit_article_update(
         text,
         params.summary,
+        false,
         article.latest_edit_version(&context)?,
         &article,
         user.person.clone().into(),
+        None,
         true,
         &context,
     )
@@ -101,6 +208,207 @@ pub(crate) async fn create_article(
     Ok(Json(article_view))
 }
 
+/// Create a redirect from the given title to an existing article. If there is already a local
+/// article with this title it is turned into a redirect, otherwise a new local article is created.
+/// The redirect is removed again by editing the redirect article.
+#[debug_handler]
+pub(crate) async fn create_redirect(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(params): Form<CreateRedirectParams>,
+) -> BackendResult<Json<ArticleView>> {
+    validate_article_title(&params.from_title)?;
+    check_is_editor(&user, &context)?;
+    let target = Article::read_view(params.to_article_id, Some(&user), &context)?.article;
+    if target.redirect_to.is_some() {
+        return Err(anyhow!("Cannot redirect to another redirect").into());
+    }
+
+    let article = match Article::read_view((&params.from_title, None), Some(&user), &context) {
+        Ok(existing) => {
+            check_can_edit(&existing.article, &user, &context)?;
+            existing.article
+        }
+        Err(_) => {
+            check_new_account_cooldown(&user, &context)?;
+            let instance = Instance::read_local(&context)?;
+            let ap_id = generate_article_ap_id(&params.from_title, &instance)?;
+            create_redirect_article(params.from_title, ap_id, &target, &user, &context).await?
+        }
+    };
+    if article.id == target.id {
+        return Err(anyhow!("Article cannot redirect to itself").into());
+    }
+    Article::update_redirect(article.id, Some(target.id), &context)?;
+    Ok(Json(Article::read_view(article.id, Some(&user), &context)?))
+}
+
+/// Create a new local article which redirects to the target. The redirect target still needs
+/// to be set with [Article::update_redirect].
+async fn create_redirect_article(
+    title: String,
+    ap_id: DbUrl,
+    target: &Article,
+    user: &LocalUserView,
+    context: &IbisContext,
+) -> BackendResult<Article> {
+    let instance = Instance::read_local(context)?;
+    let form = DbArticleForm {
+        title,
+        text: String::new(),
+        ap_id,
+        instance_id: instance.id,
+        local: true,
+        protection: ProtectionLevel::Unprotected,
+        updated: Utc::now(),
+        pending: false,
+        forked_by: None,
+    };
+    let article = Article::create(form, user.person.id, context).await?;
+    submit_article_update(
+        format!("Redirect to {}\n", target.title),
+        "Create redirect".to_string(),
+        false,
+        article.latest_edit_version(context)?,
+        &article,
+        user.person.clone().into(),
+        None,
+        true,
+        context,
+    )
+    .await?;
+    Ok(article)
+}
+
+/// Rename a local article. The old title becomes a redirect to the article and the rename is
+/// recorded in the article history.
+#[debug_handler]
+pub(crate) async fn move_article(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(mut params): Form<MoveArticleParams>,
+) -> BackendResult<Json<ArticleView>> {
+    params.new_title = normalize_article_title(&params.new_title);
+    validate_article_title(&params.new_title)?;
+    check_is_editor(&user, &context)?;
+    let article = Article::read_view(params.article_id, Some(&user), &context)?.article;
+    if !article.local {
+        return Err(anyhow!("Only local articles can be moved").into());
+    }
+    if article.redirect_to.is_some() {
+        return Err(anyhow!("Cannot move a redirect").into());
+    }
+    check_can_edit(&article, &user, &context)?;
+    if article.title == params.new_title {
+        return Err(anyhow!("Article already has this title").into());
+    }
+    if Article::read_view((&params.new_title, None), Some(&user), &context).is_ok() {
+        return Err(anyhow!("A local article with this title already exists").into());
+    }
+
+    let old_title = article.title.clone();
+    let article = Article::update_title(article.id, &params.new_title, &context)?;
+    ArticleMove::create(
+        &DbArticleMoveForm {
+            article_id: article.id,
+            creator_id: user.person.id,
+            old_title: old_title.clone(),
+            new_title: article.title.clone(),
+        },
+        &context,
+    )?;
+
+    // The moved article keeps its ap_id so that other instances still recognize it. If the ap_id
+    // belongs to the old title, the redirect needs a different one. Federation routes look up
+    // articles by ap_id before title, so both stay resolvable.
+    let instance = Instance::read_local(&context)?;
+    let mut ap_id = generate_article_ap_id(&old_title, &instance)?;
+    if Article::read_from_ap_id(&ap_id, &context).is_ok() {
+        let redirect_title = format!("{old_title} (redirect {})", article.id.0);
+        ap_id = generate_article_ap_id(&redirect_title, &instance)?;
+    }
+    let redirect = create_redirect_article(old_title, ap_id, &article, &user, &context).await?;
+    Article::update_redirect(redirect.id, Some(article.id), &context)?;
+
+    UpdateArticle::send(article.clone().into(), &context).await?;
+    Ok(Json(Article::read_view(article.id, Some(&user), &context)?))
+}
+
+/// Check protection level of the article against role, account age and email verification of the
+/// user
+fn check_can_edit(
+    article: &Article,
+    user: &LocalUserView,
+    context: &IbisContext,
+) -> BackendResult<()> {
+    can_edit_article(
+        article,
+        user.local_user.role,
+        user.person.is_banned(),
+        user.local_user.published,
+        user.local_user.email_verified,
+        &context.conf.options,
+    )?;
+    Ok(())
+}
+
+fn check_is_editor(user: &LocalUserView, context: &IbisContext) -> BackendResult<()> {
+    if user.person.is_banned() {
+        return Err(anyhow!("You are banned from creating articles").into());
+    }
+    let role = user.local_user.role;
+    if !role.can_edit() {
+        return Err(anyhow!("Readers can not create articles").into());
+    }
+    let options = &context.conf.options;
+    let require_verified = options.email_required || options.edit_requires_verified_email;
+    if require_verified && !user.local_user.email_verified && !role.can_moderate() {
+        return Err(anyhow!("Verify your email address to create articles").into());
+    }
+    Ok(())
+}
+
+/// New accounts have to wait for the configured cooldown before creating articles, to slow down
+/// spam from throwaway accounts.
+fn check_new_account_cooldown(user: &LocalUserView, context: &IbisContext) -> BackendResult<()> {
+    let cooldown = context.conf.options.new_account_cooldown_minutes;
+    if user.local_user.role.can_moderate() || cooldown == 0 {
+        return Ok(());
+    }
+    let cooldown_end = user.local_user.published + Duration::minutes(cooldown.into());
+    if cooldown_end > Utc::now() {
+        return Err(anyhow!(
+            "New accounts cannot create articles yet, try again after {}",
+            cooldown_end.to_rfc3339()
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Limit how many edits a user can make within a sliding window, to slow down vandalism. Users
+/// without verified email get a stricter limit.
+fn check_edit_rate_limit(user: &LocalUserView, context: &IbisContext) -> BackendResult<()> {
+    let options = &context.conf.options;
+    let limit = if user.local_user.email_verified {
+        options.edit_rate_limit
+    } else {
+        options.edit_rate_limit_unverified
+    };
+    if user.local_user.role.can_moderate() || limit == 0 {
+        return Ok(());
+    }
+    let since = Utc::now() - Duration::seconds(options.edit_rate_limit_window.into());
+    if Edit::count_since(user.person.id, since, context)? >= limit.into() {
+        return Err(anyhow!(
+            "Edit rate limit exceeded, at most {limit} edits are allowed per {} seconds",
+            options.edit_rate_limit_window
+        )
+        .into());
+    }
+    Ok(())
+}
+
 /// Edit an existing article (local or remote).
 ///
 /// It gracefully handles the case where multiple users edit an article at the same time, by
@@ -109,50 +417,72 @@ pub(crate) async fn create_article(
 /// way-merge (similar to git). After the conflict is resolved, resubmit the edit with
 /// `resolve_conflict_id` and uppdated `previous_version`.
 ///
-/// Conflicts are stored in the database so they can be retrieved later from `/api/v3/edit_conflicts`.
+/// Conflicts are stored in the database so they can be retrieved later from `/api/v1/conflict/list`.
+///
+/// Without login this is only possible if `allow_anonymous_edits` is enabled, see
+/// [edit_article_anonymous].
 #[debug_handler]
 pub(crate) async fn edit_article(
-    user: UserExt,
+    user: UserExtOpt,
     context: Data<IbisContext>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Form(mut params): Form<EditArticleParams>,
 ) -> BackendResult<Json<Option<ApiConflict>>> {
     validate_not_empty(&params.new_text)?;
-    // resolve conflict if any
-    if let Some(resolve_conflict_id) = params.resolve_conflict_id {
-        Conflict::delete(resolve_conflict_id, user.person.id, &context)?;
-    }
+    validate_text_content(&params.new_text)?;
+    validate_article_size(&params.new_text, &context.conf.options)?;
+    let Some(user) = user.inner() else {
+        let client = client_ip(peer.ip(), &headers, context.conf.trusted_proxies.as_deref());
+        return edit_article_anonymous(params, client, &context).await;
+    };
+    let resolving = params
+        .resolve_conflict_id
+        .map(|id| Conflict::touch(id, user.person.id, &context))
+        .transpose()?;
     let original_article = Article::read_view(params.article_id, Some(&user), &context)?;
-    if params.new_text == original_article.article.text {
-        return Err(anyhow!("Edit contains no changes").into());
-    }
-    if params.summary.is_empty() {
-        return Err(anyhow!("No summary given").into());
-    }
-    can_edit_article(&original_article.article, user.local_user.admin)?;
+    validate_edit_summary(&params.summary, &context.conf.options)?;
+    check_can_edit(&original_article.article, &user, &context)?;
+    check_edit_rate_limit(&user, &context)?;
     // ensure trailing newline for clean diffs
     if !params.new_text.ends_with('\n') {
         params.new_text.push('\n');
     }
-    let local_link = format!("](https://{}", context.conf.federation.domain);
-    if params.new_text.contains(&local_link) {
-        return Err(anyhow!("Links to local instance don't work over federation").into());
-    }
+    check_local_links(&params.new_text, &context)?;
 
     // Markdown formatting
     let new_text = format_markdown(&params.new_text)?;
+    // compare after formatting, so that whitespace changes alone don't create an empty edit
+    check_has_changes(&new_text, &original_article.article)?;
+
+    // resolve conflict if any, only after the edit passed all checks so that a rejected edit
+    // can be submitted again
+    let mut attempts = 0;
+    if let Some(conflict) = resolving {
+        Conflict::delete(
+            conflict.id,
+            user.person.id,
+            ConflictOutcome::Resolved,
+            &context,
+        )?;
+        attempts = conflict.attempts + 1;
+    }
 
     if params.previous_version_id == original_article.latest_version {
         // No intermediate changes, simply submit new version
         submit_article_update(
             new_text.clone(),
             params.summary.clone(),
+            params.minor.unwrap_or_default(),
             params.previous_version_id,
             &original_article.article,
             user.person.clone().into(),
+            None,
             false,
             &context,
         )
         .await?;
+        clear_redirect(&original_article.article, &context)?;
         Ok(Json(None))
     } else {
         // There have been other changes since this edit was initiated. Get the common ancestor
@@ -169,56 +499,360 @@ pub(crate) async fn edit_article(
             creator_id: user.person.id,
             article_id: original_article.article.id,
             previous_version_id: previous_version.hash,
+            attempts,
         };
         let conflict = Conflict::create(&form, &context)?;
-        Ok(Json(
-            db_conflict_to_api_conflict(conflict, true, &context).await?,
-        ))
+        let api_conflict = db_conflict_to_api_conflict(conflict, true, &context).await?;
+        let max_attempts = context.conf.options.max_conflict_resolution_attempts;
+        if api_conflict.is_some() && attempts > max_attempts as i32 {
+            // Give up and remove stale conflicts, so the user can start over from latest version
+            Conflict::delete_for_article(original_article.article.id, user.person.id, &context)?;
+            return Err(anyhow!(
+                "Edit still conflicts after {max_attempts} attempts to resolve it, please reload the article and edit again from scratch"
+            )
+            .into());
+        }
+        if api_conflict.is_none() {
+            clear_redirect(&original_article.article, &context)?;
+        }
+        Ok(Json(api_conflict))
+    }
+}
+
+/// Reject edits which don't change the article text, to keep them out of the history.
+fn check_has_changes(new_text: &str, article: &Article) -> BackendResult<()> {
+    if new_text == article.text {
+        return Err(anyhow!("Edit contains no changes").into());
     }
+    Ok(())
+}
+
+/// Edit an unprotected article without login. The edit is attributed to the shared anonymous user
+/// and tagged with a hash of the client IP, which is also used for rate limiting. There is no
+/// account to store conflicts for, so edits based on an outdated version are rejected.
+async fn edit_article_anonymous(
+    mut params: EditArticleParams,
+    client: IpAddr,
+    context: &Data<IbisContext>,
+) -> BackendResult<Json<Option<ApiConflict>>> {
+    let original_article = Article::read_view(params.article_id, None, context)?;
+    can_edit_article_anonymous(&original_article.article, &context.conf.options)?;
+    if params.resolve_conflict_id.is_some()
+        || params.previous_version_id != original_article.latest_version
+    {
+        return Err(anyhow!(
+            "Article was edited in the meantime, please reload the article and edit again"
+        )
+        .into());
+    }
+    validate_edit_summary(&params.summary, &context.conf.options)?;
+    let anonymous_id = Edit::anonymous_id(&client.to_string(), context)?;
+    let options = &context.conf.options;
+    let limit = options.edit_rate_limit_unverified;
+    let since = Utc::now() - Duration::seconds(options.edit_rate_limit_window.into());
+    if limit != 0 && Edit::count_since_anonymous(&anonymous_id, since, context)? >= limit.into() {
+        return Err(anyhow!(
+            "Edit rate limit exceeded, at most {limit} edits are allowed per {} seconds",
+            options.edit_rate_limit_window
+        )
+        .into());
+    }
+    if !params.new_text.ends_with('\n') {
+        params.new_text.push('\n');
+    }
+    check_local_links(&params.new_text, context)?;
+    let new_text = format_markdown(&params.new_text)?;
+    check_has_changes(&new_text, &original_article.article)?;
+
+    submit_article_update(
+        new_text,
+        params.summary,
+        params.minor.unwrap_or_default(),
+        params.previous_version_id,
+        &original_article.article,
+        Person::anonymous(context)?.into(),
+        Some(anonymous_id),
+        false,
+        context,
+    )
+    .await?;
+    clear_redirect(&original_article.article, context)?;
+    Ok(Json(None))
+}
+
+/// Editing a redirect article turns it back into a normal article
+fn clear_redirect(article: &Article, context: &IbisContext) -> BackendResult<()> {
+    if article.redirect_to.is_some() {
+        Article::update_redirect(article.id, None, context)?;
+    }
+    Ok(())
+}
+
+fn check_local_links(text: &str, context: &IbisContext) -> BackendResult<()> {
+    let local_link = format!("](https://{}", context.conf.federation.domain);
+    if text.contains(&local_link) {
+        return Err(anyhow!("Links to local instance don't work over federation").into());
+    }
+    Ok(())
+}
+
+/// Run the same checks as [edit_article] and return the result of each, without storing or
+/// federating anything. Useful for bots to check an edit in advance.
+#[debug_handler]
+pub(crate) async fn validate_edit(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(params): Form<EditArticleParams>,
+) -> BackendResult<Json<Vec<EditCheck>>> {
+    let article = Article::read_view(params.article_id, Some(&user), &context)?;
+    let text = &params.new_text;
+    // normalize the same way as edit_article, so that formatting changes alone count as no changes
+    let mut normalized = text.clone();
+    if !normalized.ends_with('\n') {
+        normalized.push('\n');
+    }
+    let formatted = format_markdown(&normalized);
+    let up_to_date = if params.previous_version_id == article.latest_version {
+        Ok(())
+    } else {
+        Err(anyhow!("Article was changed since this version, edit may result in a conflict").into())
+    };
+    let checks: [(&str, BackendResult<()>); 10] = [
+        (
+            "permission",
+            check_can_edit(&article.article, &user, &context),
+        ),
+        ("rate_limit", check_edit_rate_limit(&user, &context)),
+        ("not_empty", validate_not_empty(text)),
+        ("text_content", validate_text_content(text)),
+        ("size", validate_article_size(text, &context.conf.options)),
+        (
+            "summary",
+            validate_edit_summary(&params.summary, &context.conf.options),
+        ),
+        (
+            "changes",
+            formatted
+                .as_ref()
+                .map_err(|e| (*e).into())
+                .and_then(|t| check_has_changes(t, &article.article)),
+        ),
+        ("local_links", check_local_links(&normalized, &context)),
+        (
+            "formatting",
+            formatted.as_ref().map(|_| ()).map_err(|e| (*e).into()),
+        ),
+        ("up_to_date", up_to_date),
+    ];
+    Ok(Json(
+        checks
+            .into_iter()
+            .map(|(name, res)| EditCheck {
+                name: name.to_string(),
+                passed: res.is_ok(),
+                message: res.err().map(|e| e.to_string()),
+            })
+            .collect(),
+    ))
+}
+
+/// Render article text in the same way as the article page, so that editors can check their
+/// changes before saving. Nothing is written to the database.
+#[debug_handler]
+pub(crate) async fn preview_article(
+    context: Data<IbisContext>,
+    Form(params): Form<PreviewArticleParams>,
+) -> BackendResult<Json<ArticlePreview>> {
+    validate_text_content(&params.text)?;
+    let missing_links = Article::read_missing_links(&params.text, &context)?;
+    let options = &context.conf.options;
+    let html = render_article_markdown_with_links(
+        &params.text,
+        options.math_rendering,
+        options.break_hint_length,
+        true,
+        &missing_links,
+    );
+    Ok(Json(ArticlePreview {
+        html,
+        missing_links,
+    }))
 }
 
 /// Retrieve an article by ID. It must already be stored in the local database.
+///
+/// If only a title is given, the local article with this title is returned. Otherwise articles
+/// with this title on other instances are checked. If there are multiple, an
+/// [ArticleDisambiguation] is returned with status `300 Multiple Choices`.
 #[debug_handler]
 pub(crate) async fn get_article(
     user: UserExtOpt,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Query(query): Query<GetArticleParams>,
     context: Data<IbisContext>,
-) -> BackendResult<Json<ArticleView>> {
+) -> BackendResult<Response> {
     let user = user.inner();
-    match (query.title, query.id) {
-        (Some(title), None) => Ok(Json(Article::read_view(
-            (&title, query.domain),
-            user.as_ref(),
-            &context,
-        )?)),
+    let article = match (query.title, query.id) {
+        (Some(title), None) if query.domain.is_none() => {
+            match Article::read_view((&title, None), user.as_ref(), &context) {
+                Ok(article) => article,
+                Err(e) => match resolve_ambiguous_title(&title, user.as_ref(), &context) {
+                    Ok(ArticleLookup::Article(article)) => *article,
+                    Ok(ArticleLookup::Disambiguation(d)) => {
+                        return Ok((StatusCode::MULTIPLE_CHOICES, Json(d)).into_response());
+                    }
+                    Err(_) => return Err(e.or_not_found(format!("Article {title} not found"))),
+                },
+            }
+        }
+        (Some(title), None) => Article::read_view((&title, query.domain), user.as_ref(), &context)
+            .map_err(|e| e.or_not_found(format!("Article {title} not found")))?,
         (None, Some(id)) => {
             if query.domain.is_some() {
                 return Err(anyhow!("Cant combine id and instance_domain").into());
             }
-            let article = Article::read_view(id, user.as_ref(), &context)?;
-            Ok(Json(article))
+            Article::read_view(id, user.as_ref(), &context)?
+        }
+        _ => return Err(anyhow!("Must pass exactly one of title, id").into()),
+    };
+    // Only follow a single redirect, to avoid loops
+    let article = match article.article.redirect_to {
+        Some(target) if query.follow_redirect.unwrap_or(true) => {
+            let mut target = Article::read_view(target, user.as_ref(), &context)?;
+            target.redirected_from = Some(article.article);
+            target
         }
-        _ => Err(anyhow!("Must pass exactly one of title, id").into()),
+        _ => article,
+    };
+    if context.conf.options.article_view_count {
+        let client = client_ip(peer.ip(), &headers, context.conf.trusted_proxies.as_deref());
+        count_article_view(article.article.id, user.as_ref(), client, &context)?;
     }
+    Ok(Json(article).into_response())
 }
 
+/// Find remote articles with the given title. Returns the article directly if there is only a
+/// single match.
+fn resolve_ambiguous_title(
+    title: &str,
+    user: Option<&LocalUserView>,
+    context: &IbisContext,
+) -> BackendResult<ArticleLookup> {
+    const EXCERPT_LENGTH: usize = 200;
+    let limit = context.conf.options.max_disambiguation_candidates.into();
+    let mut articles = Article::read_by_title(title, limit, context)?;
+    match articles.len() {
+        0 => Err(anyhow!("Article not found").into()),
+        1 => {
+            let (article, _) = articles.remove(0);
+            let view = Article::read_view(article.id, user, context)?;
+            Ok(ArticleLookup::Article(Box::new(view)))
+        }
+        _ => {
+            let candidates = articles
+                .into_iter()
+                .map(|(article, instance)| {
+                    let excerpt = article
+                        .text
+                        .split_whitespace()
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                        .chars()
+                        .take(EXCERPT_LENGTH)
+                        .collect();
+                    DisambiguationCandidate {
+                        article,
+                        instance_domain: instance.domain,
+                        excerpt,
+                    }
+                })
+                .collect();
+            Ok(ArticleLookup::Disambiguation(ArticleDisambiguation {
+                title: title.to_string(),
+                candidates,
+            }))
+        }
+    }
+}
+
+/// Count the view unless the same client has already viewed this article recently. Clients are
+/// identified by their account if logged in, otherwise by ip address, see [client_ip].
+fn count_article_view(
+    article_id: ArticleId,
+    user: Option<&LocalUserView>,
+    client: IpAddr,
+    context: &IbisContext,
+) -> BackendResult<()> {
+    static RECENT_VIEWS: LazyLock<Cache<(String, ArticleId), ()>> = LazyLock::new(|| {
+        Cache::builder()
+            .max_capacity(100_000)
+            .time_to_live(std::time::Duration::from_secs(60 * 60))
+            .build()
+    });
+    let client = match user {
+        Some(u) => u.person.ap_id.to_string(),
+        None => client.to_string(),
+    };
+    let key = (client, article_id);
+    if !RECENT_VIEWS.contains_key(&key) {
+        RECENT_VIEWS.insert(key, ());
+        Article::add_view(article_id, context)?;
+    }
+    Ok(())
+}
+
+/// Upper limit for the page size of article listings, regardless of request or instance default
+const MAX_ARTICLE_PAGE_SIZE: i64 = 200;
+
+/// List articles with pagination. Sort and page size fall back to the instance defaults if they
+/// are not specified in the request. The total count allows rendering page controls.
 #[debug_handler]
 pub(crate) async fn list_articles(
     user: UserExtOpt,
     Query(query): Query<ListArticlesParams>,
     context: Data<IbisContext>,
-) -> BackendResult<Json<Vec<Article>>> {
+) -> BackendResult<Json<ArticleList>> {
     let include_removed = user
         .as_ref()
-        .map(|u| u.local_user.admin)
+        .map(|u| u.local_user.role.can_moderate())
         .unwrap_or_default()
         && query.include_removed.unwrap_or_default();
-    Ok(Json(Article::read_all(
+    let options = &context.conf.options;
+    let sort = query.sort.unwrap_or(options.default_article_sort);
+    let limit = query
+        .limit
+        .unwrap_or(options.default_page_size.into())
+        .clamp(1, MAX_ARTICLE_PAGE_SIZE);
+    let offset = match query.offset {
+        Some(offset) => offset.max(0),
+        None => (query.page.unwrap_or(1).max(1) - 1) * limit,
+    };
+    let articles = Article::read_all(
         query.only_local,
         query.instance_id,
         include_removed,
+        sort,
+        Some(limit),
+        offset,
         &context,
-    )?))
+    )?;
+    let total = Article::count_all(
+        query.only_local,
+        query.instance_id,
+        include_removed,
+        &context,
+    )?;
+    let ids: Vec<_> = articles.iter().map(|a| a.id).collect();
+    let mut latest_edits = Edit::read_latest(&ids, &context)?;
+    let articles = articles
+        .into_iter()
+        .map(|article| ArticleListEntry {
+            latest_edit: latest_edits.remove(&article.id),
+            article,
+        })
+        .collect();
+    Ok(Json(ArticleList { articles, total }))
 }
 
 /// Fork a remote article to local instance. This is useful if there are disagreements about
@@ -229,20 +863,31 @@ pub(crate) async fn fork_article(
     context: Data<IbisContext>,
     Form(params): Form<ForkArticleParams>,
 ) -> BackendResult<Json<ArticleView>> {
+    check_is_editor(&user, &context)?;
     let original_article = Article::read_view(params.article_id, Some(&user), &context)?;
-    validate_article_title(&params.new_title)?;
+    let title = params
+        .new_title
+        .unwrap_or_else(|| original_article.article.title.clone());
+    validate_article_title(&title)?;
+    if Article::read_view((&title, None), Some(&user), &context).is_ok() {
+        return Err(anyhow!(
+            "A local article with the title {title} already exists, choose a new title for the fork"
+        )
+        .into());
+    }
 
     let local_instance = Instance::read_local(&context)?;
-    let ap_id = generate_article_ap_id(&params.new_title, &local_instance)?;
+    let ap_id = generate_article_ap_id(&title, &local_instance)?;
     let form = DbArticleForm {
-        title: params.new_title,
+        title,
         text: original_article.article.text.clone(),
         ap_id,
         instance_id: local_instance.id,
         local: true,
-        protected: false,
+        protection: ProtectionLevel::Unprotected,
         updated: Utc::now(),
         pending: false,
+        forked_by: Some(user.person.id),
     };
     let article = Article::create(form, user.person.id, &context).await?;
 
@@ -262,6 +907,8 @@ pub(crate) async fn fork_article(
             previous_version_id: e.previous_version_id,
             published: e.published,
             pending: false,
+            minor: e.minor,
+            anonymous_id: e.anonymous_id,
         };
         Edit::create_or_update(&form, false, &context).await?;
     }
@@ -285,27 +932,169 @@ pub(super) async fn resolve_article(
     Ok(Json(Article::read_view(article.id, Some(&user), &context)?))
 }
 
+/// Maximum number of search results, for both normal and streaming search
+const MAX_SEARCH_RESULTS: i64 = 100;
+/// Number of results which are sent in each event during streaming search
+const SEARCH_STREAM_PAGE_SIZE: i64 = 10;
+
 /// Search articles for matching title or body text.
 #[debug_handler]
 pub(super) async fn search_article(
     Query(query): Query<SearchArticleParams>,
     context: Data<IbisContext>,
-) -> BackendResult<Json<Vec<Article>>> {
+) -> BackendResult<Json<Vec<SearchResult>>> {
     if query.query.is_empty() {
         return Err(anyhow!("Query is empty").into());
     }
-    let article = Article::search(&query.query, &context)?;
-    Ok(Json(article))
+    let results = Article::search(
+        &query.query,
+        query.search_mode.unwrap_or_default(),
+        query.fuzzy.unwrap_or_default(),
+        MAX_SEARCH_RESULTS,
+        0,
+        &context,
+    )?;
+    Ok(Json(results))
+}
+
+/// Same as [search_article], but sends results as server-sent events in small batches so that
+/// the first results can be shown quickly. Each event contains a json array of search results.
+/// The database is only queried for the next batch after the previous one was sent, so if the
+/// client disconnects the stream is dropped and no further work is done.
+#[debug_handler]
+pub(super) async fn search_article_stream(
+    Query(query): Query<SearchArticleParams>,
+    context: Data<IbisContext>,
+) -> BackendResult<Sse<impl Stream<Item = Result<Event, axum::Error>>>> {
+    if query.query.is_empty() {
+        return Err(anyhow!("Query is empty").into());
+    }
+    let events = stream::unfold(
+        (Some(0), query, context),
+        |(offset, query, context)| async move {
+            let offset = offset?;
+            let limit = SEARCH_STREAM_PAGE_SIZE.min(MAX_SEARCH_RESULTS - offset);
+            let results = Article::search(
+                &query.query,
+                query.search_mode.unwrap_or_default(),
+                query.fuzzy.unwrap_or_default(),
+                limit,
+                offset,
+                &context,
+            );
+            let (event, next) = match results {
+                Ok(results) => {
+                    let next = offset + limit;
+                    let has_more = results.len() as i64 == limit && next < MAX_SEARCH_RESULTS;
+                    (
+                        Event::default().json_data(&results),
+                        has_more.then_some(next),
+                    )
+                }
+                Err(e) => (
+                    Ok(Event::default().event("error").data(e.to_string())),
+                    None,
+                ),
+            };
+            Some((event, (next, query, context)))
+        },
+    );
+    Ok(Sse::new(events))
+}
+
+/// Restore the article text from the latest edit at or before the given time. This creates a
+/// new edit which is federated like any other.
+#[debug_handler]
+pub(crate) async fn revert_article_to_date(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(params): Form<RevertToDateParams>,
+) -> BackendResult<Json<ArticleView>> {
+    check_is_moderator(&user)?;
+    let article = Article::read_view(params.article_id, Some(&user), &context)?;
+    check_can_edit(&article.article, &user, &context)?;
+    let edits = Edit::list_for_article(article.article.id, &context)?;
+    let Some(version) = edits.iter().rev().find(|e| e.published <= params.timestamp) else {
+        return Err(anyhow!(
+            "Article did not exist yet at {}",
+            params.timestamp.to_rfc3339()
+        )
+        .into());
+    };
+    let text = generate_article_version(&edits, &version.hash)?;
+    let summary = format!("Revert to version from {}", params.timestamp.to_rfc3339());
+    Ok(Json(
+        submit_revert(article, text, summary, &user, &context).await?,
+    ))
+}
+
+/// Restore the article text from a previous version. Unlike [revert_article_to_date] this is
+/// available to all users who can edit the article, as it is the same as a manual edit.
+#[debug_handler]
+pub(crate) async fn revert_article(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(params): Form<RevertArticleParams>,
+) -> BackendResult<Json<ArticleView>> {
+    let article = Article::read_view(params.article_id, Some(&user), &context)?;
+    check_can_edit(&article.article, &user, &context)?;
+    if params.revert_to_version == EditVersion::default() {
+        return Err(anyhow!("Can not revert to empty article").into());
+    }
+    let text = text_at_version(article.article.id, &params.revert_to_version, &context)?;
+    let hash = params.revert_to_version.hash();
+    let summary = format!("Revert to version {}", &hash[..8]);
+    Ok(Json(
+        submit_revert(article, text, summary, &user, &context).await?,
+    ))
+}
+
+/// Full text of the article as of the given version, reconstructed from its edit history.
+fn text_at_version(
+    article_id: ArticleId,
+    version: &EditVersion,
+    context: &IbisContext,
+) -> BackendResult<String> {
+    let edits = Edit::list_for_article(article_id, context)?;
+    generate_article_version(&edits, version)
+}
+
+/// Submit the restored text of a previous version as new edit on top of the latest version.
+async fn submit_revert(
+    article: ArticleView,
+    text: String,
+    summary: String,
+    user: &LocalUserView,
+    context: &Data<IbisContext>,
+) -> BackendResult<ArticleView> {
+    if text == article.article.text {
+        return Err(anyhow!("Article already has the text from this version").into());
+    }
+    submit_article_update(
+        text,
+        summary,
+        false,
+        article.latest_version,
+        &article.article,
+        user.person.clone().into(),
+        None,
+        false,
+        context,
+    )
+    .await?;
+    Article::read_view(article.article.id, Some(user), context)
 }
 
 #[debug_handler]
 pub(crate) async fn protect_article(
     user: UserExt,
     context: Data<IbisContext>,
-    Form(params): Form<ProtectArticleParams>,
+    Form(params): Form<SetProtectionParams>,
 ) -> BackendResult<Json<Article>> {
-    check_is_admin(&user)?;
-    let article = Article::update_protected(params.article_id, params.protected, &context)?;
+    check_is_moderator(&user)?;
+    let article = Article::update_protection(params.article_id, params.level, &context)?;
+    // protection level is signed, so the article needs to be signed and sent again
+    UpdateArticle::send(article.clone().into(), &context).await?;
     Ok(Json(article))
 }
 
@@ -315,7 +1104,7 @@ pub async fn remove_article(
     context: Data<IbisContext>,
     Form(params): Form<RemoveArticleParams>,
 ) -> BackendResult<Json<()>> {
-    check_is_admin(&user)?;
+    check_is_moderator(&user)?;
     let article = Article::update_removed(params.article_id, params.remove, &context)?;
     let actor = user.person.ap_id.clone().into();
     if params.remove {
@@ -326,6 +1115,16 @@ pub async fn remove_article(
     Ok(Json(()))
 }
 
+/// List removed articles so that moderators can find and restore them
+#[debug_handler]
+pub(crate) async fn list_removed_articles(
+    user: UserExt,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<Vec<Article>>> {
+    check_is_moderator(&user)?;
+    Ok(Json(Article::read_removed(&context)?))
+}
+
 #[debug_handler]
 pub async fn get_conflict(
     user: UserExt,
@@ -339,13 +1138,463 @@ pub async fn get_conflict(
     Ok(Json(conflict))
 }
 
+/// Attach external media to an article. Only possible for local articles, as changes are federated
+/// by the home instance.
+#[debug_handler]
+pub(crate) async fn add_attachment(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(params): Form<AddAttachmentParams>,
+) -> BackendResult<Json<Attachment>> {
+    let article = Article::read(params.article_id, &context)?;
+    check_can_change_attachments(&article, &user, &context)?;
+    validate_attachment_url(&params.url, &context)?;
+    if Attachment::list_for_article(article.id, &context)?.len() >= MAX_ARTICLE_ATTACHMENTS {
+        return Err(anyhow!("Article has too many attachments").into());
+    }
+    let form = DbAttachmentForm {
+        article_id: article.id,
+        url: params.url.to_string(),
+        alt_text: params.alt_text,
+        media_type: params.media_type,
+    };
+    let attachment = Attachment::create(&form, &context)?;
+    UpdateArticle::send(article.into(), &context).await?;
+    Ok(Json(attachment))
+}
+
+#[debug_handler]
+pub(crate) async fn remove_attachment(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(params): Form<RemoveAttachmentParams>,
+) -> BackendResult<Json<SuccessResponse>> {
+    let attachment = Attachment::read(params.id, &context)?;
+    let article = Article::read(attachment.article_id, &context)?;
+    check_can_change_attachments(&article, &user, &context)?;
+    Attachment::delete(attachment.id, &context)?;
+    UpdateArticle::send(article.into(), &context).await?;
+    Ok(Json(SuccessResponse::default()))
+}
+
+fn check_can_change_attachments(
+    article: &Article,
+    user: &LocalUserView,
+    context: &IbisContext,
+) -> BackendResult<()> {
+    if !article.local {
+        return Err(anyhow!("Attachments can only be changed on the home instance").into());
+    }
+    check_can_edit(article, user, context)?;
+    Ok(())
+}
+
+/// Articles with the most views across all instances. Views are written to the database
+/// periodically, so recent views may not be included yet.
+#[debug_handler]
+pub(crate) async fn get_popular_articles(
+    Query(params): Query<GetPopularArticles>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<Vec<Article>>> {
+    let limit = params.limit.unwrap_or(20).clamp(1, 50);
+    Ok(Json(Article::read_all(
+        None,
+        None,
+        false,
+        ArticleSort::MostViewed,
+        Some(limit),
+        0,
+        &context,
+    )?))
+}
+
+/// Articles which link to the given article with `[[Title]]` syntax
+#[debug_handler]
+pub(crate) async fn get_backlinks(
+    user: UserExtOpt,
+    Query(params): Query<GetBacklinks>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<Vec<Article>>> {
+    let article = Article::read_view(params.article_id, user.inner().as_ref(), &context)?.article;
+    Ok(Json(Article::read_backlinks(&article, &context)?))
+}
+
+/// Compare the article text at two versions, which don't need to be adjacent
+#[debug_handler]
+pub(crate) async fn compare_revisions(
+    user: UserExtOpt,
+    Query(params): Query<CompareRevisionsParams>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<String>> {
+    let article = Article::read_view(params.article_id, user.inner().as_ref(), &context)?;
+    let edits = Edit::list_for_article(article.article.id, &context)?;
+    Ok(Json(diff_article_versions(
+        &edits,
+        &params.from,
+        &params.to,
+    )?))
+}
+
+/// Mark a version of the article with a named tag. Tagging another version with an existing
+/// tag moves the tag.
+#[debug_handler]
+pub(crate) async fn tag_revision(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(params): Form<TagRevisionParams>,
+) -> BackendResult<Json<RevisionTag>> {
+    let tag = params.tag.trim().to_string();
+    validate_not_empty(&tag)?;
+    if tag.len() > 50 {
+        return Err(anyhow!("Tag is too long").into());
+    }
+    let article = Article::read_view(params.article_id, Some(&user), &context)?.article;
+    check_can_edit(&article, &user, &context)?;
+    let edits = Edit::list_for_article(article.id, &context)?;
+    if !edits.iter().any(|e| e.hash == params.version) {
+        return Err(anyhow!("Version does not belong to this article").into());
+    }
+    let form = DbRevisionTagForm {
+        article_id: article.id,
+        version: params.version,
+        tag,
+        creator_id: user.person.id,
+    };
+    Ok(Json(RevisionTag::create(&form, &context)?))
+}
+
+/// List the tagged versions of an article, oldest tag first
+#[debug_handler]
+pub(crate) async fn get_tagged_revisions(
+    user: UserExtOpt,
+    Query(params): Query<GetTaggedRevisions>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<Vec<RevisionTag>>> {
+    let article = Article::read_view(params.article_id, user.inner().as_ref(), &context)?;
+    Ok(Json(RevisionTag::list_for_article(
+        article.article.id,
+        &context,
+    )?))
+}
+
+/// Manually add an article to a category. Categories from `[[Category:Name]]` markers in the
+/// text are added automatically.
+#[debug_handler]
+pub(crate) async fn add_to_category(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(params): Form<AddToCategoryParams>,
+) -> BackendResult<Json<Category>> {
+    let name = params.category.trim().replace('_', " ");
+    validate_not_empty(&name)?;
+    let article = Article::read_view(params.article_id, Some(&user), &context)?.article;
+    check_can_edit(&article, &user, &context)?;
+    Ok(Json(Category::add_article(article.id, &name, &context)?))
+}
+
+/// List the articles in a category, ordered by title
+#[debug_handler]
+pub(crate) async fn get_category(
+    Query(params): Query<GetCategoryParams>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<CategoryView>> {
+    let name = params.name.replace('_', " ");
+    Ok(Json(Category::read_view(&name, &context)?))
+}
+
+/// Preview how many instances and users would receive an edit to the article. Edits of local
+/// articles are sent to followers of the wiki bot and of the local instance, and to remote users
+/// subscribed to the article. Edits of remote articles are sent to the home instance, which
+/// forwards them to its own followers.
+#[debug_handler]
+pub(crate) async fn get_edit_reach(
+    user: UserExt,
+    Query(params): Query<GetEditReach>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<EditReach>> {
+    let article = Article::read(params.article_id, &context)?;
+    let mut domains: Vec<String> = if article.local {
+        let local_instance = Instance::read_local(&context)?;
+        // same person may follow both the instance and the wiki bot
+        Person::read_followers(Person::wikibot(&context)?.id, &context)?
+            .into_iter()
+            .chain(Instance::read_followers(local_instance.id, &context)?)
+            .chain(Article::read_subscribers(article.id, &context)?)
+            .filter(|p| !p.local)
+            .map(|p| extract_domain(p.ap_id.inner()))
+            .collect()
+    } else {
+        vec![Instance::read(article.instance_id, &context)?.domain]
+    };
+    domains.sort();
+    domains.dedup();
+    Ok(Json(EditReach {
+        instances: domains.len(),
+        local_followers: Article::read_follower_count(article.id, &context)?,
+        instance_domains: user.local_user.is_admin().then_some(domains),
+    }))
+}
+
+/// Users who edited the article, ordered by number of edits and then by size of their changes.
+#[debug_handler]
+pub(crate) async fn get_contributors(
+    Query(params): Query<GetContributorsParams>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<Vec<Contributor>>> {
+    let article = Article::read(params.article_id, &context)?;
+    if article.removed {
+        return Err(anyhow!("Article was removed").into());
+    }
+    let views = Edit::list_views(
+        ViewEditParams::ArticleId(article.id),
+        false,
+        &None,
+        &context,
+    )?;
+    let mut text = String::new();
+    let mut contributors: Vec<Contributor> = vec![];
+    for view in views {
+        let patch = Patch::from_str(&view.edit.diff)?;
+        let new_text = apply(&text, &patch)?;
+        let byte_delta = new_text.len() as i64 - text.len() as i64;
+        text = new_text;
+        match contributors
+            .iter_mut()
+            .find(|c| c.person.id == view.creator.id)
+        {
+            Some(c) => {
+                c.edits += 1;
+                c.byte_delta += byte_delta;
+            }
+            None => contributors.push(Contributor {
+                person: view.creator,
+                edits: 1,
+                byte_delta,
+            }),
+        }
+    }
+    contributors.sort_by(|a, b| {
+        b.edits
+            .cmp(&a.edits)
+            .then(b.byte_delta.abs().cmp(&a.byte_delta.abs()))
+    });
+    Ok(Json(contributors))
+}
+
+/// Export an article with its full edit history and contributors as JSON.
+#[debug_handler]
+pub(crate) async fn export_article(
+    Query(params): Query<ExportArticleParams>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<ExportArticle>> {
+    let article = Article::read(params.article_id, &context)?;
+    if article.removed {
+        return Err(anyhow!("Article was removed").into());
+    }
+    let views = Edit::list_views(
+        ViewEditParams::ArticleId(article.id),
+        false,
+        &None,
+        &context,
+    )?;
+    let mut contributors: Vec<Person> = vec![];
+    let mut edits = vec![];
+    for view in views {
+        if !contributors.iter().any(|c| c.id == view.creator.id) {
+            contributors.push(view.creator.clone());
+        }
+        edits.push(ExportEdit {
+            edit: view.edit,
+            creator: view.creator.ap_id,
+        });
+    }
+    Ok(Json(ExportArticle {
+        article,
+        edits,
+        contributors,
+    }))
+}
+
+/// Recreate an article with its edit history from the output of [export_article], for example to
+/// move it to another instance. The edits must result in the exported article text. Creators are
+/// fetched from their home instance, or replaced by the ghost user if that fails.
+#[debug_handler]
+pub(crate) async fn import_article(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(params): Form<ImportArticleParams>,
+) -> BackendResult<Json<ArticleView>> {
+    check_is_admin(&user)?;
+    let data: ExportArticle = serde_json::from_str(&params.data)?;
+    let title = data.article.title;
+    validate_article_title(&title)?;
+    if Article::read_view((&title, None), Some(&user), &context).is_ok() {
+        return Err(anyhow!("A local article with the title {title} already exists").into());
+    }
+    let edits: Vec<Edit> = data.edits.iter().map(|e| e.edit.clone()).collect();
+    let latest_version = edits
+        .last()
+        .map(|e| e.hash.clone())
+        .ok_or(anyhow!("Export contains no edits"))?;
+    let text = generate_article_version(&edits, &latest_version)?;
+    if text != data.article.text {
+        return Err(anyhow!("Edit history doesn't match article text").into());
+    }
+
+    let local_instance = Instance::read_local(&context)?;
+    let ap_id = generate_article_ap_id(&title, &local_instance)?;
+    let form = DbArticleForm {
+        title,
+        text,
+        ap_id,
+        instance_id: local_instance.id,
+        local: true,
+        protection: ProtectionLevel::Unprotected,
+        updated: Utc::now(),
+        pending: false,
+        forked_by: None,
+    };
+    let article = Article::create(form, user.person.id, &context).await?;
+    for e in data.edits {
+        let creator_id = match ObjectId::<PersonWrapper>::from(e.creator)
+            .dereference(&context)
+            .await
+        {
+            Ok(creator) => creator.id,
+            Err(_) => Person::ghost(&context)?.id,
+        };
+        let form = DbEditForm {
+            ap_id: DbEditForm::generate_ap_id(&article, &e.edit.hash)?,
+            diff: e.edit.diff,
+            summary: e.edit.summary,
+            creator_id,
+            article_id: article.id,
+            hash: e.edit.hash,
+            previous_version_id: e.edit.previous_version_id,
+            published: e.edit.published,
+            pending: false,
+            minor: e.edit.minor,
+            anonymous_id: None,
+        };
+        Edit::create_or_update(&form, false, &context).await?;
+    }
+
+    UpdateArticle::send(article.clone().into(), &context).await?;
+    Ok(Json(Article::read_view(article.id, Some(&user), &context)?))
+}
+
+/// Export the edit history of an article as `git fast-import` stream, with one commit per edit.
+/// All edits are read upfront, but commits are generated one by one while the response is sent
+/// so that the full output is never held in memory.
+#[debug_handler]
+pub(crate) async fn export_article_history(
+    Query(params): Query<ExportArticleHistory>,
+    context: Data<IbisContext>,
+) -> BackendResult<Response> {
+    let article = Article::read(params.article_id, &context)?;
+    if article.removed {
+        return Err(anyhow!("Article was removed").into());
+    }
+    let edits = Edit::list_views(
+        ViewEditParams::ArticleId(article.id),
+        false,
+        &None,
+        &context,
+    )?;
+    let path = git_file_name(&article.title);
+    let disposition = format!("attachment; filename=\"{}.fi\"", article.id.0);
+    let commits =
+        stream::iter(edits.into_iter().enumerate()).scan(String::new(), move |text, (i, view)| {
+            let author = git_author(&view.creator);
+            let commit = git_fast_import_commit(i + 1, &view.edit, &author, &path, text)
+                .map_err(|e| std::io::Error::other(e.to_string()));
+            future::ready(Some(commit))
+        });
+    Ok((
+        [
+            (CONTENT_TYPE, "text/plain; charset=utf-8".to_string()),
+            (CONTENT_DISPOSITION, disposition),
+        ],
+        Body::from_stream(commits),
+    )
+        .into_response())
+}
+
+/// Find local articles with similar text, which may be candidates for merging. Only for admins.
+#[debug_handler]
+pub(crate) async fn find_duplicates(
+    user: UserExt,
+    Query(params): Query<FindDuplicates>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<Vec<DuplicateArticles>>> {
+    const MAX_PAIRS: usize = 100;
+    check_is_admin(&user)?;
+    if params.threshold <= 0.0 || params.threshold > 1.0 {
+        return Err(anyhow!("Threshold must be between 0 and 1").into());
+    }
+    let articles = Article::read_all(
+        Some(true),
+        None,
+        false,
+        ArticleSort::Updated,
+        None,
+        0,
+        &context,
+    )?;
+    let texts: Vec<_> = articles.iter().map(|a| a.text.as_str()).collect();
+    let duplicates = find_similar_texts(&texts, params.threshold, MAX_PAIRS)
+        .into_iter()
+        .map(|(a, b, similarity)| DuplicateArticles {
+            first: articles[a].clone(),
+            second: articles[b].clone(),
+            similarity,
+        })
+        .collect();
+    Ok(Json(duplicates))
+}
+
+/// List all pending edit conflicts of the current user.
+#[debug_handler]
+pub(crate) async fn list_conflicts(
+    user: UserExt,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<Vec<Conflict>>> {
+    Ok(Json(Conflict::list_for_user(user.person.id, &context)?))
+}
+
+/// Articles with the most edit conflicts within the given time window, for admins to find
+/// contentious articles.
+#[debug_handler]
+pub(crate) async fn get_conflict_stats(
+    user: UserExt,
+    Query(params): Query<GetConflictStats>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<Vec<ConflictStats>>> {
+    check_is_admin(&user)?;
+    let limit = params.limit.unwrap_or(20).clamp(1, 100);
+    let offset = (params.page.unwrap_or(1).max(1) - 1) * limit;
+    Ok(Json(Conflict::stats(
+        params.since,
+        params.until,
+        limit,
+        offset,
+        &context,
+    )?))
+}
+
+/// Dismiss a pending edit conflict of the current user, discarding the conflicting edit.
 #[debug_handler]
 pub async fn delete_conflict(
     user: UserExt,
     context: Data<IbisContext>,
     Form(params): Form<DeleteConflictParams>,
 ) -> BackendResult<Json<()>> {
-    Conflict::delete(params.conflict_id, user.person.id, &context)?;
+    Conflict::delete(
+        params.conflict_id,
+        user.person.id,
+        ConflictOutcome::Abandoned,
+        &context,
+    )?;
     Ok(Json(()))
 }
 
@@ -360,6 +1609,18 @@ pub(crate) async fn follow_article(
     } else {
         Article::unfollow(params.id, &user, &context)?;
     }
+    // Subscribe to remote article on its home instance, so that its updates are delivered even
+    // without following the whole instance
+    let article = Article::read(params.id, &context)?;
+    if !article.local {
+        let person: PersonWrapper = user.person.clone().into();
+        let instance: InstanceWrapper = Instance::read(article.instance_id, &context)?.into();
+        if params.follow {
+            Follow::send_for_article(&person, &article, &instance, &context).await?;
+        } else {
+            UndoFollow::send_for_article(&person, &article, &instance, &context).await?;
+        }
+    }
     Ok(Json(SuccessResponse::default()))
 }
 
@@ -384,24 +1645,34 @@ pub async fn db_conflict_to_api_conflict(
     let patch = Patch::from_str(&conflict.diff)?;
     // apply self.diff to ancestor to get `ours`
     let ours = apply(&ancestor, &patch)?;
-    match merge(&ancestor, &ours, &original_article.text) {
+    match three_way_merge(&ancestor, &ours, &original_article.text) {
         Ok(new_text) => {
-            let person = Person::read(conflict.creator_id, context)?.into();
-            // patch applies cleanly so we are done, federate the change
-            submit_article_update(
-                new_text,
-                conflict.summary.clone(),
-                conflict.previous_version_id.clone(),
-                &original_article,
-                person,
-                false,
+            // if the same change was already made by someone else, there is nothing to submit
+            if new_text != original_article.text {
+                let person = Person::read(conflict.creator_id, context)?.into();
+                // patch applies cleanly so we are done, federate the change
+                submit_article_update(
+                    new_text,
+                    conflict.summary.clone(),
+                    false,
+                    conflict.previous_version_id.clone(),
+                    &original_article,
+                    person,
+                    None,
+                    false,
+                    context,
+                )
+                .await?;
+            }
+            Conflict::delete(
+                conflict.id,
+                conflict.creator_id,
+                ConflictOutcome::Resolved,
                 context,
-            )
-            .await?;
-            Conflict::delete(conflict.id, conflict.creator_id, context)?;
+            )?;
             Ok(None)
         }
-        Err(three_way_merge) => {
+        Err(ConflictMarkers(three_way_merge)) => {
             // there is a merge conflict, user needs to do three-way-merge
             Ok(Some(ApiConflict {
                 id: conflict.id,
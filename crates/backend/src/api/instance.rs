@@ -1,15 +1,39 @@
-use super::{UserExt, empty_to_none};
+use super::{UserExt, check_is_admin, empty_to_none};
 use crate::api::UserExtOpt;
 use activitypub_federation::{config::Data, fetch::object_id::ObjectId};
 use anyhow::anyhow;
 use axum::{Form, Json, extract::Query};
 use axum_macros::debug_handler;
-use ibis_api_client::instance::{FollowInstanceParams, GetInstanceParams, UpdateInstanceParams};
+use ibis_api_client::instance::{
+    AllowInstanceParams,
+    BlockInstanceParams,
+    FollowInstanceParams,
+    GetFederationStats,
+    GetInstanceActivity,
+    GetInstanceFollowers,
+    GetInstanceParams,
+    ReplayDeadLetterParams,
+    RetryDeliveryParams,
+    SetRegistrationOpenParams,
+    SyncInstanceParams,
+    UpdateInstanceParams,
+};
 use ibis_database::{
     common::{
         ResolveObjectParams,
         SuccessResponse,
-        instance::{Instance, InstanceView},
+        article::{Edit, EditView},
+        instance::{
+            AllowedInstance,
+            BlockedInstance,
+            DeliveryStatus,
+            FederationStats,
+            InboundActivity,
+            Instance,
+            InstanceFollowers,
+            InstanceView,
+            QueuedDelivery,
+        },
         user::Person,
         utils::http_protocol_str,
     },
@@ -21,10 +45,14 @@ use ibis_database::{
 };
 use ibis_federate::{
     activities::following::{follow::Follow, undo_follow::UndoFollow},
-    objects::instance::InstanceWrapper,
+    collections::articles_collection::sync_articles,
+    delivery_retry::retry_delivery,
+    inbox_retry::replay_dead_letter,
+    objects::{instance::InstanceWrapper, refresh_stale_actor},
 };
 use moka::sync::Cache;
 use std::{ops::Deref, sync::LazyLock, time::Duration};
+use url::Url;
 
 /// Retrieve details about an instance. If no id is provided, return local instance.
 #[debug_handler]
@@ -35,7 +63,7 @@ pub(crate) async fn get_instance(
 ) -> BackendResult<Json<InstanceView>> {
     use InstanceViewQuery::*;
     let person_id = user.as_ref().map(|u| u.person.id);
-    let instance = match (params.id, params.hostname) {
+    let mut instance = match (params.id, params.hostname) {
         (Some(id), None) => Instance::read_view(Id(id), person_id, &context)?,
         (None, Some(hostname)) => {
             if let Ok(i) = Instance::read_view(Hostname(&hostname), person_id, &context) {
@@ -53,23 +81,70 @@ pub(crate) async fn get_instance(
         }
         _ => return Err(anyhow!("invalid params").into()),
     };
+    instance.instance = refresh_stale_actor(InstanceWrapper(instance.instance), &context)
+        .await?
+        .0;
 
     Ok(Json(instance))
 }
 
+/// Users who follow the instance, ordered by name
+#[debug_handler]
+pub(crate) async fn get_instance_followers(
+    Query(params): Query<GetInstanceFollowers>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<InstanceFollowers>> {
+    let limit = params.limit.unwrap_or(20).clamp(1, 50);
+    let offset = params.offset.unwrap_or_default().max(0);
+    let instance = Instance::read(params.instance_id, &context)?;
+    let followers = Instance::list_followers(instance.id, limit, offset, &context)?;
+    let (total, _) = instance.follow_counts(&context)?;
+    Ok(Json(InstanceFollowers { followers, total }))
+}
+
+/// Change name and topic of the local instance. The topic is federated as instance summary.
+#[debug_handler]
 pub(crate) async fn update_instance(
+    user: UserExt,
     context: Data<IbisContext>,
     Form(mut params): Form<UpdateInstanceParams>,
 ) -> BackendResult<Json<Instance>> {
+    check_is_admin(&user)?;
     empty_to_none(&mut params.name);
     empty_to_none(&mut params.topic);
+    empty_to_none(&mut params.icon_url);
+    let icon_url = match params.icon_url {
+        Some(url) => {
+            let url = Url::parse(&url)?;
+            if !matches!(url.scheme(), "http" | "https") {
+                return Err(anyhow!("Invalid icon url").into());
+            }
+            Some(url.into())
+        }
+        None => None,
+    };
     let form = DbInstanceUpdateForm {
         name: params.name,
         topic: params.topic,
+        icon_url,
     };
     Ok(Json(Instance::update(form, &context)?))
 }
 
+/// Open or close registration of new accounts. Existing users are not affected.
+#[debug_handler]
+pub(crate) async fn set_registration_open(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(params): Form<SetRegistrationOpenParams>,
+) -> BackendResult<Json<Instance>> {
+    check_is_admin(&user)?;
+    Ok(Json(Instance::set_registration_open(
+        params.open,
+        &context,
+    )?))
+}
+
 /// Make the local instance follow a given remote instance, to receive activities about new and
 /// updated articles.
 #[debug_handler]
@@ -92,6 +167,63 @@ pub(crate) async fn follow_instance(
     Ok(Json(SuccessResponse::default()))
 }
 
+/// Block or unblock federation with a remote domain.
+#[debug_handler]
+pub(crate) async fn block_instance(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(params): Form<BlockInstanceParams>,
+) -> BackendResult<Json<SuccessResponse>> {
+    check_is_admin(&user)?;
+    if params.domain.trim().is_empty() || params.domain == context.conf.federation.domain {
+        return Err(anyhow!("Invalid domain").into());
+    }
+    if params.block {
+        BlockedInstance::block(&params.domain, &context)?;
+    } else {
+        BlockedInstance::unblock(&params.domain, &context)?;
+    }
+    Ok(Json(SuccessResponse::default()))
+}
+
+#[debug_handler]
+pub(crate) async fn list_blocked_instances(
+    user: UserExt,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<Vec<BlockedInstance>>> {
+    check_is_admin(&user)?;
+    Ok(Json(BlockedInstance::list(&context)?))
+}
+
+/// Add or remove a remote domain from the allowlist, which is used if `federation_mode` is
+/// `AllowList`.
+#[debug_handler]
+pub(crate) async fn allow_instance(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(params): Form<AllowInstanceParams>,
+) -> BackendResult<Json<SuccessResponse>> {
+    check_is_admin(&user)?;
+    if params.domain.trim().is_empty() {
+        return Err(anyhow!("Invalid domain").into());
+    }
+    if params.allow {
+        AllowedInstance::allow(&params.domain, &context)?;
+    } else {
+        AllowedInstance::disallow(&params.domain, &context)?;
+    }
+    Ok(Json(SuccessResponse::default()))
+}
+
+#[debug_handler]
+pub(crate) async fn list_allowed_instances(
+    user: UserExt,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<Vec<AllowedInstance>>> {
+    check_is_admin(&user)?;
+    Ok(Json(AllowedInstance::list(&context)?))
+}
+
 /// Fetch a remote instance actor. This automatically synchronizes the remote articles collection to
 /// the local instance, and allows for interactions such as following.
 #[debug_handler]
@@ -137,3 +269,98 @@ pub(crate) async fn list_instance_views(
     }
     Ok(Json(instances))
 }
+
+/// Recent edits from a single instance, based on the data which was already received via
+/// federation.
+#[debug_handler]
+pub(crate) async fn get_instance_activity(
+    Query(params): Query<GetInstanceActivity>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<Vec<EditView>>> {
+    let limit = params.limit.unwrap_or(20).clamp(1, 50);
+    Ok(Json(Edit::list_for_instance(
+        params.instance_id,
+        params.before,
+        limit,
+        &context,
+    )?))
+}
+
+/// Overview of federation health for admins. The list of remote instances is paginated and
+/// includes instances without any deliveries.
+#[debug_handler]
+pub(crate) async fn federation_stats(
+    user: UserExt,
+    Query(params): Query<GetFederationStats>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<FederationStats>> {
+    check_is_admin(&user)?;
+    let limit = params.limit.unwrap_or(50).clamp(1, 100);
+    let offset = (params.page.unwrap_or(1).max(1) - 1) * limit;
+    Ok(Json(FederationStats {
+        followers: Instance::read_local_followers_count(&context)?,
+        following: Instance::read_following_count(&context)?,
+        queued_activities: QueuedDelivery::count(false, &context)?,
+        dead_deliveries: QueuedDelivery::count(true, &context)?,
+        failed_deliveries: DeliveryStatus::count_failures(&context)?,
+        peers: DeliveryStatus::list_peers(limit, offset, &context)?,
+    }))
+}
+
+/// Incoming activities which still failed after all retries.
+#[debug_handler]
+pub(crate) async fn list_dead_letters(
+    user: UserExt,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<Vec<InboundActivity>>> {
+    check_is_admin(&user)?;
+    Ok(Json(InboundActivity::list_dead(&context)?))
+}
+
+/// Outgoing deliveries which failed and are waiting for retry, or were given up.
+#[debug_handler]
+pub(crate) async fn list_delivery_queue(
+    user: UserExt,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<Vec<QueuedDelivery>>> {
+    check_is_admin(&user)?;
+    Ok(Json(QueuedDelivery::list(&context)?))
+}
+
+/// Send a queued delivery immediately, for example after the remote instance is back online.
+#[debug_handler]
+pub(crate) async fn retry_delivery_handler(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(params): Form<RetryDeliveryParams>,
+) -> BackendResult<Json<SuccessResponse>> {
+    check_is_admin(&user)?;
+    retry_delivery(params.id, &context).await?;
+    Ok(Json(SuccessResponse::default()))
+}
+
+/// Process a dead letter again, for example after the cause of the failure was fixed.
+#[debug_handler]
+pub(crate) async fn replay_dead_letter_handler(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(params): Form<ReplayDeadLetterParams>,
+) -> BackendResult<Json<SuccessResponse>> {
+    check_is_admin(&user)?;
+    replay_dead_letter(params.id, &context).await?;
+    Ok(Json(SuccessResponse::default()))
+}
+
+/// Import all articles of a remote instance with their edit history, in addition to the updates
+/// which are pushed by followed instances. Can be repeated to pick up missed changes.
+#[debug_handler]
+pub(crate) async fn sync_instance(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(params): Form<SyncInstanceParams>,
+) -> BackendResult<Json<SuccessResponse>> {
+    check_is_admin(&user)?;
+    let instance = Instance::read(params.instance_id, &context)?;
+    sync_articles(&instance, &context).await?;
+    Ok(Json(SuccessResponse::default()))
+}
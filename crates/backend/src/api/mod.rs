@@ -1,22 +1,51 @@
 use crate::api::{
     article::{
         create_article,
+        create_redirect,
         edit_article,
         fork_article,
         get_article,
         get_conflict,
         list_articles,
+        list_conflicts,
+        move_article,
         protect_article,
         resolve_article,
         search_article,
+        search_article_stream,
     },
     comment::{create_comment, edit_comment},
     instance::{follow_instance, get_instance, resolve_instance},
-    user::{get_user, login_user, logout_user, register::register_user},
+    user::{get_user, login_user, logout_all, logout_user, register::register_user},
 };
 use activitypub_federation::config::Data;
 use anyhow::anyhow;
-use article::{delete_conflict, follow_article, remove_article};
+use article::{
+    add_attachment,
+    add_to_category,
+    compare_revisions,
+    delete_conflict,
+    export_article,
+    export_article_history,
+    find_duplicates,
+    follow_article,
+    get_backlinks,
+    get_category,
+    get_conflict_stats,
+    get_contributors,
+    get_edit_reach,
+    get_popular_articles,
+    get_tagged_revisions,
+    import_article,
+    list_removed_articles,
+    preview_article,
+    remove_article,
+    remove_attachment,
+    revert_article,
+    revert_article_to_date,
+    tag_revision,
+    validate_edit,
+};
 use axum::{
     Extension,
     Json,
@@ -28,27 +57,49 @@ use axum::{
 use axum_macros::{FromRequestParts, debug_handler};
 use comment::get_comment;
 use http::StatusCode;
-use ibis_api_client::article::GetEditList;
+use ibis_api_client::article::{GetAnonymousEdits, GetEditList, GetRecentChanges};
 use ibis_database::{
     common::{
-        article::{Edit, EditView},
+        article::{AnonymousEdit, Edit, EditView},
         instance::{Instance, SiteView},
         user::{LocalUserView, Person},
     },
     error::BackendResult,
     impls::{IbisContext, edit::ViewEditParams},
 };
-use instance::{list_instance_views, update_instance};
+use instance::{
+    allow_instance,
+    block_instance,
+    federation_stats,
+    get_instance_activity,
+    get_instance_followers,
+    list_allowed_instances,
+    list_blocked_instances,
+    list_dead_letters,
+    list_delivery_queue,
+    list_instance_views,
+    replay_dead_letter_handler,
+    retry_delivery_handler,
+    set_registration_open,
+    sync_instance,
+    update_instance,
+};
 use std::ops::Deref;
 use user::{
     article_notif_mark_as_read,
+    ban_user,
     change_password,
     change_password_after_reset,
     count_notifications,
+    get_user_articles,
+    get_user_contributions,
     get_user_follows,
+    get_watchlist,
+    list_admins,
     list_notifications,
     register::authenticate_with_oauth,
     request_reset_password,
+    set_user_role,
     update_user_profile,
     verify_email,
 };
@@ -66,13 +117,39 @@ pub fn api_routes() -> Router<()> {
         )
         .route("/article/list", get(list_articles))
         .route("/article/fork", post(fork_article))
+        .route("/article/validate_edit", post(validate_edit))
+        .route("/article/preview", post(preview_article))
         .route("/article/resolve", get(resolve_article))
         .route("/article/protect", post(protect_article))
+        .route("/article/redirect", post(create_redirect))
+        .route("/article/move", post(move_article))
+        .route("/article/revert", post(revert_article))
+        .route("/article/revert_to_date", post(revert_article_to_date))
         .route("/article/remove", post(remove_article))
+        .route("/article/removed", get(list_removed_articles))
         .route("/article/follow", post(follow_article))
+        .route("/article/duplicates", get(find_duplicates))
+        .route("/article/edit_reach", get(get_edit_reach))
+        .route("/article/popular", get(get_popular_articles))
+        .route("/article/backlinks", get(get_backlinks))
+        .route("/article/compare", get(compare_revisions))
+        .route("/article/tag", post(tag_revision))
+        .route("/article/tags", get(get_tagged_revisions))
+        .route("/article/contributors", get(get_contributors))
+        .route("/article/export", get(export_article))
+        .route("/article/import", post(import_article))
+        .route("/article/export_history", get(export_article_history))
+        .route("/article/attachment", post(add_attachment))
+        .route("/article/attachment", delete(remove_attachment))
+        .route("/article/category", post(add_to_category))
+        .route("/category", get(get_category))
         .route("/edit/list", get(edit_list))
+        .route("/edit/recent", get(recent_changes))
+        .route("/edit/anonymous", get(anonymous_edits))
         .route("/conflict", get(get_conflict))
         .route("/conflict", delete(delete_conflict))
+        .route("/conflict/list", get(list_conflicts))
+        .route("/conflict/stats", get(get_conflict_stats))
         .route("/comment", post(create_comment))
         .route("/comment", patch(edit_comment))
         .route("/comment", get(get_comment))
@@ -81,9 +158,35 @@ pub fn api_routes() -> Router<()> {
         .route("/instance/follow", post(follow_instance))
         .route("/instance/resolve", get(resolve_instance))
         .route("/instance/list", get(list_instance_views))
+        .route("/instance/federation_stats", get(federation_stats))
+        .route("/instance/activity", get(get_instance_activity))
+        .route("/instance/followers", get(get_instance_followers))
+        .route("/instance/dead_letters", get(list_dead_letters))
+        .route(
+            "/instance/dead_letters/replay",
+            post(replay_dead_letter_handler),
+        )
+        .route("/instance/delivery_queue", get(list_delivery_queue))
+        .route(
+            "/instance/delivery_queue/retry",
+            post(retry_delivery_handler),
+        )
+        .route("/instance/block", post(block_instance))
+        .route("/instance/blocked", get(list_blocked_instances))
+        .route("/instance/allow", post(allow_instance))
+        .route("/instance/allowed", get(list_allowed_instances))
+        .route("/instance/registration", post(set_registration_open))
+        .route("/instance/sync", post(sync_instance))
         .route("/search", get(search_article))
+        .route("/search/stream", get(search_article_stream))
         .route("/user", get(get_user))
+        .route("/user/articles", get(get_user_articles))
+        .route("/user/contributions", get(get_user_contributions))
         .route("/user/follows", get(get_user_follows))
+        .route("/user/watchlist", get(get_watchlist))
+        .route("/user/role", post(set_user_role))
+        .route("/user/admins", get(list_admins))
+        .route("/user/ban", post(ban_user))
         .route("/user/notifications/list", get(list_notifications))
         .route("/user/notifications/count", get(count_notifications))
         .route(
@@ -93,6 +196,7 @@ pub fn api_routes() -> Router<()> {
         .route("/account/register", post(register_user))
         .route("/account/login", post(login_user))
         .route("/account/logout", post(logout_user))
+        .route("/account/logout_all", post(logout_all))
         .route("/account/update", post(update_user_profile))
         .route("/account/oauth/authenticate", post(authenticate_with_oauth))
         .route("/account/verify_email", post(verify_email))
@@ -109,12 +213,19 @@ pub fn api_routes() -> Router<()> {
 }
 
 pub fn check_is_admin(user: &LocalUserView) -> BackendResult<()> {
-    if !user.local_user.admin {
+    if !user.local_user.is_admin() {
         return Err(anyhow!("Only admin can perform this action").into());
     }
     Ok(())
 }
 
+pub fn check_is_moderator(user: &LocalUserView) -> BackendResult<()> {
+    if !user.local_user.role.can_moderate() {
+        return Err(anyhow!("Only moderators can perform this action").into());
+    }
+    Ok(())
+}
+
 #[debug_handler]
 pub(crate) async fn site_view(
     context: Data<IbisContext>,
@@ -128,11 +239,16 @@ pub(crate) async fn site_view(
         .map(Into::into)
         .collect();
 
+    let instance = Instance::read_local(&context)?;
+    let mut config = context.conf.options.clone();
+    if let Some(registration_open) = instance.registration_open {
+        config.registration_open = registration_open;
+    }
     Ok(Json(SiteView {
         my_profile: user.inner(),
-        config: context.conf.options.clone(),
+        config,
         admin: Person::read_admin(&context)?,
-        instance: Instance::read_local(&context)?,
+        instance,
         oauth_providers,
     }))
 }
@@ -151,7 +267,39 @@ pub async fn edit_list(
     } else {
         return Err(anyhow!("Must provide article_id or person_id").into());
     };
-    Ok(Json(Edit::list_views(params, &user.inner(), &context)?))
+    Ok(Json(Edit::list_views(
+        params,
+        query.hide_minor.unwrap_or_default(),
+        &user.inner(),
+        &context,
+    )?))
+}
+
+/// Edits of an article which were made without login, with the identifier of the client which
+/// made them. Allows moderators to tell anonymous contributors apart.
+#[debug_handler]
+pub(crate) async fn anonymous_edits(
+    Query(query): Query<GetAnonymousEdits>,
+    user: UserExt,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<Vec<AnonymousEdit>>> {
+    check_is_moderator(&user)?;
+    Ok(Json(Edit::list_anonymous(query.article_id, &context)?))
+}
+
+/// Recent edits across all articles, newest first.
+#[debug_handler]
+pub async fn recent_changes(
+    Query(query): Query<GetRecentChanges>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<Vec<EditView>>> {
+    let limit = query.limit.unwrap_or(50).clamp(1, 100);
+    Ok(Json(Edit::list_recent(
+        query.only_local.unwrap_or_default(),
+        query.since,
+        limit,
+        &context,
+    )?))
 }
 
 /// Trims the string param, and converts to None if it is empty
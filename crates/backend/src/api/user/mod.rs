@@ -1,4 +1,4 @@
-use super::{UserExt, empty_to_none};
+use super::{UserExt, check_is_admin, check_is_moderator, empty_to_none};
 use crate::api::UserExtOpt;
 use activitypub_federation::config::Data;
 use anyhow::anyhow;
@@ -10,11 +10,15 @@ use chrono::Utc;
 use ibis_api_client::{
     notifications::MarkAsReadParams,
     user::{
+        BanUserParams,
         ChangePasswordAfterReset,
         ChangePasswordParams,
+        GetUserArticles,
+        GetUserContributions,
         GetUserParams,
         LoginUserParams,
         PasswordReset,
+        SetUserRoleParams,
         UpdateUserParams,
         VerifyEmailParams,
     },
@@ -23,6 +27,7 @@ use ibis_database::{
     common::{
         AUTH_COOKIE,
         SuccessResponse,
+        article::{Article, Edit, EditView},
         instance::InstanceFollow,
         notifications::ApiNotification,
         user::{LocalUser, LocalUserView, Person},
@@ -34,12 +39,16 @@ use ibis_database::{
     error::{BackendError, BackendResult},
     impls::{
         IbisContext,
+        auth_token::AuthToken,
         notifications::Notification,
         read_jwt_secret,
         user::{LocalUserUpdateForm, LocalUserViewQuery, PersonUpdateForm},
     },
 };
-use ibis_federate::validate::{validate_display_name, validate_email};
+use ibis_federate::{
+    objects::{refresh_stale_actor, user::PersonWrapper},
+    validate::{validate_display_name, validate_email},
+};
 use jsonwebtoken::{
     DecodingKey,
     EncodingKey,
@@ -67,17 +76,24 @@ struct Claims {
     pub iat: i64,
     /// Expiration time
     pub exp: u64,
+    /// Session token stored in `auth_token` table, to allow revoking the session
+    pub jti: String,
 }
 
-pub async fn validate(jwt: &str, context: &IbisContext) -> BackendResult<LocalUserView> {
+fn decode_claims(jwt: &str, context: &IbisContext) -> BackendResult<Claims> {
     let validation = Validation::default();
     let secret = read_jwt_secret(context)?;
     let key = DecodingKey::from_secret(secret.as_bytes());
-    let claims = decode::<Claims>(jwt, &key, &validation)?;
-    LocalUserView::read(
-        LocalUserViewQuery::LocalNameOrEmail(&claims.claims.sub),
-        context,
-    )
+    Ok(decode::<Claims>(jwt, &key, &validation)?.claims)
+}
+
+pub async fn validate(jwt: &str, context: &IbisContext) -> BackendResult<LocalUserView> {
+    let claims = decode_claims(jwt, context)?;
+    let user = LocalUserView::read(LocalUserViewQuery::LocalNameOrEmail(&claims.sub), context)?;
+    if !AuthToken::is_valid(&claims.jti, user.person.id, context)? {
+        return Err(anyhow!("Session was revoked").into());
+    }
+    Ok(user)
 }
 
 fn validate_password(user: &LocalUserView, password: &str) -> BackendResult<()> {
@@ -123,6 +139,7 @@ pub(crate) fn add_login_cookie(
         iss: context.conf.domain.clone(),
         iat: Utc::now().timestamp(),
         exp: get_current_timestamp() + 60 * 60 * 24 * 365,
+        jti: AuthToken::create(person.id, context)?,
     };
 
     let secret = read_jwt_secret(context)?;
@@ -157,6 +174,24 @@ pub(crate) async fn logout_user(
     context: Data<IbisContext>,
     jar: CookieJar,
 ) -> BackendResult<(CookieJar, Json<SuccessResponse>)> {
+    if let Some(claims) = jar
+        .get(AUTH_COOKIE)
+        .and_then(|c| decode_claims(c.value(), &context).ok())
+    {
+        AuthToken::delete(&claims.jti, &context)?;
+    }
+    let jar = jar.remove(create_cookie(String::new(), &context));
+    Ok((jar, Json(SuccessResponse::default())))
+}
+
+/// Revoke all sessions of the user, including those on other devices.
+#[debug_handler]
+pub(crate) async fn logout_all(
+    user: UserExt,
+    context: Data<IbisContext>,
+    jar: CookieJar,
+) -> BackendResult<(CookieJar, Json<SuccessResponse>)> {
+    AuthToken::delete_all(user.person.id, &context)?;
     let jar = jar.remove(create_cookie(String::new(), &context));
     Ok((jar, Json(SuccessResponse::default())))
 }
@@ -166,13 +201,105 @@ pub(crate) async fn get_user(
     params: Query<GetUserParams>,
     context: Data<IbisContext>,
 ) -> BackendResult<Json<Person>> {
-    Ok(Json(Person::read_from_name(
-        &params.name,
-        &params.domain,
+    let person = Person::read_from_name(&params.name, &params.domain, &context)?;
+    let person = refresh_stale_actor(PersonWrapper::from(person), &context).await?;
+    Ok(Json(person.deref().clone()))
+}
+
+/// List articles which were originally created by the user, including remote users.
+#[debug_handler]
+pub(crate) async fn get_user_articles(
+    Query(params): Query<GetUserArticles>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<Vec<Article>>> {
+    let person = Person::read_from_name(&params.name, &params.domain, &context)?;
+    let limit = params.limit.unwrap_or(20).clamp(1, 50);
+    let offset = (params.page.unwrap_or(1).max(1) - 1) * limit;
+    Ok(Json(Article::read_created_by(
+        person.id, limit, offset, &context,
+    )?))
+}
+
+/// List edits made by the user across all articles, newest first.
+#[debug_handler]
+pub(crate) async fn get_user_contributions(
+    Query(params): Query<GetUserContributions>,
+    user: UserExtOpt,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<Vec<EditView>>> {
+    let person_id = match (params.person_id, &params.name) {
+        (Some(person_id), _) => person_id,
+        (None, Some(name)) => Person::read_from_name(name, &params.domain, &context)?.id,
+        (None, None) => return Err(anyhow!("Must provide person_id or name").into()),
+    };
+    let limit = params.limit.unwrap_or(20).clamp(1, 50);
+    let offset = (params.page.unwrap_or(1).max(1) - 1) * limit;
+    Ok(Json(Edit::list_for_person(
+        person_id,
+        limit,
+        offset,
+        &user.inner(),
         &context,
     )?))
 }
 
+/// Articles which the user follows to get notified about changes, most recently edited first
+#[debug_handler]
+pub(crate) async fn get_watchlist(
+    user: UserExt,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<Vec<Article>>> {
+    Ok(Json(Article::read_followed(user.local_user.id, &context)?))
+}
+
+/// Change the role of a local user. The last remaining admin can't be demoted, so that the
+/// instance can always be managed.
+#[debug_handler]
+pub(crate) async fn set_user_role(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(params): Form<SetUserRoleParams>,
+) -> BackendResult<Json<LocalUser>> {
+    check_is_admin(&user)?;
+    Ok(Json(LocalUser::update_role(
+        params.person_id,
+        params.role,
+        &context,
+    )?))
+}
+
+/// Ban a user from editing articles, or remove the ban. Edits of banned remote users are also
+/// rejected when received via federation.
+#[debug_handler]
+pub(crate) async fn ban_user(
+    user: UserExt,
+    context: Data<IbisContext>,
+    Form(params): Form<BanUserParams>,
+) -> BackendResult<Json<Person>> {
+    check_is_moderator(&user)?;
+    if params.person_id == user.person.id {
+        return Err(anyhow!("Can't ban yourself").into());
+    }
+    if Person::list_admins(&context)?
+        .iter()
+        .any(|a| a.id == params.person_id)
+    {
+        return Err(anyhow!("Admins can't be banned").into());
+    }
+    Ok(Json(Person::update_ban(
+        params.person_id,
+        params.ban,
+        params.expires,
+        &context,
+    )?))
+}
+
+/// List all admins of the local instance
+#[debug_handler]
+pub(crate) async fn list_admins(context: Data<IbisContext>) -> BackendResult<Json<Vec<Person>>> {
+    Ok(Json(Person::list_admins(&context)?))
+}
+
 #[debug_handler]
 pub(crate) async fn get_user_follows(
     user: UserExt,
@@ -256,7 +383,7 @@ pub(crate) async fn change_password(
     Form(params): Form<ChangePasswordParams>,
 ) -> BackendResult<Json<SuccessResponse>> {
     validate_password(&user, &params.old_password)?;
-    validate_new_password(&params.new_password, &params.confirm_new_password)?;
+    validate_new_password(&params.new_password, &params.confirm_new_password, &context)?;
     LocalUser::update_password(params.new_password, user.local_user.id, &context)?;
     Ok(Json(SuccessResponse::default()))
 }
@@ -279,12 +406,16 @@ pub async fn change_password_after_reset(
     context: Data<IbisContext>,
     Form(params): Form<ChangePasswordAfterReset>,
 ) -> BackendResult<Json<SuccessResponse>> {
+    // Validate first so that a mistyped password doesn't consume the token.
+    validate_new_password(&params.password, &params.confirm_password, &context)?;
+
     let local_user_id =
         PasswordResetRequest::read_and_delete(&params.token, &context)?.local_user_id;
 
-    validate_new_password(&params.password, &params.confirm_password)?;
-
     LocalUser::update_password(params.password, local_user_id, &context)?;
+    // whoever knew the old password may still be logged in
+    let user = LocalUserView::read(LocalUserViewQuery::Id(local_user_id), &context)?;
+    AuthToken::delete_all(user.person.id, &context)?;
 
     Ok(Json(SuccessResponse::default()))
 }
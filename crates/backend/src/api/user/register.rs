@@ -11,7 +11,10 @@ use ibis_api_client::user::{
     RegistrationResponse,
 };
 use ibis_database::{
-    common::user::{LocalUser, LocalUserView},
+    common::{
+        instance::Instance,
+        user::{LocalUser, LocalUserView, UserRole},
+    },
     config::OAuthProvider,
     email::verification::send_verification_email,
     error::{BackendError, BackendResult},
@@ -20,7 +23,7 @@ use ibis_database::{
         user::{LocalUserViewQuery, OAuthAccount, OAuthAccountInsertForm},
     },
 };
-use ibis_federate::validate::{validate_email, validate_user_name};
+use ibis_federate::validate::{validate_email, validate_password, validate_user_name};
 use log::debug;
 use serde::{Deserialize, Serialize};
 
@@ -33,11 +36,11 @@ pub async fn register_user(
     Form(mut params): Form<RegisterUserParams>,
 ) -> RegisterReturnType {
     empty_to_none(&mut params.email);
-    if !context.conf.options.registration_open {
+    if !Instance::registration_open(&context)? {
         return Err(anyhow!("Registration is closed").into());
     }
 
-    validate_new_password(&params.password, &params.confirm_password)?;
+    validate_new_password(&params.password, &params.confirm_password, &context)?;
 
     if context.conf.options.email_required && params.email.is_none() {
         return Err(anyhow!("Email required").into());
@@ -49,7 +52,7 @@ pub async fn register_user(
     let user = LocalUserView::create(
         params.username,
         Some(params.password),
-        false,
+        UserRole::default(),
         None,
         &context,
     )?;
@@ -143,7 +146,8 @@ pub async fn authenticate_with_oauth(
                 .ok_or(anyhow!("Username is required to register new account"))?;
 
             check_new_user(&username, Some(&email), &context)?;
-            let user = LocalUserView::create(username, None, false, Some(email), &context)?;
+            let user =
+                LocalUserView::create(username, None, UserRole::default(), Some(email), &context)?;
 
             // Create the oauth account
             let oauth_account_form = OAuthAccountInsertForm {
@@ -245,11 +249,12 @@ fn register_return(
     ))
 }
 
-pub(super) fn validate_new_password(password: &str, confirm_password: &str) -> BackendResult<()> {
-    if password.len() < 8 {
-        return Err(anyhow!("Passwords must have at least 8 characters").into());
-    }
-
+pub(super) fn validate_new_password(
+    password: &str,
+    confirm_password: &str,
+    context: &IbisContext,
+) -> BackendResult<()> {
+    validate_password(password, &context.conf.options)?;
     if password != confirm_password {
         return Err(anyhow!("Passwords dont match").into());
     }
@@ -6,10 +6,15 @@ use ibis_database::{
     impls::IbisContext,
     scheduled_tasks,
 };
-use ibis_federate::VerifyUrlData;
+use ibis_federate::{
+    VerifyUrlData,
+    delivery_retry::federation_client,
+    objects::instance::InstanceWrapper,
+    start_retry_task,
+};
 use log::info;
 use server::{setup::setup, start_server};
-use std::{net::SocketAddr, thread};
+use std::{net::SocketAddr, ops::Deref, thread};
 use tokio::sync::oneshot;
 
 pub mod api;
@@ -19,30 +24,47 @@ pub mod utils;
 pub async fn start(
     config: IbisConfig,
     override_hostname: Option<SocketAddr>,
-    notify_start: Option<oneshot::Sender<()>>,
+    notify_start: Option<oneshot::Sender<IbisContext>>,
 ) -> BackendResult<()> {
     let context = IbisContext::init(config, override_hostname.is_some())?;
-    let data = FederationConfig::builder()
-        .domain(context.conf.federation.domain.clone())
-        .url_verifier(Box::new(VerifyUrlData(context.conf.clone())))
-        .app_data(context.clone())
-        .client(context.client.clone().into())
-        .http_fetch_limit(1000)
-        .debug(cfg!(debug_assertions))
-        .build()
-        .await?;
+    let data = federation_config(&context, None).await?;
 
     if Instance::read_local(&data).is_err() {
         info!("Running setup for new instance");
         setup(&data.to_request_data()).await?;
     }
 
-    let db_pool = data.db_pool.clone();
+    // Sign fetches with the local instance actor, so that objects can also be fetched from
+    // instances which use authorized fetch.
+    let local_instance = InstanceWrapper(Instance::read_local(&data)?);
+    let data = federation_config(&context, Some(&local_instance)).await?;
+
+    start_retry_task(data.to_request_data());
+
+    let context_ = data.deref().clone();
     thread::spawn(move || {
-        scheduled_tasks::start(db_pool);
+        scheduled_tasks::start(context_);
     });
 
     start_server(data, override_hostname, notify_start).await?;
 
     Ok(())
 }
+
+async fn federation_config(
+    context: &IbisContext,
+    signed_fetch_actor: Option<&InstanceWrapper>,
+) -> BackendResult<FederationConfig<IbisContext>> {
+    let mut builder = FederationConfig::builder();
+    builder
+        .domain(context.conf.federation.domain.clone())
+        .url_verifier(Box::new(VerifyUrlData(context.clone())))
+        .app_data(context.clone())
+        .client(federation_client(context))
+        .http_fetch_limit(1000)
+        .debug(cfg!(debug_assertions));
+    if let Some(actor) = signed_fetch_actor {
+        builder.signed_fetch_actor(actor);
+    }
+    Ok(builder.build().await?)
+}
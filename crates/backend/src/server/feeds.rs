@@ -0,0 +1,140 @@
+use activitypub_federation::config::Data;
+use atom_syndication::{EntryBuilder, Feed, FeedBuilder, LinkBuilder, Person};
+use axum::{
+    Router,
+    extract::{Path, Query},
+    http::header::CONTENT_TYPE,
+    response::IntoResponse,
+    routing::get,
+};
+use chrono::Utc;
+use ibis_api_client::article::GetRecentChanges;
+use ibis_database::{
+    common::{
+        article::{Article, Edit, EditView},
+        instance::Instance,
+        newtypes::ArticleId,
+        utils::http_protocol_str,
+    },
+    error::BackendResult,
+    impls::{IbisContext, edit::ViewEditParams},
+};
+use ibis_frontend_components::utils::formatting::{article_path, edit_path, instance_title};
+
+/// Number of edits included in each feed
+const FEED_LENGTH: usize = 50;
+
+/// Atom feeds for recent changes and article history, so that users can subscribe with a feed
+/// reader.
+pub(super) fn config() -> Router<()> {
+    Router::new()
+        .route("/feeds/recent_changes", get(recent_changes_feed))
+        .route("/feeds/article/{id}", get(article_history_feed))
+}
+
+async fn recent_changes_feed(
+    Query(query): Query<GetRecentChanges>,
+    context: Data<IbisContext>,
+) -> BackendResult<impl IntoResponse> {
+    let instance = Instance::read_local(&context)?;
+    let edits = Edit::list_recent(
+        query.only_local.unwrap_or_default(),
+        query.since,
+        FEED_LENGTH as i64,
+        &context,
+    )?;
+    let title = format!("Recent changes - {}", instance_title(&instance));
+    let feed = edits_feed(
+        title,
+        "/feeds/recent_changes",
+        "/recent_changes",
+        edits,
+        &context,
+    );
+    Ok(atom_response(feed))
+}
+
+async fn article_history_feed(
+    Path(id): Path<ArticleId>,
+    context: Data<IbisContext>,
+) -> BackendResult<impl IntoResponse> {
+    let article = Article::read(id, &context)?;
+    let mut edits = Edit::list_views(
+        ViewEditParams::ArticleId(article.id),
+        false,
+        &None,
+        &context,
+    )?;
+    // edits are returned oldest first
+    edits.reverse();
+    edits.truncate(FEED_LENGTH);
+    let title = format!("History of {}", article.title());
+    let feed = edits_feed(
+        title,
+        &format!("/feeds/article/{}", article.id.0),
+        &format!("{}/history", article_path(&article)),
+        edits,
+        &context,
+    );
+    Ok(atom_response(feed))
+}
+
+/// Build a feed where each entry is an edit, newest first. Paths are relative to the local
+/// instance.
+fn edits_feed(
+    title: String,
+    self_path: &str,
+    html_path: &str,
+    edits: Vec<EditView>,
+    context: &IbisContext,
+) -> Feed {
+    let base_url = format!(
+        "{}://{}",
+        http_protocol_str(),
+        context.conf.federation.domain
+    );
+    let updated = edits
+        .first()
+        .map(|e| e.edit.published)
+        .unwrap_or_else(Utc::now);
+    let entries = edits
+        .into_iter()
+        .map(|e| {
+            let link = format!("{base_url}{}", edit_path(&e.edit, &e.article));
+            EntryBuilder::default()
+                .id(e.edit.ap_id.to_string())
+                .title(e.edit.summary)
+                .author(Person {
+                    name: e.creator.username,
+                    uri: Some(e.creator.ap_id.to_string()),
+                    ..Default::default()
+                })
+                .link(LinkBuilder::default().href(link).build())
+                .updated(e.edit.published)
+                .build()
+        })
+        .collect::<Vec<_>>();
+    FeedBuilder::default()
+        .id(format!("{base_url}{self_path}"))
+        .title(title)
+        .updated(updated)
+        .link(
+            LinkBuilder::default()
+                .href(format!("{base_url}{self_path}"))
+                .rel("self")
+                .build(),
+        )
+        .link(
+            LinkBuilder::default()
+                .href(format!("{base_url}{html_path}"))
+                .rel("alternate")
+                .mime_type(Some("text/html".to_string()))
+                .build(),
+        )
+        .entries(entries)
+        .build()
+}
+
+fn atom_response(feed: Feed) -> impl IntoResponse {
+    ([(CONTENT_TYPE, "application/atom+xml")], feed.to_string())
+}
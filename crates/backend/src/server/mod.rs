@@ -1,5 +1,5 @@
 use crate::api::api_routes;
-use activitypub_federation::config::{FederationConfig, FederationMiddleware};
+use activitypub_federation::config::{Data, FederationConfig, FederationMiddleware};
 use assets::file_and_error_handler;
 use axum::{
     Extension,
@@ -7,7 +7,7 @@ use axum::{
     ServiceExt,
     body::Body,
     extract::State,
-    http::Request,
+    http::{HeaderValue, Request, header::CONTENT_SECURITY_POLICY},
     middleware::from_fn_with_state,
     response::{IntoResponse, Response},
     routing::get,
@@ -15,23 +15,31 @@ use axum::{
 use ibis_database::{common::Auth, error::BackendResult, impls::IbisContext};
 use ibis_federate::{nodeinfo, routes::federation_routes, webfinger};
 use ibis_frontend::app::{App, shell};
-use leptos::prelude::*;
+use leptos::{
+    nonce::{provide_nonce, use_nonce},
+    prelude::*,
+};
 use leptos_axum::{LeptosRoutes, generate_route_list};
 use log::info;
 use middleware::{FEDERATION_ROUTES_PREFIX, auth_middleware, federation_routes_middleware};
-use std::{net::SocketAddr, ops::Deref, sync::Arc};
+use std::{
+    net::SocketAddr,
+    ops::Deref,
+    sync::{Arc, OnceLock},
+};
 use tokio::{net::TcpListener, sync::oneshot};
 use tower_http::{compression::CompressionLayer, cors::CorsLayer};
 use tower_layer::Layer;
 
 mod assets;
+mod feeds;
 mod middleware;
 pub(super) mod setup;
 
 pub(super) async fn start_server(
     context: FederationConfig<IbisContext>,
     override_hostname: Option<SocketAddr>,
-    notify_start: Option<oneshot::Sender<()>>,
+    notify_start: Option<oneshot::Sender<IbisContext>>,
 ) -> BackendResult<()> {
     let leptos_options = get_config_from_str(include_str!("../../../../Cargo.toml"))?;
     let mut addr = leptos_options.site_addr;
@@ -40,7 +48,8 @@ pub(super) async fn start_server(
     }
     let routes = generate_route_list(App);
 
-    let arc_data = Arc::new(context.deref().clone());
+    let app_data = context.deref().clone();
+    let arc_data = Arc::new(app_data.clone());
     let app = Router::new()
         .leptos_routes_with_handler(routes, get(leptos_routes_handler))
         .fallback(file_and_error_handler)
@@ -49,6 +58,7 @@ pub(super) async fn start_server(
         .nest("/api/v1", api_routes())
         .merge(nodeinfo::config())
         .merge(webfinger::config())
+        .merge(feeds::config())
         .layer(FederationMiddleware::new(context))
         .layer(CorsLayer::permissive())
         .layer(CompressionLayer::new())
@@ -62,18 +72,31 @@ pub(super) async fn start_server(
     info!("Listening on {}", &addr);
     let listener = TcpListener::bind(&addr).await?;
     if let Some(notify_start) = notify_start {
-        notify_start.send(()).expect("send oneshot");
+        // tests use the context to access server state directly
+        if notify_start.send(app_data).is_err() {
+            panic!("send oneshot");
+        }
     }
-    axum::serve(listener, app_with_middleware.into_make_service()).await?;
+    // connection address is needed to identify clients without login
+    axum::serve(
+        listener,
+        app_with_middleware.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
     Ok(())
 }
 
-/// Make auth token available in hydrate mode
+/// Make auth token available in hydrate mode, and set Content-Security-Policy header with
+/// nonce for the hydration scripts.
 async fn leptos_routes_handler(
     auth: Option<Extension<Auth>>,
+    context: Data<IbisContext>,
     State(leptos_options): State<LeptosOptions>,
     request: Request<Body>,
 ) -> Response {
+    let policy = context.conf.content_security_policy.clone();
+    let nonce = Arc::new(OnceLock::new());
+    let nonce_ = nonce.clone();
     let leptos_options_ = leptos_options.clone();
     let handler = leptos_axum::render_app_async_with_context(
         move || {
@@ -81,9 +104,27 @@ async fn leptos_routes_handler(
             if let Some(auth) = &auth {
                 provide_context(auth.0.clone());
             }
+            if !policy.is_empty() {
+                provide_nonce();
+                if let Some(n) = use_nonce() {
+                    nonce_.set(n.to_string()).ok();
+                }
+            }
         },
         move || shell(leptos_options.clone()),
     );
 
-    handler(request).await.into_response()
+    let mut response = handler(request).await.into_response();
+    if let Some(nonce) = nonce.get() {
+        let policy = context
+            .conf
+            .content_security_policy
+            .replace("{nonce}", nonce);
+        if let Ok(value) = HeaderValue::from_str(&policy) {
+            response
+                .headers_mut()
+                .insert(CONTENT_SECURITY_POLICY, value);
+        }
+    }
+    response
 }
@@ -4,9 +4,9 @@ use chrono::Utc;
 use ibis_database::{
     common::{
         MAIN_PAGE_NAME,
-        article::{Article, EditVersion},
+        article::{Article, EditVersion, ProtectionLevel},
         instance::Instance,
-        user::{LocalUserView, Person},
+        user::{LocalUserView, Person, UserRole},
         utils::http_protocol_str,
     },
     error::BackendError,
@@ -45,13 +45,14 @@ pub async fn setup(context: &Data<IbisContext>) -> Result<(), BackendError> {
         local: true,
         topic: None,
         name: None,
+        icon_url: None,
     };
     let instance = Instance::create(&form, context)?;
 
     let admin = LocalUserView::create(
         context.conf.setup.admin_username.clone(),
         Some(context.conf.setup.admin_password.clone()),
-        true,
+        UserRole::Admin,
         None,
         context,
     )?;
@@ -66,25 +67,29 @@ pub async fn setup(context: &Data<IbisContext>) -> Result<(), BackendError> {
         ap_id: generate_article_ap_id(MAIN_PAGE_NAME, &instance)?,
         instance_id: instance.id,
         local: true,
-        protected: true,
+        protection: ProtectionLevel::AdminOnly,
         updated: Utc::now(),
         pending: false,
+        forked_by: None,
     };
     let article = Article::create_or_update(form, admin.person.id, context).await?;
     // also create an article so its included in most recently edited list
     submit_article_update(
         MAIN_PAGE_DEFAULT_TEXT.to_string(),
         "Default main page".to_string(),
+        false,
         EditVersion::default(),
         &article,
         admin.person.into(),
+        None,
         true,
         context,
     )
     .await?;
 
-    // create ghost user
+    // create ghost and anonymous users, so that nobody can register with these names
     Person::ghost(context)?;
+    Person::anonymous(context)?;
 
     Ok(())
 }
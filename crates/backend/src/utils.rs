@@ -1,20 +1,31 @@
 use anyhow::anyhow;
-use diffy::{Patch, apply};
+use axum::http::HeaderMap;
+use diffy::{Patch, apply, create_patch};
 use ibis_database::{
     DbUrl,
     common::{
         article::{Edit, EditVersion},
         instance::Instance,
+        user::Person,
         utils::{extract_domain, http_protocol_str},
     },
     error::BackendResult,
 };
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+    hash::{DefaultHasher, Hash, Hasher},
+    net::IpAddr,
+};
 use url::Url;
 
-/// Starting from empty string, apply edits until the specified version is reached. If no version is
-/// given, apply all edits up to latest version.
+/// Starting from empty string, apply edits in the order they were applied to the article, until
+/// the specified version is reached.
+///
+/// The diff of a merged edit is based on the latest version at the time, so edits can't be
+/// ordered by `previous_version_id` alone. Instead the links are used to verify that the history
+/// is complete: each edit must be based on a version which comes before it.
 ///
-/// TODO: testing
 /// TODO: should cache all these generated versions
 pub(super) fn generate_article_version(
     edits: &Vec<Edit>,
@@ -24,14 +35,55 @@ pub(super) fn generate_article_version(
     if version == &EditVersion::default() {
         return Ok(generated);
     }
+    let mut known_versions = HashSet::from([EditVersion::default()]);
     for e in edits {
+        if !known_versions.contains(&e.previous_version_id) {
+            return Err(anyhow!(
+                "Edit {} is based on unknown version {}, article history is incomplete",
+                e.hash.hash(),
+                e.previous_version_id.hash()
+            )
+            .into());
+        }
         let patch = Patch::from_str(&e.diff)?;
-        generated = apply(&generated, &patch)?;
+        generated = apply(&generated, &patch)
+            .map_err(|err| anyhow!("Failed to apply edit {}: {err}", e.hash.hash()))?;
         if &e.hash == version {
             return Ok(generated);
         }
+        known_versions.insert(e.hash.clone());
     }
-    Err(anyhow!("failed to generate article version").into())
+    Err(anyhow!("Version {} does not exist for this article", version.hash()).into())
+}
+
+/// Unified diff between the article text at two versions, which don't need to be adjacent. If
+/// `from` is newer than `to` they are swapped, so that the diff always goes forward in time.
+pub(super) fn diff_article_versions(
+    edits: &Vec<Edit>,
+    from: &EditVersion,
+    to: &EditVersion,
+) -> BackendResult<String> {
+    // the empty version comes before all edits
+    let position = |version: &EditVersion| -> BackendResult<Option<usize>> {
+        if version == &EditVersion::default() {
+            return Ok(None);
+        }
+        edits
+            .iter()
+            .position(|e| &e.hash == version)
+            .map(Some)
+            .ok_or_else(|| {
+                anyhow!("Version {} does not exist for this article", version.hash()).into()
+            })
+    };
+    let (from, to) = if position(from)? > position(to)? {
+        (to, from)
+    } else {
+        (from, to)
+    };
+    let old = generate_article_version(edits, from)?;
+    let new = generate_article_version(edits, to)?;
+    Ok(create_patch(&old, &new).to_string())
 }
 
 pub(crate) fn generate_article_ap_id(title: &str, instance: &Instance) -> BackendResult<DbUrl> {
@@ -44,16 +96,183 @@ pub(crate) fn generate_article_ap_id(title: &str, instance: &Instance) -> Backen
     .into())
 }
 
+/// Git identity for the creator of an edit. Uses the display name if available, and a
+/// pseudo email address built from username and instance domain, which is stable across exports.
+pub(crate) fn git_author(person: &Person) -> String {
+    let name = person
+        .display_name
+        .as_deref()
+        .filter(|n| !n.trim().is_empty())
+        .unwrap_or(&person.username);
+    // angle brackets and newlines are not allowed in git identities
+    let name: String = name
+        .chars()
+        .filter(|c| !matches!(c, '<' | '>' | '\n'))
+        .collect();
+    let domain = extract_domain(person.ap_id.inner());
+    format!("{} <{}@{domain}>", name.trim(), person.username)
+}
+
+/// File name for the article text in exported git history.
+pub(crate) fn git_file_name(title: &str) -> String {
+    let name: String = title
+        .chars()
+        .map(|c| {
+            if matches!(c, '/' | '"' | '\n') {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+    format!("{name}.md")
+}
+
+/// Convert an edit into a commit in `git fast-import` format. `text` needs to contain the article
+/// text before the edit, and is updated to the new version. Commits are marked with their
+/// position in the history, so that each commit can reference its parent.
+pub(crate) fn git_fast_import_commit(
+    mark: usize,
+    edit: &Edit,
+    author: &str,
+    path: &str,
+    text: &mut String,
+) -> BackendResult<String> {
+    let patch = Patch::from_str(&edit.diff)?;
+    *text = apply(text, &patch)?;
+    let ident = format!("{author} {} +0000", edit.published.timestamp());
+    let mut out = String::new();
+    writeln!(out, "commit refs/heads/main")?;
+    writeln!(out, "mark :{mark}")?;
+    writeln!(out, "author {ident}")?;
+    writeln!(out, "committer {ident}")?;
+    writeln!(out, "data {}\n{}", edit.summary.len(), edit.summary)?;
+    if mark > 1 {
+        writeln!(out, "from :{}", mark - 1)?;
+    }
+    writeln!(out, "M 100644 inline {path}")?;
+    writeln!(out, "data {}\n{text}", text.len())?;
+    Ok(out)
+}
+
+/// Number of words in each shingle which is used for similarity comparison
+const SHINGLE_SIZE: usize = 3;
+/// Locality sensitive hashing parameters. With 16 bands of 4 rows, pairs with similarity above
+/// ~0.5 are very likely to become candidates.
+const LSH_BANDS: usize = 16;
+const LSH_ROWS: usize = 4;
+
+/// Find pairs of similar texts, returning their indices and estimated Jaccard similarity, most
+/// similar first. Texts are compared by MinHash signatures of word shingles. Candidate pairs are
+/// found via locality sensitive hashing, so that not every pair of texts needs to be compared.
+pub(crate) fn find_similar_texts(
+    texts: &[&str],
+    threshold: f32,
+    max_pairs: usize,
+) -> Vec<(usize, usize, f32)> {
+    let signatures: Vec<_> = texts.iter().map(|t| minhash_signature(t)).collect();
+    let mut candidates = HashSet::new();
+    for band in 0..LSH_BANDS {
+        let mut buckets: HashMap<&[u64], Vec<usize>> = HashMap::new();
+        for (i, signature) in signatures.iter().enumerate() {
+            if let Some(signature) = signature {
+                let rows = &signature[band * LSH_ROWS..(band + 1) * LSH_ROWS];
+                buckets.entry(rows).or_default().push(i);
+            }
+        }
+        for bucket in buckets.values() {
+            for (n, a) in bucket.iter().enumerate() {
+                for b in &bucket[n + 1..] {
+                    candidates.insert((*a, *b));
+                }
+            }
+        }
+    }
+
+    let mut pairs: Vec<_> = candidates
+        .into_iter()
+        .filter_map(|(a, b)| {
+            let (sig_a, sig_b) = (signatures[a].as_ref()?, signatures[b].as_ref()?);
+            let equal = sig_a.iter().zip(sig_b).filter(|(x, y)| x == y).count();
+            let similarity = equal as f32 / sig_a.len() as f32;
+            (similarity >= threshold).then_some((a, b, similarity))
+        })
+        .collect();
+    pairs.sort_by(|x, y| y.2.total_cmp(&x.2).then((x.0, x.1).cmp(&(y.0, y.1))));
+    pairs.truncate(max_pairs);
+    pairs
+}
+
+/// Returns `None` if the text has no words.
+fn minhash_signature(text: &str) -> Option<Vec<u64>> {
+    let words: Vec<_> = text.split_whitespace().map(str::to_lowercase).collect();
+    if words.is_empty() {
+        return None;
+    }
+    let shingles: HashSet<u64> = words
+        .windows(SHINGLE_SIZE.min(words.len()))
+        .map(|w| {
+            let mut hasher = DefaultHasher::new();
+            w.hash(&mut hasher);
+            hasher.finish()
+        })
+        .collect();
+    let signature = (0..(LSH_BANDS * LSH_ROWS) as u64)
+        .map(|i| {
+            let seed = mix(i);
+            shingles
+                .iter()
+                .map(|s| mix(s ^ seed))
+                .min()
+                .unwrap_or_default()
+        })
+        .collect();
+    Some(signature)
+}
+
+/// Finalizer of splitmix64, used as a fast way to derive independent hash functions.
+fn mix(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e3779b97f4a7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
+}
+
+/// Address of the client which sent a request. If the connection comes from one of the
+/// `trusted_proxies`, the last address in `X-Forwarded-For` is used. That one was added by the
+/// proxy itself, while earlier entries can be set freely by the client. Requests from other
+/// addresses can't be trusted to set these headers, so they are ignored.
+pub(super) fn client_ip(
+    peer: IpAddr,
+    headers: &HeaderMap,
+    trusted_proxies: Option<&str>,
+) -> IpAddr {
+    let trusted = trusted_proxies
+        .into_iter()
+        .flat_map(|p| p.split(','))
+        .filter_map(|p| p.trim().parse::<IpAddr>().ok())
+        .any(|p| p == peer);
+    if !trusted {
+        return peer;
+    }
+    headers
+        .get("x-forwarded-for")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.rsplit(',').next())
+        .or_else(|| headers.get("x-real-ip").and_then(|h| h.to_str().ok()))
+        .and_then(|ip| ip.trim().parse().ok())
+        .unwrap_or(peer)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use chrono::Utc;
-    use diffy::create_patch;
     use ibis_database::common::newtypes::{ArticleId, EditId, PersonId};
     use url::Url;
 
     fn create_edits() -> BackendResult<Vec<Edit>> {
-        let generate_edit = |a, b| -> BackendResult<Edit> {
+        let generate_edit = |a, b, previous_version_id| -> BackendResult<Edit> {
             let diff = create_patch(a, b).to_string();
             Ok(Edit {
                 id: EditId(0),
@@ -63,17 +282,18 @@ mod test {
                 diff,
                 summary: String::new(),
                 article_id: ArticleId(0),
-                previous_version_id: Default::default(),
+                previous_version_id,
                 published: Utc::now(),
                 pending: false,
+                sequence: 0,
+                minor: false,
+                anonymous_id: None,
             })
         };
-        Ok([
-            generate_edit("", "test\n")?,
-            generate_edit("test\n", "sda\n")?,
-            generate_edit("sda\n", "123\n")?,
-        ]
-        .to_vec())
+        let first = generate_edit("", "test\n", EditVersion::default())?;
+        let second = generate_edit("test\n", "sda\n", first.hash.clone())?;
+        let third = generate_edit("sda\n", "123\n", second.hash.clone())?;
+        Ok(vec![first, second, third])
     }
 
     #[test]
@@ -84,6 +304,25 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_generate_latest_version() -> BackendResult<()> {
+        let edits = create_edits()?;
+        let generated = generate_article_version(&edits, &edits[2].hash)?;
+        assert_eq!("123\n", generated);
+        Ok(())
+    }
+
+    #[test]
+    fn test_generate_version_missing_link() -> BackendResult<()> {
+        let mut edits = create_edits()?;
+        edits.remove(1);
+        let Err(err) = generate_article_version(&edits, &edits[1].hash) else {
+            panic!("missing edit should result in error");
+        };
+        assert!(err.to_string().contains("history is incomplete"));
+        Ok(())
+    }
+
     #[test]
     fn test_generate_invalid_version() -> BackendResult<()> {
         let edits = create_edits()?;
@@ -99,4 +338,90 @@ mod test {
         assert_eq!("", generated);
         Ok(())
     }
+
+    #[test]
+    fn test_diff_article_versions() -> BackendResult<()> {
+        let edits = create_edits()?;
+        // non-adjacent versions, intermediate text doesn't show up
+        let diff = diff_article_versions(&edits, &edits[0].hash, &edits[2].hash)?;
+        assert_eq!(create_patch("test\n", "123\n").to_string(), diff);
+        assert!(!diff.contains("sda"));
+
+        // order is swapped if necessary
+        let swapped = diff_article_versions(&edits, &edits[2].hash, &edits[0].hash)?;
+        assert_eq!(diff, swapped);
+
+        // diff from empty article
+        let diff = diff_article_versions(&edits, &EditVersion::default(), &edits[1].hash)?;
+        assert_eq!(create_patch("", "sda\n").to_string(), diff);
+
+        assert!(
+            diff_article_versions(&edits, &edits[0].hash, &EditVersion::new("invalid")).is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_git_fast_import_commit() -> BackendResult<()> {
+        let edits = create_edits()?;
+        let path = git_file_name("Test/Article");
+        assert_eq!("Test_Article.md", path);
+
+        let mut text = String::new();
+        let author = "Alice <alice@example.com>";
+        let first = git_fast_import_commit(1, &edits[0], author, &path, &mut text)?;
+        assert_eq!("test\n", text);
+        let ts = edits[0].published.timestamp();
+        assert_eq!(
+            format!(
+                "commit refs/heads/main\nmark :1\nauthor {author} {ts} +0000\n\
+                committer {author} {ts} +0000\ndata 0\n\nM 100644 inline Test_Article.md\n\
+                data 5\ntest\n\n"
+            ),
+            first
+        );
+
+        let second = git_fast_import_commit(2, &edits[1], author, &path, &mut text)?;
+        assert_eq!("sda\n", text);
+        assert!(second.contains("mark :2\n"));
+        assert!(second.contains("from :1\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_similar_texts() {
+        let original = "The quick brown fox jumps over the lazy dog while the cat watches from \
+            the window and the bird sings in the old oak tree next to the river bank";
+        let modified = original.replace("bird sings", "bird sings loudly");
+        let texts = [
+            original,
+            "Something completely different which has nothing in common with other texts",
+            modified.as_str(),
+            "",
+        ];
+        let pairs = find_similar_texts(&texts, 0.5, 10);
+        assert_eq!(1, pairs.len());
+        assert_eq!((0, 2), (pairs[0].0, pairs[0].1));
+        assert!(pairs[0].2 > 0.7);
+
+        assert!(find_similar_texts(&texts, 0.5, 0).is_empty());
+    }
+
+    #[test]
+    fn test_client_ip() -> BackendResult<()> {
+        let proxy: IpAddr = "127.0.0.1".parse()?;
+        let client: IpAddr = "203.0.113.7".parse()?;
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", "198.51.100.1, 203.0.113.7".parse()?);
+        let trusted = Some("::1,127.0.0.1");
+
+        // forwarded address is only used behind a trusted proxy, and only the entry added by it
+        assert_eq!(client, client_ip(proxy, &headers, trusted));
+        assert_eq!(proxy, client_ip(proxy, &headers, None));
+        assert_eq!(client, client_ip(client, &headers, trusted));
+
+        // requests which didn't go through the proxy use the address of the connection
+        assert_eq!(proxy, client_ip(proxy, &HeaderMap::new(), trusted));
+        Ok(())
+    }
 }
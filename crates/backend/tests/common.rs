@@ -1,10 +1,16 @@
 #![expect(clippy::unwrap_used)]
 
+use chrono::Utc;
 use ibis::start;
 use ibis_api_client::{ApiClient, user::RegisterUserParams};
 use ibis_database::{
-    common::instance::Options,
+    common::{
+        article::{Article, Edit, EditVersion},
+        instance::{Instance, Options},
+        user::Person,
+    },
     config::{IbisConfig, IbisConfigDatabase, IbisConfigFederation},
+    impls::{IbisContext, article::DbArticleForm, edit::DbEditForm},
 };
 use log::LevelFilter;
 use std::{
@@ -25,6 +31,7 @@ use tokio::{
     task::{JoinHandle, spawn_blocking},
     time::sleep,
 };
+use url::Url;
 
 pub struct TestData(pub IbisInstance, pub IbisInstance, pub IbisInstance);
 
@@ -86,13 +93,26 @@ pub struct IbisInstance {
     db_path: String,
     db_handle: JoinHandle<()>,
     pub hostname: String,
+    context: IbisContext,
 }
 
 impl IbisInstance {
     async fn new(name: &'static str, port: i32) -> Self {
+        let options = Options {
+            registration_open: true,
+            email_required: false,
+            // many tests edit in quick succession
+            edit_rate_limit_unverified: 0,
+            ..Default::default()
+        };
+        Self::new_with_options(name, port, options).await
+    }
+
+    /// Start an additional instance with custom options, it needs to be stopped manually.
+    pub async fn new_with_options(name: &'static str, port: i32, options: Options) -> Self {
         let db_path = Self::generate_db_path(name, port);
         Self::prepare_db(db_path.clone()).await;
-        Self::start(db_path, port, name).await
+        Self::start(db_path, port, name, options).await
     }
 
     /// Generate a unique db path for each postgres so that tests can run in parallel.
@@ -120,8 +140,12 @@ impl IbisInstance {
         .unwrap();
     }
 
-    async fn start(db_path: String, port: i32, username: &str) -> Self {
-        let connection_url = format!("postgresql://ibis:password@/ibis?host={db_path}");
+    fn connection_url(db_path: &str) -> String {
+        format!("postgresql://ibis:password@/ibis?host={db_path}")
+    }
+
+    async fn start(db_path: String, port: i32, username: &str, options: Options) -> Self {
+        let connection_url = Self::connection_url(&db_path);
 
         let hostname = format!("localhost:{port}");
         let config = IbisConfig {
@@ -133,14 +157,11 @@ impl IbisInstance {
                 domain: hostname.clone(),
                 ..Default::default()
             },
-            options: Options {
-                registration_open: true,
-                email_required: false,
-            },
+            options,
             ..Default::default()
         };
         let api_client = ApiClient::new(Some(hostname.clone()));
-        let (tx, rx) = oneshot::channel::<()>();
+        let (tx, rx) = oneshot::channel::<IbisContext>();
         let db_handle = tokio::task::spawn(async move {
             let hostname = format!("127.0.0.1:{port}");
             start(config, Some(hostname.parse().unwrap()), Some(tx))
@@ -148,7 +169,7 @@ impl IbisInstance {
                 .unwrap();
         });
         // wait for the backend to start
-        rx.await.unwrap();
+        let context = rx.await.unwrap();
         let params = RegisterUserParams {
             username: username.to_string(),
             password: "hunter22".to_string(),
@@ -161,10 +182,62 @@ impl IbisInstance {
             db_path,
             db_handle,
             hostname,
+            context,
         }
     }
 
-    async fn stop(self) {
+    /// Insert local articles with a single edit directly into the database. This is much faster
+    /// than creating them over the api, for tests which need many articles.
+    pub async fn seed_articles(&self, titles: impl IntoIterator<Item = String>) {
+        let config = IbisConfig {
+            database: IbisConfigDatabase {
+                connection_url: Self::connection_url(&self.db_path),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let context = IbisContext::init(config, true).unwrap();
+        let instance = Instance::read_local(&context).unwrap();
+        let creator = Person::wikibot(&context).unwrap();
+        for title in titles {
+            let ap_id = format!("{}article/{}", instance.ap_id, title.replace(' ', "_"));
+            let form = DbArticleForm {
+                title,
+                text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+                ap_id: ap_id.parse::<Url>().unwrap().into(),
+                instance_id: instance.id,
+                local: true,
+                protection: Default::default(),
+                updated: Utc::now(),
+                pending: false,
+                forked_by: None,
+            };
+            let article = Article::create(form, creator.id, &context).await.unwrap();
+            let form = DbEditForm::new(
+                &Article {
+                    text: String::new(),
+                    ..article.clone()
+                },
+                creator.id,
+                &article.text,
+                "seed".to_string(),
+                false,
+                EditVersion::default(),
+                false,
+            )
+            .unwrap();
+            Edit::create_or_update(&form, false, &context)
+                .await
+                .unwrap();
+        }
+    }
+
+    /// Write article views to the database, which otherwise happens once per minute.
+    pub fn flush_views(&self) {
+        Article::flush_views(&self.context).unwrap();
+    }
+
+    pub async fn stop(self) {
         self.db_handle.abort();
         Self::stop_internal(self.db_path).await;
     }
@@ -2,27 +2,84 @@
 
 mod common;
 
-use crate::common::{TEST_ARTICLE_DEFAULT_TEXT, TestData};
+use crate::common::{IbisInstance, TEST_ARTICLE_DEFAULT_TEXT, TestData};
 use anyhow::Result;
+use atom_syndication::Feed;
+use chrono::Utc;
 use ibis_api_client::{
+    ApiClient,
     article::{
+        AddAttachmentParams,
+        AddToCategoryParams,
+        CompareRevisionsParams,
         CreateArticleParams,
+        CreateRedirectParams,
         EditArticleParams,
+        FindDuplicates,
         ForkArticleParams,
         GetArticleParams,
+        GetBacklinks,
+        GetCategoryParams,
+        GetConflictStats,
+        GetEditList,
+        GetEditReach,
+        GetPopularArticles,
+        GetRecentChanges,
         ListArticlesParams,
-        ProtectArticleParams,
+        MoveArticleParams,
+        RemoveAttachmentParams,
+        RevertArticleParams,
+        RevertToDateParams,
+        SetProtectionParams,
+        TagRevisionParams,
     },
     comment::{CreateCommentParams, EditCommentParams},
-    instance::SearchArticleParams,
-    user::{GetUserParams, LoginUserParams, RegisterUserParams},
+    instance::{
+        AllowInstanceParams,
+        BlockInstanceParams,
+        GetFederationStats,
+        GetInstanceActivity,
+        GetInstanceFollowers,
+        GetInstanceParams,
+        RetryDeliveryParams,
+        SearchArticleParams,
+        SyncInstanceParams,
+        UpdateInstanceParams,
+    },
+    user::{
+        BanUserParams,
+        ChangePasswordAfterReset,
+        GetUserArticles,
+        GetUserContributions,
+        GetUserParams,
+        LoginUserParams,
+        PasswordReset,
+        RegisterUserParams,
+        SetUserRoleParams,
+        UpdateUserParams,
+    },
 };
 use ibis_database::common::{
-    article::ArticleView,
+    MAIN_PAGE_NAME,
+    article::{
+        Article,
+        ArticleLookup,
+        ArticleSort,
+        ArticleView,
+        CategoryView,
+        EditVersion,
+        ProtectionLevel,
+        SearchMode,
+    },
+    instance::{FederationMode, Options},
     notifications::ApiNotificationData,
+    user::{Person, UserRole},
     utils::extract_domain,
 };
+use ibis_federate::nodeinfo::NodeInfo;
+use ibis_markdown::{render_article_markdown, render_article_markdown_with_links};
 use pretty_assertions::assert_eq;
+use reqwest::header::{CONTENT_DISPOSITION, CONTENT_SECURITY_POLICY, CONTENT_TYPE};
 use retry_future::{LinearRetryStrategy, RetryFuture, RetryPolicy};
 use std::time::Duration;
 use test_context::test_context;
@@ -54,6 +111,7 @@ async fn api_test_create_read_and_edit_local_article(
         title: Some(create_res.article.title.clone()),
         domain: None,
         id: None,
+        follow_redirect: None,
     };
     let get_res = alpha.get_article(get_article_data.clone()).await.unwrap();
     assert_eq!(create_params.title, get_res.article.title);
@@ -71,6 +129,7 @@ async fn api_test_create_read_and_edit_local_article(
         summary: "summary".to_string(),
         previous_version_id: get_res.latest_version,
         resolve_conflict_id: None,
+        minor: None,
     };
     let edit_res = alpha
         .edit_article_without_conflict(&edit_params)
@@ -83,284 +142,3126 @@ async fn api_test_create_read_and_edit_local_article(
 
     let search_params = SearchArticleParams {
         query: create_params.title.clone(),
+        search_mode: None,
+        fuzzy: None,
     };
     let search_res = alpha.search(&search_params).await.unwrap();
     assert_eq!(1, search_res.len());
-    assert_eq!(edit_res.article, search_res[0]);
+    assert_eq!(edit_res.article, search_res[0].article);
 
     let list_articles = alpha
         .list_articles(ListArticlesParams {
             only_local: Some(false),
             instance_id: None,
             include_removed: None,
+            sort: None,
+            page: None,
+            limit: None,
+            offset: None,
         })
         .await
-        .unwrap();
+        .unwrap()
+        .articles;
     assert_eq!(2, list_articles.len());
-    assert_eq!(edit_res.article, list_articles[0]);
+    assert_eq!(edit_res.article, list_articles[0].article);
 
     Ok(())
 }
 
 #[test_context(TestData)]
 #[tokio::test]
-async fn api_test_create_duplicate_article(TestData(alpha, _, _): &mut TestData) -> Result<()> {
-    // create article
-    let create_params = create_test_article_params();
-    let create_res = alpha.create_article(&create_params).await.unwrap();
-    assert_eq!(create_params.title, create_res.article.title);
-    assert!(create_res.article.local);
+async fn api_test_edit_without_changes(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let create_res = alpha.create_article(&create_test_article_params()).await?;
+    let mut edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: create_res.article.text.clone(),
+        summary: "summary".to_string(),
+        previous_version_id: create_res.latest_version.clone(),
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    let res = alpha.edit_article(&edit_params).await;
+    assert!(res.unwrap_err().to_string().contains("no changes"));
 
-    let create_res = alpha.create_article(&create_params).await;
-    assert!(create_res.is_err());
+    // text which only becomes identical after adding the trailing newline is also rejected
+    edit_params.new_text = TEST_ARTICLE_DEFAULT_TEXT.trim_end().to_string();
+    let res = alpha.edit_article(&edit_params).await;
+    assert!(res.unwrap_err().to_string().contains("no changes"));
 
+    // no empty edits were added to the history
+    let edits = alpha.get_article_edits(create_res.article.id).await?;
+    assert_eq!(1, edits.len());
     Ok(())
 }
 
 #[test_context(TestData)]
 #[tokio::test]
-async fn api_test_follow_instance(TestData(alpha, beta, _): &mut TestData) -> Result<()> {
-    // check initial state
-    let alpha_follows = alpha.get_follows().await.unwrap();
-    assert_eq!(0, alpha_follows.len());
-    let beta_follows = beta.get_follows().await.unwrap();
-    assert_eq!(0, beta_follows.len());
+async fn api_test_reject_binary_text(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    // null bytes and mostly control characters are rejected on create
+    let mut create_params = create_test_article_params();
+    create_params.text = "binary\0text".to_string();
+    assert!(alpha.create_article(&create_params).await.is_err());
+    create_params.text = "\u{1}\u{2}\u{7}\u{1b}text".repeat(10);
+    assert!(alpha.create_article(&create_params).await.is_err());
 
-    alpha
-        .follow_instance_with_resolve(&beta.hostname)
-        .await
-        .unwrap();
+    // unicode and a few stray control characters are fine
+    create_params.text = format!("ünïcödé 日本語 🦩\n{}\u{c}\n", "a".repeat(200));
+    let create_res = alpha.create_article(&create_params).await?;
 
-    // check that follow was federated
-    let alpha_follows = alpha.get_follows().await.unwrap();
-    assert_eq!(1, alpha_follows.len());
-    assert!(!alpha_follows[0].pending);
-    let beta_site = beta.site().await.unwrap();
-    assert_eq!(beta_site.instance.ap_id, alpha_follows[0].instance.ap_id);
+    // same check on edit
+    let edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: "\u{FFFD}".repeat(5),
+        summary: "summary".to_string(),
+        previous_version_id: create_res.latest_version,
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    assert!(alpha.edit_article(&edit_params).await.is_err());
+    let article = alpha
+        .get_article(GetArticleParams {
+            title: None,
+            domain: None,
+            id: Some(create_res.article.id),
+            follow_redirect: None,
+        })
+        .await?;
+    assert_eq!(create_res.article.text, article.article.text);
 
-    // unfollow
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_list_articles_pagination(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    for title in ["Apple", "Banana", "Cherry"] {
+        let params = CreateArticleParams {
+            title: title.to_string(),
+            ..create_test_article_params()
+        };
+        alpha.create_article(&params).await?;
+    }
+
+    // instance defaults are exposed for the frontend
+    let instance = alpha
+        .get_instance(&GetInstanceParams {
+            id: None,
+            hostname: Some(alpha.hostname.clone()),
+        })
+        .await?;
+    assert_eq!(ArticleSort::Updated, instance.default_sort);
+    assert_eq!(50, instance.default_page_size);
+
+    // defaults are used if request doesn't specify sort and limit, main page is included
+    let all = alpha.list_articles(Default::default()).await?;
+    assert_eq!(4, all.total);
+    assert_eq!(4, all.articles.len());
+    assert_eq!("Cherry", all.articles[0].article.title);
+
+    // values from request take precedence
+    let params = ListArticlesParams {
+        limit: Some(2),
+        ..Default::default()
+    };
+    let page1 = alpha.list_articles(params.clone()).await?;
+    assert_eq!(4, page1.total);
+    assert_eq!(2, page1.articles.len());
+    assert_eq!("Cherry", page1.articles[0].article.title);
+    assert_eq!("Banana", page1.articles[1].article.title);
+    let page2 = alpha
+        .list_articles(ListArticlesParams {
+            page: Some(2),
+            ..params.clone()
+        })
+        .await?;
+    assert_eq!(4, page2.total);
+    assert_eq!(2, page2.articles.len());
+    assert_eq!("Apple", page2.articles[0].article.title);
+
+    // offset takes precedence over page
+    let sliced = alpha
+        .list_articles(ListArticlesParams {
+            page: Some(2),
+            offset: Some(1),
+            ..params
+        })
+        .await?;
+    assert_eq!(4, sliced.total);
+    assert_eq!(
+        vec!["Banana", "Apple"],
+        sliced
+            .articles
+            .iter()
+            .map(|a| a.article.title.as_str())
+            .collect::<Vec<_>>()
+    );
+
+    // limit is capped at 200
     alpha
-        .follow_instance(alpha_follows[0].instance.id, false)
-        .await
-        .unwrap();
-    let alpha_follows = alpha.get_follows().await.unwrap();
-    assert_eq!(0, alpha_follows.len());
+        .seed_articles((0..197).map(|i| format!("Article {i}")))
+        .await;
+    let capped = alpha
+        .list_articles(ListArticlesParams {
+            limit: Some(1000),
+            ..Default::default()
+        })
+        .await?;
+    assert_eq!(201, capped.total);
+    assert_eq!(200, capped.articles.len());
 
     Ok(())
 }
 
 #[test_context(TestData)]
 #[tokio::test]
-async fn api_test_synchronize_articles(TestData(alpha, beta, _): &mut TestData) -> Result<()> {
-    // create article on alpha
-    let create_params = create_test_article_params();
-    let create_res = alpha.create_article(&create_params).await.unwrap();
-    assert_eq!(create_params.title, create_res.article.title);
-    let edits = alpha
-        .get_article_edits(create_res.article.id)
-        .await
+async fn api_test_list_articles_latest_edit(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let create_res = alpha.create_article(&create_test_article_params()).await?;
+    let admin = ApiClient::new(Some(alpha.hostname.clone()));
+    let admin_user = admin
+        .login(LoginUserParams {
+            username_or_email: "ibis".to_string(),
+            password: "ibis".to_string(),
+        })
+        .await?;
+    let edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: "Lorem Ipsum\n".to_string(),
+        summary: "latest summary".to_string(),
+        previous_version_id: create_res.latest_version,
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    let edit_res = admin.edit_article_without_conflict(&edit_params).await?;
+    let edits = alpha.get_article_edits(create_res.article.id).await?;
+
+    let list = alpha.list_articles(Default::default()).await?;
+    let entry = list
+        .articles
+        .iter()
+        .find(|a| a.article.id == create_res.article.id)
         .unwrap();
-    assert_eq!(1, edits.len());
-    assert!(create_res.article.local);
+    assert_eq!(edit_res.article, entry.article);
+    let latest_edit = entry.latest_edit.as_ref().unwrap();
+    assert_eq!("latest summary", latest_edit.summary);
+    assert_eq!(admin_user.person.id, latest_edit.creator.id);
+    let edit = edits
+        .iter()
+        .find(|e| e.edit.summary == "latest summary")
+        .unwrap();
+    assert_eq!(edit.edit.published, latest_edit.published);
+    Ok(())
+}
 
-    // edit the article
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_list_articles_sort(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let mut created = vec![];
+    for title in ["Apple", "Banana"] {
+        let params = CreateArticleParams {
+            title: title.to_string(),
+            ..create_test_article_params()
+        };
+        created.push(alpha.create_article(&params).await?);
+    }
+
+    // editing the older article bumps its updated time
+    let apple = &created[0];
     let edit_params = EditArticleParams {
-        article_id: create_res.article.id,
+        article_id: apple.article.id,
         new_text: "Lorem Ipsum 2\n".to_string(),
         summary: "summary".to_string(),
-        previous_version_id: create_res.latest_version,
+        previous_version_id: apple.latest_version.clone(),
         resolve_conflict_id: None,
+        minor: None,
     };
-    let edit_res = alpha
+    let edited = alpha
         .edit_article_without_conflict(&edit_params)
         .await
         .unwrap();
+    assert!(edited.article.updated > apple.article.updated);
+    assert_eq!(apple.article.published, edited.article.published);
 
-    // fetch alpha instance on beta, articles are also fetched automatically
-    let instance = beta
-        .resolve_instance(Url::parse(&format!("http://{}", &alpha.hostname))?)
-        .await
-        .unwrap();
+    let expected = [
+        (ArticleSort::Updated, ["Apple", "Banana"]),
+        (ArticleSort::Newest, ["Banana", "Apple"]),
+        (ArticleSort::Oldest, ["Apple", "Banana"]),
+    ];
+    for (sort, expected) in expected {
+        let params = ListArticlesParams {
+            sort: Some(sort),
+            ..Default::default()
+        };
+        let titles: Vec<_> = alpha
+            .list_articles(params)
+            .await?
+            .articles
+            .into_iter()
+            .map(|a| a.article.title)
+            .filter(|t| t == "Apple" || t == "Banana")
+            .collect();
+        assert_eq!(expected.to_vec(), titles);
+    }
 
-    let get_article_data = GetArticleParams {
-        title: Some(create_res.article.title.clone()),
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_article_view_count(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let viewed = alpha.create_article(&create_test_article_params()).await?;
+    let params = CreateArticleParams {
+        title: "Unread".to_string(),
+        ..create_test_article_params()
+    };
+    alpha.create_article(&params).await?;
+    let get_params = GetArticleParams {
+        id: Some(viewed.article.id),
         ..Default::default()
     };
 
-    // try to read remote article by name, fails without domain
-    let get_res = beta.get_article(get_article_data.clone()).await;
-    assert!(get_res.is_err());
+    // the view is counted after the article is returned, and only once per user
+    assert_eq!(0, alpha.get_article(get_params.clone()).await?.view_count);
+    alpha.flush_views();
+    assert_eq!(1, alpha.get_article(get_params.clone()).await?.view_count);
+    alpha.flush_views();
 
-    // get the article with instance id and compare
-    let get_res = RetryFuture::new(
-        || async {
-            let get_article_data = GetArticleParams {
-                title: Some(create_res.article.title.clone()),
-                domain: Some(instance.domain.clone()),
-                id: None,
+    // views from another user are counted separately
+    let viewer = ApiClient::new(Some(alpha.hostname.clone()));
+    let password = "hunter22".to_string();
+    viewer
+        .register(RegisterUserParams {
+            username: "viewer".to_string(),
+            password: password.clone(),
+            email: None,
+            confirm_password: password,
+        })
+        .await?;
+    viewer.get_article(get_params.clone()).await?;
+    alpha.flush_views();
+    assert_eq!(2, alpha.get_article(get_params).await?.view_count);
+
+    let params = ListArticlesParams {
+        sort: Some(ArticleSort::MostViewed),
+        ..Default::default()
+    };
+    let titles: Vec<_> = alpha
+        .list_articles(params)
+        .await?
+        .articles
+        .into_iter()
+        .map(|a| a.article.title)
+        .filter(|t| t == &viewed.article.title || t == "Unread")
+        .collect();
+    assert_eq!(vec![viewed.article.title, "Unread".to_string()], titles);
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_popular_articles(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let mut ids = vec![];
+    for (title, views) in [("Rarely read", 1), ("Often read", 3), ("Sometimes read", 2)] {
+        let params = CreateArticleParams {
+            title: title.to_string(),
+            ..create_test_article_params()
+        };
+        let article = alpha.create_article(&params).await?.article;
+        // views are counted once per user, so read with different users
+        for i in 0..views {
+            let viewer = ApiClient::new(Some(alpha.hostname.clone()));
+            viewer
+                .register(RegisterUserParams {
+                    username: format!("viewer{}{i}", article.id.0),
+                    password: "hunter22".to_string(),
+                    email: None,
+                    confirm_password: "hunter22".to_string(),
+                })
+                .await?;
+            let params = GetArticleParams {
+                id: Some(article.id),
+                ..Default::default()
             };
-            match beta.get_article(get_article_data).await {
-                Err(_) => Err(RetryPolicy::<String>::Retry(None)),
-                Ok(a) if a.latest_version != edit_res.latest_version => {
-                    Err(RetryPolicy::Retry(None))
-                }
-                Ok(a) => Ok(a),
-            }
-        },
-        LinearRetryStrategy::new(),
-    )
-    .await?;
-    let beta_edits = beta.get_article_edits(get_res.article.id).await.unwrap();
-    assert_eq!(create_res.article.ap_id, get_res.article.ap_id);
-    assert_eq!(create_params.title, get_res.article.title);
-    assert_eq!(2, beta_edits.len());
-    assert_eq!(edit_params.new_text, get_res.article.text);
-    assert!(!get_res.article.local);
+            viewer.get_article(params).await?;
+        }
+        ids.push(article.id);
+    }
+
+    // views are only included after they are written to the database
+    alpha.flush_views();
+    let popular: Vec<_> = alpha
+        .get_popular_articles(&GetPopularArticles { limit: Some(3) })
+        .await?
+        .into_iter()
+        .map(|a| a.id)
+        .collect();
+    assert_eq!(vec![ids[1], ids[2], ids[0]], popular);
 
     Ok(())
 }
 
 #[test_context(TestData)]
 #[tokio::test]
-async fn api_test_edit_local_article(TestData(alpha, beta, _): &mut TestData) -> Result<()> {
-    let beta_instance = alpha
-        .follow_instance_with_resolve(&beta.hostname)
+async fn api_test_search_article_body(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let articles = [
+        ("Volcano", "Mountains which sometimes erupt with lava."),
+        (
+            "Lava",
+            "Molten rock. Lava flows out of a volcano during an eruption.",
+        ),
+        ("Gardening", "Planting flowers and vegetables."),
+    ];
+    for (title, text) in articles {
+        let params = CreateArticleParams {
+            title: title.to_string(),
+            text: text.to_string(),
+            ..create_test_article_params()
+        };
+        alpha.create_article(&params).await?;
+    }
+
+    // title match comes first, then body matches by relevance, with word stemming
+    let mut params = SearchArticleParams {
+        query: "lava".to_string(),
+        search_mode: None,
+        fuzzy: None,
+    };
+    let res = alpha.search(&params).await?;
+    assert_eq!(
+        vec!["Lava", "Volcano"],
+        res.iter()
+            .map(|r| r.article.title.as_str())
+            .collect::<Vec<_>>()
+    );
+    // snippet highlights the matched terms
+    assert!(res[1].snippet.contains("<b>lava</b>"));
+    params.query = "flowering".to_string();
+    let res = alpha.search(&params).await?;
+    assert_eq!(1, res.len());
+    assert_eq!("Gardening", res[0].article.title);
+    assert!(res[0].snippet.contains("<b>flowers</b>"));
+
+    // body is ignored when only searching titles
+    params.search_mode = Some(SearchMode::TitleOnly);
+    assert!(alpha.search(&params).await?.is_empty());
+    params.query = "lava".to_string();
+    let res = alpha.search(&params).await?;
+    assert_eq!(1, res.len());
+    assert_eq!("Lava", res[0].article.title);
+
+    // typo only finds the article with fuzzy search
+    params.search_mode = None;
+    params.query = "Gardenign".to_string();
+    assert!(alpha.search(&params).await?.is_empty());
+    params.fuzzy = Some(true);
+    let res = alpha.search(&params).await?;
+    assert_eq!(1, res.len());
+    assert_eq!("Gardening", res[0].article.title);
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_search_article_stream(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    for i in 0..12 {
+        let params = CreateArticleParams {
+            title: format!("Streamed {i}"),
+            ..create_test_article_params()
+        };
+        alpha.create_article(&params).await?;
+    }
+    let params = SearchArticleParams {
+        query: "streamed".to_string(),
+        search_mode: None,
+        fuzzy: None,
+    };
+    let expected: Vec<_> = alpha
+        .search(&params)
+        .await?
+        .into_iter()
+        .map(|r| r.article.title)
+        .collect();
+    assert_eq!(12, expected.len());
+
+    // results are sent in batches of 10, in the same order as normal search
+    let url = format!(
+        "http://{}/api/v1/search/stream?query={}",
+        alpha.hostname, params.query
+    );
+    let body = reqwest::Client::new()
+        .get(&url)
+        .send()
+        .await?
+        .text()
+        .await?;
+    let batches = body
+        .lines()
+        .filter_map(|l| l.strip_prefix("data:"))
+        .map(|data| serde_json::from_str::<Vec<serde_json::Value>>(data.trim()))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(
+        vec![10, 2],
+        batches.iter().map(|b| b.len()).collect::<Vec<_>>()
+    );
+    let streamed: Vec<_> = batches
+        .iter()
+        .flatten()
+        .map(|r| r["article"]["title"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(expected, streamed);
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_article_redirect(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let create_params = CreateArticleParams {
+        title: "New York City".to_string(),
+        ..create_test_article_params()
+    };
+    let target = alpha.create_article(&create_params).await?;
+
+    // cannot redirect an article to itself
+    let params = CreateRedirectParams {
+        from_title: target.article.title.clone(),
+        to_article_id: target.article.id,
+    };
+    assert!(alpha.create_redirect(&params).await.is_err());
+
+    let params = CreateRedirectParams {
+        from_title: "NYC".to_string(),
+        to_article_id: target.article.id,
+    };
+    let redirect = alpha.create_redirect(&params).await?;
+    assert_eq!("NYC", redirect.article.title);
+    assert_eq!(Some(target.article.id), redirect.article.redirect_to);
+
+    // reading the redirect returns the target article
+    let mut get_params = GetArticleParams {
+        title: Some("NYC".to_string()),
+        ..Default::default()
+    };
+    let res = alpha.get_article(get_params.clone()).await?;
+    assert_eq!(target.article.id, res.article.id);
+    assert_eq!(Some(redirect.article.id), res.redirected_from.map(|a| a.id));
+
+    // redirect article itself can still be read and edited, which removes the redirect
+    get_params.follow_redirect = Some(false);
+    let res = alpha.get_article(get_params.clone()).await?;
+    assert_eq!(redirect.article.id, res.article.id);
+    let edit_params = EditArticleParams {
+        article_id: redirect.article.id,
+        new_text: "Abbreviation for New York City\n".to_string(),
+        summary: "remove redirect".to_string(),
+        previous_version_id: res.latest_version,
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    alpha.edit_article(&edit_params).await?;
+    get_params.follow_redirect = None;
+    let res = alpha.get_article(get_params).await?;
+    assert_eq!(redirect.article.id, res.article.id);
+    assert_eq!(None, res.article.redirect_to);
+    assert!(res.redirected_from.is_none());
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_move_article(TestData(alpha, beta, _): &mut TestData) -> Result<()> {
+    let mut articles = vec![];
+    for title in ["Peking", "Shanghai"] {
+        let params = CreateArticleParams {
+            title: title.to_string(),
+            ..create_test_article_params()
+        };
+        articles.push(alpha.create_article(&params).await?);
+    }
+    let article = &articles[0].article;
+
+    // cannot move to the title of an existing local article
+    let mut move_params = MoveArticleParams {
+        article_id: article.id,
+        new_title: "Shanghai".to_string(),
+    };
+    assert!(alpha.move_article(&move_params).await.is_err());
+
+    move_params.new_title = "Beijing".to_string();
+    let moved = alpha.move_article(&move_params).await?;
+    assert_eq!(article.id, moved.article.id);
+    assert_eq!("Beijing", moved.article.title);
+    assert_eq!(article.ap_id, moved.article.ap_id);
+    assert_eq!(1, moved.moves.len());
+    assert_eq!("Peking", moved.moves[0].article_move.old_title);
+    assert_eq!("Beijing", moved.moves[0].article_move.new_title);
+    assert!(moved.moves[0].creator.local);
+
+    // old title redirects to the moved article
+    let get_params = GetArticleParams {
+        title: Some("Peking".to_string()),
+        ..Default::default()
+    };
+    let res = alpha.get_article(get_params).await?;
+    assert_eq!(article.id, res.article.id);
+    assert_eq!(
+        Some("Peking".to_string()),
+        res.redirected_from.map(|a| a.title)
+    );
+
+    // other instances still find the article under its original id
+    let resolved = beta.resolve_article(article.ap_id.clone().into()).await?;
+    assert_eq!("Beijing", resolved.article.title);
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_resolve_article_cached(TestData(alpha, beta, _): &mut TestData) -> Result<()> {
+    let article = alpha.create_article(&create_test_article_params()).await?;
+
+    // first resolve fetches the article and its edits
+    let ap_id = article.article.ap_id.inner().clone();
+    let resolved = beta.resolve_article(ap_id.clone()).await?;
+    assert!(!resolved.article.local);
+    assert_eq!(article.article.text, resolved.article.text);
+    let edits = beta.get_article_edits(resolved.article.id).await?;
+    assert_eq!(1, edits.len());
+
+    // beta doesn't follow alpha, so it only learns about this edit by fetching again
+    let edit_params = EditArticleParams {
+        article_id: article.article.id,
+        new_text: "Lorem Ipsum 2\n".to_string(),
+        summary: "summary".to_string(),
+        previous_version_id: article.latest_version,
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    alpha
+        .edit_article_without_conflict(&edit_params)
         .await
         .unwrap();
 
-    // create new article
-    let create_params = create_test_article_params();
-    let create_res = beta.create_article(&create_params).await.unwrap();
-    assert_eq!(create_params.title, create_res.article.title);
-    assert!(create_res.article.local);
+    // second resolve within the refresh interval is served from the local copy
+    let cached = beta.resolve_article(ap_id).await?;
+    assert_eq!(resolved.article.id, cached.article.id);
+    assert_eq!(article.article.text, cached.article.text);
+    assert_eq!(resolved.latest_version, cached.latest_version);
 
-    // article should be federated to alpha
-    let get_article_data = GetArticleParams {
-        title: Some(create_res.article.title.to_string()),
-        domain: Some(beta_instance.domain),
-        id: None,
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_article_links(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let params = CreateArticleParams {
+        title: "Rust".to_string(),
+        text: "Built with [[Cargo]] by [[Ferris|the crab]]\n".to_string(),
+        ..create_test_article_params()
     };
-    let get_res = alpha.get_article(get_article_data.clone()).await.unwrap();
-    let edits = alpha.get_article_edits(get_res.article.id).await.unwrap();
-    assert_eq!(create_res.article.title, get_res.article.title);
-    assert_eq!(1, edits.len());
-    assert!(!get_res.article.local);
-    assert_eq!(create_res.article.text, get_res.article.text);
+    let rust = alpha.create_article(&params).await?;
+    assert_eq!(vec!["Cargo", "Ferris"], rust.missing_links);
+    let params = CreateArticleParams {
+        title: "Build Tools".to_string(),
+        text: "See [[cargo]]\n".to_string(),
+        ..create_test_article_params()
+    };
+    let tools = alpha.create_article(&params).await?;
+    assert_eq!(vec!["cargo"], tools.missing_links);
 
-    // edit the article
+    let params = CreateArticleParams {
+        title: "Cargo".to_string(),
+        ..create_test_article_params()
+    };
+    let cargo = alpha.create_article(&params).await?;
+    let params = CreateArticleParams {
+        title: "Cargo Cult".to_string(),
+        text: "Not about [[Cargo_Cult_Science]]\n".to_string(),
+        ..create_test_article_params()
+    };
+    alpha.create_article(&params).await?;
+
+    // link target exists now
+    let get_params = GetArticleParams {
+        id: Some(rust.article.id),
+        ..Default::default()
+    };
+    let rust = alpha.get_article(get_params).await?;
+    assert_eq!(vec!["Ferris"], rust.missing_links);
+    // links ignore case like title lookups
+    let get_params = GetArticleParams {
+        id: Some(tools.article.id),
+        ..Default::default()
+    };
+    let tools = alpha.get_article(get_params).await?;
+    assert!(tools.missing_links.is_empty());
+
+    // only matches of the full title are counted as backlinks
+    let backlinks = alpha
+        .get_backlinks(&GetBacklinks {
+            article_id: cargo.article.id,
+        })
+        .await?;
+    assert_eq!(2, backlinks.len());
+    assert_eq!(tools.article.id, backlinks[0].id);
+    assert_eq!(rust.article.id, backlinks[1].id);
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_watchlist(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let mut articles = vec![];
+    for title in ["Apple", "Banana"] {
+        let params = CreateArticleParams {
+            title: title.to_string(),
+            ..create_test_article_params()
+        };
+        articles.push(alpha.create_article(&params).await?);
+    }
+    assert!(alpha.get_watchlist().await?.is_empty());
+
+    // watching twice has no effect
+    for article in &articles {
+        alpha.follow_article(article.article.id, true).await?;
+    }
+    alpha.follow_article(articles[0].article.id, true).await?;
+    let watchlist = alpha.get_watchlist().await?;
+    assert_eq!(
+        vec!["Banana", "Apple"],
+        watchlist
+            .iter()
+            .map(|a| a.title.as_str())
+            .collect::<Vec<_>>()
+    );
+    let view = alpha.get_article(GetArticleParams {
+        id: Some(articles[0].article.id),
+        ..Default::default()
+    });
+    assert!(view.await?.following);
+
+    // most recently edited first
+    let edit_params = EditArticleParams {
+        article_id: articles[0].article.id,
+        new_text: "Apple is a fruit\n".to_string(),
+        summary: "summary".to_string(),
+        previous_version_id: articles[0].latest_version.clone(),
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    alpha.edit_article(&edit_params).await?;
+    let watchlist = alpha.get_watchlist().await?;
+    assert_eq!("Apple", watchlist[0].title);
+
+    // unwatch
+    alpha.follow_article(articles[0].article.id, false).await?;
+    let watchlist = alpha.get_watchlist().await?;
+    assert_eq!(1, watchlist.len());
+    assert_eq!("Banana", watchlist[0].title);
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_watcher_edit_notifications(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let create_res = alpha.create_article(&create_test_article_params()).await?;
+    let mut watchers = vec![];
+    for username in ["watcher_one", "watcher_two"] {
+        let client = ApiClient::new(Some(alpha.hostname.clone()));
+        let password = "hunter22".to_string();
+        client
+            .register(RegisterUserParams {
+                username: username.to_string(),
+                password: password.clone(),
+                email: None,
+                confirm_password: password,
+            })
+            .await?;
+        client.follow_article(create_res.article.id, true).await?;
+        watchers.push(client);
+    }
+    alpha.follow_article(create_res.article.id, true).await?;
+    let alpha_count = alpha.notifications_count().await?;
+
+    // first watcher edits, all other watchers get exactly one notification
+    let edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: "Lorem Ipsum\n".to_string(),
+        summary: "summary".to_string(),
+        previous_version_id: create_res.latest_version,
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    watchers[0].edit_article(&edit_params).await?;
+    assert_eq!(0, watchers[0].notifications_count().await?);
+    assert_eq!(alpha_count + 1, alpha.notifications_count().await?);
+    let notifications = watchers[1].notifications_list().await?;
+    assert_eq!(1, notifications.len());
+    let ApiNotificationData::Edit(edit) = &notifications[0].data else {
+        panic!("expected edit notification");
+    };
+    assert_eq!(edit_params.summary, edit.summary);
+
+    // marking as read removes it
+    watchers[1]
+        .article_notif_mark_as_read(notifications[0].id)
+        .await?;
+    assert_eq!(0, watchers[1].notifications_count().await?);
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_create_duplicate_article(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    // create article
+    let create_params = create_test_article_params();
+    let create_res = alpha.create_article(&create_params).await.unwrap();
+    assert_eq!(create_params.title, create_res.article.title);
+    assert!(create_res.article.local);
+
+    let create_res = alpha.create_article(&create_params).await;
+    assert!(create_res.is_err());
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_article_title_variants(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let create_res = alpha.create_article(&create_test_article_params()).await?;
+    assert_eq!("Manu Chao", create_res.article.title);
+
+    // case, underscores and extra whitespace all refer to the same article
+    let get_params = |title: &str| GetArticleParams {
+        title: Some(title.to_string()),
+        domain: None,
+        id: None,
+        follow_redirect: None,
+    };
+    for title in ["Manu Chao", "manu chao", "Manu_Chao", " MANU__chao "] {
+        let res = alpha.get_article(get_params(title)).await?;
+        assert_eq!(create_res.article.id, res.article.id);
+    }
+    for title in ["Main Page", "main_page", "MAIN PAGE"] {
+        let res = alpha.get_article(get_params(title)).await?;
+        assert_eq!(MAIN_PAGE_NAME, res.article.title);
+    }
+
+    // variant of an existing title can't be created
+    let create_params = CreateArticleParams {
+        title: "manu_chao".to_string(),
+        ..create_test_article_params()
+    };
+    let err = alpha.create_article(&create_params).await.unwrap_err();
+    assert!(err.to_string().contains("already exists"));
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_get_article_not_found(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    // unknown title is reported as not found, so that the frontend can offer to create it
+    let mut params = GetArticleParams {
+        title: Some("Unknown Article".to_string()),
+        domain: None,
+        id: None,
+        follow_redirect: None,
+    };
+    let err = alpha.get_article(params.clone()).await.unwrap_err();
+    assert!(err.is_not_found());
+    assert!(
+        err.to_string()
+            .contains("Article Unknown Article not found")
+    );
+    params.domain = Some(alpha.hostname.clone());
+    assert!(
+        alpha
+            .get_article(params.clone())
+            .await
+            .unwrap_err()
+            .is_not_found()
+    );
+
+    // other errors are distinct
+    params.title = None;
+    params.domain = None;
+    let err = alpha.get_article(params).await.unwrap_err();
+    assert!(!err.is_not_found());
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_follow_instance(TestData(alpha, beta, _): &mut TestData) -> Result<()> {
+    // check initial state
+    let alpha_follows = alpha.get_follows().await.unwrap();
+    assert_eq!(0, alpha_follows.len());
+    let beta_follows = beta.get_follows().await.unwrap();
+    assert_eq!(0, beta_follows.len());
+
+    alpha
+        .follow_instance_with_resolve(&beta.hostname)
+        .await
+        .unwrap();
+
+    // check that follow was federated, and confirmed with accept
+    let alpha_follows = alpha.get_follows().await.unwrap();
+    assert_eq!(1, alpha_follows.len());
+    assert!(!alpha_follows[0].pending);
+    let beta_site = beta.site().await.unwrap();
+    assert_eq!(beta_site.instance.ap_id, alpha_follows[0].instance.ap_id);
+
+    // beta lists alpha user as follower
+    let beta_admin = ApiClient::new(Some(beta.hostname.clone()));
+    beta_admin
+        .login(LoginUserParams {
+            username_or_email: "ibis".to_string(),
+            password: "ibis".to_string(),
+        })
+        .await?;
+    let stats_params = GetFederationStats::default();
+    let stats = beta_admin.federation_stats(&stats_params).await?;
+    assert_eq!(1, stats.followers);
+
+    // the accept was delivered before the follow call returned, so its status is recorded
+    assert_eq!(0, stats.queued_activities);
+    assert_eq!(0, stats.dead_deliveries);
+    assert_eq!(0, stats.failed_deliveries);
+    let alpha_ap_id = alpha.site().await?.instance.ap_id;
+    let alpha_peer = stats
+        .peers
+        .iter()
+        .find(|p| p.instance.ap_id == alpha_ap_id)
+        .and_then(|p| p.delivery.clone())
+        .expect("delivery to alpha recorded");
+    assert!(alpha_peer.last_success.is_some());
+    assert_eq!(0, alpha_peer.failures);
+
+    // follow counts match the follow rows, for local and remote instances
+    let beta_params = GetInstanceParams {
+        id: Some(beta_site.instance.id),
+        hostname: None,
+    };
+    let beta_view = beta.get_instance(&beta_params).await?;
+    assert_eq!(1, beta_view.follower_count);
+    assert_eq!(0, beta_view.following_count);
+    let followers_params = GetInstanceFollowers {
+        instance_id: beta_site.instance.id,
+        limit: None,
+        offset: None,
+    };
+    let followers = beta.get_instance_followers(&followers_params).await?;
+    assert_eq!(1, followers.total);
+    assert_eq!(1, followers.followers.len());
+    assert_eq!("alpha", followers.followers[0].username);
+    let alpha_params = GetInstanceParams {
+        id: None,
+        hostname: Some(alpha.hostname.clone()),
+    };
+    let alpha_view = alpha.get_instance(&alpha_params).await?;
+    assert_eq!(0, alpha_view.follower_count);
+    assert_eq!(1, alpha_view.following_count);
+    let alpha_view = beta.get_instance(&alpha_params).await?;
+    assert_eq!(1, alpha_view.following_count);
+
+    // unfollow
+    let beta_id = alpha_follows[0].instance.id;
+    alpha.follow_instance(beta_id, false).await.unwrap();
+    let alpha_follows = alpha.get_follows().await.unwrap();
+    assert_eq!(0, alpha_follows.len());
+    let stats = beta_admin.federation_stats(&stats_params).await?;
+    assert_eq!(0, stats.followers);
+    assert_eq!(0, beta.get_instance(&beta_params).await?.follower_count);
+    let followers = beta.get_instance_followers(&followers_params).await?;
+    assert_eq!(0, followers.total);
+    assert!(followers.followers.is_empty());
+
+    // unfollowing again is a no-op
+    alpha.follow_instance(beta_id, false).await.unwrap();
+    assert_eq!(0, alpha.get_follows().await.unwrap().len());
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_instance_followers_pagination(
+    TestData(alpha, beta, _): &mut TestData,
+) -> Result<()> {
+    let alpha_instance = beta.follow_instance_with_resolve(&alpha.hostname).await?;
+    for username in ["follower_a", "follower_b", "follower_c"] {
+        let client = ApiClient::new(Some(beta.hostname.clone()));
+        let password = "hunter22".to_string();
+        client
+            .register(RegisterUserParams {
+                username: username.to_string(),
+                password: password.clone(),
+                email: None,
+                confirm_password: password,
+            })
+            .await?;
+        client.follow_instance(alpha_instance.id, true).await?;
+    }
+
+    let mut params = GetInstanceFollowers {
+        instance_id: alpha.site().await?.instance.id,
+        limit: None,
+        offset: None,
+    };
+    let all = alpha.get_instance_followers(&params).await?;
+    assert_eq!(4, all.total);
+    assert_eq!(4, all.followers.len());
+
+    // offset and limit return the matching slice, with the total of all pages
+    params.offset = Some(1);
+    params.limit = Some(2);
+    let res = alpha.get_instance_followers(&params).await?;
+    assert_eq!(4, res.total);
+    assert_eq!(all.followers[1..3], res.followers);
+
+    params.offset = Some(4);
+    assert!(
+        alpha
+            .get_instance_followers(&params)
+            .await?
+            .followers
+            .is_empty()
+    );
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_follow_instance_rejected(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    // use a separate port range from the default test instances
+    let options = Options {
+        accept_follows: false,
+        ..Default::default()
+    };
+    let closed = IbisInstance::new_with_options("closed", 8099, options).await;
+
+    let closed_instance = alpha.follow_instance_with_resolve(&closed.hostname).await;
+    let follows = alpha.get_follows().await;
+    closed.stop().await;
+
+    // pending follow is removed after receiving the reject
+    closed_instance?;
+    assert_eq!(0, follows?.len());
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_follow_article_rejected(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let options = Options {
+        accept_follows: false,
+        ..Default::default()
+    };
+    let closed = IbisInstance::new_with_options("closed_article", 8089, options).await;
+    let res = async {
+        let article = closed.create_article(&create_test_article_params()).await?;
+        let article_alpha = alpha
+            .resolve_article(article.article.ap_id.inner().clone())
+            .await?;
+        alpha.follow_article(article_alpha.article.id, true).await?;
+
+        // the article follow is rejected like an instance follow, so edits are not delivered
+        let edit_params = EditArticleParams {
+            article_id: article.article.id,
+            new_text: "Updated text\n".to_string(),
+            summary: "update".to_string(),
+            previous_version_id: article.latest_version.clone(),
+            resolve_conflict_id: None,
+            minor: None,
+        };
+        closed
+            .edit_article_without_conflict(&edit_params)
+            .await
+            .unwrap();
+        let params = GetArticleParams {
+            id: Some(article_alpha.article.id),
+            ..Default::default()
+        };
+        let updated = alpha.get_article(params).await?;
+        assert_eq!(article_alpha.article.text, updated.article.text);
+        Ok::<_, anyhow::Error>(())
+    }
+    .await;
+    closed.stop().await;
+    res
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_delivery_retry(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let admin = ApiClient::new(Some(alpha.hostname.clone()));
+    admin
+        .login(LoginUserParams {
+            username_or_email: "ibis".to_string(),
+            password: "ibis".to_string(),
+        })
+        .await?;
+
+    // follower instance goes offline
+    let flaky = IbisInstance::new_with_options("flaky", 8091, Default::default()).await;
+    flaky.follow_instance_with_resolve(&alpha.hostname).await?;
+    flaky.stop().await;
+
+    // so the new article can't be delivered and is queued for retry
+    alpha.create_article(&create_test_article_params()).await?;
+    let queue = admin.list_delivery_queue().await?;
+    assert!(!queue.is_empty());
+    for delivery in &queue {
+        assert_eq!(Some(8091), delivery.inbox.inner().port());
+        assert_eq!(1, delivery.attempts);
+        assert!(!delivery.dead);
+    }
+    let stats = admin
+        .federation_stats(&GetFederationStats::default())
+        .await?;
+    assert_eq!(queue.len() as i64, stats.queued_activities);
+    assert_eq!(0, stats.dead_deliveries);
+
+    // retrying while it is still offline counts as another attempt
+    let params = RetryDeliveryParams { id: queue[0].id };
+    assert!(admin.retry_delivery(&params).await.is_err());
+    let queue = admin.list_delivery_queue().await?;
+    let delivery = queue.iter().find(|d| d.id == params.id).unwrap();
+    assert_eq!(2, delivery.attempts);
+
+    // once it is back online the deliveries succeed and are removed from the queue
+    let flaky = IbisInstance::new_with_options("flaky", 8091, Default::default()).await;
+    let mut res = Ok(());
+    for delivery in &queue {
+        let params = RetryDeliveryParams { id: delivery.id };
+        res = res.and(admin.retry_delivery(&params).await);
+    }
+    flaky.stop().await;
+    res?;
+    assert!(admin.list_delivery_queue().await?.is_empty());
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_sync_instance(TestData(alpha, beta, _): &mut TestData) -> Result<()> {
+    let create_res = alpha.create_article(&create_test_article_params()).await?;
+    let edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: "Lorem Ipsum\n".to_string(),
+        summary: "first edit".to_string(),
+        previous_version_id: create_res.latest_version,
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    alpha
+        .edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+
+    let alpha_instance = beta.follow_instance_with_resolve(&alpha.hostname).await?;
+    let sync_params = SyncInstanceParams {
+        instance_id: alpha_instance.id,
+    };
+    // only admins can sync
+    assert!(beta.sync_instance(&sync_params).await.is_err());
+
+    let beta_admin = ApiClient::new(Some(beta.hostname.clone()));
+    beta_admin
+        .login(LoginUserParams {
+            username_or_email: "ibis".to_string(),
+            password: "ibis".to_string(),
+        })
+        .await?;
+    // syncing twice doesn't create duplicates
+    beta_admin.sync_instance(&sync_params).await?;
+    beta_admin.sync_instance(&sync_params).await?;
+    let articles = beta
+        .list_articles(ListArticlesParams {
+            instance_id: Some(alpha_instance.id),
+            ..Default::default()
+        })
+        .await?
+        .articles;
+    assert_eq!(1, articles.len());
+    let article = &articles[0].article;
+    assert_eq!(create_res.article.title, article.title);
+    assert_eq!(edit_params.new_text, article.text);
+    let edits = beta.get_article_edits(article.id).await?;
+    assert_eq!(2, edits.len());
+
+    // local instance can't be synced
+    let sync_params = SyncInstanceParams {
+        instance_id: beta_admin.site().await?.instance.id,
+    };
+    assert!(beta_admin.sync_instance(&sync_params).await.is_err());
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_nodeinfo(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    // discovery document points to the nodeinfo schema
+    let client = reqwest::Client::new();
+    let well_known: serde_json::Value = client
+        .get(format!("http://{}/.well-known/nodeinfo", alpha.hostname))
+        .send()
+        .await?
+        .json()
+        .await?;
+    let link = &well_known["links"][0];
+    assert_eq!(
+        Some("http://nodeinfo.diaspora.software/ns/schema/2.1"),
+        link["rel"].as_str()
+    );
+    let Some(href) = link["href"].as_str() else {
+        panic!("nodeinfo link has no href");
+    };
+
+    let node_info: NodeInfo = client.get(href).send().await?.json().await?;
+    assert_eq!("2.1", node_info.version);
+    assert_eq!("ibis", node_info.software.name);
+    assert_eq!(env!("CARGO_PKG_VERSION"), node_info.software.version);
+    assert!(node_info.usage.users.total.is_some());
+    assert!(node_info.usage.local_posts.is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn api_test_nodeinfo_without_usage() -> Result<()> {
+    let options = Options {
+        registration_open: true,
+        nodeinfo_usage_stats: false,
+        ..Default::default()
+    };
+    let private = IbisInstance::new_with_options("private", 8086, options).await;
+    let res = async {
+        let json: serde_json::Value = reqwest::Client::new()
+            .get(format!("http://{}/nodeinfo/2.1.json", private.hostname))
+            .send()
+            .await?
+            .json()
+            .await?;
+        // counts are left out, but the required users object and metadata map are still there
+        assert_eq!(serde_json::json!({ "users": {} }), json["usage"]);
+        assert!(json["metadata"].is_object());
+        Ok::<_, anyhow::Error>(())
+    }
+    .await;
+    private.stop().await;
+    res
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_export_article_history(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let article = alpha.create_article(&create_test_article_params()).await?;
+    let edit_params = EditArticleParams {
+        article_id: article.article.id,
+        new_text: "Lorem Ipsum 2\n".to_string(),
+        summary: "second edit".to_string(),
+        previous_version_id: article.latest_version,
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    alpha
+        .edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+
+    let url = format!(
+        "http://{}/api/v1/article/export_history?article_id={}",
+        alpha.hostname, article.article.id.0
+    );
+    let res = reqwest::Client::new().get(&url).send().await?;
+    assert!(res.status().is_success());
+    let disposition = res
+        .headers()
+        .get(CONTENT_DISPOSITION)
+        .and_then(|h| h.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    assert!(disposition.contains(&format!("{}.fi", article.article.id.0)));
+
+    // one commit per edit, each based on the previous one
+    let export = res.text().await?;
+    assert_eq!(2, export.matches("commit refs/heads/main").count());
+    assert_eq!(1, export.matches("from :1").count());
+    assert!(export.contains("M 100644 inline Manu Chao.md"));
+    assert!(export.contains("author alpha <alpha@"));
+    assert!(export.contains(&format!(
+        "data {}\n{}",
+        edit_params.summary.len(),
+        edit_params.summary
+    )));
+    assert!(export.ends_with(&edit_params.new_text));
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_content_security_policy(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let client = reqwest::Client::new();
+    let mut nonces = vec![];
+    for _ in 0..2 {
+        let res = client
+            .get(format!("http://{}/", alpha.hostname))
+            .send()
+            .await?;
+        let policy = res
+            .headers()
+            .get(CONTENT_SECURITY_POLICY)
+            .and_then(|h| h.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        assert!(policy.contains("default-src 'self'"));
+        let Some(nonce) = policy
+            .split("'nonce-")
+            .nth(1)
+            .and_then(|n| n.split('\'').next())
+        else {
+            panic!("policy has no nonce: {policy}");
+        };
+        // hydration scripts are allowed via the nonce from the header
+        let html = res.text().await?;
+        assert!(html.contains(&format!("nonce=\"{nonce}\"")));
+        nonces.push(nonce.to_string());
+    }
+    // each request gets a new nonce
+    assert_ne!(nonces[0], nonces[1]);
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_atom_feeds(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let article = alpha.create_article(&create_test_article_params()).await?;
+    let edit_params = EditArticleParams {
+        article_id: article.article.id,
+        new_text: "Lorem Ipsum 2\n".to_string(),
+        summary: "summary".to_string(),
+        previous_version_id: article.latest_version,
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    alpha.edit_article(&edit_params).await?;
+    let edits = alpha.get_article_edits(article.article.id).await?;
+
+    // article history, newest first
+    let client = reqwest::Client::new();
+    let feed_url = format!(
+        "http://{}/feeds/article/{}",
+        alpha.hostname, article.article.id.0
+    );
+    let res = client.get(&feed_url).send().await?;
+    assert_eq!(
+        Some("application/atom+xml"),
+        res.headers()
+            .get(CONTENT_TYPE)
+            .and_then(|h| h.to_str().ok())
+    );
+    let xml = res.text().await?;
+    assert!(xml.contains("<feed xmlns=\"http://www.w3.org/2005/Atom\""));
+    let feed = Feed::read_from(xml.as_bytes())?;
+    assert_eq!(feed_url, feed.id);
+    assert_eq!("History of Manu Chao", feed.title.value);
+    assert!(
+        feed.links
+            .iter()
+            .any(|l| l.rel == "self" && l.href == feed_url)
+    );
+    assert!(
+        feed.links
+            .iter()
+            .any(|l| l.rel == "alternate" && l.href.ends_with("/article/Manu_Chao/history"))
+    );
+    assert_eq!(2, feed.entries.len());
+    let entry = &feed.entries[0];
+    assert_eq!("summary", entry.title.value);
+    assert_eq!(edits[1].edit.ap_id.to_string(), entry.id);
+    assert_eq!(edits[1].creator.username, entry.authors[0].name);
+    assert_eq!(edits[1].edit.published, entry.updated);
+    assert!(
+        entry.links[0]
+            .href
+            .ends_with(&format!("/diff/{}", edits[1].edit.hash.0))
+    );
+    assert_eq!("create article", feed.entries[1].title.value);
+
+    // recent changes across all articles
+    let xml = client
+        .get(format!("http://{}/feeds/recent_changes", alpha.hostname))
+        .send()
+        .await?
+        .text()
+        .await?;
+    let feed = Feed::read_from(xml.as_bytes())?;
+    assert!(feed.title.value.starts_with("Recent changes"));
+    assert_eq!("summary", feed.entries[0].title.value);
+    assert!(feed.entries.len() >= 2);
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_webfinger(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let Some(user) = alpha.site().await?.my_profile else {
+        panic!("not logged in");
+    };
+    let client = reqwest::Client::new();
+    let webfinger = |name: &str| {
+        client
+            .get(format!("http://{}/.well-known/webfinger", alpha.hostname))
+            .query(&[("resource", format!("acct:{name}@{}", alpha.hostname))])
+            .send()
+    };
+
+    // local user is found, with actor id as self link
+    let res = webfinger(&user.person.username).await?;
+    assert!(res.status().is_success());
+    let json: serde_json::Value = res.json().await?;
+    let self_link = json["links"]
+        .as_array()
+        .and_then(|links| links.iter().find(|l| l["rel"] == "self"));
+    let Some(self_link) = self_link else {
+        panic!("webfinger response has no self link");
+    };
+    assert_eq!(
+        Some("application/activity+json"),
+        self_link["type"].as_str()
+    );
+    assert_eq!(
+        Some(user.person.ap_id.to_string().as_str()),
+        self_link["href"].as_str()
+    );
+
+    // unknown user
+    let res = webfinger("unknown_user").await?;
+    assert_eq!(reqwest::StatusCode::NOT_FOUND, res.status());
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_fetch_edits_collection(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let create_res = alpha.create_article(&create_test_article_params()).await?;
+    let mut edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: String::new(),
+        summary: String::new(),
+        previous_version_id: create_res.latest_version,
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    for i in 1..=2 {
+        edit_params.new_text = format!("Lorem Ipsum {i}\n");
+        edit_params.summary = format!("edit {i}");
+        let res = alpha
+            .edit_article_without_conflict(&edit_params)
+            .await
+            .unwrap();
+        edit_params.previous_version_id = res.latest_version;
+    }
+
+    // full history is available as ordered collection, oldest edit first
+    let edits_id = format!("{}/edits", create_res.article.ap_id);
+    let json: serde_json::Value = reqwest::Client::new()
+        .get(&edits_id)
+        .header("Accept", "application/activity+json")
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(Some(edits_id.as_str()), json["id"].as_str());
+    assert_eq!(Some("OrderedCollection"), json["type"].as_str());
+    assert_eq!(Some(3), json["totalItems"].as_i64());
+    let summaries: Vec<_> = json["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["summary"].as_str().unwrap())
+        .collect();
+    assert_eq!(vec!["create article", "edit 1", "edit 2"], summaries);
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_fetch_edit(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let create_res = alpha.create_article(&create_test_article_params()).await?;
+    let edits = alpha.get_article_edits(create_res.article.id).await?;
+    let edit_ap_id = edits[0].edit.ap_id.to_string();
+
+    // known edit can be fetched via federation
+    let client = reqwest::Client::new();
+    let res = client
+        .get(&edit_ap_id)
+        .header("Accept", "application/activity+json")
+        .send()
+        .await?;
+    assert!(res.status().is_success());
+    let json: serde_json::Value = res.json().await?;
+    assert_eq!(Some(edit_ap_id.as_str()), json["id"].as_str());
+    assert_eq!(Some("Patch"), json["type"].as_str());
+
+    // unknown edit gives an error
+    let unknown = format!(
+        "{}/{}",
+        create_res.article.ap_id,
+        EditVersion::new("unknown").hash()
+    );
+    let res = client
+        .get(&unknown)
+        .header("Accept", "application/activity+json")
+        .send()
+        .await?;
+    assert!(!res.status().is_success());
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_synchronize_articles(TestData(alpha, beta, _): &mut TestData) -> Result<()> {
+    // create article on alpha
+    let create_params = create_test_article_params();
+    let create_res = alpha.create_article(&create_params).await.unwrap();
+    assert_eq!(create_params.title, create_res.article.title);
+    let edits = alpha
+        .get_article_edits(create_res.article.id)
+        .await
+        .unwrap();
+    assert_eq!(1, edits.len());
+    assert!(create_res.article.local);
+
+    // edit the article
+    let edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: "Lorem Ipsum 2\n".to_string(),
+        summary: "summary".to_string(),
+        previous_version_id: create_res.latest_version,
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    let edit_res = alpha
+        .edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+
+    // fetch alpha instance on beta, articles are also fetched automatically
+    let instance = beta
+        .resolve_instance(Url::parse(&format!("http://{}", &alpha.hostname))?)
+        .await
+        .unwrap();
+
+    let get_article_data = GetArticleParams {
+        title: Some(create_res.article.title.clone()),
+        ..Default::default()
+    };
+
+    // try to read remote article by name, fails without domain
+    let get_res = beta.get_article(get_article_data.clone()).await;
+    assert!(get_res.is_err());
+
+    // get the article with instance id and compare
+    let get_res = RetryFuture::new(
+        || async {
+            let get_article_data = GetArticleParams {
+                title: Some(create_res.article.title.clone()),
+                domain: Some(instance.domain.clone()),
+                id: None,
+                follow_redirect: None,
+            };
+            match beta.get_article(get_article_data).await {
+                Err(_) => Err(RetryPolicy::<String>::Retry(None)),
+                Ok(a) if a.latest_version != edit_res.latest_version => {
+                    Err(RetryPolicy::Retry(None))
+                }
+                Ok(a) => Ok(a),
+            }
+        },
+        LinearRetryStrategy::new(),
+    )
+    .await?;
+    let beta_edits = beta.get_article_edits(get_res.article.id).await.unwrap();
+    assert_eq!(create_res.article.ap_id, get_res.article.ap_id);
+    assert_eq!(create_params.title, get_res.article.title);
+    assert_eq!(2, beta_edits.len());
+    assert_eq!(edit_params.new_text, get_res.article.text);
+    assert!(!get_res.article.local);
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_instance_activity(TestData(alpha, beta, gamma): &mut TestData) -> Result<()> {
+    let beta_instance = alpha
+        .follow_instance_with_resolve(&beta.hostname)
+        .await
+        .unwrap();
+    let gamma_instance = alpha
+        .follow_instance_with_resolve(&gamma.hostname)
+        .await
+        .unwrap();
+
+    // create articles on beta and gamma, which are federated to alpha
+    let create_params = create_test_article_params();
+    let beta_article = beta.create_article(&create_params).await.unwrap();
+    let mut create_params = create_test_article_params();
+    create_params.title = "Another article".to_string();
+    gamma.create_article(&create_params).await.unwrap();
+    let edit_params = EditArticleParams {
+        article_id: beta_article.article.id,
+        new_text: "Lorem Ipsum 2\n".to_string(),
+        summary: "summary".to_string(),
+        previous_version_id: beta_article.latest_version,
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    beta.edit_article(&edit_params).await.unwrap();
+
+    // only activity from beta is returned, newest first
+    let mut params = GetInstanceActivity {
+        instance_id: beta_instance.id,
+        limit: Some(1),
+        before: None,
+    };
+    let activity = alpha.get_instance_activity(&params).await.unwrap();
+    assert_eq!(1, activity.len());
+    assert_eq!("summary", activity[0].edit.summary);
+    assert_eq!(beta_article.article.ap_id, activity[0].article.ap_id);
+
+    // next page
+    params.before = Some(activity[0].edit.published);
+    let activity = alpha.get_instance_activity(&params).await.unwrap();
+    assert_eq!(1, activity.len());
+    assert_eq!(create_params.summary, activity[0].edit.summary);
+
+    params.instance_id = gamma_instance.id;
+    params.before = None;
+    params.limit = None;
+    let activity = alpha.get_instance_activity(&params).await.unwrap();
+    assert_eq!(1, activity.len());
+    assert_eq!(create_params.title, activity[0].article.title);
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_compare_prev_cur(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let article = alpha.create_article(&create_test_article_params()).await?;
+    let mut previous_version_id = article.latest_version;
+    for i in 1..=2 {
+        let edit_params = EditArticleParams {
+            article_id: article.article.id,
+            new_text: format!("Lorem Ipsum {i}\n"),
+            summary: format!("edit {i}"),
+            previous_version_id,
+            resolve_conflict_id: None,
+            minor: None,
+        };
+        previous_version_id = alpha
+            .edit_article_without_conflict(&edit_params)
+            .await
+            .unwrap()
+            .latest_version;
+    }
+    let edits = alpha.get_article_edits(article.article.id).await?;
+    assert_eq!(3, edits.len());
+    let latest = &edits[2].edit.hash;
+
+    for (i, e) in edits.iter().enumerate().skip(1) {
+        // prev links to the diff between the previous version and this one
+        assert_eq!(edits[i - 1].edit.hash, e.edit.previous_version_id);
+        let prev = CompareRevisionsParams {
+            article_id: article.article.id,
+            from: e.edit.previous_version_id.clone(),
+            to: e.edit.hash.clone(),
+        };
+        assert_eq!(e.edit.diff, alpha.compare_revisions(&prev).await?);
+    }
+
+    // cur compares with the latest version, which is the last edit for the second to last
+    let cur = CompareRevisionsParams {
+        article_id: article.article.id,
+        from: edits[1].edit.hash.clone(),
+        to: latest.clone(),
+    };
+    assert_eq!(edits[2].edit.diff, alpha.compare_revisions(&cur).await?);
+
+    // for the first version, cur contains the changes of all later edits
+    let cur = CompareRevisionsParams {
+        article_id: article.article.id,
+        from: edits[0].edit.hash.clone(),
+        to: latest.clone(),
+    };
+    let diff = alpha.compare_revisions(&cur).await?;
+    assert!(diff.contains(&format!("-{TEST_ARTICLE_DEFAULT_TEXT}")));
+    assert!(diff.contains("+Lorem Ipsum 2"));
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_recent_changes(TestData(alpha, beta, _): &mut TestData) -> Result<()> {
+    let article = alpha.create_article(&create_test_article_params()).await?;
+    let edit_params = EditArticleParams {
+        article_id: article.article.id,
+        new_text: "Lorem Ipsum 2\n".to_string(),
+        summary: "summary".to_string(),
+        previous_version_id: article.latest_version,
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    alpha.edit_article(&edit_params).await?;
+
+    // remote article fetched by alpha
+    let mut create_params = create_test_article_params();
+    create_params.title = "Remote article".to_string();
+    let remote = beta.create_article(&create_params).await?;
+    alpha
+        .resolve_article(remote.article.ap_id.clone().into())
+        .await?;
+
+    // edits appear newest first, before the main page created during setup
+    let mut params = GetRecentChanges {
+        limit: Some(3),
+        ..Default::default()
+    };
+    let recent = alpha.get_recent_changes(&params).await?;
+    assert_eq!(3, recent.len());
+    assert_eq!(remote.article.ap_id, recent[0].article.ap_id);
+    assert_eq!("summary", recent[1].edit.summary);
+    assert_eq!("create article", recent[2].edit.summary);
+
+    // only local articles
+    params.only_local = Some(true);
+    let local = alpha.get_recent_changes(&params).await?;
+    assert_eq!(2, local.len());
+    assert!(local.iter().all(|e| e.article.local));
+
+    // only edits newer than the given time
+    params.only_local = None;
+    params.since = Some(recent[2].edit.published);
+    let since = alpha.get_recent_changes(&params).await?;
+    assert_eq!(2, since.len());
+    assert_eq!("summary", since[1].edit.summary);
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_edit_local_article(TestData(alpha, beta, _): &mut TestData) -> Result<()> {
+    let beta_instance = alpha
+        .follow_instance_with_resolve(&beta.hostname)
+        .await
+        .unwrap();
+
+    // create new article
+    let create_params = create_test_article_params();
+    let create_res = beta.create_article(&create_params).await.unwrap();
+    assert_eq!(create_params.title, create_res.article.title);
+    assert!(create_res.article.local);
+
+    // article should be federated to alpha
+    let get_article_data = GetArticleParams {
+        title: Some(create_res.article.title.to_string()),
+        domain: Some(beta_instance.domain),
+        id: None,
+        follow_redirect: None,
+    };
+    let get_res = alpha.get_article(get_article_data.clone()).await.unwrap();
+    let edits = alpha.get_article_edits(get_res.article.id).await.unwrap();
+    assert_eq!(create_res.article.title, get_res.article.title);
+    assert_eq!(1, edits.len());
+    assert!(!get_res.article.local);
+    assert_eq!(create_res.article.text, get_res.article.text);
+
+    // edit the article
+    let edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: "Lorem Ipsum 2\n".to_string(),
+        summary: "summary".to_string(),
+        previous_version_id: get_res.latest_version,
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    let edit_res = beta
+        .edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+    let edits = beta.get_article_edits(edit_res.article.id).await.unwrap();
+    assert_eq!(edit_res.article.text, edit_params.new_text);
+    assert_eq!(edits.len(), 2);
+    assert!(
+        edits[0]
+            .edit
+            .ap_id
+            .to_string()
+            .starts_with(&edit_res.article.ap_id.to_string())
+    );
+
+    // edit should be federated to alpha
+    let get_res = alpha.get_article(get_article_data).await.unwrap();
+    let edits = alpha.get_article_edits(get_res.article.id).await.unwrap();
+    assert_eq!(edit_res.article.title, get_res.article.title);
+    assert_eq!(edits.len(), 2);
+    assert_eq!(edit_res.article.text, get_res.article.text);
+    assert_eq!(edit_res.latest_version, get_res.latest_version);
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_edit_history_order(TestData(alpha, beta, _): &mut TestData) -> Result<()> {
+    let beta_instance = alpha.follow_instance_with_resolve(&beta.hostname).await?;
+    let create_res = beta.create_article(&create_test_article_params()).await?;
+    let mut edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: String::new(),
+        summary: "summary".to_string(),
+        previous_version_id: create_res.latest_version,
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    for i in 1..=3 {
+        edit_params.new_text = format!("Lorem Ipsum {i}\n");
+        let res = beta
+            .edit_article_without_conflict(&edit_params)
+            .await
+            .unwrap();
+        edit_params.previous_version_id = res.latest_version;
+    }
+
+    // both locally and on the follower, each edit comes after the one it is based on
+    let get_params = GetArticleParams {
+        title: Some(create_res.article.title.clone()),
+        domain: Some(beta_instance.domain),
+        id: None,
+        follow_redirect: None,
+    };
+    let alpha_article = alpha.get_article(get_params).await?;
+    for edits in [
+        beta.get_article_edits(create_res.article.id).await?,
+        alpha.get_article_edits(alpha_article.article.id).await?,
+    ] {
+        assert_eq!(4, edits.len());
+        assert_eq!(EditVersion::default(), edits[0].edit.previous_version_id);
+        for pair in edits.windows(2) {
+            assert_eq!(pair[0].edit.hash, pair[1].edit.previous_version_id);
+            assert!(pair[0].edit.sequence < pair[1].edit.sequence);
+        }
+        assert_eq!(
+            Some(&edit_params.previous_version_id),
+            edits.last().map(|e| &e.edit.hash)
+        );
+    }
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_edit_reach(TestData(alpha, beta, gamma): &mut TestData) -> Result<()> {
+    beta.follow_instance_with_resolve(&alpha.hostname).await?;
+    gamma.follow_instance_with_resolve(&alpha.hostname).await?;
+    let article = alpha.create_article(&create_test_article_params()).await?;
+    let params = GetEditReach {
+        article_id: article.article.id,
+    };
+
+    // edits of local articles go to all following instances, domains are hidden for normal users
+    let reach = alpha.get_edit_reach(&params).await?;
+    assert_eq!(2, reach.instances);
+    assert_eq!(None, reach.instance_domains);
+
+    // local followers of the article are counted
+    let admin = ApiClient::new(Some(alpha.hostname.clone()));
+    admin
+        .login(LoginUserParams {
+            username_or_email: "ibis".to_string(),
+            password: "ibis".to_string(),
+        })
+        .await?;
+    admin.follow_article(article.article.id, true).await?;
+    let admin_reach = admin.get_edit_reach(&params).await?;
+    assert_eq!(reach.local_followers + 1, admin_reach.local_followers);
+    let mut expected = vec![beta.hostname.clone(), gamma.hostname.clone()];
+    expected.sort();
+    assert_eq!(Some(expected), admin_reach.instance_domains);
+
+    // edits of remote articles only go to the home instance
+    let remote = beta.create_article(&create_test_article_params()).await?;
+    let resolved = admin
+        .resolve_article(remote.article.ap_id.inner().clone())
+        .await?;
+    let reach = admin
+        .get_edit_reach(&GetEditReach {
+            article_id: resolved.article.id,
+        })
+        .await?;
+    assert_eq!(1, reach.instances);
+    assert_eq!(Some(vec![beta.hostname.clone()]), reach.instance_domains);
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_edit_remote_article(TestData(alpha, beta, gamma): &mut TestData) -> Result<()> {
+    let beta_id_on_alpha = alpha
+        .follow_instance_with_resolve(&beta.hostname)
+        .await
+        .unwrap();
+    let beta_id_on_gamma = gamma
+        .follow_instance_with_resolve(&beta.hostname)
+        .await
+        .unwrap();
+
+    // create new article
+    let create_params = create_test_article_params();
+    let create_res = beta.create_article(&create_params).await.unwrap();
+    assert_eq!(&create_params.title, &create_res.article.title);
+    assert!(create_res.article.local);
+
+    // article should be federated to alpha and gamma
+    let get_article_data_alpha = GetArticleParams {
+        title: Some(create_res.article.title.to_string()),
+        domain: Some(beta_id_on_alpha.domain),
+        id: None,
+        follow_redirect: None,
+    };
+    let get_res = alpha
+        .get_article(get_article_data_alpha.clone())
+        .await
+        .unwrap();
+    assert_eq!(create_res.article.title, get_res.article.title);
+    let edits = alpha.get_article_edits(get_res.article.id).await.unwrap();
+    assert_eq!(1, edits.len());
+    assert!(!get_res.article.local);
+
+    let get_article_data_gamma = GetArticleParams {
+        title: Some(create_res.article.title.to_string()),
+        domain: Some(beta_id_on_gamma.domain),
+        id: None,
+        follow_redirect: None,
+    };
+    let get_res = gamma
+        .get_article(get_article_data_gamma.clone())
+        .await
+        .unwrap();
+    assert_eq!(create_res.article.title, get_res.article.title);
+    assert_eq!(create_res.article.text, get_res.article.text);
+
+    let edit_params = EditArticleParams {
+        article_id: get_res.article.id,
+        new_text: "Lorem Ipsum 2\n".to_string(),
+        summary: "summary".to_string(),
+        previous_version_id: get_res.latest_version,
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    let edit_res = alpha
+        .edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+    assert_eq!(edit_params.new_text, edit_res.article.text);
+    let edits = alpha.get_article_edits(edit_res.article.id).await.unwrap();
+    assert_eq!(2, edits.len());
+    assert!(!edit_res.article.local);
+    assert!(
+        edits[0]
+            .edit
+            .ap_id
+            .to_string()
+            .starts_with(&edit_res.article.ap_id.to_string())
+    );
+
+    // edit should be federated to beta and gamma
+    let get_res = beta.get_article(get_article_data_alpha).await.unwrap();
+    let edits = beta.get_article_edits(get_res.article.id).await.unwrap();
+    assert_eq!(edit_res.article.title, get_res.article.title);
+    assert_eq!(edits.len(), 2);
+    assert_eq!(edit_res.article.text, get_res.article.text);
+
+    let get_res = gamma.get_article(get_article_data_gamma).await.unwrap();
+    let edits = gamma.get_article_edits(edit_res.article.id).await.unwrap();
+    assert_eq!(edit_res.article.title, get_res.article.title);
+    assert_eq!(edits.len(), 2);
+    assert_eq!(edit_res.article.text, get_res.article.text);
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_article_categories(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let mut articles = vec![];
+    for (title, text) in [
+        ("Cherry", "Small and red.\n"),
+        ("Banana", "Long and yellow. [[Category:Fruit]]\n"),
+        (
+            "Apple",
+            "Round. [[Category:Fruit]] [[Category:Red_things]]\n",
+        ),
+    ] {
+        let params = CreateArticleParams {
+            title: title.to_string(),
+            text: text.to_string(),
+            ..create_test_article_params()
+        };
+        articles.push(alpha.create_article(&params).await?);
+    }
+
+    // markers add articles to categories, manual adds work as well
+    let cherry = &articles[0];
+    let params = AddToCategoryParams {
+        article_id: cherry.article.id,
+        category: "Fruit".to_string(),
+    };
+    alpha.add_to_category(&params).await?;
+    let titles = |category: CategoryView| {
+        category
+            .articles
+            .into_iter()
+            .map(|a| a.title)
+            .collect::<Vec<_>>()
+    };
+    let params = GetCategoryParams {
+        name: "Fruit".to_string(),
+    };
+    assert_eq!(
+        vec!["Apple", "Banana", "Cherry"],
+        titles(alpha.get_category(&params).await?)
+    );
+    let red = GetCategoryParams {
+        name: "Red_things".to_string(),
+    };
+    assert_eq!(vec!["Apple"], titles(alpha.get_category(&red).await?));
+
+    // categories are shown in article view
+    let apple = alpha.get_article(GetArticleParams {
+        title: Some("Apple".to_string()),
+        ..Default::default()
+    });
+    let names: Vec<_> = apple
+        .await?
+        .categories
+        .into_iter()
+        .map(|c| c.name)
+        .collect();
+    assert_eq!(vec!["Fruit", "Red things"], names);
+
+    // removing the marker removes the membership
+    let banana = &articles[1];
+    let edit_params = EditArticleParams {
+        article_id: banana.article.id,
+        new_text: "Long and yellow.\n".to_string(),
+        summary: "summary".to_string(),
+        previous_version_id: banana.latest_version.clone(),
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    alpha
+        .edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+    assert_eq!(
+        vec!["Apple", "Cherry"],
+        titles(alpha.get_category(&params).await?)
+    );
+
+    // unknown category
+    let params = GetCategoryParams {
+        name: "Vegetables".to_string(),
+    };
+    assert!(alpha.get_category(&params).await.is_err());
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_minor_edit(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let article = alpha.create_article(&create_test_article_params()).await?;
+    let mut edit_params = EditArticleParams {
+        article_id: article.article.id,
+        new_text: "Lorem Ipsum 2\n".to_string(),
+        summary: "rewrite".to_string(),
+        previous_version_id: article.latest_version,
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    let res = alpha
+        .edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+    edit_params.new_text = "Lorem Ipsum 3\n".to_string();
+    edit_params.summary = "typo".to_string();
+    edit_params.previous_version_id = res.latest_version;
+    edit_params.minor = Some(true);
+    alpha
+        .edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+
+    // flag is stored
+    let edits = alpha.get_article_edits(article.article.id).await?;
+    assert_eq!(
+        vec![false, false, true],
+        edits.iter().map(|e| e.edit.minor).collect::<Vec<_>>()
+    );
+
+    // and minor edits can be filtered out
+    let params = GetEditList {
+        article_id: Some(article.article.id),
+        hide_minor: Some(true),
+        ..Default::default()
+    };
+    let edits = alpha.list_edits(&params).await?;
+    assert_eq!(2, edits.len());
+    assert!(edits.iter().all(|e| !e.edit.minor));
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_password_reset_invalid_token(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    // unknown emails are not revealed
+    let params = PasswordReset {
+        email: "nobody@example.com".to_string(),
+    };
+    alpha.request_password_reset(params).await?;
+
+    // an unknown token is rejected
+    let params = ChangePasswordAfterReset {
+        token: "invalid".to_string(),
+        password: "hunter2hunter2".to_string(),
+        confirm_password: "hunter2hunter2".to_string(),
+    };
+    assert!(alpha.change_password_after_reset(params).await.is_err());
+
+    // old password still works
+    let login = LoginUserParams {
+        username_or_email: "alpha".to_string(),
+        password: "hunter22".to_string(),
+    };
+    alpha.login(login).await?;
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_logout_revokes_session(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let login = LoginUserParams {
+        username_or_email: "alpha".to_string(),
+        password: "hunter22".to_string(),
+    };
+    let second = ApiClient::new(Some(alpha.hostname.clone()));
+    second.login(login.clone()).await?;
+    let third = ApiClient::new(Some(alpha.hostname.clone()));
+    third.login(login).await?;
+    assert!(second.site().await?.my_profile.is_some());
+
+    // normal logout only ends the current session
+    second.logout().await?;
+    assert!(second.site().await?.my_profile.is_none());
+    assert!(third.site().await?.my_profile.is_some());
+    assert!(alpha.site().await?.my_profile.is_some());
+
+    // logout all revokes the token of every other client
+    third.logout_all().await?;
+    assert!(third.site().await?.my_profile.is_none());
+    assert!(alpha.site().await?.my_profile.is_none());
+    assert!(alpha.get_watchlist().await.is_err());
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_max_article_bytes(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let options = Options {
+        registration_open: true,
+        max_article_bytes: 100,
+        ..Default::default()
+    };
+    let small = IbisInstance::new_with_options("small", 8093, options).await;
+    let res = async {
+        // over-size local edit is rejected
+        let article = small.create_article(&create_test_article_params()).await?;
+        let mut edit_params = EditArticleParams {
+            article_id: article.article.id,
+            new_text: "a".repeat(101),
+            summary: "summary".to_string(),
+            previous_version_id: article.latest_version,
+            resolve_conflict_id: None,
+            minor: None,
+        };
+        let err = small.edit_article(&edit_params).await.unwrap_err();
+        assert!(err.to_string().contains("exceeds the maximum of 100 bytes"));
+        edit_params.new_text = "a".repeat(100);
+        small
+            .edit_article_without_conflict(&edit_params)
+            .await
+            .unwrap();
+
+        // over-size remote edit is dropped
+        let alpha_instance = small.follow_instance_with_resolve(&alpha.hostname).await?;
+        let article = alpha.create_article(&create_test_article_params()).await?;
+        let get_params = GetArticleParams {
+            title: Some(article.article.title.clone()),
+            domain: Some(alpha_instance.domain),
+            id: None,
+            follow_redirect: None,
+        };
+        let remote = small.get_article(get_params.clone()).await?;
+        assert_eq!(article.article.text, remote.article.text);
+        let edit_params = EditArticleParams {
+            article_id: article.article.id,
+            new_text: "b".repeat(200),
+            summary: "summary".to_string(),
+            previous_version_id: article.latest_version,
+            resolve_conflict_id: None,
+            minor: None,
+        };
+        alpha
+            .edit_article_without_conflict(&edit_params)
+            .await
+            .unwrap();
+        let remote = small.get_article(get_params).await?;
+        assert_eq!(article.article.text, remote.article.text);
+        Ok::<_, anyhow::Error>(())
+    }
+    .await;
+    small.stop().await;
+    res
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_edit_summary_validation(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    // summary is required by default, and limited in length
+    let article = alpha.create_article(&create_test_article_params()).await?;
+    let mut edit_params = EditArticleParams {
+        article_id: article.article.id,
+        new_text: "Lorem Ipsum\n".to_string(),
+        summary: String::new(),
+        previous_version_id: article.latest_version,
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    let err = alpha.edit_article(&edit_params).await.unwrap_err();
+    assert!(err.to_string().contains("Edit summary is required"));
+    edit_params.summary = "a".repeat(501);
+    let err = alpha.edit_article(&edit_params).await.unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("exceeds the maximum of 500 characters")
+    );
+    edit_params.summary = "a".repeat(500);
+    alpha
+        .edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+
+    // empty summary is allowed if not required
+    let options = Options {
+        registration_open: true,
+        require_edit_summary: false,
+        ..Default::default()
+    };
+    let optional = IbisInstance::new_with_options("optional", 8090, options).await;
+    let res = async {
+        let article = optional
+            .create_article(&create_test_article_params())
+            .await?;
+        let mut edit_params = EditArticleParams {
+            article_id: article.article.id,
+            new_text: "Lorem Ipsum\n".to_string(),
+            summary: String::new(),
+            previous_version_id: article.latest_version,
+            resolve_conflict_id: None,
+            minor: None,
+        };
+        optional
+            .edit_article_without_conflict(&edit_params)
+            .await
+            .unwrap();
+        edit_params.summary = "a".repeat(501);
+        assert!(optional.edit_article(&edit_params).await.is_err());
+        Ok::<_, anyhow::Error>(())
+    }
+    .await;
+    optional.stop().await;
+    res
+}
+
+#[tokio::test]
+async fn api_test_edit_rate_limit() -> Result<()> {
+    let options = Options {
+        registration_open: true,
+        edit_rate_limit_unverified: 3,
+        edit_rate_limit_window: 3,
+        ..Default::default()
+    };
+    let limited = IbisInstance::new_with_options("limited", 8098, options).await;
+    let res = async {
+        // creating the article counts as first edit
+        let article = limited
+            .create_article(&create_test_article_params())
+            .await?;
+        let mut edit_params = EditArticleParams {
+            article_id: article.article.id,
+            new_text: String::new(),
+            summary: "summary".to_string(),
+            previous_version_id: article.latest_version,
+            resolve_conflict_id: None,
+            minor: None,
+        };
+        for i in 0..2 {
+            edit_params.new_text = format!("Edit {i}\n");
+            let res = limited
+                .edit_article_without_conflict(&edit_params)
+                .await
+                .unwrap();
+            edit_params.previous_version_id = res.latest_version;
+        }
+
+        // limit is reached
+        edit_params.new_text = "Too fast\n".to_string();
+        assert!(limited.edit_article(&edit_params).await.is_err());
+
+        // after the window passed, editing works again
+        sleep(Duration::from_secs(3)).await;
+        limited
+            .edit_article_without_conflict(&edit_params)
+            .await
+            .unwrap();
+        Ok::<_, anyhow::Error>(())
+    }
+    .await;
+    limited.stop().await;
+    res
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_math_rendering(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let params = CreateArticleParams {
+        text: "Energy $E=mc^2$ costs $5 or $10, broken $\\frac{$ stays\n".to_string(),
+        ..create_test_article_params()
+    };
+    let fetch_content = |ap_id: String| async move {
+        let json: serde_json::Value = reqwest::Client::new()
+            .get(ap_id)
+            .header("Accept", "application/activity+json")
+            .send()
+            .await?
+            .json()
+            .await?;
+        Ok::<_, anyhow::Error>(json["content"].as_str().unwrap_or_default().to_string())
+    };
+
+    // inline math is rendered in the federated content, prices and malformed equations are not
+    let article = alpha.create_article(&params).await?;
+    let content = fetch_content(article.article.ap_id.to_string()).await?;
+    assert!(content.contains("katex"));
+    assert!(!content.contains("$E=mc^2$"));
+    assert!(content.contains("$5 or $10"));
+    assert!(content.contains("$\\frac{$"));
+
+    // with math rendering disabled the equation is kept as plain text
+    let options = Options {
+        registration_open: true,
+        math_rendering: false,
+        ..Default::default()
+    };
+    let no_math = IbisInstance::new_with_options("no_math", 8087, options).await;
+    let res = async {
+        let article = no_math.create_article(&params).await?;
+        let content = fetch_content(article.article.ap_id.to_string()).await?;
+        assert!(!content.contains("katex"));
+        assert!(content.contains("$E=mc^2$"));
+        Ok::<_, anyhow::Error>(())
+    }
+    .await;
+    no_math.stop().await;
+    res
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_break_hints(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let params = CreateArticleParams {
+        text: "Chemical Pneumonoultramicroscopicsilicovolcanoconiosis\n".to_string(),
+        ..create_test_article_params()
+    };
+    let article = alpha.create_article(&params).await?;
+
+    // long words get break hints when rendered with the instance config
+    let config = alpha.site().await?.config;
+    assert_eq!(30, config.break_hint_length);
+    let render = |break_hint_length| {
+        render_article_markdown(
+            &article.article.text,
+            config.math_rendering,
+            break_hint_length,
+            false,
+        )
+    };
+    assert!(render(config.break_hint_length).contains("<wbr>"));
+    assert!(!render(0).contains("<wbr>"));
+
+    // but not in the federated content, which is rendered by the receiving instance
+    let json: serde_json::Value = reqwest::Client::new()
+        .get(article.article.ap_id.to_string())
+        .header("Accept", "application/activity+json")
+        .send()
+        .await?
+        .json()
+        .await?;
+    let content = json["content"].as_str().unwrap_or_default();
+    assert!(content.contains("Pneumonoultramicroscopicsilicovolcanoconiosis"));
+
+    // the length is configurable, and zero disables hints
+    let options = Options {
+        registration_open: true,
+        break_hint_length: 0,
+        ..Default::default()
+    };
+    let no_hints = IbisInstance::new_with_options("no_hints", 8085, options).await;
+    let res = async {
+        assert_eq!(0, no_hints.site().await?.config.break_hint_length);
+        Ok::<_, anyhow::Error>(())
+    }
+    .await;
+    no_hints.stop().await;
+    res
+}
+
+#[tokio::test]
+async fn api_test_new_account_cooldown() -> Result<()> {
+    let options = Options {
+        registration_open: true,
+        new_account_cooldown_minutes: 60,
+        ..Default::default()
+    };
+    let cooldown = IbisInstance::new_with_options("cooldown", 8088, options).await;
+    let res = async {
+        // the freshly registered user cannot create articles yet
+        assert!(
+            cooldown
+                .create_article(&create_test_article_params())
+                .await
+                .is_err()
+        );
+
+        // admins are exempt
+        let admin = ApiClient::new(Some(cooldown.hostname.clone()));
+        admin
+            .login(LoginUserParams {
+                username_or_email: "ibis".to_string(),
+                password: "ibis".to_string(),
+            })
+            .await?;
+        let article = admin.create_article(&create_test_article_params()).await?;
+
+        // editing existing articles is still possible
+        let edit_params = EditArticleParams {
+            article_id: article.article.id,
+            new_text: "Edited by new account\n".to_string(),
+            summary: "summary".to_string(),
+            previous_version_id: article.latest_version,
+            resolve_conflict_id: None,
+            minor: None,
+        };
+        cooldown
+            .edit_article_without_conflict(&edit_params)
+            .await
+            .unwrap();
+        Ok::<_, anyhow::Error>(())
+    }
+    .await;
+    cooldown.stop().await;
+    res
+}
+
+#[tokio::test]
+async fn api_test_edit_requires_verified_email() -> Result<()> {
+    let options = Options {
+        registration_open: true,
+        edit_requires_verified_email: true,
+        ..Default::default()
+    };
+    let verified = IbisInstance::new_with_options("verified", 8097, options).await;
+    let res = async {
+        // user registered without email, so it can't create articles
+        assert!(
+            verified
+                .create_article(&create_test_article_params())
+                .await
+                .is_err()
+        );
+
+        // admin is exempt
+        let admin = ApiClient::new(Some(verified.hostname.clone()));
+        admin
+            .login(LoginUserParams {
+                username_or_email: "ibis".to_string(),
+                password: "ibis".to_string(),
+            })
+            .await?;
+        let article = admin.create_article(&create_test_article_params()).await?;
+
+        // unverified user also can't edit
+        let edit_params = EditArticleParams {
+            article_id: article.article.id,
+            new_text: "Lorem Ipsum 2\n".to_string(),
+            summary: "summary".to_string(),
+            previous_version_id: article.latest_version,
+            resolve_conflict_id: None,
+            minor: None,
+        };
+        assert!(verified.edit_article(&edit_params).await.is_err());
+
+        // invalid verification token is rejected
+        assert!(verified.verify_email("invalid".to_string()).await.is_err());
+        let site = verified.site().await?;
+        assert!(!site.my_profile.unwrap().local_user.email_verified);
+        Ok::<_, anyhow::Error>(())
+    }
+    .await;
+    verified.stop().await;
+    res
+}
+
+#[tokio::test]
+async fn api_test_anonymous_edit() -> Result<()> {
+    let options = Options {
+        allow_anonymous_edits: true,
+        ..Default::default()
+    };
+    let open = IbisInstance::new_with_options("anon_edit", 8096, options).await;
+    let res = async {
+        let article = open.create_article(&create_test_article_params()).await?;
+
+        // edit without login is attributed to anonymous user
+        let anonymous = ApiClient::new(Some(open.hostname.clone()));
+        let mut edit_params = EditArticleParams {
+            article_id: article.article.id,
+            new_text: "Lorem Ipsum 2\n".to_string(),
+            summary: "anonymous edit".to_string(),
+            previous_version_id: article.latest_version,
+            resolve_conflict_id: None,
+            minor: None,
+        };
+        let edit_res = anonymous.edit_article(&edit_params).await?;
+        assert!(edit_res.is_none());
+        let edits = open.get_article_edits(article.article.id).await?;
+        assert_eq!(2, edits.len());
+        assert_eq!("anonymous", edits[1].creator.username);
+
+        // editing an outdated version is rejected
+        edit_params.new_text = "Lorem Ipsum 3\n".to_string();
+        assert!(anonymous.edit_article(&edit_params).await.is_err());
+
+        // protected articles need login
+        let admin = ApiClient::new(Some(open.hostname.clone()));
+        admin
+            .login(LoginUserParams {
+                username_or_email: "ibis".to_string(),
+                password: "ibis".to_string(),
+            })
+            .await?;
+        admin
+            .protect_article(&SetProtectionParams {
+                article_id: article.article.id,
+                level: ProtectionLevel::AutoConfirmed,
+            })
+            .await?;
+        edit_params.previous_version_id = edits[1].edit.hash.clone();
+        assert!(anonymous.edit_article(&edit_params).await.is_err());
+
+        // moderators can see which anonymous client made the edit
+        let anonymous_edits = admin.get_anonymous_edits(article.article.id).await?;
+        assert_eq!(1, anonymous_edits.len());
+        assert_eq!(edits[1].edit.hash, anonymous_edits[0].edit.hash);
+        assert!(!anonymous_edits[0].anonymous_id.is_empty());
+        assert!(open.get_anonymous_edits(article.article.id).await.is_err());
+        Ok::<_, anyhow::Error>(())
+    }
+    .await;
+    open.stop().await;
+    res
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_anonymous_edit_disabled(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let article = alpha.create_article(&create_test_article_params()).await?;
+    let anonymous = ApiClient::new(Some(alpha.hostname.clone()));
+    let edit_params = EditArticleParams {
+        article_id: article.article.id,
+        new_text: "Lorem Ipsum 2\n".to_string(),
+        summary: "anonymous edit".to_string(),
+        previous_version_id: article.latest_version,
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    assert!(anonymous.edit_article(&edit_params).await.is_err());
+
+    // name of the shared anonymous user can't be registered
+    let password = "hunter22".to_string();
+    let register_params = RegisterUserParams {
+        username: "Anonymous".to_string(),
+        password: password.clone(),
+        email: None,
+        confirm_password: password,
+    };
+    assert!(anonymous.register(register_params).await.is_err());
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_block_instance(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let create_res = alpha.create_article(&create_test_article_params()).await?;
+    let mut block_params = BlockInstanceParams {
+        domain: "blocked.example".to_string(),
+        block: true,
+    };
+    // only admin can block
+    assert!(alpha.block_instance(&block_params).await.is_err());
+
+    let admin = ApiClient::new(Some(alpha.hostname.clone()));
+    admin
+        .login(LoginUserParams {
+            username_or_email: "ibis".to_string(),
+            password: "ibis".to_string(),
+        })
+        .await?;
+    admin.block_instance(&block_params).await?;
+    let blocked = admin.list_blocked_instances().await?;
+    assert_eq!(1, blocked.len());
+    assert_eq!("blocked.example", blocked[0].domain);
+
+    // urls on blocked domain fail verification
+    let attachment_params = AddAttachmentParams {
+        article_id: create_res.article.id,
+        url: Url::parse("https://blocked.example/diagram.png")?,
+        alt_text: None,
+        media_type: None,
+    };
+    assert!(alpha.add_attachment(&attachment_params).await.is_err());
+
+    // after unblocking they pass again
+    block_params.block = false;
+    admin.block_instance(&block_params).await?;
+    assert!(admin.list_blocked_instances().await?.is_empty());
+    alpha.add_attachment(&attachment_params).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn api_test_federation_mode() -> Result<()> {
+    let attachment_params = |article_id| AddAttachmentParams {
+        article_id,
+        url: Url::parse("https://friend.example/diagram.png").unwrap(),
+        alt_text: None,
+        media_type: None,
+    };
+    let allow_params = AllowInstanceParams {
+        domain: "friend.example".to_string(),
+        allow: true,
+    };
+
+    // allowlist mode only accepts domains which were allowed by admin
+    let options = Options {
+        federation_mode: FederationMode::AllowList,
+        ..Default::default()
+    };
+    let allowlist = IbisInstance::new_with_options("allowlist", 8095, options).await;
+    let res = async {
+        let article = allowlist
+            .create_article(&create_test_article_params())
+            .await?;
+        let params = attachment_params(article.article.id);
+        assert!(allowlist.add_attachment(&params).await.is_err());
+
+        let admin = ApiClient::new(Some(allowlist.hostname.clone()));
+        admin
+            .login(LoginUserParams {
+                username_or_email: "ibis".to_string(),
+                password: "ibis".to_string(),
+            })
+            .await?;
+        admin.allow_instance(&allow_params).await?;
+        assert_eq!(1, admin.list_allowed_instances().await?.len());
+        allowlist.add_attachment(&params).await?;
+
+        // other domains are still rejected
+        let other_params = AddAttachmentParams {
+            url: Url::parse("https://other.example/diagram.png")?,
+            ..params
+        };
+        assert!(allowlist.add_attachment(&other_params).await.is_err());
+        Ok::<_, anyhow::Error>(())
+    }
+    .await;
+    allowlist.stop().await;
+    res?;
+
+    // closed mode rejects all domains, even allowed ones
+    let options = Options {
+        federation_mode: FederationMode::Closed,
+        ..Default::default()
+    };
+    let closed = IbisInstance::new_with_options("closed", 8094, options).await;
+    let res = async {
+        let article = closed.create_article(&create_test_article_params()).await?;
+        let admin = ApiClient::new(Some(closed.hostname.clone()));
+        admin
+            .login(LoginUserParams {
+                username_or_email: "ibis".to_string(),
+                password: "ibis".to_string(),
+            })
+            .await?;
+        admin.allow_instance(&allow_params).await?;
+        let params = attachment_params(article.article.id);
+        assert!(closed.add_attachment(&params).await.is_err());
+        Ok::<_, anyhow::Error>(())
+    }
+    .await;
+    closed.stop().await;
+    res
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_federation_mode_open(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    // open mode is the default and accepts any domain which isn't blocked
+    let article = alpha.create_article(&create_test_article_params()).await?;
+    let params = AddAttachmentParams {
+        article_id: article.article.id,
+        url: Url::parse("https://friend.example/diagram.png")?,
+        alt_text: None,
+        media_type: None,
+    };
+    alpha.add_attachment(&params).await?;
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_article_subscription(TestData(alpha, beta, _): &mut TestData) -> Result<()> {
+    // create two articles on alpha and fetch both to beta, without following alpha
+    let subscribed = alpha.create_article(&create_test_article_params()).await?;
+    let other = alpha
+        .create_article(&CreateArticleParams {
+            title: "Other Article".to_string(),
+            ..create_test_article_params()
+        })
+        .await?;
+    let subscribed_beta = beta
+        .resolve_article(subscribed.article.ap_id.inner().clone())
+        .await?;
+    let other_beta = beta
+        .resolve_article(other.article.ap_id.inner().clone())
+        .await?;
+
+    // subscribe to one of them
+    beta.follow_article(subscribed_beta.article.id, true)
+        .await?;
+
+    // the subscriber's instance is included in the reach of the subscribed article only
+    let reach = |article_id| alpha.get_edit_reach(&GetEditReach { article_id });
+    assert_eq!(1, reach(subscribed.article.id).await?.instances);
+    assert_eq!(0, reach(other.article.id).await?.instances);
+
+    // edit both articles on alpha
+    for article in [&subscribed, &other] {
+        let edit_params = EditArticleParams {
+            article_id: article.article.id,
+            new_text: "Updated text\n".to_string(),
+            summary: "update".to_string(),
+            previous_version_id: article.latest_version.clone(),
+            resolve_conflict_id: None,
+            minor: None,
+        };
+        alpha
+            .edit_article_without_conflict(&edit_params)
+            .await
+            .unwrap();
+    }
+
+    // only the edit to the subscribed article is delivered
+    let get_params = |title: &str| GetArticleParams {
+        title: Some(title.to_string()),
+        domain: Some(subscribed.instance.domain.clone()),
+        ..Default::default()
+    };
+    let subscribed_beta = beta
+        .get_article(get_params(&subscribed.article.title))
+        .await?;
+    assert_eq!("Updated text\n", subscribed_beta.article.text);
+    let other_beta_updated = beta.get_article(get_params(&other.article.title)).await?;
+    assert_eq!(other_beta.article.text, other_beta_updated.article.text);
+
+    // after unsubscribing, edits are not delivered anymore
+    beta.follow_article(subscribed_beta.article.id, false)
+        .await?;
+    let edit_params = EditArticleParams {
+        article_id: subscribed.article.id,
+        new_text: "Another update\n".to_string(),
+        summary: "update".to_string(),
+        previous_version_id: subscribed_beta.latest_version,
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    alpha
+        .edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+    let subscribed_beta = beta
+        .get_article(get_params(&subscribed.article.title))
+        .await?;
+    assert_eq!("Updated text\n", subscribed_beta.article.text);
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_export_import_article(TestData(alpha, beta, _): &mut TestData) -> Result<()> {
+    // create article with multiple edits on alpha
+    let create_res = alpha.create_article(&create_test_article_params()).await?;
+    let edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: "Lorem Ipsum 2\n".to_string(),
+        summary: "summary".to_string(),
+        previous_version_id: create_res.latest_version,
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    let edit_res = alpha
+        .edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+
+    let export = alpha.export_article(create_res.article.id).await?;
+    assert_eq!(2, export.edits.len());
+    assert_eq!(1, export.contributors.len());
+    assert_eq!(edit_res.article.text, export.article.text);
+
+    // only admin can import
+    assert!(beta.import_article(&export).await.is_err());
+    let admin = ApiClient::new(Some(beta.hostname.clone()));
+    admin
+        .login(LoginUserParams {
+            username_or_email: "ibis".to_string(),
+            password: "ibis".to_string(),
+        })
+        .await?;
+    let imported = admin.import_article(&export).await?;
+    assert!(imported.article.local);
+    assert_eq!(export.article.title, imported.article.title);
+    assert_eq!(export.article.text, imported.article.text);
+    assert_eq!(edit_res.latest_version, imported.latest_version);
+    let edits = beta.get_article_edits(imported.article.id).await?;
+    assert_eq!(2, edits.len());
+    assert_eq!(export.contributors[0].ap_id, edits[1].creator.ap_id);
+
+    // tampered history is rejected
+    let mut tampered = export.clone();
+    tampered.article.title = "Tampered".to_string();
+    tampered.article.text = "Something else\n".to_string();
+    assert!(admin.import_article(&tampered).await.is_err());
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_article_contributors(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    // article created and edited twice by alpha user
+    let create_res = alpha.create_article(&create_test_article_params()).await?;
+    let mut edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: format!("{}more text\n", create_res.article.text),
+        summary: "add text".to_string(),
+        previous_version_id: create_res.latest_version,
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    let first_edit = alpha
+        .edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+
+    // then edited by admin
+    let admin = ApiClient::new(Some(alpha.hostname.clone()));
+    admin
+        .login(LoginUserParams {
+            username_or_email: "ibis".to_string(),
+            password: "ibis".to_string(),
+        })
+        .await?;
+    edit_params.new_text = "short\n".to_string();
+    edit_params.previous_version_id = first_edit.latest_version;
+    let edit_res = admin
+        .edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+
+    let contributors = alpha.get_contributors(create_res.article.id).await?;
+    assert_eq!(2, contributors.len());
+    let site = alpha.site().await?;
+    let user = site.my_profile.unwrap().person;
+    assert_eq!(user.id, contributors[0].person.id);
+    assert_eq!(2, contributors[0].edits);
+    assert_eq!(
+        first_edit.article.text.len() as i64,
+        contributors[0].byte_delta
+    );
+    assert_eq!("ibis", contributors[1].person.username);
+    assert_eq!(1, contributors[1].edits);
+    // all deltas together give the final article length
+    let total: i64 = contributors.iter().map(|c| c.byte_delta).sum();
+    assert_eq!(edit_res.article.text.len() as i64, total);
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_preview_article(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let text = "# Preview\n\nLink to [[Missing_Article]] and <script>alert(1)</script>\n\n\
+        ```rust\nfn main() {}\n```\n";
+    let preview = alpha.preview_article(text.to_string()).await?;
+    assert_eq!(vec!["Missing Article"], preview.missing_links);
+    // red link leads to the create form with the title filled in
+    assert!(
+        preview
+            .html
+            .contains("href=\"/create-article?title=Missing_Article\"")
+    );
+    assert!(!preview.html.contains("<script>"));
+
+    // nothing is stored, only the main page exists
+    let list = alpha.list_articles(Default::default()).await?;
+    assert_eq!(1, list.total);
+
+    // preview matches the article page after saving
+    let params = CreateArticleParams {
+        title: "Preview".to_string(),
+        text: text.to_string(),
+        summary: "create article".to_string(),
+        instance_id: None,
+    };
+    let article = alpha.create_article(&params).await?;
+    let config = alpha.site().await?.config;
+    let rendered = render_article_markdown_with_links(
+        &article.article.text,
+        config.math_rendering,
+        config.break_hint_length,
+        true,
+        &article.missing_links,
+    );
+    assert_eq!(rendered, preview.html);
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_revision_tag(TestData(alpha, beta, _): &mut TestData) -> Result<()> {
+    let article = alpha.create_article(&create_test_article_params()).await?;
+    let first_version = article.latest_version.clone();
     let edit_params = EditArticleParams {
-        article_id: create_res.article.id,
+        article_id: article.article.id,
         new_text: "Lorem Ipsum 2\n".to_string(),
         summary: "summary".to_string(),
-        previous_version_id: get_res.latest_version,
+        previous_version_id: article.latest_version,
         resolve_conflict_id: None,
+        minor: None,
     };
-    let edit_res = beta
+    let article = alpha
         .edit_article_without_conflict(&edit_params)
         .await
         .unwrap();
-    let edits = beta.get_article_edits(edit_res.article.id).await.unwrap();
-    assert_eq!(edit_res.article.text, edit_params.new_text);
-    assert_eq!(edits.len(), 2);
-    assert!(
-        edits[0]
-            .edit
-            .ap_id
-            .to_string()
-            .starts_with(&edit_res.article.ap_id.to_string())
-    );
 
-    // edit should be federated to alpha
-    let get_res = alpha.get_article(get_article_data).await.unwrap();
-    let edits = alpha.get_article_edits(get_res.article.id).await.unwrap();
-    assert_eq!(edit_res.article.title, get_res.article.title);
-    assert_eq!(edits.len(), 2);
-    assert_eq!(edit_res.article.text, get_res.article.text);
+    // tag the first version
+    let mut params = TagRevisionParams {
+        article_id: article.article.id,
+        version: first_version.clone(),
+        tag: "v1.0".to_string(),
+    };
+    let tag = alpha.tag_revision(&params).await?;
+    assert_eq!("v1.0", tag.tag);
+    assert_eq!(first_version, tag.version);
+
+    // retrieve it by article
+    let tags = alpha.get_tagged_revisions(article.article.id).await?;
+    assert_eq!(vec![tag], tags);
+
+    // tagging another version with the same name moves the tag
+    params.version = article.latest_version.clone();
+    alpha.tag_revision(&params).await?;
+    let tags = alpha.get_tagged_revisions(article.article.id).await?;
+    assert_eq!(1, tags.len());
+    assert_eq!(article.latest_version, tags[0].version);
+
+    // versions of other articles can't be tagged
+    let other = beta.create_article(&create_test_article_params()).await?;
+    params.version = other.latest_version;
+    assert!(alpha.tag_revision(&params).await.is_err());
 
     Ok(())
 }
 
 #[test_context(TestData)]
 #[tokio::test]
-async fn api_test_edit_remote_article(TestData(alpha, beta, gamma): &mut TestData) -> Result<()> {
-    let beta_id_on_alpha = alpha
-        .follow_instance_with_resolve(&beta.hostname)
-        .await
-        .unwrap();
-    let beta_id_on_gamma = gamma
-        .follow_instance_with_resolve(&beta.hostname)
-        .await
-        .unwrap();
-
-    // create new article
-    let create_params = create_test_article_params();
-    let create_res = beta.create_article(&create_params).await.unwrap();
-    assert_eq!(&create_params.title, &create_res.article.title);
-    assert!(create_res.article.local);
-
-    // article should be federated to alpha and gamma
-    let get_article_data_alpha = GetArticleParams {
-        title: Some(create_res.article.title.to_string()),
-        domain: Some(beta_id_on_alpha.domain),
-        id: None,
-    };
-    let get_res = alpha
-        .get_article(get_article_data_alpha.clone())
-        .await
-        .unwrap();
-    assert_eq!(create_res.article.title, get_res.article.title);
-    let edits = alpha.get_article_edits(get_res.article.id).await.unwrap();
-    assert_eq!(1, edits.len());
-    assert!(!get_res.article.local);
-
-    let get_article_data_gamma = GetArticleParams {
-        title: Some(create_res.article.title.to_string()),
-        domain: Some(beta_id_on_gamma.domain),
-        id: None,
-    };
-    let get_res = gamma
-        .get_article(get_article_data_gamma.clone())
-        .await
-        .unwrap();
-    assert_eq!(create_res.article.title, get_res.article.title);
-    assert_eq!(create_res.article.text, get_res.article.text);
+async fn api_test_concurrent_edit_conflict(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let create_res = alpha.create_article(&create_test_article_params()).await?;
+    let admin = ApiClient::new(Some(alpha.hostname.clone()));
+    admin
+        .login(LoginUserParams {
+            username_or_email: "ibis".to_string(),
+            password: "ibis".to_string(),
+        })
+        .await?;
 
-    let edit_params = EditArticleParams {
-        article_id: get_res.article.id,
-        new_text: "Lorem Ipsum 2\n".to_string(),
+    // both users start editing the same version, admin saves first
+    let mut edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: "edited by admin\n".to_string(),
         summary: "summary".to_string(),
-        previous_version_id: get_res.latest_version,
+        previous_version_id: create_res.latest_version.clone(),
         resolve_conflict_id: None,
+        minor: None,
     };
-    let edit_res = alpha
-        .edit_article_without_conflict(&edit_params)
-        .await
-        .unwrap();
-    assert_eq!(edit_params.new_text, edit_res.article.text);
-    let edits = alpha.get_article_edits(edit_res.article.id).await.unwrap();
-    assert_eq!(2, edits.len());
-    assert!(!edit_res.article.local);
+    let admin_edit = admin.edit_article_without_conflict(&edit_params).await?;
+
+    // second edit is not saved over the first one, but returns a conflict
+    edit_params.new_text = "edited by user\n".to_string();
+    let conflict = alpha.edit_article(&edit_params).await?.unwrap();
+    assert_eq!(create_res.latest_version, conflict.previous_version_id);
     assert!(
-        edits[0]
-            .edit
-            .ap_id
-            .to_string()
-            .starts_with(&edit_res.article.ap_id.to_string())
+        conflict
+            .three_way_merge
+            .contains("<<<<<<< ours\nedited by user")
+    );
+    assert!(
+        conflict
+            .three_way_merge
+            .contains("edited by admin\n>>>>>>> theirs")
     );
+    let article = alpha
+        .get_article(GetArticleParams {
+            title: None,
+            domain: None,
+            id: Some(create_res.article.id),
+            follow_redirect: None,
+        })
+        .await?;
+    assert_eq!(admin_edit.article.text, article.article.text);
 
-    // edit should be federated to beta and gamma
-    let get_res = beta.get_article(get_article_data_alpha).await.unwrap();
-    let edits = beta.get_article_edits(get_res.article.id).await.unwrap();
-    assert_eq!(edit_res.article.title, get_res.article.title);
-    assert_eq!(edits.len(), 2);
-    assert_eq!(edit_res.article.text, get_res.article.text);
+    // conflict is stored for the user who made the conflicting edit
+    assert_eq!(conflict, alpha.get_conflict(conflict.id).await?);
+    assert!(admin.get_conflict(conflict.id).await.is_err());
+    Ok(())
+}
 
-    let get_res = gamma.get_article(get_article_data_gamma).await.unwrap();
-    let edits = gamma.get_article_edits(edit_res.article.id).await.unwrap();
-    assert_eq!(edit_res.article.title, get_res.article.title);
-    assert_eq!(edits.len(), 2);
-    assert_eq!(edit_res.article.text, get_res.article.text);
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_update_instance(TestData(alpha, beta, _): &mut TestData) -> Result<()> {
+    let params = UpdateInstanceParams {
+        name: Some("Alpha Wiki".to_string()),
+        topic: Some("Articles about the alphabet".to_string()),
+        icon_url: Some("https://example.com/alpha.png".to_string()),
+    };
+    // only admin can change the instance
+    assert!(alpha.update_local_instance(&params).await.is_err());
+
+    let admin = ApiClient::new(Some(alpha.hostname.clone()));
+    admin
+        .login(LoginUserParams {
+            username_or_email: "ibis".to_string(),
+            password: "ibis".to_string(),
+        })
+        .await?;
+    let instance = admin.update_local_instance(&params).await?;
+    assert_eq!(params.topic, instance.topic);
+
+    let read = alpha
+        .get_instance(&GetInstanceParams {
+            id: None,
+            hostname: Some(alpha.hostname.clone()),
+        })
+        .await?;
+    assert_eq!(params.name, read.instance.name);
+    assert_eq!(params.topic, read.instance.topic);
 
+    assert_eq!(
+        params.icon_url,
+        read.instance.icon_url.map(|u| u.to_string())
+    );
+
+    // name, topic and icon are included in the federated instance object
+    let json: serde_json::Value = reqwest::Client::new()
+        .get(instance.ap_id.to_string())
+        .header("Accept", "application/activity+json")
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(params.name.as_deref(), json["name"].as_str());
+    assert_eq!(params.topic.as_deref(), json["summary"].as_str());
+    assert_eq!("Image", json["icon"]["type"]);
+    assert_eq!(params.icon_url.as_deref(), json["icon"]["url"].as_str());
+
+    // and are stored by remote instances
+    let remote = beta.resolve_instance(instance.ap_id.clone().into()).await?;
+    assert_eq!(params.name, remote.name);
+    assert_eq!(params.topic, remote.topic);
+    assert_eq!(params.icon_url, remote.icon_url.map(|u| u.to_string()));
+
+    // name is also shown in nodeinfo
+    let node_info: NodeInfo = reqwest::Client::new()
+        .get(format!("http://{}/nodeinfo/2.1.json", alpha.hostname))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(
+        params.name.as_deref(),
+        node_info.metadata["nodeName"].as_str()
+    );
+
+    // icon must be a valid url
+    let invalid = UpdateInstanceParams {
+        icon_url: Some("javascript:alert(1)".to_string()),
+        ..params
+    };
+    assert!(admin.update_local_instance(&invalid).await.is_err());
     Ok(())
 }
 
@@ -380,6 +3281,7 @@ async fn api_test_local_edit_conflict(TestData(alpha, _, _): &mut TestData) -> R
         summary: "summary".to_string(),
         previous_version_id: create_res.latest_version.clone(),
         resolve_conflict_id: None,
+        minor: None,
     };
     let edit_res = alpha
         .edit_article_without_conflict(&edit_params)
@@ -396,6 +3298,7 @@ async fn api_test_local_edit_conflict(TestData(alpha, _, _): &mut TestData) -> R
         summary: "summary".to_string(),
         previous_version_id: create_res.latest_version,
         resolve_conflict_id: None,
+        minor: None,
     };
     let edit_res = alpha.edit_article(&edit_params).await.unwrap().unwrap();
     assert_eq!(
@@ -421,6 +3324,7 @@ async fn api_test_local_edit_conflict(TestData(alpha, _, _): &mut TestData) -> R
         summary: "summary".to_string(),
         previous_version_id: edit_res.previous_version_id,
         resolve_conflict_id: Some(edit_res.id),
+        minor: None,
     };
     let edit_res = alpha
         .edit_article_without_conflict(&edit_params)
@@ -433,6 +3337,171 @@ async fn api_test_local_edit_conflict(TestData(alpha, _, _): &mut TestData) -> R
     Ok(())
 }
 
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_list_dismiss_conflicts(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let create_res = alpha.create_article(&create_test_article_params()).await?;
+    let mut edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: "Lorem Ipsum\n".to_string(),
+        summary: "summary".to_string(),
+        previous_version_id: create_res.latest_version.clone(),
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    alpha.edit_article_without_conflict(&edit_params).await?;
+    assert!(alpha.list_conflicts().await?.is_empty());
+
+    // concurrent edit based on outdated version results in conflict
+    edit_params.new_text = "Ipsum Lorem\n".to_string();
+    let conflict = alpha.edit_article(&edit_params).await?.unwrap();
+    let conflicts = alpha.list_conflicts().await?;
+    assert_eq!(1, conflicts.len());
+    assert_eq!(conflict.id, conflicts[0].id);
+    assert_eq!(create_res.article.id, conflicts[0].article_id);
+
+    // dismiss the conflict
+    alpha.delete_conflict(conflict.id).await?;
+    assert!(alpha.list_conflicts().await?.is_empty());
+    assert!(alpha.get_conflict(conflict.id).await.is_err());
+
+    // another conflict which is resolved by submitting the merged text
+    let conflict = alpha.edit_article(&edit_params).await?.unwrap();
+    assert_eq!(1, alpha.list_conflicts().await?.len());
+    let resolve_params = EditArticleParams {
+        new_text: "Lorem Ipsum and Ipsum Lorem\n".to_string(),
+        previous_version_id: conflict.previous_version_id,
+        resolve_conflict_id: Some(conflict.id),
+        ..edit_params
+    };
+    alpha.edit_article_without_conflict(&resolve_params).await?;
+    assert!(alpha.list_conflicts().await?.is_empty());
+    assert!(alpha.get_conflict(conflict.id).await.is_err());
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_conflict_stats(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let create_res = alpha.create_article(&create_test_article_params()).await?;
+    let mut edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: "Lorem Ipsum\n".to_string(),
+        summary: "summary".to_string(),
+        previous_version_id: create_res.latest_version.clone(),
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    alpha.edit_article_without_conflict(&edit_params).await?;
+
+    // one conflict is dismissed
+    edit_params.new_text = "Ipsum Lorem\n".to_string();
+    let conflict = alpha.edit_article(&edit_params).await?.unwrap();
+    alpha.delete_conflict(conflict.id).await?;
+
+    // another one is resolved
+    let conflict = alpha.edit_article(&edit_params).await?.unwrap();
+    let resolve_params = EditArticleParams {
+        new_text: "Lorem Ipsum and Ipsum Lorem\n".to_string(),
+        previous_version_id: conflict.previous_version_id,
+        resolve_conflict_id: Some(conflict.id),
+        minor: None,
+        ..edit_params
+    };
+    alpha.edit_article_without_conflict(&resolve_params).await?;
+
+    // only admins can see stats
+    let params = GetConflictStats::default();
+    assert!(alpha.get_conflict_stats(&params).await.is_err());
+    let admin = ApiClient::new(Some(alpha.hostname.clone()));
+    admin
+        .login(LoginUserParams {
+            username_or_email: "ibis".to_string(),
+            password: "ibis".to_string(),
+        })
+        .await?;
+    let stats = admin.get_conflict_stats(&params).await?;
+    assert_eq!(1, stats.len());
+    assert_eq!(create_res.article.id, stats[0].article.id);
+    assert_eq!(2, stats[0].total);
+    assert_eq!(1, stats[0].resolved);
+    assert_eq!(1, stats[0].abandoned);
+    assert_eq!(0, stats[0].pending);
+
+    // conflicts outside of time window are ignored
+    let params = GetConflictStats {
+        since: Some(Utc::now()),
+        ..Default::default()
+    };
+    assert!(admin.get_conflict_stats(&params).await?.is_empty());
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_repeated_edit_conflict(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let create_res = alpha
+        .create_article(&create_test_article_params())
+        .await
+        .unwrap();
+    let intermediate_edit = |text: String, previous_version_id| EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: text,
+        summary: "summary".to_string(),
+        previous_version_id,
+        resolve_conflict_id: None,
+        minor: None,
+    };
+
+    // create initial conflict
+    alpha
+        .edit_article_without_conflict(&intermediate_edit(
+            "Lorem Ipsum\n".to_string(),
+            create_res.latest_version.clone(),
+        ))
+        .await
+        .unwrap();
+    let mut conflict = alpha
+        .edit_article(&intermediate_edit(
+            "Ipsum Lorem\n".to_string(),
+            create_res.latest_version.clone(),
+        ))
+        .await
+        .unwrap()
+        .unwrap();
+
+    // every resolution runs into a new conflict because of another concurrent edit
+    for i in 0..=3 {
+        alpha
+            .edit_article_without_conflict(&intermediate_edit(
+                format!("Intermediate {i}\n"),
+                conflict.previous_version_id.clone(),
+            ))
+            .await
+            .unwrap();
+        let resolve_params = EditArticleParams {
+            new_text: format!("Resolved {i}\n"),
+            previous_version_id: conflict.previous_version_id.clone(),
+            resolve_conflict_id: Some(conflict.id),
+            minor: None,
+            article_id: create_res.article.id,
+            summary: "summary".to_string(),
+        };
+        let res = alpha.edit_article(&resolve_params).await;
+        if i < 3 {
+            conflict = res.unwrap().unwrap();
+        } else {
+            // attempts are exhausted, edit is rejected and stale conflicts are removed
+            assert!(res.is_err());
+        }
+    }
+    assert_eq!(0, alpha.notifications_count().await.unwrap());
+
+    Ok(())
+}
+
 #[test_context(TestData)]
 #[tokio::test]
 #[ignore]
@@ -463,6 +3532,7 @@ async fn api_test_federated_edit_conflict(
         title: Some(create_params.title.to_string()),
         domain: Some(beta_id_on_alpha.domain),
         id: None,
+        follow_redirect: None,
     };
     let get_res = alpha.get_article(get_article_data).await.unwrap();
     let alpha_edits = alpha.get_article_edits(get_res.article.id).await.unwrap();
@@ -474,6 +3544,7 @@ async fn api_test_federated_edit_conflict(
         summary: "first edit".to_string(),
         previous_version_id: create_res.latest_version.clone(),
         resolve_conflict_id: None,
+        minor: None,
     };
     let edit_res = alpha
         .edit_article_without_conflict(&edit_params)
@@ -499,6 +3570,7 @@ async fn api_test_federated_edit_conflict(
         summary: "second edit".to_string(),
         previous_version_id: create_res.latest_version,
         resolve_conflict_id: None,
+        minor: None,
     };
     let edit_res = gamma
         .edit_article_without_conflict(&edit_params)
@@ -530,6 +3602,7 @@ async fn api_test_federated_edit_conflict(
         summary: "resolve conflict".to_string(),
         previous_version_id: conflict.previous_version_id.clone(),
         resolve_conflict_id: Some(conflict.id),
+        minor: None,
     };
     let edit_res = gamma
         .edit_article_without_conflict(&edit_params)
@@ -582,6 +3655,7 @@ async fn api_test_overlapping_edits_no_conflict(
         summary: "summary".to_string(),
         previous_version_id: create_res.latest_version.clone(),
         resolve_conflict_id: None,
+        minor: None,
     };
     let edit_res = alpha
         .edit_article_without_conflict(&edit_params)
@@ -603,6 +3677,7 @@ async fn api_test_overlapping_edits_no_conflict(
         summary: "summary".to_string(),
         previous_version_id: create_res.latest_version,
         resolve_conflict_id: None,
+        minor: None,
     };
     let edit_res = alpha
         .edit_article_without_conflict(&edit_params)
@@ -623,6 +3698,151 @@ async fn api_test_overlapping_edits_no_conflict(
     Ok(())
 }
 
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_find_duplicates(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let text = "The quick brown fox jumps over the lazy dog while the farmer watches from the \
+        porch and the chickens scatter across the yard in every direction";
+    let articles = [
+        ("Fox", format!("{text}.\n")),
+        ("Fox Copy", format!("{text} again.\n")),
+        (
+            "Gardening",
+            "Planting flowers and vegetables in the spring.\n".to_string(),
+        ),
+    ];
+    for (title, text) in articles {
+        let params = CreateArticleParams {
+            title: title.to_string(),
+            text,
+            ..create_test_article_params()
+        };
+        alpha.create_article(&params).await?;
+    }
+    let params = FindDuplicates { threshold: 0.7 };
+
+    // only for admins
+    assert!(alpha.find_duplicates(&params).await.is_err());
+
+    let admin = ApiClient::new(Some(alpha.hostname.clone()));
+    admin
+        .login(LoginUserParams {
+            username_or_email: "ibis".to_string(),
+            password: "ibis".to_string(),
+        })
+        .await?;
+    let duplicates = admin.find_duplicates(&params).await?;
+    assert_eq!(1, duplicates.len());
+    let mut titles = [
+        duplicates[0].first.title.as_str(),
+        duplicates[0].second.title.as_str(),
+    ];
+    titles.sort();
+    assert_eq!(["Fox", "Fox Copy"], titles);
+    assert!(duplicates[0].similarity >= params.threshold);
+
+    // threshold must be in range
+    let params = FindDuplicates { threshold: 1.5 };
+    assert!(admin.find_duplicates(&params).await.is_err());
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_user_articles(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let mut created = vec![];
+    for title in ["First", "Second", "Third"] {
+        let params = CreateArticleParams {
+            title: title.to_string(),
+            ..create_test_article_params()
+        };
+        created.push(alpha.create_article(&params).await?);
+    }
+
+    // editing an article doesn't make the editor its creator
+    let editor = ApiClient::new(Some(alpha.hostname.clone()));
+    let password = "hunter22".to_string();
+    editor
+        .register(RegisterUserParams {
+            username: "editor".to_string(),
+            password: password.clone(),
+            email: None,
+            confirm_password: password,
+        })
+        .await?;
+    let edit_params = EditArticleParams {
+        article_id: created[0].article.id,
+        new_text: "Edited\n".to_string(),
+        summary: "summary".to_string(),
+        previous_version_id: created[0].latest_version.clone(),
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    editor
+        .edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+    let mut params = GetUserArticles {
+        name: "editor".to_string(),
+        domain: None,
+        page: None,
+        limit: None,
+    };
+    assert!(alpha.get_user_articles(params.clone()).await?.is_empty());
+
+    // newest first, with paging
+    params.name = "alpha".to_string();
+    params.limit = Some(2);
+    let titles = |articles: Vec<Article>| articles.into_iter().map(|a| a.title).collect::<Vec<_>>();
+    let page = alpha.get_user_articles(params.clone()).await?;
+    assert_eq!(vec!["Third", "Second"], titles(page));
+    params.page = Some(2);
+    let page = alpha.get_user_articles(params).await?;
+    assert_eq!(vec!["First"], titles(page));
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_fork_article_title_collision(
+    TestData(alpha, beta, _): &mut TestData,
+) -> Result<()> {
+    let create_params = create_test_article_params();
+    let create_res = alpha.create_article(&create_params).await?;
+    let resolved = beta
+        .resolve_article(create_res.article.ap_id.into())
+        .await?;
+    let resolved_edits = beta.get_article_edits(resolved.article.id).await?;
+
+    // beta already has a local article with the same title
+    beta.create_article(&create_params).await?;
+    let mut fork_params = ForkArticleParams {
+        article_id: resolved.article.id,
+        new_title: None,
+    };
+    assert!(beta.fork_article(&fork_params).await.is_err());
+
+    // forking works with a different title, and keeps the edit history
+    fork_params.new_title = Some(format!("{} (fork)", create_params.title));
+    let fork_res = beta.fork_article(&fork_params).await?;
+    assert_eq!(fork_params.new_title, Some(fork_res.article.title.clone()));
+    assert!(fork_res.article.local);
+    assert_eq!(resolved.article.text, fork_res.article.text);
+    assert_eq!(resolved.latest_version, fork_res.latest_version);
+    let fork_edits = beta.get_article_edits(fork_res.article.id).await?;
+    assert_eq!(
+        resolved_edits
+            .iter()
+            .map(|e| &e.edit.hash)
+            .collect::<Vec<_>>(),
+        fork_edits.iter().map(|e| &e.edit.hash).collect::<Vec<_>>()
+    );
+
+    Ok(())
+}
+
 #[test_context(TestData)]
 #[tokio::test]
 async fn api_test_fork_article(TestData(alpha, beta, _): &mut TestData) -> Result<()> {
@@ -648,7 +3868,7 @@ async fn api_test_fork_article(TestData(alpha, beta, _): &mut TestData) -> Resul
     // fork the article to local instance
     let fork_params = ForkArticleParams {
         article_id: resolved_article.id,
-        new_title: resolved_article.title.clone(),
+        new_title: None,
     };
     let fork_res = beta.fork_article(&fork_params).await.unwrap();
     let forked_article = fork_res.article;
@@ -666,12 +3886,101 @@ async fn api_test_fork_article(TestData(alpha, beta, _): &mut TestData) -> Resul
     let beta_site = beta.site().await.unwrap();
     assert_eq!(forked_article.instance_id, beta_site.instance.id);
 
+    // original article is attributed to alpha user, fork to beta user
+    let alpha_articles = beta
+        .get_user_articles(GetUserArticles {
+            name: "alpha".to_string(),
+            domain: Some(alpha.hostname.clone()),
+            page: None,
+            limit: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        vec![resolved_article.id],
+        alpha_articles.iter().map(|a| a.id).collect::<Vec<_>>()
+    );
+    let beta_articles = beta
+        .get_user_articles(GetUserArticles {
+            name: "beta".to_string(),
+            domain: None,
+            page: None,
+            limit: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        vec![forked_article.id],
+        beta_articles.iter().map(|a| a.id).collect::<Vec<_>>()
+    );
+
     // now search returns two articles for this title (original and forked)
     let search_params = SearchArticleParams {
         query: create_params.title.clone(),
+        search_mode: None,
+        fuzzy: None,
+    };
+    let search_res = beta.search(&search_params).await.unwrap();
+    assert_eq!(2, search_res.len());
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_user_contributions(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let article = alpha.create_article(&create_test_article_params()).await?;
+    let mut previous_version_id = article.latest_version;
+    for i in 1..=2 {
+        let edit_params = EditArticleParams {
+            article_id: article.article.id,
+            new_text: format!("Lorem Ipsum {i}\n"),
+            summary: format!("edit {i}"),
+            previous_version_id,
+            resolve_conflict_id: None,
+            minor: None,
+        };
+        previous_version_id = alpha
+            .edit_article_without_conflict(&edit_params)
+            .await
+            .unwrap()
+            .latest_version;
+    }
+
+    // newest edits first
+    let mut params = GetUserContributions {
+        name: Some("alpha".to_string()),
+        limit: Some(2),
+        ..Default::default()
+    };
+    let contributions = alpha.get_user_contributions(&params).await?;
+    let summaries: Vec<_> = contributions
+        .iter()
+        .map(|e| e.edit.summary.as_str())
+        .collect();
+    assert_eq!(vec!["edit 2", "edit 1"], summaries);
+
+    // next page
+    params.page = Some(2);
+    let contributions = alpha.get_user_contributions(&params).await?;
+    assert_eq!(1, contributions.len());
+    assert_eq!("create article", contributions[0].edit.summary);
+    params.page = Some(3);
+    assert!(alpha.get_user_contributions(&params).await?.is_empty());
+
+    // same result when querying by id
+    let person_id = contributions[0].creator.id;
+    let params = GetUserContributions {
+        person_id: Some(person_id),
+        ..Default::default()
     };
-    let search_res = beta.search(&search_params).await.unwrap();
-    assert_eq!(2, search_res.len());
+    let contributions = alpha.get_user_contributions(&params).await?;
+    assert_eq!(3, contributions.len());
+    assert!(contributions.iter().all(|e| e.creator.id == person_id));
+
+    // either id or name is required
+    let params = GetUserContributions::default();
+    assert!(alpha.get_user_contributions(&params).await.is_err());
 
     Ok(())
 }
@@ -713,6 +4022,57 @@ async fn api_test_user_registration_login(TestData(alpha, _, _): &mut TestData)
     Ok(())
 }
 
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_registration_open(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let register_params = |username: &str| RegisterUserParams {
+        username: username.to_string(),
+        password: "hunter22".to_string(),
+        email: None,
+        confirm_password: "hunter22".to_string(),
+    };
+    let admin = ApiClient::new(Some(alpha.hostname.clone()));
+    admin
+        .login(LoginUserParams {
+            username_or_email: "ibis".to_string(),
+            password: "ibis".to_string(),
+        })
+        .await?;
+    // only admin can change registration
+    assert!(alpha.set_registration_open(false).await.is_err());
+
+    // registration is open by default
+    let client = ApiClient::new(Some(alpha.hostname.clone()));
+    client.register(register_params("first_user")).await?;
+    assert!(alpha.site().await?.config.registration_open);
+
+    // new users cant register after it is closed
+    let instance = admin.set_registration_open(false).await?;
+    assert_eq!(Some(false), instance.registration_open);
+    assert!(!alpha.site().await?.config.registration_open);
+    let client = ApiClient::new(Some(alpha.hostname.clone()));
+    let res = client.register(register_params("second_user")).await;
+    assert!(
+        res.unwrap_err()
+            .to_string()
+            .contains("Registration is closed")
+    );
+
+    // existing users can still login
+    client
+        .login(LoginUserParams {
+            username_or_email: "first_user".to_string(),
+            password: "hunter22".to_string(),
+        })
+        .await?;
+
+    // open again
+    admin.set_registration_open(true).await?;
+    let client = ApiClient::new(Some(alpha.hostname.clone()));
+    client.register(register_params("second_user")).await?;
+    Ok(())
+}
+
 #[test_context(TestData)]
 #[tokio::test]
 async fn api_test_user_profile(TestData(alpha, beta, _): &mut TestData) -> Result<()> {
@@ -746,30 +4106,162 @@ async fn api_test_user_profile(TestData(alpha, beta, _): &mut TestData) -> Resul
     Ok(())
 }
 
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_refresh_remote_user(TestData(alpha, beta, _): &mut TestData) -> Result<()> {
+    let options = Options {
+        registration_open: true,
+        remote_actor_refresh_seconds: 1,
+        ..Default::default()
+    };
+    let refresh = IbisInstance::new_with_options("refresh", 8092, options).await;
+    let res = async {
+        // federate the user who created the article to both instances
+        let article = alpha.create_article(&create_test_article_params()).await?;
+        beta.resolve_article(article.article.ap_id.clone().into())
+            .await?;
+        refresh
+            .resolve_article(article.article.ap_id.clone().into())
+            .await?;
+
+        alpha
+            .update_user_profile(UpdateUserParams {
+                display_name: Some("Alpha Centauri".to_string()),
+                bio: None,
+                email: None,
+                email_notifications: None,
+            })
+            .await?;
+        let params = GetUserParams {
+            name: "alpha".to_string(),
+            domain: Some(extract_domain(article.article.ap_id.inner())),
+        };
+
+        // within the refresh interval the stored user is returned
+        let user = beta.get_user(params.clone()).await?;
+        assert_eq!(None, user.display_name);
+
+        // afterwards the stored user is returned and fetched again in the background
+        sleep(Duration::from_secs(2)).await;
+        let user = refresh.get_user(params.clone()).await?;
+        assert_eq!(None, user.display_name);
+        let user = refresh.get_user(params).await?;
+        assert_eq!(Some("Alpha Centauri".to_string()), user.display_name);
+        Ok::<_, anyhow::Error>(())
+    }
+    .await;
+    refresh.stop().await;
+    res
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_article_signature(TestData(alpha, beta, _): &mut TestData) -> Result<()> {
+    let article = alpha.create_article(&create_test_article_params()).await?;
+    let fetch_json = || async {
+        let json: serde_json::Value = reqwest::Client::new()
+            .get(article.article.ap_id.to_string())
+            .header("Accept", "application/activity+json")
+            .send()
+            .await?
+            .json()
+            .await?;
+        Ok::<_, anyhow::Error>(json)
+    };
+
+    // the signature is stored, so it stays the same until the article changes
+    let json = fetch_json().await?;
+    let signature = json["signature"]["signatureValue"].clone();
+    assert!(signature.is_string());
+    assert_eq!(
+        signature,
+        fetch_json().await?["signature"]["signatureValue"]
+    );
+
+    // protection level and attachments are covered by the signature, so changing them
+    // creates a new one which remote instances can verify
+    alpha
+        .add_attachment(&AddAttachmentParams {
+            article_id: article.article.id,
+            url: Url::parse("https://example.com/diagram.png")?,
+            alt_text: Some("Diagram".to_string()),
+            media_type: None,
+        })
+        .await?;
+    let admin = ApiClient::new(Some(alpha.hostname.clone()));
+    admin
+        .login(LoginUserParams {
+            username_or_email: "ibis".to_string(),
+            password: "ibis".to_string(),
+        })
+        .await?;
+    admin
+        .protect_article(&SetProtectionParams {
+            article_id: article.article.id,
+            level: ProtectionLevel::AutoConfirmed,
+        })
+        .await?;
+    let json = fetch_json().await?;
+    assert_ne!(signature, json["signature"]["signatureValue"]);
+
+    let resolved = beta
+        .resolve_article(article.article.ap_id.inner().clone())
+        .await?;
+    assert_eq!(ProtectionLevel::AutoConfirmed, resolved.article.protection);
+    assert_eq!(1, resolved.attachments.len());
+    assert_eq!(
+        Some("Diagram".to_string()),
+        resolved.attachments[0].alt_text
+    );
+
+    Ok(())
+}
+
 #[test_context(TestData)]
 #[tokio::test]
 async fn api_test_lock_article(TestData(alpha, _, gamma): &mut TestData) -> Result<()> {
     // create article
     let create_params = create_test_article_params();
     let create_res = alpha.create_article(&create_params).await.unwrap();
-    assert!(!create_res.article.protected);
+    assert_eq!(ProtectionLevel::Unprotected, create_res.article.protection);
 
     // lock from normal user fails
-    let lock_params = ProtectArticleParams {
+    let mut lock_params = SetProtectionParams {
         article_id: create_res.article.id,
-        protected: true,
+        level: ProtectionLevel::AutoConfirmed,
     };
     let lock_res = alpha.protect_article(&lock_params).await;
     assert!(lock_res.is_err());
 
-    // login as admin to lock article
+    // login as admin with separate client to lock article
+    let admin = ApiClient::new(Some(alpha.hostname.clone()));
     let params = LoginUserParams {
         username_or_email: "ibis".to_string(),
         password: "ibis".to_string(),
     };
-    alpha.login(params).await.unwrap();
-    let lock_res = alpha.protect_article(&lock_params).await.unwrap();
-    assert!(lock_res.protected);
+    admin.login(params).await?;
+    let lock_res = admin.protect_article(&lock_params).await?;
+    assert_eq!(ProtectionLevel::AutoConfirmed, lock_res.protection);
+
+    // new account can't edit, but admin can
+    let mut edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: "semi-protected".to_string(),
+        summary: "test".to_string(),
+        previous_version_id: create_res.latest_version,
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    assert!(alpha.edit_article(&edit_params).await.is_err());
+    let edit_res = admin.edit_article_without_conflict(&edit_params).await;
+    assert!(edit_res.is_some());
+
+    lock_params.level = ProtectionLevel::AdminOnly;
+    let lock_res = admin.protect_article(&lock_params).await?;
+    assert_eq!(ProtectionLevel::AdminOnly, lock_res.protection);
+    edit_params.new_text = "protected".to_string();
+    edit_params.previous_version_id = edit_res.map(|a| a.latest_version).unwrap_or_default();
+    assert!(alpha.edit_article(&edit_params).await.is_err());
 
     let resolve_res: ArticleView = gamma
         .resolve_article(create_res.article.ap_id.inner().clone())
@@ -781,6 +4273,7 @@ async fn api_test_lock_article(TestData(alpha, _, gamma): &mut TestData) -> Resu
         summary: "test".to_string(),
         previous_version_id: resolve_res.latest_version,
         resolve_conflict_id: None,
+        minor: None,
     };
     let edit_res = gamma.edit_article_without_conflict(&edit_params).await;
     assert!(edit_res.is_none());
@@ -788,6 +4281,338 @@ async fn api_test_lock_article(TestData(alpha, _, gamma): &mut TestData) -> Resu
     Ok(())
 }
 
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_user_roles(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let create_res = alpha
+        .create_article(&create_test_article_params())
+        .await
+        .unwrap();
+    let lock_params = SetProtectionParams {
+        article_id: create_res.article.id,
+        level: ProtectionLevel::AdminOnly,
+    };
+    assert!(alpha.protect_article(&lock_params).await.is_err());
+
+    // login as admin with separate client
+    let admin = ApiClient::new(Some(alpha.hostname.clone()));
+    let params = LoginUserParams {
+        username_or_email: "ibis".to_string(),
+        password: "ibis".to_string(),
+    };
+    let admin_user = admin.login(params).await.unwrap();
+    let alpha_user = alpha.site().await.unwrap().my_profile.unwrap();
+    assert_eq!(UserRole::Editor, alpha_user.local_user.role);
+
+    // moderator can protect articles, but not change roles
+    let set_role = |role| SetUserRoleParams {
+        person_id: alpha_user.person.id,
+        role,
+    };
+    let res = admin.set_user_role(set_role(UserRole::Moderator)).await;
+    assert_eq!(UserRole::Moderator, res.unwrap().role);
+    assert_eq!(
+        ProtectionLevel::AdminOnly,
+        alpha
+            .protect_article(&lock_params)
+            .await
+            .unwrap()
+            .protection
+    );
+    let res = alpha
+        .set_user_role(SetUserRoleParams {
+            person_id: admin_user.person.id,
+            role: UserRole::Reader,
+        })
+        .await;
+    assert!(res.is_err());
+
+    // reader can't create articles
+    admin
+        .set_user_role(set_role(UserRole::Reader))
+        .await
+        .unwrap();
+    let mut create_params = create_test_article_params();
+    create_params.title = "Another article".to_string();
+    assert!(alpha.create_article(&create_params).await.is_err());
+
+    // last admin can't demote themself
+    let ids = |persons: Vec<Person>| persons.iter().map(|p| p.id).collect::<Vec<_>>();
+    assert_eq!(vec![admin_user.person.id], ids(alpha.list_admins().await?));
+    let demote_admin = SetUserRoleParams {
+        person_id: admin_user.person.id,
+        role: UserRole::Reader,
+    };
+    let res = admin.set_user_role(demote_admin.clone()).await;
+    assert!(res.unwrap_err().to_string().contains("last admin"));
+
+    // promote another admin, then the first one can be demoted
+    admin.set_user_role(set_role(UserRole::Admin)).await?;
+    assert_eq!(
+        vec![admin_user.person.id, alpha_user.person.id],
+        ids(alpha.list_admins().await?)
+    );
+    admin.set_user_role(demote_admin).await?;
+    assert_eq!(vec![alpha_user.person.id], ids(alpha.list_admins().await?));
+
+    // new admin is now the last one and can't be demoted either
+    assert!(
+        alpha
+            .set_user_role(set_role(UserRole::Editor))
+            .await
+            .is_err()
+    );
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_ban_user(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let create_res = alpha.create_article(&create_test_article_params()).await?;
+    let admin = ApiClient::new(Some(alpha.hostname.clone()));
+    admin
+        .login(LoginUserParams {
+            username_or_email: "ibis".to_string(),
+            password: "ibis".to_string(),
+        })
+        .await?;
+    let person = alpha.site().await?.my_profile.unwrap().person;
+    let mut edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: "Lorem Ipsum\n".to_string(),
+        summary: "summary".to_string(),
+        previous_version_id: create_res.latest_version,
+        resolve_conflict_id: None,
+        minor: None,
+    };
+
+    // only moderators can ban
+    let ban = |ban, expires| BanUserParams {
+        person_id: person.id,
+        ban,
+        expires,
+    };
+    assert!(alpha.ban_user(ban(true, None)).await.is_err());
+
+    // permanent ban prevents edits
+    let banned = admin.ban_user(ban(true, None)).await?;
+    assert!(banned.is_banned());
+    let res = alpha.edit_article(&edit_params).await;
+    assert!(res.unwrap_err().to_string().contains("banned"));
+    let mut create_params = create_test_article_params();
+    create_params.title = "Another article".to_string();
+    assert!(alpha.create_article(&create_params).await.is_err());
+
+    // temporary ban ends automatically
+    let expires = Utc::now() + chrono::Duration::seconds(2);
+    let banned = admin.ban_user(ban(true, Some(expires))).await?;
+    assert!(banned.is_banned());
+    assert!(alpha.edit_article(&edit_params).await.is_err());
+    sleep(Duration::from_secs(3)).await;
+    let edit_res = alpha.edit_article_without_conflict(&edit_params).await?;
+
+    // ban can be removed
+    admin.ban_user(ban(true, None)).await?;
+    let unbanned = admin.ban_user(ban(false, None)).await?;
+    assert!(!unbanned.is_banned());
+    edit_params.new_text = "Ipsum Lorem\n".to_string();
+    edit_params.previous_version_id = edit_res.latest_version;
+    alpha.edit_article_without_conflict(&edit_params).await?;
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_revert_to_date(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let params = LoginUserParams {
+        username_or_email: "ibis".to_string(),
+        password: "ibis".to_string(),
+    };
+    alpha.login(params).await.unwrap();
+    let before_create = Utc::now();
+    let create_res = alpha
+        .create_article(&create_test_article_params())
+        .await
+        .unwrap();
+    sleep(Duration::from_millis(100)).await;
+    let after_create = Utc::now();
+
+    let edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: "vandalism\n".to_string(),
+        summary: "bad edit".to_string(),
+        previous_version_id: create_res.latest_version,
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    alpha.edit_article(&edit_params).await.unwrap();
+
+    // timestamp before article creation
+    let mut revert_params = RevertToDateParams {
+        article_id: create_res.article.id,
+        timestamp: before_create,
+    };
+    assert!(alpha.revert_article_to_date(&revert_params).await.is_err());
+
+    revert_params.timestamp = after_create;
+    let revert_res = alpha.revert_article_to_date(&revert_params).await.unwrap();
+    assert_eq!(create_res.article.text, revert_res.article.text);
+    let edits = alpha
+        .get_article_edits(create_res.article.id)
+        .await
+        .unwrap();
+    assert_eq!(3, edits.len());
+    assert!(edits[2].edit.summary.contains(&after_create.to_rfc3339()));
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_revert_article(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let create_res = alpha.create_article(&create_test_article_params()).await?;
+    let edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: "vandalism\n".to_string(),
+        summary: "bad edit".to_string(),
+        previous_version_id: create_res.latest_version.clone(),
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    let edit_res = alpha
+        .edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+
+    // reverting to the current version does nothing
+    let mut revert_params = RevertArticleParams {
+        article_id: create_res.article.id,
+        revert_to_version: edit_res.latest_version,
+    };
+    assert!(alpha.revert_article(&revert_params).await.is_err());
+
+    // version from a different article
+    let other_params = CreateArticleParams {
+        title: "Other".to_string(),
+        ..create_test_article_params()
+    };
+    let other = alpha.create_article(&other_params).await?;
+    revert_params.revert_to_version = other.latest_version;
+    assert!(alpha.revert_article(&revert_params).await.is_err());
+
+    // revert as normal user creates a new edit
+    revert_params.revert_to_version = create_res.latest_version.clone();
+    let revert_res = alpha.revert_article(&revert_params).await?;
+    assert_eq!(create_res.article.text, revert_res.article.text);
+    let edits = alpha.get_article_edits(create_res.article.id).await?;
+    assert_eq!(3, edits.len());
+    let hash = create_res.latest_version.hash();
+    assert_eq!(
+        format!("Revert to version {}", &hash[..8]),
+        edits[2].edit.summary
+    );
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_article_disambiguation(
+    TestData(alpha, beta, gamma): &mut TestData,
+) -> Result<()> {
+    // same title on alpha and beta
+    let create_params = create_test_article_params();
+    let alpha_article = alpha.create_article(&create_params).await.unwrap();
+    let beta_article = beta.create_article(&create_params).await.unwrap();
+    let mut other_params = create_test_article_params();
+    other_params.title = "Other".to_string();
+    let other_article = alpha.create_article(&other_params).await.unwrap();
+    for article in [&alpha_article, &beta_article, &other_article] {
+        gamma
+            .resolve_article(article.article.ap_id.inner().clone())
+            .await
+            .unwrap();
+    }
+
+    // ambiguous title returns candidates
+    let params = GetArticleParams {
+        title: Some(create_params.title.clone()),
+        domain: None,
+        id: None,
+        follow_redirect: None,
+    };
+    assert!(gamma.get_article(params.clone()).await.is_err());
+    let ArticleLookup::Disambiguation(res) = gamma.lookup_article(params.clone()).await.unwrap()
+    else {
+        panic!("expected disambiguation");
+    };
+    assert_eq!(2, res.candidates.len());
+    assert_eq!(alpha.hostname, res.candidates[0].instance_domain);
+    assert_eq!(beta.hostname, res.candidates[1].instance_domain);
+    assert_eq!(TEST_ARTICLE_DEFAULT_TEXT.trim(), res.candidates[0].excerpt);
+
+    // local article is preferred
+    let res = alpha.get_article(params).await.unwrap();
+    assert_eq!(alpha_article.article.id, res.article.id);
+
+    // single remote match is returned directly
+    let params = GetArticleParams {
+        title: Some(other_params.title.clone()),
+        domain: None,
+        id: None,
+        follow_redirect: None,
+    };
+    let res = gamma.get_article(params).await.unwrap();
+    assert_eq!(other_article.article.ap_id, res.article.ap_id);
+
+    Ok(())
+}
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_validate_edit(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let create_res = alpha
+        .create_article(&create_test_article_params())
+        .await
+        .unwrap();
+    let mut edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: create_res.article.text.clone(),
+        summary: String::new(),
+        previous_version_id: create_res.latest_version.clone(),
+        resolve_conflict_id: None,
+        minor: None,
+    };
+    let checks = alpha.validate_edit(&edit_params).await.unwrap();
+    let failed: Vec<_> = checks
+        .iter()
+        .filter(|c| !c.passed)
+        .map(|c| c.name.as_str())
+        .collect();
+    assert_eq!(vec!["summary", "changes"], failed);
+
+    // text is normalized like for a real edit, so a missing trailing newline is no change
+    edit_params.new_text = create_res.article.text.trim_end().to_string();
+    edit_params.summary = "summary".to_string();
+    let checks = alpha.validate_edit(&edit_params).await.unwrap();
+    let changes = checks.iter().find(|c| c.name == "changes").unwrap();
+    assert!(!changes.passed);
+
+    // valid edit passes all checks, and nothing is changed
+    edit_params.new_text = "new text\n".to_string();
+    edit_params.summary = "summary".to_string();
+    let checks = alpha.validate_edit(&edit_params).await.unwrap();
+    assert!(checks.iter().all(|c| c.passed));
+    let edits = alpha
+        .get_article_edits(create_res.article.id)
+        .await
+        .unwrap();
+    assert_eq!(1, edits.len());
+
+    Ok(())
+}
+
 #[test_context(TestData)]
 #[tokio::test]
 async fn api_test_synchronize_instances(TestData(alpha, beta, gamma): &mut TestData) -> Result<()> {
@@ -839,15 +4664,15 @@ async fn api_test_remove_article(TestData(alpha, beta, _): &mut TestData) -> Res
     let create_params = create_test_article_params();
     let create_res = alpha.create_article(&create_params).await.unwrap();
 
-    let list_alpha = alpha.list_articles(Default::default()).await.unwrap();
-    let article_to_remove_id = list_alpha[0].id;
+    let list_alpha = alpha.list_articles(Default::default()).await?.articles;
+    let article_to_remove_id = list_alpha[0].article.id;
     // count also includes auto-created main page
     assert_eq!(2, list_alpha.len());
     assert_eq!(article_to_remove_id, create_res.article.id);
-    let list_beta = beta.list_articles(Default::default()).await.unwrap();
+    let list_beta = beta.list_articles(Default::default()).await?.articles;
     // count also includes main pages from alpha and beta
     assert_eq!(3, list_beta.len());
-    assert_eq!(create_res.article.ap_id, list_beta[0].ap_id);
+    assert_eq!(create_res.article.ap_id, list_beta[0].article.ap_id);
 
     // login as admin to remove article
     let params = LoginUserParams {
@@ -870,7 +4695,7 @@ async fn api_test_remove_article(TestData(alpha, beta, _): &mut TestData) -> Res
     // cannot get the article
     sleep(Duration::from_secs(1)).await;
     assert!(beta.get_article(params.clone()).await.is_err());
-    let list_beta = beta.list_articles(Default::default()).await?;
+    let list_beta = beta.list_articles(Default::default()).await?.articles;
     assert_eq!(2, list_beta.len());
 
     // except as admin with include_removed
@@ -879,9 +4704,25 @@ async fn api_test_remove_article(TestData(alpha, beta, _): &mut TestData) -> Res
             include_removed: Some(true),
             ..Default::default()
         })
-        .await
+        .await?;
+    assert_eq!(2, list_all.articles.len());
+    let removed = list_all
+        .articles
+        .iter()
+        .find(|a| a.article.id == article_to_remove_id)
         .unwrap();
-    assert_eq!(2, list_all.len());
+    assert!(removed.article.removed);
+    assert!(removed.article.removed_at.is_some());
+
+    // removed articles are listed separately for moderators
+    let removed = alpha.list_removed_articles().await?;
+    assert_eq!(1, removed.len());
+    assert_eq!(article_to_remove_id, removed[0].id);
+    assert!(beta.list_removed_articles().await.is_err());
+
+    // and its history can't be exported or used for attribution
+    assert!(alpha.export_article(article_to_remove_id).await.is_err());
+    assert!(alpha.get_contributors(article_to_remove_id).await.is_err());
 
     // restore article
     alpha
@@ -890,8 +4731,11 @@ async fn api_test_remove_article(TestData(alpha, beta, _): &mut TestData) -> Res
         .unwrap();
 
     // now it can be viewed again
-    assert!(alpha.get_article(params).await.is_ok());
-    let list_beta = beta.list_articles(Default::default()).await?;
+    let restored = alpha.get_article(params).await?;
+    assert!(!restored.article.removed);
+    assert!(restored.article.removed_at.is_none());
+    assert!(alpha.list_removed_articles().await?.is_empty());
+    let list_beta = beta.list_articles(Default::default()).await?.articles;
     assert_eq!(3, list_beta.len());
 
     Ok(())
@@ -980,6 +4824,65 @@ async fn api_test_comment_create_edit(TestData(alpha, beta, gamma): &mut TestDat
     Ok(())
 }
 
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_comment_nested_replies(TestData(alpha, _, _): &mut TestData) -> Result<()> {
+    let article = alpha.create_article(&create_test_article_params()).await?;
+
+    // top-level comment with a reply to a reply, and a second top-level comment
+    let mut parent_id = None;
+    let mut thread = vec![];
+    for content in ["top", "reply", "nested reply"] {
+        let params = CreateCommentParams {
+            content: content.to_string(),
+            article_id: article.article.id,
+            parent_id,
+        };
+        let comment = alpha.create_comment(&params).await?.comment;
+        parent_id = Some(comment.id);
+        thread.push(comment);
+    }
+    let params = CreateCommentParams {
+        content: "second top".to_string(),
+        article_id: article.article.id,
+        parent_id: None,
+    };
+    let second_top = alpha.create_comment(&params).await?.comment;
+    assert_eq!(
+        vec![0, 1, 2],
+        thread.iter().map(|c| c.depth).collect::<Vec<_>>()
+    );
+    assert_eq!(Some(thread[1].id), thread[2].parent_id);
+    assert_eq!(0, second_top.depth);
+
+    // comments are returned newest first, so replies come before their parents
+    let get_params = GetArticleParams {
+        id: Some(article.article.id),
+        ..Default::default()
+    };
+    let comments = alpha.get_article(get_params).await?.comments;
+    let ids: Vec<_> = comments.iter().map(|c| c.comment.id).collect();
+    assert_eq!(
+        vec![second_top.id, thread[2].id, thread[1].id, thread[0].id],
+        ids
+    );
+
+    // replying to a comment of another article is not possible
+    let other_params = CreateArticleParams {
+        title: "Other".to_string(),
+        ..create_test_article_params()
+    };
+    let other = alpha.create_article(&other_params).await?;
+    let params = CreateCommentParams {
+        content: "wrong article".to_string(),
+        article_id: other.article.id,
+        parent_id: Some(thread[0].id),
+    };
+    assert!(alpha.create_comment(&params).await.is_err());
+
+    Ok(())
+}
+
 #[test_context(TestData)]
 #[tokio::test]
 async fn api_test_comment_delete_restore(TestData(alpha, beta, _): &mut TestData) -> Result<()> {
@@ -1089,3 +4992,52 @@ async fn api_test_create_remote_article(TestData(alpha, beta, _): &mut TestData)
 
     Ok(())
 }
+
+#[test_context(TestData)]
+#[tokio::test]
+async fn api_test_article_attachments(TestData(alpha, beta, _): &mut TestData) -> Result<()> {
+    let create_res = alpha
+        .create_article(&create_test_article_params())
+        .await
+        .unwrap();
+
+    // add attachment
+    let params = AddAttachmentParams {
+        article_id: create_res.article.id,
+        url: Url::parse("https://example.com/diagram.png")?,
+        alt_text: Some("A diagram".to_string()),
+        media_type: Some("image/png".to_string()),
+    };
+    let attachment = alpha.add_attachment(&params).await.unwrap();
+    assert_eq!(params.alt_text, attachment.alt_text);
+
+    // invalid url is rejected
+    let invalid_params = AddAttachmentParams {
+        url: Url::parse("ftp://example.com/diagram.png")?,
+        ..params
+    };
+    assert!(alpha.add_attachment(&invalid_params).await.is_err());
+
+    // attachment is federated
+    let beta_article = beta
+        .resolve_article(create_res.article.ap_id.into())
+        .await
+        .unwrap();
+    assert_eq!(1, beta_article.attachments.len());
+    assert_eq!(attachment.url, beta_article.attachments[0].url);
+    assert_eq!(attachment.alt_text, beta_article.attachments[0].alt_text);
+
+    // remove attachment
+    alpha
+        .remove_attachment(&RemoveAttachmentParams { id: attachment.id })
+        .await
+        .unwrap();
+    let get_params = GetArticleParams {
+        id: Some(create_res.article.id),
+        ..Default::default()
+    };
+    let alpha_article = alpha.get_article(get_params).await.unwrap();
+    assert!(alpha_article.attachments.is_empty());
+
+    Ok(())
+}
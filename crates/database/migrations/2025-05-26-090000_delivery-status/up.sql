@@ -0,0 +1,6 @@
+create table delivery_status (
+    instance_id int primary key references instance on update cascade on delete cascade,
+    last_success timestamptz,
+    last_failure timestamptz,
+    failures int not null default 0
+);
@@ -0,0 +1 @@
+alter table local_user drop column published;
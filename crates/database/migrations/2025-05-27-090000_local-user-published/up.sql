@@ -0,0 +1,4 @@
+alter table local_user add column published timestamptz not null default now();
+
+-- Existing accounts should not be affected by the new account cooldown
+update local_user set published = '1970-01-01';
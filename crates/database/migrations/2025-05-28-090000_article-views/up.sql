@@ -0,0 +1,4 @@
+create table article_views (
+    article_id int primary key references article on update cascade on delete cascade,
+    views bigint not null default 0
+);
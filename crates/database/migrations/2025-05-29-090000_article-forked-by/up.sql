@@ -0,0 +1 @@
+alter table article add column forked_by int references person(id) on update cascade on delete set null;
@@ -0,0 +1 @@
+alter table conflict add column attempts int not null default 0;
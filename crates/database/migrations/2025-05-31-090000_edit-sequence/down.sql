@@ -0,0 +1 @@
+alter table edit drop column sequence;
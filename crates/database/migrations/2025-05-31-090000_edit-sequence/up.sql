@@ -0,0 +1,18 @@
+-- Monotonic ordering key for edits, assigned on insert. Gaps are possible and harmless.
+create sequence edit_sequence_seq as bigint;
+
+alter table edit add column sequence bigint;
+
+-- Backfill existing edits in the order they were published
+update edit set sequence = ordered.n
+from (select id, row_number() over (order by published, id) as n from edit) as ordered
+where edit.id = ordered.id;
+
+select setval('edit_sequence_seq', coalesce((select max(sequence) from edit), 0) + 1, false);
+
+alter table edit alter column sequence set default nextval('edit_sequence_seq'),
+    alter column sequence set not null;
+
+alter sequence edit_sequence_seq owned by edit.sequence;
+
+create index idx_edit_article_sequence on edit (article_id, sequence);
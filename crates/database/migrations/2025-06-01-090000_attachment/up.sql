@@ -0,0 +1,9 @@
+create table attachment (
+    id serial primary key,
+    article_id int not null references article on update cascade on delete cascade,
+    url text not null,
+    alt_text text,
+    media_type text,
+    published timestamptz not null default now(),
+    unique (article_id, url)
+);
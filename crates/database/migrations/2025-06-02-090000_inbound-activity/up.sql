@@ -0,0 +1,14 @@
+-- Incoming activities which failed to process. They are retried with backoff, and kept as
+-- dead letters once all retries are exhausted.
+create table inbound_activity (
+    id serial primary key,
+    ap_id varchar(255) not null unique,
+    json text not null,
+    error text not null,
+    attempts int not null default 1,
+    next_retry timestamptz not null,
+    dead boolean not null default false,
+    published timestamptz not null default now()
+);
+
+create index idx_inbound_activity_next_retry on inbound_activity (next_retry) where not dead;
@@ -0,0 +1,5 @@
+alter table local_user add column admin boolean not null default false;
+
+update local_user set admin = true where role = 'Admin';
+
+alter table local_user drop column role;
@@ -0,0 +1,6 @@
+alter table local_user add column role varchar(20) not null default 'Editor'
+    check (role in ('Admin', 'Moderator', 'Editor', 'Reader'));
+
+update local_user set role = 'Admin' where admin;
+
+alter table local_user drop column admin;
@@ -0,0 +1 @@
+alter table conflict drop column updated;
@@ -0,0 +1 @@
+alter table conflict add column updated timestamptz not null default now();
@@ -0,0 +1 @@
+drop table conflict_log;
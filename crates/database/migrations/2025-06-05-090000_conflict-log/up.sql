@@ -0,0 +1,15 @@
+-- Permanent record of edit conflicts for moderation statistics. Rows in the conflict table are
+-- deleted once the conflict is resolved or abandoned, so the outcome is stored here.
+create table conflict_log (
+    id serial primary key,
+    conflict_id int not null unique,
+    article_id int not null references article ON UPDATE CASCADE ON DELETE CASCADE,
+    published timestamptz not null,
+    resolved timestamptz,
+    abandoned timestamptz
+);
+
+create index idx_conflict_log_published on conflict_log (published);
+
+insert into conflict_log (conflict_id, article_id, published)
+select id, article_id, published from conflict;
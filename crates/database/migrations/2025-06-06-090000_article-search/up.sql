@@ -0,0 +1 @@
+create index idx_article_text_search on article using gin (to_tsvector('english', text));
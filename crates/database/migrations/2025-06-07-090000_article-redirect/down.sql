@@ -0,0 +1 @@
+alter table article drop column redirect_to;
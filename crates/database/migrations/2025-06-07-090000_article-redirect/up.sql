@@ -0,0 +1 @@
+alter table article add column redirect_to int references article on delete set null;
@@ -0,0 +1,5 @@
+alter table article add column protected boolean not null default false;
+
+update article set protected = true where protection != 'Unprotected';
+
+alter table article drop column protection;
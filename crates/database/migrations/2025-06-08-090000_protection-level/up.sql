@@ -0,0 +1,6 @@
+alter table article add column protection varchar(20) not null default 'Unprotected'
+    check (protection in ('Unprotected', 'AutoConfirmed', 'AdminOnly'));
+
+update article set protection = 'AdminOnly' where protected;
+
+alter table article drop column protected;
@@ -0,0 +1,10 @@
+create table article_move (
+    id serial primary key,
+    article_id int not null references article on delete cascade,
+    creator_id int not null references person on delete cascade,
+    old_title text not null,
+    new_title text not null,
+    published timestamptz not null default now()
+);
+
+create index idx_article_move_article on article_move (article_id);
@@ -0,0 +1 @@
+alter table edit add column minor bool not null default false;
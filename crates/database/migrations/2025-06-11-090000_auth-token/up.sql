@@ -0,0 +1,8 @@
+-- Login sessions. Each auth cookie references one row, so that sessions can be revoked.
+create table auth_token (
+    token text primary key,
+    person_id int not null references person on delete cascade,
+    published timestamptz not null default now()
+);
+
+create index idx_auth_token_person_id on auth_token (person_id);
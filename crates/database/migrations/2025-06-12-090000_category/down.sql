@@ -0,0 +1,2 @@
+drop table article_category;
+drop table category;
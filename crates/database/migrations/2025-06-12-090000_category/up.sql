@@ -0,0 +1,16 @@
+create table category (
+    id serial primary key,
+    name text not null unique,
+    published timestamptz not null default now()
+);
+
+-- Articles are added to categories either manually or via `[[Category:Name]]` markers in the
+-- text. Marker memberships are replaced whenever the article text changes.
+create table article_category (
+    article_id int not null references article on delete cascade,
+    category_id int not null references category on delete cascade,
+    from_marker boolean not null default false,
+    primary key (article_id, category_id)
+);
+
+create index idx_article_category_category_id on article_category (category_id);
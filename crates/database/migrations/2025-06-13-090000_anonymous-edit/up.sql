@@ -0,0 +1 @@
+alter table edit add column anonymous_id text;
@@ -0,0 +1,2 @@
+-- removal time of existing removed articles is unknown, so it stays null
+alter table article add column removed_at timestamptz;
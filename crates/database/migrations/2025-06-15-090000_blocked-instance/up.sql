@@ -0,0 +1,4 @@
+create table blocked_instance (
+    domain text primary key,
+    published timestamptz not null default now()
+);
@@ -0,0 +1 @@
+drop table allowed_instance;
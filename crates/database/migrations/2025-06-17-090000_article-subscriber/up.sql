@@ -0,0 +1,6 @@
+create table article_subscriber (
+    article_id int not null references article on update cascade on delete cascade,
+    person_id int not null references person on update cascade on delete cascade,
+    published timestamptz not null default now(),
+    primary key (article_id, person_id)
+);
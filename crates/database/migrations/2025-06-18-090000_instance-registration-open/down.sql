@@ -0,0 +1 @@
+alter table instance drop column registration_open;
@@ -0,0 +1,2 @@
+-- Overrides registration_open from config if set, only used for the local instance
+alter table instance add column registration_open boolean;
@@ -0,0 +1,2 @@
+alter table person drop column banned;
+alter table person drop column ban_expires;
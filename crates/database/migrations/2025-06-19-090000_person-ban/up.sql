@@ -0,0 +1,2 @@
+alter table person add column banned boolean not null default false;
+alter table person add column ban_expires timestamptz;
@@ -0,0 +1 @@
+drop table revision_tag;
@@ -0,0 +1,9 @@
+create table revision_tag (
+    id serial primary key,
+    article_id int not null references article on update cascade on delete cascade,
+    version uuid not null,
+    tag text not null,
+    creator_id int not null references person on update cascade on delete cascade,
+    published timestamptz not null default now(),
+    unique (article_id, tag)
+);
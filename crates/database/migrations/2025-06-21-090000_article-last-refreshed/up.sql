@@ -0,0 +1 @@
+alter table article add column last_refreshed_at timestamptz not null default now();
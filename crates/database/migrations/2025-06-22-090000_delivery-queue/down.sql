@@ -0,0 +1 @@
+drop table delivery_queue;
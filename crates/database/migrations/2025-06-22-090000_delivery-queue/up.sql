@@ -0,0 +1,17 @@
+-- Outgoing activities which couldn't be delivered to an inbox. They are retried with backoff,
+-- and marked as dead once all retries are exhausted.
+create table delivery_queue (
+    id serial primary key,
+    activity_id varchar(255) not null,
+    actor_id varchar(255) not null,
+    inbox text not null,
+    json text not null,
+    error text not null,
+    attempts int not null default 1,
+    next_retry timestamptz not null,
+    dead boolean not null default false,
+    published timestamptz not null default now(),
+    unique (activity_id, inbox)
+);
+
+create index idx_delivery_queue_next_retry on delivery_queue (next_retry) where not dead;
@@ -0,0 +1 @@
+alter table instance drop column icon_url;
@@ -0,0 +1 @@
+alter table instance add column icon_url varchar(255);
@@ -0,0 +1,3 @@
+create extension if not exists pg_trgm;
+
+create index idx_article_title_trgm on article using gin (title gin_trgm_ops);
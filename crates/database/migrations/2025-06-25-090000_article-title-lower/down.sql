@@ -0,0 +1 @@
+drop index idx_article_title_lower;
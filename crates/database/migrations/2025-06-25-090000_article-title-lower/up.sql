@@ -0,0 +1 @@
+create index idx_article_title_lower on article (lower(title));
@@ -0,0 +1 @@
+alter table article drop column signature;
@@ -0,0 +1,2 @@
+-- Signature of local articles by the local instance, created whenever the article changes
+alter table article add column signature text;
@@ -1,18 +1,36 @@
 use super::{
     comment::CommentView,
-    instance::Instance,
-    newtypes::{ArticleId, ConflictId, EditId, InstanceId, PersonId},
-    user::Person,
+    instance::{Instance, Options},
+    newtypes::{
+        ArticleId,
+        ArticleMoveId,
+        AttachmentId,
+        CategoryId,
+        ConflictId,
+        EditId,
+        InstanceId,
+        PersonId,
+        RevisionTagId,
+    },
+    user::{Person, UserRole},
 };
 use crate::{DbUrl, common::utils::extract_domain};
 use anyhow::anyhow;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 #[cfg(feature = "ssr")]
 use {
     diesel::{Identifiable, Queryable, Selectable},
-    ibis_database_schema::{article, conflict, edit},
+    ibis_database_schema::{
+        article,
+        article_move,
+        attachment,
+        category,
+        conflict,
+        edit,
+        revision_tag,
+    },
     sha2::{Digest, Sha256},
 };
 
@@ -31,6 +49,10 @@ pub struct Conflict {
     pub article_id: ArticleId,
     pub previous_version_id: EditVersion,
     pub published: DateTime<Utc>,
+    /// How often the user tried to resolve this conflict, only to run into another conflict
+    pub attempts: i32,
+    /// Last time the conflict was opened for resolution. Stale conflicts are purged based on this.
+    pub updated: DateTime<Utc>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -42,6 +64,238 @@ pub struct ArticleView {
     pub comments: Vec<CommentView>,
     pub latest_version: EditVersion,
     pub following: bool,
+    pub view_count: i64,
+    pub attachments: Vec<Attachment>,
+    /// Set if the requested article was a redirect to this one
+    pub redirected_from: Option<Article>,
+    /// Previous renames of the article, oldest first
+    pub moves: Vec<ArticleMoveView>,
+    /// Titles of local articles which are linked from the text but don't exist yet
+    pub missing_links: Vec<String>,
+    /// Categories which the article belongs to, ordered by name
+    pub categories: Vec<Category>,
+}
+
+/// Named group of articles. Articles are added manually or with a `[[Category:Name]]` marker in
+/// the text.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "ssr", diesel(table_name = category, check_for_backend(diesel::pg::Pg)))]
+pub struct Category {
+    pub id: CategoryId,
+    pub name: String,
+    pub published: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CategoryView {
+    pub category: Category,
+    /// Member articles, ordered by title
+    pub articles: Vec<Article>,
+}
+
+/// Record of an article being renamed, shown in the article history
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "ssr", diesel(table_name = article_move, check_for_backend(diesel::pg::Pg)))]
+pub struct ArticleMove {
+    pub id: ArticleMoveId,
+    pub article_id: ArticleId,
+    pub creator_id: PersonId,
+    pub old_title: String,
+    pub new_title: String,
+    pub published: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ArticleMoveView {
+    pub article_move: ArticleMove,
+    pub creator: Person,
+}
+
+/// Reference to external media like an image or diagram which belongs to an article
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "ssr", diesel(table_name = attachment, check_for_backend(diesel::pg::Pg)))]
+pub struct Attachment {
+    pub id: AttachmentId,
+    pub article_id: ArticleId,
+    pub url: String,
+    /// Description of the media for screen readers
+    pub alt_text: Option<String>,
+    /// Mime type such as `image/png`
+    pub media_type: Option<String>,
+    pub published: DateTime<Utc>,
+}
+
+/// Named milestone for a specific version of an article, such as `v1.0` or `reviewed`
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "ssr", diesel(table_name = revision_tag, check_for_backend(diesel::pg::Pg)))]
+pub struct RevisionTag {
+    pub id: RevisionTagId,
+    pub article_id: ArticleId,
+    pub version: EditVersion,
+    /// Unique per article
+    pub tag: String,
+    pub creator_id: PersonId,
+    pub published: DateTime<Utc>,
+}
+
+/// Result of a single check for a proposed edit
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct EditCheck {
+    pub name: String,
+    pub passed: bool,
+    /// Reason why the check failed
+    pub message: Option<String>,
+}
+
+/// Who would receive an edit to an article
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct EditReach {
+    /// Number of remote instances which receive the edit activity
+    pub instances: usize,
+    /// Number of local users who get notified about the edit
+    pub local_followers: i64,
+    /// Domains of the receiving instances, only available for admins
+    pub instance_domains: Option<Vec<String>>,
+}
+
+/// Two articles with similar text, which may be candidates for merging
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct DuplicateArticles {
+    pub first: Article,
+    pub second: Article,
+    /// Estimated share of common text, between 0 and 1
+    pub similarity: f32,
+}
+
+/// One page of an article listing
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ArticleList {
+    pub articles: Vec<ArticleListEntry>,
+    /// Number of articles matching the filters across all pages
+    pub total: i64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ArticleListEntry {
+    pub article: Article,
+    pub latest_edit: Option<LatestEdit>,
+}
+
+/// Details of the most recent edit of an article, without the diff
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable))]
+#[cfg_attr(feature = "ssr", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct LatestEdit {
+    pub summary: String,
+    pub published: DateTime<Utc>,
+    pub creator: Person,
+}
+
+/// Article which matches an ambiguous title, with details to tell it apart from the others
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct DisambiguationCandidate {
+    pub article: Article,
+    pub instance_domain: String,
+    /// Beginning of the article text
+    pub excerpt: String,
+}
+
+/// Returned instead of an article if multiple articles match the requested title
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ArticleDisambiguation {
+    pub title: String,
+    pub candidates: Vec<DisambiguationCandidate>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum ArticleLookup {
+    Article(Box<ArticleView>),
+    Disambiguation(ArticleDisambiguation),
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "ssr", derive(doku::Document))]
+pub enum ArticleSort {
+    /// Most recently edited first
+    #[default]
+    Updated,
+    /// Highest view count first
+    MostViewed,
+    /// Most recently created first
+    Newest,
+    /// Oldest articles first
+    Oldest,
+}
+
+/// Which parts of articles are searched
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum SearchMode {
+    /// Only match on the title
+    TitleOnly,
+    /// Match on the title, or full text search on the article body
+    #[default]
+    TitleAndBody,
+}
+
+/// Marks the start of a matched term in [SearchResult::snippet]
+pub const SEARCH_HIGHLIGHT_START: &str = "<b>";
+/// Marks the end of a matched term in [SearchResult::snippet]
+pub const SEARCH_HIGHLIGHT_END: &str = "</b>";
+
+/// Article which matches a search query
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable))]
+#[cfg_attr(feature = "ssr", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct SearchResult {
+    pub article: Article,
+    /// Excerpt of the article text around the matched terms, which are wrapped in
+    /// [SEARCH_HIGHLIGHT_START] and [SEARCH_HIGHLIGHT_END]. The text is not escaped.
+    pub snippet: String,
+}
+
+/// Who is allowed to edit an article
+#[cfg_attr(
+    feature = "ssr",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow)
+)]
+#[cfg_attr(feature = "ssr", diesel(sql_type = diesel::sql_types::Text))]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum ProtectionLevel {
+    /// All editors can edit
+    #[default]
+    Unprotected,
+    /// Only editors whose account is older than the configured `auto_confirm_days`
+    AutoConfirmed,
+    /// Only moderators and admins of the origin instance
+    AdminOnly,
+}
+
+impl ProtectionLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProtectionLevel::Unprotected => "Unprotected",
+            ProtectionLevel::AutoConfirmed => "AutoConfirmed",
+            ProtectionLevel::AdminOnly => "AdminOnly",
+        }
+    }
+}
+
+impl std::str::FromStr for ProtectionLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "Unprotected" => ProtectionLevel::Unprotected,
+            "AutoConfirmed" => ProtectionLevel::AutoConfirmed,
+            "AdminOnly" => ProtectionLevel::AdminOnly,
+            _ => return Err(format!("Unknown protection level {s}")),
+        })
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -54,11 +308,24 @@ pub struct Article {
     pub ap_id: DbUrl,
     pub instance_id: InstanceId,
     pub local: bool,
-    pub protected: bool,
     pub published: DateTime<Utc>,
     pub removed: bool,
     pub updated: DateTime<Utc>,
     pub pending: bool,
+    /// Set if this article was forked from another one, contains the user who created the fork
+    pub forked_by: Option<PersonId>,
+    /// Set if this article is a redirect, readers are sent to the target article instead
+    pub redirect_to: Option<ArticleId>,
+    pub protection: ProtectionLevel,
+    /// Set while the article is removed, so moderators can see when it happened
+    pub removed_at: Option<DateTime<Utc>>,
+    /// When a remote article was last fetched or received, used to refetch stale articles
+    #[serde(skip)]
+    pub last_refreshed_at: DateTime<Utc>,
+    /// Signature of a local article by the local instance, so that it doesn't need to be signed
+    /// again every time the article is federated
+    #[serde(skip)]
+    pub signature: Option<String>,
 }
 
 impl Article {
@@ -90,6 +357,61 @@ pub struct Edit {
     pub previous_version_id: EditVersion,
     pub published: DateTime<Utc>,
     pub pending: bool,
+    /// Increases with every edit stored locally, used to order the edit history. May have gaps.
+    pub sequence: i64,
+    /// Small change like a typo fix, which can be hidden in the history
+    pub minor: bool,
+    /// Salted hash of the client IP for edits made without login
+    #[serde(skip)]
+    pub anonymous_id: Option<String>,
+}
+
+/// Edit made without login, together with the identifier of the anonymous contributor. Edits
+/// with the same identifier were made by the same client. Only visible to moderators.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AnonymousEdit {
+    pub edit: Edit,
+    pub anonymous_id: String,
+}
+
+impl Edit {
+    /// Size of the change, computed from the diff
+    pub fn diff_stats(&self) -> DiffStats {
+        DiffStats::from_diff(&self.diff)
+    }
+}
+
+/// Number of lines which were added and removed by an edit
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DiffStats {
+    pub added: i32,
+    pub removed: i32,
+}
+
+impl DiffStats {
+    /// Count changed lines in a unified diff. Invalid diffs are counted as empty.
+    pub fn from_diff(diff: &str) -> Self {
+        let mut stats = DiffStats::default();
+        let Ok(patch) = diffy::Patch::from_str(diff) else {
+            return stats;
+        };
+        for line in patch.hunks().iter().flat_map(|h| h.lines()) {
+            match line {
+                diffy::Line::Insert(_) => stats.added += 1,
+                diffy::Line::Delete(_) => stats.removed += 1,
+                diffy::Line::Context(_) => {}
+            }
+        }
+        stats
+    }
+}
+
+/// Article text rendered to html, without saving it
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ArticlePreview {
+    pub html: String,
+    /// Titles of local articles which are linked from the text but don't exist
+    pub missing_links: Vec<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -101,6 +423,34 @@ pub struct EditView {
     pub creator: Person,
 }
 
+/// User who made edits to an article, with the size of their contributions.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Contributor {
+    pub person: Person,
+    pub edits: i64,
+    /// Total change in article length caused by the user's edits, negative if they removed more
+    /// than they added
+    pub byte_delta: i64,
+}
+
+/// Complete article with its edit history, for backups and for moving articles between
+/// instances.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ExportArticle {
+    pub article: Article,
+    /// All edits in the order they were made
+    pub edits: Vec<ExportEdit>,
+    /// Users who made at least one of the edits
+    pub contributors: Vec<Person>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ExportEdit {
+    pub edit: Edit,
+    /// Federation id of the user who made the edit
+    pub creator: DbUrl,
+}
+
 /// The version hash of a specific edit. Generated by taking an SHA256 hash of the diff
 /// and using the first 16 bytes so that it fits into UUID.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -141,11 +491,78 @@ pub struct ApiConflict {
     pub published: DateTime<Utc>,
 }
 
-pub fn can_edit_article(article: &Article, is_admin: bool) -> Result<(), anyhow::Error> {
-    if article.protected && !(article.local && is_admin) {
-        return Err(anyhow!(
-            "Article is protected, only admins on origin instance can edit".to_string()
-        ));
+/// Number of edit conflicts for a single article within a time window
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ConflictStats {
+    pub article: Article,
+    pub total: i64,
+    /// Conflicts which were resolved by editing again
+    pub resolved: i64,
+    /// Conflicts which were dismissed, given up after too many attempts or purged as stale
+    pub abandoned: i64,
+    /// Conflicts which are still waiting to be resolved
+    pub pending: i64,
+    pub last_conflict: DateTime<Utc>,
+}
+
+/// Check if a user with the given role, ban status, registration time and email status can edit
+/// the article. Moderators are exempt from the account age requirement of
+/// [ProtectionLevel::AutoConfirmed], and from email verification.
+pub fn can_edit_article(
+    article: &Article,
+    role: UserRole,
+    banned: bool,
+    registered: DateTime<Utc>,
+    email_verified: bool,
+    options: &Options,
+) -> Result<(), anyhow::Error> {
+    if banned {
+        return Err(anyhow!("You are banned from editing articles"));
+    }
+    if !role.can_edit() {
+        return Err(anyhow!("Readers can not edit articles"));
+    }
+    let require_verified = options.email_required || options.edit_requires_verified_email;
+    if require_verified && !email_verified && !role.can_moderate() {
+        return Err(anyhow!("Verify your email address to edit articles"));
+    }
+    let auto_confirm_days = options.auto_confirm_days;
+    match article.protection {
+        ProtectionLevel::Unprotected => {}
+        ProtectionLevel::AutoConfirmed => {
+            let confirmed = registered + Duration::days(auto_confirm_days.into()) <= Utc::now();
+            if !confirmed && !role.can_moderate() {
+                return Err(anyhow!(
+                    "Article is protected, only accounts older than {auto_confirm_days} days can edit"
+                ));
+            }
+        }
+        ProtectionLevel::AdminOnly => {
+            if !(article.local && role.can_moderate()) {
+                return Err(anyhow!(
+                    "Article is protected, only moderators on origin instance can edit"
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Check if the article can be edited without login. This requires `allow_anonymous_edits`,
+/// and is only possible for unprotected articles on instances which don't require email
+/// verification for edits.
+pub fn can_edit_article_anonymous(
+    article: &Article,
+    options: &Options,
+) -> Result<(), anyhow::Error> {
+    if !options.allow_anonymous_edits {
+        return Err(anyhow!("Login required to edit articles"));
+    }
+    if options.email_required || options.edit_requires_verified_email {
+        return Err(anyhow!("Verify your email address to edit articles"));
+    }
+    if article.protection != ProtectionLevel::Unprotected {
+        return Err(anyhow!("Article is protected, login required to edit"));
     }
     Ok(())
 }
@@ -158,3 +575,182 @@ fn test_edit_versions() {
     let version = EditVersion::new("test");
     assert_eq!("9f86d081884c7d659a2feaa0c55ad015", version.hash());
 }
+
+#[test]
+fn test_diff_stats() {
+    let old = "first line\nsecond line\nthird line\n";
+    let new = "first line\nsecond line changed\nthird line\nfourth line\n-- fifth line\n";
+    let diff = diffy::create_patch(old, new).to_string();
+    assert_eq!(
+        DiffStats {
+            added: 3,
+            removed: 1
+        },
+        DiffStats::from_diff(&diff)
+    );
+
+    let diff = diffy::create_patch(new, "").to_string();
+    assert_eq!(
+        DiffStats {
+            added: 0,
+            removed: 5
+        },
+        DiffStats::from_diff(&diff)
+    );
+    assert_eq!(DiffStats::default(), DiffStats::from_diff("invalid"));
+}
+
+#[test]
+fn test_can_edit_article() {
+    let article = |protection, local| Article {
+        id: ArticleId(1),
+        title: "Test".to_string(),
+        text: String::new(),
+        ap_id: DbUrl(Box::new(
+            url::Url::parse("https://example.com/article/Test").expect("valid url"),
+        )),
+        instance_id: InstanceId(1),
+        local,
+        published: Utc::now(),
+        removed: false,
+        updated: Utc::now(),
+        pending: false,
+        forked_by: None,
+        redirect_to: None,
+        protection,
+        removed_at: None,
+        last_refreshed_at: Utc::now(),
+        signature: None,
+    };
+    let new_account = Utc::now();
+    let old_account = Utc::now() - Duration::days(10);
+    let options = Options {
+        auto_confirm_days: 4,
+        ..Default::default()
+    };
+    let can_edit = |protection, local, role, registered| {
+        can_edit_article(
+            &article(protection, local),
+            role,
+            false,
+            registered,
+            false,
+            &options,
+        )
+        .is_ok()
+    };
+
+    // banned users can't edit, regardless of role
+    assert!(
+        can_edit_article(
+            &article(ProtectionLevel::Unprotected, true),
+            UserRole::Admin,
+            true,
+            old_account,
+            true,
+            &options,
+        )
+        .is_err()
+    );
+
+    // readers can never edit
+    assert!(!can_edit(
+        ProtectionLevel::Unprotected,
+        true,
+        UserRole::Reader,
+        old_account
+    ));
+    assert!(can_edit(
+        ProtectionLevel::Unprotected,
+        true,
+        UserRole::Editor,
+        new_account
+    ));
+
+    assert!(!can_edit(
+        ProtectionLevel::AutoConfirmed,
+        true,
+        UserRole::Editor,
+        new_account
+    ));
+    assert!(can_edit(
+        ProtectionLevel::AutoConfirmed,
+        true,
+        UserRole::Editor,
+        old_account
+    ));
+    assert!(can_edit(
+        ProtectionLevel::AutoConfirmed,
+        false,
+        UserRole::Editor,
+        old_account
+    ));
+    assert!(can_edit(
+        ProtectionLevel::AutoConfirmed,
+        true,
+        UserRole::Moderator,
+        new_account
+    ));
+
+    assert!(!can_edit(
+        ProtectionLevel::AdminOnly,
+        true,
+        UserRole::Editor,
+        old_account
+    ));
+    assert!(can_edit(
+        ProtectionLevel::AdminOnly,
+        true,
+        UserRole::Moderator,
+        new_account
+    ));
+    assert!(can_edit(
+        ProtectionLevel::AdminOnly,
+        true,
+        UserRole::Admin,
+        new_account
+    ));
+    assert!(!can_edit(
+        ProtectionLevel::AdminOnly,
+        false,
+        UserRole::Admin,
+        old_account
+    ));
+
+    // unverified email only matters if the instance requires verification
+    let options = Options {
+        edit_requires_verified_email: true,
+        ..Default::default()
+    };
+    let unprotected = article(ProtectionLevel::Unprotected, true);
+    assert!(
+        can_edit_article(&unprotected, UserRole::Editor, old_account, false, &options).is_err()
+    );
+    assert!(can_edit_article(&unprotected, UserRole::Editor, old_account, true, &options).is_ok());
+    assert!(
+        can_edit_article(
+            &unprotected,
+            UserRole::Moderator,
+            old_account,
+            false,
+            &options
+        )
+        .is_ok()
+    );
+
+    // anonymous edits only for unprotected articles, and only if enabled
+    let protected = article(ProtectionLevel::AutoConfirmed, true);
+    assert!(can_edit_article_anonymous(&unprotected, &Options::default()).is_err());
+    let options = Options {
+        allow_anonymous_edits: true,
+        ..Default::default()
+    };
+    assert!(can_edit_article_anonymous(&unprotected, &options).is_ok());
+    assert!(can_edit_article_anonymous(&protected, &options).is_err());
+    let options = Options {
+        allow_anonymous_edits: true,
+        edit_requires_verified_email: true,
+        ..Default::default()
+    };
+    assert!(can_edit_article_anonymous(&unprotected, &options).is_err());
+}
@@ -1,6 +1,6 @@
 use super::{
-    article::Article,
-    newtypes::InstanceId,
+    article::{Article, ArticleSort},
+    newtypes::{InboundActivityId, InstanceId, QueuedDeliveryId},
     user::{LocalUserView, Person},
 };
 use crate::DbUrl;
@@ -13,7 +13,14 @@ use {
     crate::config::OAuthProvider,
     diesel::{Identifiable, Queryable, Selectable},
     doku::Document,
-    ibis_database_schema::instance,
+    ibis_database_schema::{
+        allowed_instance,
+        blocked_instance,
+        delivery_queue,
+        delivery_status,
+        inbound_activity,
+        instance,
+    },
 };
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -35,6 +42,11 @@ pub struct Instance {
     #[cfg(feature = "ssr")]
     pub instances_url: DbUrl,
     pub name: Option<String>,
+    /// Set by admin to open or close registration, overriding the config option. Only used for
+    /// the local instance.
+    pub registration_open: Option<bool>,
+    /// Image shown in the header instead of the default logo
+    pub icon_url: Option<DbUrl>,
 }
 
 impl Instance {
@@ -49,6 +61,22 @@ pub struct InstanceView {
     pub instance: Instance,
     pub articles: Vec<Article>,
     pub following: bool,
+    /// Number of users who follow this instance
+    pub follower_count: i64,
+    /// Number of instances which are followed by users of this instance
+    pub following_count: i64,
+    /// Sort order used for article listings of this server if the request doesn't specify one
+    pub default_sort: ArticleSort,
+    /// Page size used for article listings of this server if the request doesn't specify one
+    pub default_page_size: u32,
+}
+
+/// One page of the users following an instance
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct InstanceFollowers {
+    pub followers: Vec<Person>,
+    /// Number of followers across all pages
+    pub total: i64,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -58,13 +86,104 @@ pub struct InstanceFollow {
     pub pending: bool,
 }
 
+/// Result of the most recent activity deliveries to a remote instance.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable))]
+#[cfg_attr(feature = "ssr", diesel(table_name = delivery_status, check_for_backend(diesel::pg::Pg)))]
+pub struct DeliveryStatus {
+    pub instance_id: InstanceId,
+    pub last_success: Option<DateTime<Utc>>,
+    pub last_failure: Option<DateTime<Utc>>,
+    /// Number of failed deliveries since the last successful one
+    pub failures: i32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable))]
+pub struct PeerDeliveryStatus {
+    pub instance: Instance,
+    /// Empty if no activity was ever sent to this instance
+    pub delivery: Option<DeliveryStatus>,
+}
+
+/// Remote domain which was blocked by an admin. No activities are sent to or accepted from it.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable))]
+#[cfg_attr(feature = "ssr", diesel(table_name = blocked_instance, check_for_backend(diesel::pg::Pg)))]
+pub struct BlockedInstance {
+    pub domain: String,
+    pub published: DateTime<Utc>,
+}
+
+/// Remote domain which an admin approved for federation, used with [FederationMode::AllowList].
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable))]
+#[cfg_attr(feature = "ssr", diesel(table_name = allowed_instance, check_for_backend(diesel::pg::Pg)))]
+pub struct AllowedInstance {
+    pub domain: String,
+    pub published: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct FederationStats {
+    /// Number of remote users following the local instance
+    pub followers: i64,
+    /// Number of remote instances followed by local users
+    pub following: i64,
+    /// Activity deliveries which failed temporarily and are waiting for retry
+    pub queued_activities: i64,
+    /// Activity deliveries which failed after all retries
+    pub dead_deliveries: i64,
+    /// Failed deliveries across all instances since their last successful delivery
+    pub failed_deliveries: i64,
+    pub peers: Vec<PeerDeliveryStatus>,
+}
+
+/// Incoming activity which failed to be processed. It is retried later, and kept as dead letter
+/// after all retries failed.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "ssr", diesel(table_name = inbound_activity, check_for_backend(diesel::pg::Pg)))]
+pub struct InboundActivity {
+    pub id: InboundActivityId,
+    pub ap_id: DbUrl,
+    pub json: String,
+    /// Error from the last processing attempt
+    pub error: String,
+    pub attempts: i32,
+    pub next_retry: DateTime<Utc>,
+    pub dead: bool,
+    pub published: DateTime<Utc>,
+}
+
+/// Outgoing activity which couldn't be delivered to an inbox, for example because the remote
+/// instance was down. It is retried later, and marked as dead after all retries failed.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "ssr", diesel(table_name = delivery_queue, check_for_backend(diesel::pg::Pg)))]
+pub struct QueuedDelivery {
+    pub id: QueuedDeliveryId,
+    pub activity_id: DbUrl,
+    /// Local user or instance which signs the activity
+    pub actor_id: DbUrl,
+    pub inbox: DbUrl,
+    pub json: String,
+    /// Error from the last delivery attempt
+    pub error: String,
+    pub attempts: i32,
+    pub next_retry: DateTime<Utc>,
+    pub dead: bool,
+    pub published: DateTime<Utc>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, SmartDefault)]
 #[serde(default)]
 #[serde(deny_unknown_fields)]
 #[cfg_attr(feature = "ssr", derive(Queryable, Document))]
 #[cfg_attr(feature = "ssr", diesel(check_for_backend(diesel::pg::Pg)))]
 pub struct Options {
-    /// Whether users can create new accounts
+    /// Whether users can create new accounts. Can be changed by admin in the settings, which takes
+    /// precedence.
     #[default = true]
     #[cfg_attr(feature = "ssr", doku(example = "true"))]
     pub registration_open: bool,
@@ -72,6 +191,131 @@ pub struct Options {
     #[default = false]
     #[cfg_attr(feature = "ssr", doku(example = "false"))]
     pub email_required: bool,
+    /// Whether users have to verify their email address before they can edit articles. This is
+    /// always the case if `email_required` is set. Moderators are exempt.
+    #[default = false]
+    #[cfg_attr(feature = "ssr", doku(example = "false"))]
+    pub edit_requires_verified_email: bool,
+    /// Allow editing unprotected articles without logging in. Such edits are attributed to a
+    /// shared `anonymous` user, and a salted hash of the client IP is stored with each edit.
+    #[default = false]
+    #[cfg_attr(feature = "ssr", doku(example = "false"))]
+    pub allow_anonymous_edits: bool,
+    /// Minutes after registration before a new account can create articles. Editing existing
+    /// articles is still possible. Admins are exempt.
+    #[default = 0]
+    #[cfg_attr(feature = "ssr", doku(example = "0"))]
+    pub new_account_cooldown_minutes: u32,
+    /// Days after registration before an account can edit articles with protection level
+    /// `AutoConfirmed`. Moderators are exempt.
+    #[default = 4]
+    #[cfg_attr(feature = "ssr", doku(example = "4"))]
+    pub auto_confirm_days: u32,
+    /// Whether to count how often each article is read. Can be disabled for privacy.
+    #[default = true]
+    #[cfg_attr(feature = "ssr", doku(example = "true"))]
+    pub article_view_count: bool,
+    /// How often the user can try to resolve an edit conflict, if the resolution results in
+    /// another conflict. Afterwards the edit is rejected and needs to be started from scratch.
+    #[default = 3]
+    #[cfg_attr(feature = "ssr", doku(example = "3"))]
+    pub max_conflict_resolution_attempts: u32,
+    /// Whether to render LaTeX math equations like `$E=mc^2$` in articles
+    #[default = true]
+    #[cfg_attr(feature = "ssr", doku(example = "true"))]
+    pub math_rendering: bool,
+    /// Insert line break hints into words longer than this many characters, so that long URLs
+    /// or chemical names can wrap on narrow screens. Set to 0 to disable.
+    #[default = 30]
+    #[cfg_attr(feature = "ssr", doku(example = "30"))]
+    pub break_hint_length: u32,
+    /// Maximum number of articles to list when a title without domain matches articles on
+    /// multiple instances
+    #[default = 20]
+    #[cfg_attr(feature = "ssr", doku(example = "20"))]
+    pub max_disambiguation_candidates: u32,
+    /// Whether to publish user, article and comment counts via nodeinfo. Can be disabled for
+    /// privacy.
+    #[default = true]
+    #[cfg_attr(feature = "ssr", doku(example = "true"))]
+    pub nodeinfo_usage_stats: bool,
+    /// Whether other instances can follow this instance to receive its articles. If disabled,
+    /// follow requests are rejected.
+    #[default = true]
+    #[cfg_attr(feature = "ssr", doku(example = "true"))]
+    pub accept_follows: bool,
+    /// How often to retry processing of an incoming activity which failed, for example because a
+    /// referenced object couldn't be fetched. Afterwards it is kept as dead letter for admins.
+    #[default = 5]
+    #[cfg_attr(feature = "ssr", doku(example = "5"))]
+    pub inbound_activity_retries: u32,
+    /// How often to retry delivery of an outgoing activity to an inbox which failed, for example
+    /// because the remote instance is down. Afterwards the delivery is marked as dead.
+    #[default = 5]
+    #[cfg_attr(feature = "ssr", doku(example = "5"))]
+    pub delivery_retries: u32,
+    /// Days after which unresolved edit conflicts are deleted, counted from the last time the
+    /// user opened them for resolution. Set to 0 to keep them forever.
+    #[default = 30]
+    #[cfg_attr(feature = "ssr", doku(example = "30"))]
+    pub conflict_retention_days: u32,
+    /// Sort order for article listings if the request doesn't specify one. Either `Updated` or
+    /// `MostViewed`.
+    #[default(ArticleSort::Updated)]
+    #[cfg_attr(feature = "ssr", doku(example = "Updated"))]
+    pub default_article_sort: ArticleSort,
+    /// Number of articles per page for article listings if the request doesn't specify one
+    #[default = 50]
+    #[cfg_attr(feature = "ssr", doku(example = "50"))]
+    pub default_page_size: u32,
+    /// Maximum number of article edits which a user with verified email may make within
+    /// `edit_rate_limit_window`. Admins and moderators are exempt. Set to 0 to disable.
+    #[default = 30]
+    #[cfg_attr(feature = "ssr", doku(example = "30"))]
+    pub edit_rate_limit: u32,
+    /// Stricter edit limit for users who haven't verified their email. Set to 0 to disable.
+    #[default = 10]
+    #[cfg_attr(feature = "ssr", doku(example = "10"))]
+    pub edit_rate_limit_unverified: u32,
+    /// Length in seconds of the sliding window for edit rate limits
+    #[default = 60]
+    #[cfg_attr(feature = "ssr", doku(example = "60"))]
+    pub edit_rate_limit_window: u32,
+    /// Which remote instances to federate with. Either `Open`, `AllowList` or `Closed`.
+    #[default(FederationMode::Open)]
+    #[cfg_attr(feature = "ssr", doku(example = "Open"))]
+    pub federation_mode: FederationMode,
+    /// Maximum size of article text in bytes. Larger local edits are rejected, and larger
+    /// federated edits are dropped. Set to 0 to disable.
+    #[default = 1_000_000]
+    #[cfg_attr(feature = "ssr", doku(example = "1000000"))]
+    pub max_article_bytes: u32,
+    /// Seconds after which a stored remote user or instance is fetched again when it is read,
+    /// so that changes such as key rotations are picked up. Set to 0 to disable.
+    #[default = 86400]
+    #[cfg_attr(feature = "ssr", doku(example = "86400"))]
+    pub remote_actor_refresh_seconds: u32,
+    /// Reject edits without summary. Summaries are limited to 500 characters either way.
+    #[default = true]
+    #[cfg_attr(feature = "ssr", doku(example = "true"))]
+    pub require_edit_summary: bool,
+    /// Minimum number of characters for new passwords. Passwords also need to contain at least
+    /// one letter and one digit or symbol.
+    #[default = 8]
+    #[cfg_attr(feature = "ssr", doku(example = "8"))]
+    pub min_password_length: u32,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "ssr", derive(Document))]
+pub enum FederationMode {
+    /// Federate with all instances except blocked ones
+    #[default]
+    Open,
+    /// Only federate with instances which were explicitly allowed by an admin
+    AllowList,
+    /// Reject all interaction with remote instances
+    Closed,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -0,0 +1,48 @@
+use std::fmt::{Display, Formatter};
+
+/// Text of a failed merge, containing git-style conflict markers around the overlapping changes
+/// which need to be resolved manually.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConflictMarkers(pub String);
+
+impl Display for ConflictMarkers {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Combine the changes from `ours` and `theirs` which were both made on top of `base`. Returns the
+/// merged text if the changes don't overlap.
+pub fn three_way_merge(base: &str, ours: &str, theirs: &str) -> Result<String, ConflictMarkers> {
+    diffy::merge(base, ours, theirs).map_err(ConflictMarkers)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_three_way_merge_clean() {
+        let base = "first line\nsecond line\nthird line\n";
+        let ours = "first line changed\nsecond line\nthird line\n";
+        let theirs = "first line\nsecond line\nthird line changed\n";
+        assert_eq!(
+            Ok("first line changed\nsecond line\nthird line changed\n".to_string()),
+            three_way_merge(base, ours, theirs)
+        );
+    }
+
+    #[test]
+    fn test_three_way_merge_conflict() {
+        let base = "some example text\n";
+        let ours = "Ipsum Lorem\n";
+        let theirs = "Lorem Ipsum\n";
+        assert_eq!(
+            Err(ConflictMarkers(
+                "<<<<<<< ours\nIpsum Lorem\n||||||| original\nsome example text\n=======\nLorem Ipsum\n>>>>>>> theirs\n"
+                    .to_string()
+            )),
+            three_way_merge(base, ours, theirs)
+        );
+    }
+}
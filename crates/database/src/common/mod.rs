@@ -1,6 +1,8 @@
 pub mod article;
 pub mod comment;
 pub mod instance;
+#[cfg(feature = "ssr")]
+pub mod merge;
 pub mod newtypes;
 pub mod notifications;
 pub mod user;
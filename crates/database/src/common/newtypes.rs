@@ -33,3 +33,27 @@ pub struct CommentId(pub i32);
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default, Serialize, Deserialize)]
 #[cfg_attr(feature = "ssr", derive(DieselNewType))]
 pub struct NotificationId(pub i32);
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(DieselNewType))]
+pub struct AttachmentId(pub i32);
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(DieselNewType))]
+pub struct InboundActivityId(pub i32);
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(DieselNewType))]
+pub struct QueuedDeliveryId(pub i32);
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(DieselNewType))]
+pub struct ArticleMoveId(pub i32);
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(DieselNewType))]
+pub struct CategoryId(pub i32);
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(DieselNewType))]
+pub struct RevisionTagId(pub i32);
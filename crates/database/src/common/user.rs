@@ -26,10 +26,69 @@ pub struct LocalUser {
     #[serde(skip)]
     pub password_encrypted: Option<String>,
     pub person_id: PersonId,
-    pub admin: bool,
     pub email: Option<String>,
     pub email_verified: bool,
     pub email_notifications: bool,
+    pub published: DateTime<Utc>,
+    pub role: UserRole,
+}
+
+impl LocalUser {
+    pub fn is_admin(&self) -> bool {
+        self.role == UserRole::Admin
+    }
+}
+
+/// Permissions of a local user. Each role includes the permissions of the roles below it.
+#[cfg_attr(
+    feature = "ssr",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow)
+)]
+#[cfg_attr(feature = "ssr", diesel(sql_type = diesel::sql_types::Text))]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum UserRole {
+    /// Can change instance settings and assign roles
+    Admin,
+    /// Can protect and remove articles
+    Moderator,
+    /// Can create and edit articles
+    #[default]
+    Editor,
+    /// Can only read articles and write comments
+    Reader,
+}
+
+impl UserRole {
+    pub fn can_moderate(&self) -> bool {
+        matches!(self, UserRole::Admin | UserRole::Moderator)
+    }
+
+    pub fn can_edit(&self) -> bool {
+        *self != UserRole::Reader
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserRole::Admin => "Admin",
+            UserRole::Moderator => "Moderator",
+            UserRole::Editor => "Editor",
+            UserRole::Reader => "Reader",
+        }
+    }
+}
+
+impl std::str::FromStr for UserRole {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "Admin" => UserRole::Admin,
+            "Moderator" => UserRole::Moderator,
+            "Editor" => UserRole::Editor,
+            "Reader" => UserRole::Reader,
+            _ => return Err(format!("Unknown user role {s}")),
+        })
+    }
 }
 
 /// Federation related data from a local or remote user.
@@ -50,9 +109,19 @@ pub struct Person {
     pub local: bool,
     pub display_name: Option<String>,
     pub bio: Option<String>,
+    /// Banned users can't edit articles
+    pub banned: bool,
+    /// Time when the ban ends, or `None` for a permanent ban
+    pub ban_expires: Option<DateTime<Utc>>,
 }
 
 impl Person {
+    /// Whether the user is currently banned. Temporary bans end automatically after
+    /// `ban_expires`.
+    pub fn is_banned(&self) -> bool {
+        self.banned && self.ban_expires.is_none_or(|e| e > Utc::now())
+    }
+
     pub fn inbox_url(&self) -> Url {
         Url::parse(&self.inbox_url).expect("can parse inbox url")
     }
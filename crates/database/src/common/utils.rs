@@ -15,3 +15,14 @@ pub fn http_protocol_str() -> &'static str {
         "https"
     }
 }
+
+/// Replace underscores from urls with spaces, trim and collapse repeated whitespace. All title
+/// variants which refer to the same article, like `Main_Page` and ` Main  Page` for
+/// `MAIN_PAGE_NAME`, are normalized to the stored form. Lookups by title additionally ignore case.
+pub fn normalize_article_title(title: &str) -> String {
+    title
+        .replace('_', " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -16,12 +16,34 @@ pub struct IbisConfig {
     pub setup: IbisConfigSetup,
     /// Domain for HTTP and frontend
     pub domain: String,
+    /// Content-Security-Policy header for html pages. `{nonce}` is replaced with a random value
+    /// for each request, which is used for the hydration scripts. Set to empty string to disable.
+    #[default(DEFAULT_CONTENT_SECURITY_POLICY.to_string())]
+    #[doku(
+        example = "default-src 'self'; script-src 'self' 'nonce-{nonce}' 'wasm-unsafe-eval'; \
+        style-src 'self' 'unsafe-inline'; img-src 'self' data: https:; media-src 'self' https:; \
+        object-src 'none'; base-uri 'self'; form-action 'self'; frame-ancestors 'none'"
+    )]
+    pub content_security_policy: String,
+    /// Comma separated addresses of reverse proxies in front of ibis. For requests from these
+    /// addresses, the client address is read from the `X-Forwarded-For` header. It is used to
+    /// identify anonymous editors and to count article views.
+    #[default(None)]
+    #[doku(example = "127.0.0.1,::1")]
+    pub trusted_proxies: Option<String>,
     pub federation: IbisConfigFederation,
     pub options: Options,
     pub email: Option<IbisConfigEmail>,
     pub oauth_providers: Vec<OAuthProvider>,
 }
 
+/// Only allows scripts from the same origin and with the request nonce. Inline styles are
+/// allowed for rendered math equations, and external https images for article attachments.
+const DEFAULT_CONTENT_SECURITY_POLICY: &str = "default-src 'self'; \
+    script-src 'self' 'nonce-{nonce}' 'wasm-unsafe-eval'; style-src 'self' 'unsafe-inline'; \
+    img-src 'self' data: https:; media-src 'self' https:; object-src 'none'; base-uri 'self'; \
+    form-action 'self'; frame-ancestors 'none'";
+
 impl IbisConfig {
     pub fn read() -> BackendResult<Self> {
         let config_file = if cfg!(test) {
@@ -100,7 +122,8 @@ pub struct IbisConfigFederation {
     #[doku(example = "example.com")]
     pub domain: String,
     /// Comma separated list of instances which are allowed for federation. If set, federation
-    /// with other domains is blocked
+    /// with other domains is blocked, the same as with `federation_mode = "AllowList"`. Instances
+    /// which are allowed by admins are accepted as well.
     #[default(None)]
     #[doku(example = "good.com,friends.org")]
     pub allowlist: Option<String>,
@@ -52,7 +52,12 @@ pub(crate) async fn send_notification_email(
                 // website directly after user action
                 continue;
             } else {
-                let article_text = render_article_markdown(&data.article.text);
+                let article_text = render_article_markdown(
+                    &data.article.text,
+                    context.conf.options.math_rendering,
+                    context.conf.options.break_hint_length,
+                    false,
+                );
                 (
                     format!("New article {article_title}"),
                     format!(
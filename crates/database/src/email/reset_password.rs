@@ -63,11 +63,17 @@ impl PasswordResetRequest {
         Ok(())
     }
 
+    /// Consumes the given reset token. Tokens are only valid for one hour, and any other
+    /// pending tokens of the same user are invalidated as well.
     pub fn read_and_delete(token_: &str, context: &IbisContext) -> BackendResult<Self> {
         let mut conn = context.db_pool.get()?;
-        Ok(delete(password_reset_request::table)
+        let request: Self = delete(password_reset_request::table)
             .filter(password_reset_request::token.eq(token_))
-            .filter(password_reset_request::published.gt(now.into_sql::<Timestamptz>() - 1.days()))
-            .get_result(&mut conn)?)
+            .filter(password_reset_request::published.gt(now.into_sql::<Timestamptz>() - 1.hours()))
+            .get_result(&mut conn)?;
+        delete(password_reset_request::table)
+            .filter(password_reset_request::local_user_id.eq(request.local_user_id))
+            .execute(&mut conn)?;
+        Ok(request)
     }
 }
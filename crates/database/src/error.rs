@@ -11,6 +11,16 @@ impl Display for BackendError {
     }
 }
 
+impl BackendError {
+    /// Replace an error caused by a missing database row with [NotFound], keeping other errors
+    pub fn or_not_found(self, message: impl Into<String>) -> Self {
+        match self.0.downcast_ref::<diesel::result::Error>() {
+            Some(diesel::result::Error::NotFound) => NotFound(message.into()).into(),
+            _ => self,
+        }
+    }
+}
+
 impl<T> From<T> for BackendError
 where
     T: Into<anyhow::Error>,
@@ -23,10 +33,24 @@ where
 #[cfg(feature = "ssr")]
 impl axum::response::IntoResponse for BackendError {
     fn into_response(self) -> axum::response::Response {
-        (
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-            format!("{}", self.0),
-        )
-            .into_response()
+        let status = if self.0.is::<NotFound>() {
+            axum::http::StatusCode::NOT_FOUND
+        } else {
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        };
+        (status, format!("{}", self.0)).into_response()
+    }
+}
+
+/// The requested object doesn't exist. It is returned with status `404 Not Found` so that clients
+/// can tell it apart from other errors, for example to offer creating a missing article.
+#[derive(Debug)]
+pub struct NotFound(pub String);
+
+impl Display for NotFound {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
     }
 }
+
+impl std::error::Error for NotFound {}
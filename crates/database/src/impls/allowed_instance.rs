@@ -0,0 +1,81 @@
+use super::blocked_instance::{cache_contains, clear_cache};
+use crate::{common::instance::AllowedInstance, error::BackendResult, impls::IbisContext};
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl, delete, insert_into};
+use ibis_database_schema::allowed_instance;
+use std::ops::DerefMut;
+
+impl AllowedInstance {
+    pub fn allow(domain: &str, context: &IbisContext) -> BackendResult<()> {
+        let domain = domain.trim().to_lowercase();
+        let mut conn = context.db_pool.get()?;
+        insert_into(allowed_instance::table)
+            .values(allowed_instance::domain.eq(&domain))
+            .on_conflict_do_nothing()
+            .execute(conn.deref_mut())?;
+        clear_cache(&context.allowlist);
+        Ok(())
+    }
+
+    pub fn disallow(domain: &str, context: &IbisContext) -> BackendResult<()> {
+        let domain = domain.trim().to_lowercase();
+        let mut conn = context.db_pool.get()?;
+        delete(allowed_instance::table.find(&domain)).execute(conn.deref_mut())?;
+        clear_cache(&context.allowlist);
+        Ok(())
+    }
+
+    pub fn list(context: &IbisContext) -> BackendResult<Vec<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(allowed_instance::table
+            .order_by(allowed_instance::domain)
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Domains from the `allowlist` in the config file are allowed as well, so that there is only
+    /// a single allowlist which is used for all federation modes.
+    pub fn is_allowed(domain: &str, context: &IbisContext) -> BackendResult<bool> {
+        cache_contains(&context.allowlist, domain, || {
+            let config = context
+                .conf
+                .federation
+                .allowlist
+                .iter()
+                .flat_map(|a| a.split(','))
+                .map(|d| d.trim().to_lowercase());
+            Ok(Self::list(context)?
+                .into_iter()
+                .map(|a| a.domain)
+                .chain(config)
+                .collect())
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::IbisConfig;
+
+    #[test]
+    fn test_allowlist_includes_config() -> BackendResult<()> {
+        let mut context = IbisContext::init(IbisConfig::read()?, false)?;
+        context.conf.federation.allowlist = Some("config.example, Other.example".to_string());
+        AllowedInstance::allow("admin-allowed.example", &context)?;
+
+        // domains from the config file and those allowed by admins are in the same list
+        assert!(AllowedInstance::is_allowed("config.example", &context)?);
+        assert!(AllowedInstance::is_allowed("other.example", &context)?);
+        assert!(AllowedInstance::is_allowed(
+            "admin-allowed.example",
+            &context
+        )?);
+        assert!(!AllowedInstance::is_allowed("unknown.example", &context)?);
+
+        AllowedInstance::disallow("admin-allowed.example", &context)?;
+        assert!(!AllowedInstance::is_allowed(
+            "admin-allowed.example",
+            &context
+        )?);
+        Ok(())
+    }
+}
@@ -1,15 +1,31 @@
-use super::{is_conflict, notifications::Notification};
+use super::{DbPool, coalesce, is_conflict, lower, notifications::Notification};
 use crate::{
     DbUrl,
     common::{
-        article::{Article, ArticleView, EditVersion},
+        article::{
+            Article,
+            ArticleMove,
+            ArticleSort,
+            ArticleView,
+            Attachment,
+            Category,
+            EditVersion,
+            ProtectionLevel,
+            SEARCH_HIGHLIGHT_END,
+            SEARCH_HIGHLIGHT_START,
+            SearchMode,
+            SearchResult,
+        },
         comment::Comment,
-        newtypes::{ArticleId, InstanceId, PersonId},
-        user::LocalUserView,
+        instance::Instance,
+        newtypes::{ArticleId, InstanceId, LocalUserId, PersonId},
+        user::{LocalUserView, Person},
+        utils::{extract_domain, normalize_article_title},
     },
     error::BackendResult,
     impls::IbisContext,
 };
+use anyhow::anyhow;
 use chrono::{DateTime, Utc};
 use diesel::{
     AsChangeset,
@@ -18,14 +34,26 @@ use diesel::{
     Insertable,
     JoinOnDsl,
     NullableExpressionMethods,
+    PgConnection,
+    PgSortExpressionMethods,
     PgTextExpressionMethods,
     QueryDsl,
     RunQueryDsl,
-    dsl::{delete, max, not, now, update},
+    dsl::{count_distinct, delete, exists, max, not, now, sql, update},
     insert_into,
+    sql_types::{Bool, Float, Text},
 };
-use ibis_database_schema::{article, article_follow, edit, instance};
-use std::ops::DerefMut;
+use ibis_database_schema::{
+    article,
+    article_follow,
+    article_subscriber,
+    article_views,
+    edit,
+    instance,
+    person,
+};
+use ibis_markdown::extract_article_links;
+use std::{collections::hash_map::Entry, ops::DerefMut};
 use url::Url;
 
 #[derive(Debug, Clone, Insertable, AsChangeset)]
@@ -36,9 +64,10 @@ pub struct DbArticleForm {
     pub ap_id: DbUrl,
     pub instance_id: InstanceId,
     pub local: bool,
-    pub protected: bool,
+    pub protection: ProtectionLevel,
     pub updated: DateTime<Utc>,
     pub pending: bool,
+    pub forked_by: Option<PersonId>,
 }
 
 #[derive(Debug)]
@@ -72,6 +101,7 @@ impl Article {
         let article = insert_into(article::table)
             .values(form)
             .get_result::<Self>(conn.deref_mut())?;
+        Category::update_for_article(article.id, &article.text, context)?;
 
         Notification::notify_article(&article, creator_id, context).await?;
         Ok(article)
@@ -86,33 +116,55 @@ impl Article {
         let article = insert_into(article::table)
             .values(&form)
             .get_result::<Self>(conn.deref_mut());
-        Ok(if is_conflict(&article) {
+        let article = if is_conflict(&article) {
             update(article::table)
                 .filter(article::ap_id.eq(form.ap_id.clone()))
-                .set(form)
+                .set((form, article::last_refreshed_at.eq(now)))
                 .get_result::<Self>(conn.deref_mut())?
         } else {
             let a = article?;
             Notification::notify_article(&a, creator_id, context).await?;
             a
-        })
+        };
+        Category::update_for_article(article.id, &article.text, context)?;
+        Ok(article)
     }
 
     pub fn update_text(id: ArticleId, text: &str, context: &IbisContext) -> BackendResult<Self> {
         let mut conn = context.db_pool.get()?;
-        Ok(diesel::update(article::dsl::article.find(id))
+        let article = diesel::update(article::dsl::article.find(id))
             .set((article::dsl::text.eq(text), article::dsl::updated.eq(now)))
+            .get_result(conn.deref_mut())?;
+        Category::update_for_article(id, text, context)?;
+        Ok(article)
+    }
+
+    pub fn update_title(id: ArticleId, title: &str, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(diesel::update(article::dsl::article.find(id))
+            .set(article::dsl::title.eq(title))
+            .get_result(conn.deref_mut())?)
+    }
+
+    pub fn update_protection(
+        id: ArticleId,
+        protection: ProtectionLevel,
+        context: &IbisContext,
+    ) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(diesel::update(article::dsl::article.find(id))
+            .set(article::dsl::protection.eq(protection))
             .get_result(conn.deref_mut())?)
     }
 
-    pub fn update_protected(
+    pub fn update_signature(
         id: ArticleId,
-        locked: bool,
+        signature: &str,
         context: &IbisContext,
     ) -> BackendResult<Self> {
         let mut conn = context.db_pool.get()?;
         Ok(diesel::update(article::dsl::article.find(id))
-            .set(article::dsl::protected.eq(locked))
+            .set(article::dsl::signature.eq(signature))
             .get_result(conn.deref_mut())?)
     }
 
@@ -123,7 +175,22 @@ impl Article {
     ) -> BackendResult<Self> {
         let mut conn = context.db_pool.get()?;
         Ok(diesel::update(article::dsl::article.find(id))
-            .set(article::dsl::removed.eq(removed))
+            .set((
+                article::dsl::removed.eq(removed),
+                article::dsl::removed_at.eq(removed.then(Utc::now)),
+            ))
+            .get_result(conn.deref_mut())?)
+    }
+
+    /// Turn the article into a redirect to the target, or remove the redirect with `None`
+    pub fn update_redirect(
+        id: ArticleId,
+        redirect_to: Option<ArticleId>,
+        context: &IbisContext,
+    ) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(diesel::update(article::dsl::article.find(id))
+            .set(article::dsl::redirect_to.eq(redirect_to))
             .get_result(conn.deref_mut())?)
     }
 
@@ -149,14 +216,22 @@ impl Article {
                     .eq(article::id)
                     .and(article_follow::local_user_id.nullable().eq(local_user_id))),
             )
+            .left_join(article_views::table)
             .into_boxed();
-        if !user.map(|u| u.local_user.admin).unwrap_or_default() {
+        if !user
+            .map(|u| u.local_user.role.can_moderate())
+            .unwrap_or_default()
+        {
             query = query.filter(not(article::removed));
         }
         query = match params.into() {
             ArticleViewQuery::Id(id) => query.filter(article::id.eq(id)),
             ArticleViewQuery::Name(title, domain) => {
-                query = query.filter(article::dsl::title.eq(title));
+                // ignore case and title variants, but prefer an exact match if there are several
+                let title = normalize_article_title(title);
+                query = query
+                    .filter(lower(article::title).eq(title.to_lowercase()))
+                    .order_by(article::title.eq(title).desc());
                 if let Some(domain) = domain {
                     query.filter(instance::dsl::domain.eq(domain))
                 } else {
@@ -165,21 +240,32 @@ impl Article {
             }
         };
 
-        let (article, instance, following): (Article, _, _) = query
+        let (article, instance, following, view_count): (Article, _, _, _) = query
             .select((
                 article::all_columns,
                 instance::all_columns,
                 article_follow::local_user_id.nullable().is_not_null(),
+                coalesce(article_views::views.nullable(), 0i64),
             ))
             .get_result(conn.deref_mut())?;
         let comments = Comment::read_for_article(article.id, context)?;
         let latest_version = article.latest_edit_version(context)?;
+        let attachments = Attachment::list_for_article(article.id, context)?;
+        let moves = ArticleMove::list_for_article(article.id, context)?;
+        let missing_links = Article::read_missing_links(&article.text, context)?;
+        let categories = Category::list_for_article(article.id, context)?;
         Ok(ArticleView {
             article,
             instance,
             comments,
             latest_version,
             following,
+            view_count,
+            attachments,
+            redirected_from: None,
+            moves,
+            missing_links,
+            categories,
         })
     }
 
@@ -190,24 +276,38 @@ impl Article {
             .get_result(conn.deref_mut())?)
     }
 
-    /// Read all articles, ordered by most recently edited first.
+    /// Read all articles, ordered by edit time, creation time or number of views. Without
+    /// limit all matching articles are returned.
     ///
     /// TODO: Should get rid of only_local param and rely on instance_id
     pub fn read_all(
         only_local: Option<bool>,
         instance_id: Option<InstanceId>,
         include_removed: bool,
+        sort: ArticleSort,
+        limit: Option<i64>,
+        offset: i64,
         context: &IbisContext,
     ) -> BackendResult<Vec<Self>> {
         let mut conn = context.db_pool.get()?;
         let mut query = article::table
             .inner_join(edit::table)
             .inner_join(instance::table)
+            .left_join(article_views::table)
             .group_by(article::id)
-            .order_by(max(edit::published).desc())
             .select(article::all_columns)
             .into_boxed();
 
+        query = match sort {
+            ArticleSort::Updated => query.order_by(max(edit::published).desc()),
+            ArticleSort::MostViewed => query.order_by((
+                coalesce(max(article_views::views), 0i64).desc(),
+                max(edit::published).desc(),
+            )),
+            ArticleSort::Newest => query.order_by((article::published.desc(), article::id.desc())),
+            ArticleSort::Oldest => query.order_by((article::published.asc(), article::id.asc())),
+        };
+
         if let Some(true) = only_local {
             query = query.filter(article::local);
         }
@@ -219,32 +319,221 @@ impl Article {
         if let Some(instance_id) = instance_id {
             query = query.filter(instance::dsl::id.eq(instance_id));
         }
+        if let Some(limit) = limit {
+            query = query.limit(limit).offset(offset);
+        }
         Ok(query.get_results(&mut conn)?)
     }
 
-    pub fn search(query: &str, context: &IbisContext) -> BackendResult<Vec<Self>> {
+    /// Number of articles returned by [Article::read_all] with the same filters, across all pages
+    pub fn count_all(
+        only_local: Option<bool>,
+        instance_id: Option<InstanceId>,
+        include_removed: bool,
+        context: &IbisContext,
+    ) -> BackendResult<i64> {
+        let mut conn = context.db_pool.get()?;
+        let mut query = article::table
+            .inner_join(edit::table)
+            .inner_join(instance::table)
+            .select(count_distinct(article::id))
+            .into_boxed();
+        if let Some(true) = only_local {
+            query = query.filter(article::local);
+        }
+        if !include_removed {
+            query = query
+                .filter(article::removed.eq(false))
+                .filter(article::pending.eq(false));
+        }
+        if let Some(instance_id) = instance_id {
+            query = query.filter(instance::dsl::id.eq(instance_id));
+        }
+        Ok(query.get_result(&mut conn)?)
+    }
+
+    /// Search articles by title, and depending on the mode also by full text search on the body.
+    /// Title matches are listed first, followed by body matches ranked by relevance. Each result
+    /// includes a snippet of the text with highlighted matches.
+    ///
+    /// With `fuzzy`, if nothing is found the first page is filled with articles whose title is
+    /// similar to the query by trigram similarity, so that typos still find results.
+    pub fn search(
+        query: &str,
+        mode: SearchMode,
+        fuzzy: bool,
+        limit: i64,
+        offset: i64,
+        context: &IbisContext,
+    ) -> BackendResult<Vec<SearchResult>> {
         let mut conn = context.db_pool.get()?;
         let replaced = query
             .replace('%', "\\%")
             .replace('_', "\\_")
             .replace(' ', "%");
         let replaced = format!("%{replaced}%");
+        // Needs to be identical to the expression in the index `idx_article_text_search`
+        let text_vector = "to_tsvector('english', article.text)";
+        let text_match = sql::<Bool>(&format!("{text_vector} @@ plainto_tsquery('english', "))
+            .bind::<Text, _>(query.to_string())
+            .sql(")");
+        let rank = sql::<Float>(&format!(
+            "ts_rank({text_vector}, plainto_tsquery('english', "
+        ))
+        .bind::<Text, _>(query.to_string())
+        .sql("))");
+        let snippet = || {
+            sql::<Text>("ts_headline('english', article.text, plainto_tsquery('english', ")
+                .bind::<Text, _>(query.to_string())
+                .sql(&format!(
+                    "), 'StartSel={SEARCH_HIGHLIGHT_START}, StopSel={SEARCH_HIGHLIGHT_END}, \
+                    MinWords=10, MaxWords=30, MaxFragments=2')"
+                ))
+        };
+        let mut search = article::table
+            .select((article::all_columns, snippet()))
+            .filter(not(article::removed))
+            .filter(not(article::pending))
+            .order_by((
+                article::title.ilike(replaced.clone()).desc(),
+                rank.desc(),
+                article::id,
+            ))
+            .limit(limit)
+            .offset(offset)
+            .into_boxed();
+        search = match mode {
+            SearchMode::TitleOnly => search.filter(article::title.ilike(replaced)),
+            SearchMode::TitleAndBody => {
+                search.filter(article::title.ilike(replaced).or(text_match))
+            }
+        };
+        let results = search.get_results(conn.deref_mut())?;
+        if !results.is_empty() || !fuzzy || offset > 0 {
+            return Ok(results);
+        }
+
+        // Uses the index `idx_article_title_trgm`
+        let similar_title = sql::<Bool>("article.title % ").bind::<Text, _>(query.to_string());
+        let similarity = sql::<Float>("similarity(article.title, ")
+            .bind::<Text, _>(query.to_string())
+            .sql(")");
+        Ok(article::table
+            .select((article::all_columns, snippet()))
+            .filter(not(article::removed))
+            .filter(not(article::pending))
+            .filter(similar_title)
+            .order_by((similarity.desc(), article::id))
+            .limit(limit)
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// All articles with the given title from any instance, local articles first. Case and title
+    /// variants are ignored like in [Article::read_view].
+    pub fn read_by_title(
+        title: &str,
+        limit: i64,
+        context: &IbisContext,
+    ) -> BackendResult<Vec<(Self, Instance)>> {
+        let mut conn = context.db_pool.get()?;
+        let title = normalize_article_title(title).to_lowercase();
+        Ok(article::table
+            .inner_join(instance::table)
+            .filter(lower(article::title).eq(title))
+            .filter(not(article::removed))
+            .filter(not(article::pending))
+            .order_by((article::local.desc(), instance::domain))
+            .limit(limit)
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Removed articles, most recently removed first. Articles removed before the removal time
+    /// was recorded are listed last.
+    pub fn read_removed(context: &IbisContext) -> BackendResult<Vec<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(article::table
+            .filter(article::removed)
+            .order_by((article::removed_at.desc().nulls_last(), article::id.desc()))
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Articles which were originally created by the given person, meaning the first edit was
+    /// authored by them. For forked articles the creator of the fork counts as author instead.
+    pub fn read_created_by(
+        person_id: PersonId,
+        limit: i64,
+        offset: i64,
+        context: &IbisContext,
+    ) -> BackendResult<Vec<Self>> {
+        let mut conn = context.db_pool.get()?;
+        let first_edit_by_person = exists(
+            edit::table
+                .filter(edit::article_id.eq(article::id))
+                .filter(edit::previous_version_id.eq(EditVersion::default()))
+                .filter(edit::creator_id.eq(person_id)),
+        );
         Ok(article::table
             .filter(not(article::removed))
             .filter(not(article::pending))
             .filter(
-                article::dsl::title
-                    .ilike(&replaced)
-                    .or(article::dsl::text.ilike(&replaced)),
+                article::forked_by
+                    .eq(person_id)
+                    .or(article::forked_by.is_null().and(first_edit_by_person)),
             )
+            .order_by(article::published.desc())
+            .limit(limit)
+            .offset(offset)
             .get_results(conn.deref_mut())?)
     }
 
+    /// Count a view of the article. Views are collected in memory and only written to the
+    /// database by [Article::flush_views], so that reading an article doesn't need a database
+    /// write.
+    pub fn add_view(id: ArticleId, context: &IbisContext) -> BackendResult<()> {
+        let mut pending = context.pending_views.lock().map_err(|e| anyhow!("{e}"))?;
+        *pending.entry(id).or_default() += 1;
+        Ok(())
+    }
+
+    /// Write pending views to the database. Views are only removed from memory once they are
+    /// written, so if the database is unavailable they are kept for the next attempt.
+    pub fn flush_views(context: &IbisContext) -> BackendResult<()> {
+        let pending = context
+            .pending_views
+            .lock()
+            .map_err(|e| anyhow!("{e}"))?
+            .clone();
+        let mut conn = context.db_pool.get()?;
+        for (id, count) in pending {
+            Self::write_views(id, count, conn.deref_mut())?;
+            // views which were added in the meantime stay pending
+            let mut pending = context.pending_views.lock().map_err(|e| anyhow!("{e}"))?;
+            if let Entry::Occupied(mut entry) = pending.entry(id) {
+                *entry.get_mut() -= count;
+                if *entry.get() <= 0 {
+                    entry.remove();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn write_views(id: ArticleId, count: i64, conn: &mut PgConnection) -> BackendResult<()> {
+        use article_views::dsl::{article_id, views};
+        insert_into(article_views::table)
+            .values((article_id.eq(id), views.eq(count)))
+            .on_conflict(article_id)
+            .do_update()
+            .set(views.eq(views + count))
+            .execute(conn)?;
+        Ok(())
+    }
+
     pub fn latest_edit_version(&self, context: &IbisContext) -> BackendResult<EditVersion> {
         let mut conn = context.db_pool.get()?;
         let latest_version: Option<EditVersion> = edit::table
             .filter(edit::dsl::article_id.eq(self.id))
-            .order_by(edit::dsl::id.desc())
+            .order_by(edit::dsl::sequence.desc())
             .limit(1)
             .select(edit::dsl::hash)
             .get_result(conn.deref_mut())
@@ -268,6 +557,7 @@ impl Article {
         );
         insert_into(article_follow::table)
             .values(form)
+            .on_conflict_do_nothing()
             .execute(conn.deref_mut())?;
         Ok(())
     }
@@ -289,4 +579,211 @@ impl Article {
         .execute(conn.deref_mut())?;
         Ok(())
     }
+
+    /// Remote user who wants to receive federated updates for this local article, without
+    /// following the whole instance.
+    pub fn add_subscriber(
+        article_id: ArticleId,
+        person_id: PersonId,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        insert_into(article_subscriber::table)
+            .values((
+                article_subscriber::article_id.eq(article_id),
+                article_subscriber::person_id.eq(person_id),
+            ))
+            .on_conflict_do_nothing()
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    pub fn remove_subscriber(
+        article_id: ArticleId,
+        person_id: PersonId,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        delete(article_subscriber::table.find((article_id, person_id)))
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    pub fn read_subscribers(
+        article_id: ArticleId,
+        context: &IbisContext,
+    ) -> BackendResult<Vec<Person>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(article_subscriber::table
+            .inner_join(person::table)
+            .filter(article_subscriber::article_id.eq(article_id))
+            .select(person::all_columns)
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Articles followed by the user, most recently edited first
+    pub fn read_followed(
+        local_user_id: LocalUserId,
+        context: &IbisContext,
+    ) -> BackendResult<Vec<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(article::table
+            .inner_join(article_follow::table)
+            .inner_join(edit::table)
+            .filter(article_follow::local_user_id.eq(local_user_id))
+            .filter(not(article::removed))
+            .group_by(article::id)
+            .order_by(max(edit::published).desc())
+            .select(article::all_columns)
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Titles of local articles which are linked from the text but don't exist
+    pub fn read_missing_links(text: &str, context: &IbisContext) -> BackendResult<Vec<String>> {
+        let titles: Vec<String> = extract_article_links(text)
+            .into_iter()
+            .filter(|l| l.domain.is_none())
+            .map(|l| l.title)
+            .collect();
+        if titles.is_empty() {
+            return Ok(vec![]);
+        }
+        // links resolve case-insensitively, so compare in the same way as title lookups
+        let lookup: Vec<String> = titles
+            .iter()
+            .map(|t| normalize_article_title(t).to_lowercase())
+            .collect();
+        let mut conn = context.db_pool.get()?;
+        let existing: Vec<String> = article::table
+            .filter(article::local)
+            .filter(not(article::removed))
+            .filter(lower(article::title).eq_any(&lookup))
+            .select(lower(article::title))
+            .get_results(conn.deref_mut())?;
+        Ok(titles
+            .into_iter()
+            .zip(lookup)
+            .filter(|(_, l)| !existing.contains(l))
+            .map(|(t, _)| t)
+            .collect())
+    }
+
+    /// Articles which contain a `[[Title]]` link to the given article
+    pub fn read_backlinks(article: &Article, context: &IbisContext) -> BackendResult<Vec<Self>> {
+        let mut conn = context.db_pool.get()?;
+        // Underscore is a wildcard, so this matches titles written with spaces or underscores.
+        // Candidates are checked exactly below.
+        let pattern = format!(
+            "%[[{}%",
+            article
+                .title
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace(' ', "_")
+        );
+        let candidates: Vec<Article> = article::table
+            .filter(article::text.ilike(pattern))
+            .filter(not(article::removed))
+            .filter(article::id.ne(article.id))
+            .order_by(article::title)
+            .get_results(conn.deref_mut())?;
+        let domain = extract_domain(article.ap_id.inner());
+        let title = article.title.to_lowercase();
+        Ok(candidates
+            .into_iter()
+            .filter(|c| {
+                extract_article_links(&c.text).iter().any(|l| {
+                    let same_instance = match &l.domain {
+                        Some(d) => d == &domain,
+                        None => article.local,
+                    };
+                    same_instance && normalize_article_title(&l.title).to_lowercase() == title
+                })
+            })
+            .collect())
+    }
+
+    /// Number of local users who follow the article
+    pub fn read_follower_count(id: ArticleId, context: &IbisContext) -> BackendResult<i64> {
+        let mut conn = context.db_pool.get()?;
+        Ok(article_follow::table
+            .filter(article_follow::article_id.eq(id))
+            .count()
+            .get_result(conn.deref_mut())?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{config::IbisConfig, impls::edit::DbEditForm};
+
+    #[test]
+    fn test_most_viewed_articles() -> BackendResult<()> {
+        let context = IbisContext::init(IbisConfig::read()?, false)?;
+        let instance = Instance::read_local(&context)?;
+        let creator = Person::wikibot(&context)?;
+        let mut conn = context.db_pool.get()?;
+        let mut create = |title: &str| -> BackendResult<Article> {
+            let ap_id = format!("{}article/{title}", instance.ap_id).parse::<Url>()?;
+            let article: Article = insert_into(article::table)
+                .values(DbArticleForm {
+                    title: title.to_string(),
+                    text: String::new(),
+                    ap_id: ap_id.into(),
+                    instance_id: instance.id,
+                    local: true,
+                    protection: ProtectionLevel::default(),
+                    updated: Utc::now(),
+                    pending: false,
+                    forked_by: None,
+                })
+                .get_result(conn.deref_mut())?;
+            // articles are only listed if they have an edit
+            let form = DbEditForm::new(
+                &article,
+                creator.id,
+                title,
+                String::new(),
+                false,
+                EditVersion::default(),
+                false,
+            )?;
+            insert_into(edit::table)
+                .values(&form)
+                .execute(conn.deref_mut())?;
+            Ok(article)
+        };
+        let suffix = Utc::now().timestamp_micros();
+        let articles = [
+            create(&format!("Views One {suffix}"))?,
+            create(&format!("Views Three {suffix}"))?,
+            create(&format!("Views Two {suffix}"))?,
+        ];
+        for (article, views) in articles.iter().zip([1, 3, 2]) {
+            for _ in 0..views {
+                Article::add_view(article.id, &context)?;
+            }
+        }
+        Article::flush_views(&context)?;
+
+        let ids: Vec<_> = articles.iter().map(|a| a.id).collect();
+        let most_viewed: Vec<_> = Article::read_all(
+            Some(true),
+            None,
+            false,
+            ArticleSort::MostViewed,
+            None,
+            0,
+            &context,
+        )?
+        .into_iter()
+        .map(|a| a.id)
+        .filter(|id| ids.contains(id))
+        .collect();
+        assert_eq!(vec![ids[1], ids[2], ids[0]], most_viewed);
+
+        delete(article::table.filter(article::id.eq_any(&ids))).execute(conn.deref_mut())?;
+        Ok(())
+    }
 }
@@ -0,0 +1,50 @@
+use crate::{
+    common::{
+        article::{ArticleMove, ArticleMoveView},
+        newtypes::{ArticleId, PersonId},
+        user::Person,
+    },
+    error::BackendResult,
+    impls::IbisContext,
+};
+use diesel::{ExpressionMethods, Insertable, QueryDsl, RunQueryDsl, insert_into};
+use ibis_database_schema::{article_move, person};
+use std::ops::DerefMut;
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = article_move, check_for_backend(diesel::pg::Pg))]
+pub struct DbArticleMoveForm {
+    pub article_id: ArticleId,
+    pub creator_id: PersonId,
+    pub old_title: String,
+    pub new_title: String,
+}
+
+impl ArticleMove {
+    pub fn create(form: &DbArticleMoveForm, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(insert_into(article_move::table)
+            .values(form)
+            .get_result(conn.deref_mut())?)
+    }
+
+    pub fn list_for_article(
+        id: ArticleId,
+        context: &IbisContext,
+    ) -> BackendResult<Vec<ArticleMoveView>> {
+        let mut conn = context.db_pool.get()?;
+        let moves: Vec<(ArticleMove, Person)> = article_move::table
+            .inner_join(person::table)
+            .filter(article_move::article_id.eq(id))
+            .order_by(article_move::id)
+            .select((article_move::all_columns, person::all_columns))
+            .get_results(conn.deref_mut())?;
+        Ok(moves
+            .into_iter()
+            .map(|(article_move, creator)| ArticleMoveView {
+                article_move,
+                creator,
+            })
+            .collect())
+    }
+}
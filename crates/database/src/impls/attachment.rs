@@ -0,0 +1,73 @@
+use crate::{
+    common::{
+        article::Attachment,
+        newtypes::{ArticleId, AttachmentId},
+    },
+    error::{BackendError, BackendResult},
+    impls::IbisContext,
+};
+use diesel::{
+    Connection,
+    ExpressionMethods,
+    Insertable,
+    QueryDsl,
+    RunQueryDsl,
+    delete,
+    insert_into,
+};
+use ibis_database_schema::attachment;
+use std::ops::DerefMut;
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = attachment, check_for_backend(diesel::pg::Pg))]
+pub struct DbAttachmentForm {
+    pub article_id: ArticleId,
+    pub url: String,
+    pub alt_text: Option<String>,
+    pub media_type: Option<String>,
+}
+
+impl Attachment {
+    pub fn create(form: &DbAttachmentForm, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(insert_into(attachment::table)
+            .values(form)
+            .get_result(conn.deref_mut())?)
+    }
+
+    pub fn read(id: AttachmentId, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(attachment::table.find(id).get_result(conn.deref_mut())?)
+    }
+
+    pub fn delete(id: AttachmentId, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        delete(attachment::table.find(id)).execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    pub fn list_for_article(id: ArticleId, context: &IbisContext) -> BackendResult<Vec<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(attachment::table
+            .filter(attachment::article_id.eq(id))
+            .order_by(attachment::id)
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Replace all attachments of the article, used when receiving a federated article.
+    pub fn replace_for_article(
+        id: ArticleId,
+        forms: &[DbAttachmentForm],
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        conn.transaction::<_, BackendError, _>(|conn| {
+            delete(attachment::table.filter(attachment::article_id.eq(id))).execute(conn)?;
+            insert_into(attachment::table)
+                .values(forms)
+                .on_conflict_do_nothing()
+                .execute(conn)?;
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{
+    common::newtypes::PersonId,
+    error::BackendResult,
+    impls::{DbPool, IbisContext},
+};
+use diesel::{
+    ExpressionMethods,
+    IntoSql,
+    QueryDsl,
+    RunQueryDsl,
+    delete,
+    dsl::{IntervalDsl, exists},
+    insert_into,
+    select,
+    sql_types::Timestamptz,
+};
+use ibis_database_schema::auth_token;
+use std::ops::DerefMut;
+use uuid::Uuid;
+
+/// Server-side record of login sessions. The token is embedded in the auth cookie, and a
+/// cookie is only accepted while its token is still stored here.
+pub struct AuthToken;
+
+impl AuthToken {
+    pub fn create(person_id: PersonId, context: &IbisContext) -> BackendResult<String> {
+        use auth_token::dsl;
+        let mut conn = context.db_pool.get()?;
+        let token = Uuid::new_v4().to_string();
+        insert_into(auth_token::table)
+            .values((dsl::token.eq(&token), dsl::person_id.eq(person_id)))
+            .execute(conn.deref_mut())?;
+        Ok(token)
+    }
+
+    pub fn is_valid(
+        token: &str,
+        person_id: PersonId,
+        context: &IbisContext,
+    ) -> BackendResult<bool> {
+        let mut conn = context.db_pool.get()?;
+        Ok(select(exists(
+            auth_token::table
+                .filter(auth_token::token.eq(token))
+                .filter(auth_token::person_id.eq(person_id)),
+        ))
+        .get_result(conn.deref_mut())?)
+    }
+
+    pub fn delete(token: &str, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        delete(auth_token::table.find(token)).execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    /// Revoke all sessions of the user, on every device.
+    pub fn delete_all(person_id: PersonId, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        delete(auth_token::table.filter(auth_token::person_id.eq(person_id)))
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    /// Remove sessions which are older than the one year validity of login cookies.
+    pub fn delete_expired(pool: &DbPool) -> BackendResult<usize> {
+        let mut conn = pool.get()?;
+        let now = diesel::dsl::now.into_sql::<Timestamptz>();
+        Ok(
+            delete(auth_token::table.filter(auth_token::published.lt(now - IntervalDsl::years(1))))
+                .execute(conn.deref_mut())?,
+        )
+    }
+}
@@ -0,0 +1,67 @@
+use crate::{common::instance::BlockedInstance, error::BackendResult, impls::IbisContext};
+use anyhow::anyhow;
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl, delete, insert_into};
+use ibis_database_schema::blocked_instance;
+use std::{
+    collections::HashSet,
+    ops::DerefMut,
+    sync::{Arc, RwLock},
+};
+
+/// Blocked and allowed domains are checked for every federated url, so they are kept in memory.
+/// The cache is loaded on first use and cleared whenever the list changes.
+pub(crate) type DomainCache = Arc<RwLock<Option<HashSet<String>>>>;
+
+pub(crate) fn cache_contains(
+    cache: &DomainCache,
+    domain: &str,
+    load: impl FnOnce() -> BackendResult<HashSet<String>>,
+) -> BackendResult<bool> {
+    if let Some(domains) = &*cache.read().map_err(|e| anyhow!("{e}"))? {
+        return Ok(domains.contains(domain));
+    }
+    let domains = load()?;
+    let contains = domains.contains(domain);
+    *cache.write().map_err(|e| anyhow!("{e}"))? = Some(domains);
+    Ok(contains)
+}
+
+pub(crate) fn clear_cache(cache: &DomainCache) {
+    if let Ok(mut cache) = cache.write() {
+        *cache = None;
+    }
+}
+
+impl BlockedInstance {
+    pub fn block(domain: &str, context: &IbisContext) -> BackendResult<()> {
+        let domain = domain.trim().to_lowercase();
+        let mut conn = context.db_pool.get()?;
+        insert_into(blocked_instance::table)
+            .values(blocked_instance::domain.eq(&domain))
+            .on_conflict_do_nothing()
+            .execute(conn.deref_mut())?;
+        clear_cache(&context.blocklist);
+        Ok(())
+    }
+
+    pub fn unblock(domain: &str, context: &IbisContext) -> BackendResult<()> {
+        let domain = domain.trim().to_lowercase();
+        let mut conn = context.db_pool.get()?;
+        delete(blocked_instance::table.find(&domain)).execute(conn.deref_mut())?;
+        clear_cache(&context.blocklist);
+        Ok(())
+    }
+
+    pub fn list(context: &IbisContext) -> BackendResult<Vec<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(blocked_instance::table
+            .order_by(blocked_instance::domain)
+            .get_results(conn.deref_mut())?)
+    }
+
+    pub fn is_blocked(domain: &str, context: &IbisContext) -> BackendResult<bool> {
+        cache_contains(&context.blocklist, domain, || {
+            Ok(Self::list(context)?.into_iter().map(|b| b.domain).collect())
+        })
+    }
+}
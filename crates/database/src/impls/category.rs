@@ -0,0 +1,112 @@
+use crate::{
+    common::{
+        article::{Article, Category, CategoryView},
+        newtypes::ArticleId,
+    },
+    error::BackendResult,
+    impls::IbisContext,
+};
+use diesel::{
+    ExpressionMethods,
+    PgConnection,
+    QueryDsl,
+    RunQueryDsl,
+    delete,
+    insert_into,
+    upsert::excluded,
+};
+use ibis_database_schema::{article, article_category, category};
+use ibis_markdown::extract_categories;
+use std::ops::DerefMut;
+
+impl Category {
+    pub fn read(name: &str, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(category::table
+            .filter(category::name.eq(name))
+            .get_result(conn.deref_mut())?)
+    }
+
+    pub fn read_view(name: &str, context: &IbisContext) -> BackendResult<CategoryView> {
+        let category = Self::read(name, context)?;
+        let mut conn = context.db_pool.get()?;
+        let articles = article_category::table
+            .inner_join(article::table)
+            .filter(article_category::category_id.eq(category.id))
+            .filter(article::removed.eq(false))
+            .filter(article::pending.eq(false))
+            .order_by(article::title)
+            .select(article::all_columns)
+            .get_results::<Article>(conn.deref_mut())?;
+        Ok(CategoryView { category, articles })
+    }
+
+    pub fn list_for_article(id: ArticleId, context: &IbisContext) -> BackendResult<Vec<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(article_category::table
+            .inner_join(category::table)
+            .filter(article_category::article_id.eq(id))
+            .order_by(category::name)
+            .select(category::all_columns)
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Add the article to a category, creating the category if it doesn't exist yet.
+    pub fn add_article(
+        article_id: ArticleId,
+        name: &str,
+        context: &IbisContext,
+    ) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        let category = Self::read_or_create(name, conn.deref_mut())?;
+        insert_into(article_category::table)
+            .values((
+                article_category::article_id.eq(article_id),
+                article_category::category_id.eq(category.id),
+                article_category::from_marker.eq(false),
+            ))
+            .on_conflict((article_category::article_id, article_category::category_id))
+            .do_update()
+            .set(article_category::from_marker.eq(false))
+            .execute(conn.deref_mut())?;
+        Ok(category)
+    }
+
+    /// Replace categories which were added by `[[Category:Name]]` markers with those in the
+    /// current text. Manually added categories are kept.
+    pub fn update_for_article(
+        article_id: ArticleId,
+        text: &str,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        delete(
+            article_category::table
+                .filter(article_category::article_id.eq(article_id))
+                .filter(article_category::from_marker),
+        )
+        .execute(conn.deref_mut())?;
+        for name in extract_categories(text) {
+            let category = Self::read_or_create(&name, conn.deref_mut())?;
+            insert_into(article_category::table)
+                .values((
+                    article_category::article_id.eq(article_id),
+                    article_category::category_id.eq(category.id),
+                    article_category::from_marker.eq(true),
+                ))
+                .on_conflict_do_nothing()
+                .execute(conn.deref_mut())?;
+        }
+        Ok(())
+    }
+
+    fn read_or_create(name: &str, conn: &mut PgConnection) -> BackendResult<Self> {
+        // update with the same value so that the existing row is returned
+        Ok(insert_into(category::table)
+            .values(category::name.eq(name))
+            .on_conflict(category::name)
+            .do_update()
+            .set(category::name.eq(excluded(category::name)))
+            .get_result(conn)?)
+    }
+}
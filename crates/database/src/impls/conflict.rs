@@ -1,15 +1,29 @@
 use super::notifications::NotificationInsertForm;
 use crate::{
     common::{
-        article::{Conflict, EditVersion},
+        article::{Article, Conflict, ConflictStats, EditVersion},
         newtypes::{ArticleId, ConflictId, PersonId},
         user::LocalUser,
     },
     error::BackendResult,
-    impls::IbisContext,
+    impls::{DbPool, IbisContext},
 };
-use diesel::{ExpressionMethods, Insertable, QueryDsl, RunQueryDsl, delete, insert_into};
-use ibis_database_schema::{conflict, edit, local_user, notification};
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use diesel::{
+    ExpressionMethods,
+    Insertable,
+    IntoSql,
+    PgConnection,
+    QueryDsl,
+    RunQueryDsl,
+    delete,
+    dsl::{IntervalDsl, count, count_star, max},
+    insert_into,
+    sql_types::Timestamptz,
+    update,
+};
+use ibis_database_schema::{article, conflict, conflict_log, edit, local_user, notification};
 use std::ops::DerefMut;
 
 #[derive(Debug, Clone, Insertable)]
@@ -21,6 +35,16 @@ pub struct DbConflictForm {
     pub creator_id: PersonId,
     pub article_id: ArticleId,
     pub previous_version_id: EditVersion,
+    pub attempts: i32,
+}
+
+/// How a conflict was removed, stored in the conflict log for statistics
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictOutcome {
+    /// User submitted an edit which resolves the conflict, or it could be applied cleanly
+    Resolved,
+    /// User dismissed the conflict or gave up, or it was purged as stale
+    Abandoned,
 }
 
 impl Conflict {
@@ -29,6 +53,13 @@ impl Conflict {
         let conflict: Conflict = insert_into(conflict::table)
             .values(form)
             .get_result(conn.deref_mut())?;
+        insert_into(conflict_log::table)
+            .values((
+                conflict_log::conflict_id.eq(conflict.id),
+                conflict_log::article_id.eq(conflict.article_id),
+                conflict_log::published.eq(conflict.published),
+            ))
+            .execute(conn.deref_mut())?;
         let local_user: LocalUser = local_user::table
             .filter(local_user::person_id.eq(conflict.creator_id))
             .get_result(&mut conn)?;
@@ -61,12 +92,39 @@ impl Conflict {
             .get_result(conn.deref_mut())?)
     }
 
+    /// Read conflict for resolution, this also marks it as recently used so that it doesn't get
+    /// purged in the meantime.
+    pub fn touch(
+        id: ConflictId,
+        person_id: PersonId,
+        context: &IbisContext,
+    ) -> BackendResult<Conflict> {
+        let mut conn = context.db_pool.get()?;
+        Ok(update(
+            conflict::table
+                .find(id)
+                .filter(conflict::dsl::creator_id.eq(person_id)),
+        )
+        .set(conflict::dsl::updated.eq(Utc::now()))
+        .get_result(conn.deref_mut())?)
+    }
+
+    /// List all unresolved conflicts of the user, newest first
+    pub fn list_for_user(person_id: PersonId, context: &IbisContext) -> BackendResult<Vec<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(conflict::table
+            .filter(conflict::dsl::creator_id.eq(person_id))
+            .order_by(conflict::dsl::published.desc())
+            .get_results(conn.deref_mut())?)
+    }
+
     /// Delete merge conflict which was created by specific user
     pub fn delete(
         id: ConflictId,
         creator_id: PersonId,
+        outcome: ConflictOutcome,
         context: &IbisContext,
-    ) -> BackendResult<()> {
+    ) -> BackendResult<Self> {
         let mut conn = context.db_pool.get()?;
         let conflict: Self = delete(
             conflict::table
@@ -80,6 +138,106 @@ impl Conflict {
                 .filter(edit::dsl::hash.eq(conflict.hash)),
         )
         .execute(conn.deref_mut())?;
+        log_outcome(&[conflict.id], outcome, conn.deref_mut())?;
+        Ok(conflict)
+    }
+
+    /// Delete all remaining merge conflicts of the user for the given article
+    pub fn delete_for_article(
+        article_id: ArticleId,
+        creator_id: PersonId,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        let ids: Vec<ConflictId> = delete(
+            conflict::table
+                .filter(conflict::dsl::creator_id.eq(creator_id))
+                .filter(conflict::dsl::article_id.eq(article_id)),
+        )
+        .returning(conflict::dsl::id)
+        .get_results(conn.deref_mut())?;
+        log_outcome(&ids, ConflictOutcome::Abandoned, conn.deref_mut())?;
         Ok(())
     }
+
+    /// Delete conflicts which were not touched for the given number of days. Conflicts which are
+    /// currently being resolved are kept, as submitting a resolution updates the timestamp.
+    pub fn delete_stale(retention_days: u32, pool: &DbPool) -> BackendResult<usize> {
+        let days = i32::try_from(retention_days)
+            .map_err(|_| anyhow!("Conflict retention of {retention_days} days is too long"))?;
+        let mut conn = pool.get()?;
+        let now = diesel::dsl::now.into_sql::<Timestamptz>();
+        let ids: Vec<ConflictId> = delete(
+            conflict::table.filter(conflict::dsl::updated.lt(now - IntervalDsl::days(days))),
+        )
+        .returning(conflict::dsl::id)
+        .get_results(conn.deref_mut())?;
+        log_outcome(&ids, ConflictOutcome::Abandoned, conn.deref_mut())?;
+        Ok(ids.len())
+    }
+
+    /// Articles with the most edit conflicts created within the given time window. This includes
+    /// conflicts which were already resolved or abandoned.
+    pub fn stats(
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+        limit: i64,
+        offset: i64,
+        context: &IbisContext,
+    ) -> BackendResult<Vec<ConflictStats>> {
+        let mut conn = context.db_pool.get()?;
+        let mut query = conflict_log::table
+            .inner_join(article::table)
+            .group_by(article::id)
+            .select((
+                article::all_columns,
+                count_star(),
+                count(conflict_log::resolved),
+                count(conflict_log::abandoned),
+                max(conflict_log::published),
+            ))
+            .order_by((count_star().desc(), max(conflict_log::published).desc()))
+            .limit(limit)
+            .offset(offset)
+            .into_boxed();
+        if let Some(since) = since {
+            query = query.filter(conflict_log::published.ge(since));
+        }
+        if let Some(until) = until {
+            query = query.filter(conflict_log::published.lt(until));
+        }
+        let stats = query
+            .get_results::<(Article, i64, i64, i64, Option<DateTime<Utc>>)>(conn.deref_mut())?
+            .into_iter()
+            .map(
+                |(article, total, resolved, abandoned, last_conflict)| ConflictStats {
+                    article,
+                    total,
+                    resolved,
+                    abandoned,
+                    pending: total - resolved - abandoned,
+                    last_conflict: last_conflict.unwrap_or_default(),
+                },
+            )
+            .collect();
+        Ok(stats)
+    }
+}
+
+fn log_outcome(
+    ids: &[ConflictId],
+    outcome: ConflictOutcome,
+    conn: &mut PgConnection,
+) -> BackendResult<()> {
+    let log = conflict_log::table.filter(conflict_log::conflict_id.eq_any(ids));
+    let now = Some(Utc::now());
+    match outcome {
+        ConflictOutcome::Resolved => update(log)
+            .set(conflict_log::resolved.eq(now))
+            .execute(conn),
+        ConflictOutcome::Abandoned => update(log)
+            .set(conflict_log::abandoned.eq(now))
+            .execute(conn),
+    }?;
+    Ok(())
 }
@@ -0,0 +1,186 @@
+use crate::{
+    DbUrl,
+    common::{instance::QueuedDelivery, newtypes::QueuedDeliveryId},
+    error::BackendResult,
+    impls::{IbisContext, retry_backoff},
+};
+use chrono::Utc;
+use diesel::{
+    ExpressionMethods,
+    OptionalExtension,
+    QueryDsl,
+    RunQueryDsl,
+    delete,
+    dsl::not,
+    insert_into,
+    update,
+};
+use ibis_database_schema::delivery_queue;
+use std::ops::DerefMut;
+
+impl QueuedDelivery {
+    /// Store a delivery after sending failed for the first time with a temporary error. If the
+    /// same activity is already queued for this inbox it is left unchanged.
+    pub fn create(
+        activity_id: DbUrl,
+        actor_id: DbUrl,
+        inbox: DbUrl,
+        json: String,
+        error: String,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        use delivery_queue::dsl;
+        let mut conn = context.db_pool.get()?;
+        let max_retries = context.conf.options.delivery_retries;
+        insert_into(delivery_queue::table)
+            .values((
+                dsl::activity_id.eq(activity_id),
+                dsl::actor_id.eq(actor_id),
+                dsl::inbox.eq(inbox),
+                dsl::json.eq(json),
+                dsl::error.eq(error),
+                dsl::attempts.eq(1),
+                dsl::next_retry.eq(Utc::now() + retry_backoff(1)),
+                dsl::dead.eq(max_retries == 0),
+            ))
+            .on_conflict((dsl::activity_id, dsl::inbox))
+            .do_nothing()
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    pub fn read(id: QueuedDeliveryId, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(delivery_queue::table
+            .find(id)
+            .get_result(conn.deref_mut())?)
+    }
+
+    /// Queued delivery of the given activity to the inbox, if there is one
+    pub fn read_for_inbox(
+        activity_id: &DbUrl,
+        inbox: &DbUrl,
+        context: &IbisContext,
+    ) -> BackendResult<Option<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(delivery_queue::table
+            .filter(delivery_queue::activity_id.eq(activity_id))
+            .filter(delivery_queue::inbox.eq(inbox))
+            .first(conn.deref_mut())
+            .optional()?)
+    }
+
+    /// Deliveries which should be retried now
+    pub fn read_due(context: &IbisContext) -> BackendResult<Vec<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(delivery_queue::table
+            .filter(not(delivery_queue::dead))
+            .filter(delivery_queue::next_retry.le(Utc::now()))
+            .order_by(delivery_queue::next_retry)
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// All queued deliveries including dead ones, most recent first
+    pub fn list(context: &IbisContext) -> BackendResult<Vec<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(delivery_queue::table
+            .order_by(delivery_queue::published.desc())
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Number of deliveries which are waiting for retry, or which are dead
+    pub fn count(dead: bool, context: &IbisContext) -> BackendResult<i64> {
+        let mut conn = context.db_pool.get()?;
+        Ok(delivery_queue::table
+            .filter(delivery_queue::dead.eq(dead))
+            .count()
+            .get_result(conn.deref_mut())?)
+    }
+
+    /// Schedule another retry, or mark as dead if there are no retries left.
+    pub fn mark_failed(&self, error: String, context: &IbisContext) -> BackendResult<Self> {
+        use delivery_queue::dsl;
+        let mut conn = context.db_pool.get()?;
+        let max_retries = context.conf.options.delivery_retries as i32;
+        let attempts = self.attempts + 1;
+        Ok(update(delivery_queue::table.find(self.id))
+            .set((
+                dsl::error.eq(error),
+                dsl::attempts.eq(attempts),
+                dsl::next_retry.eq(Utc::now() + retry_backoff(attempts)),
+                dsl::dead.eq(attempts > max_retries),
+            ))
+            .get_result(conn.deref_mut())?)
+    }
+
+    /// Give up on the delivery without further retries, for example because the remote instance
+    /// rejected the activity.
+    pub fn mark_dead(&self, error: String, context: &IbisContext) -> BackendResult<Self> {
+        use delivery_queue::dsl;
+        let mut conn = context.db_pool.get()?;
+        Ok(update(delivery_queue::table.find(self.id))
+            .set((dsl::error.eq(error), dsl::dead.eq(true)))
+            .get_result(conn.deref_mut())?)
+    }
+
+    pub fn delete(id: QueuedDeliveryId, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        delete(delivery_queue::table.find(id)).execute(conn.deref_mut())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::IbisConfig;
+
+    #[test]
+    fn test_delivery_queue_gives_up() -> BackendResult<()> {
+        let context = IbisContext::init(IbisConfig::read()?, false)?;
+        let max_retries = context.conf.options.delivery_retries as i32;
+        let activity_id: DbUrl = "https://example.com/activity/delivery-queue-test"
+            .parse::<url::Url>()?
+            .into();
+        let inbox: DbUrl = "https://unreachable.example/inbox"
+            .parse::<url::Url>()?
+            .into();
+        QueuedDelivery::create(
+            activity_id.clone(),
+            "https://example.com/".parse::<url::Url>()?.into(),
+            inbox.clone(),
+            "{}".to_string(),
+            "connection refused".to_string(),
+            &context,
+        )?;
+        let mut delivery = QueuedDelivery::list(&context)?
+            .into_iter()
+            .find(|d| d.activity_id == activity_id && d.inbox == inbox)
+            .ok_or(anyhow::anyhow!("delivery not queued"))?;
+        assert_eq!(1, delivery.attempts);
+        assert!(!delivery.dead);
+        assert!(delivery.next_retry > Utc::now());
+
+        // transient failures are retried with increasing delay
+        let previous_retry = delivery.next_retry;
+        delivery = delivery.mark_failed("timeout".to_string(), &context)?;
+        assert!(!delivery.dead);
+        assert!(delivery.next_retry > previous_retry);
+
+        // permanent failure is given up after all retries
+        while delivery.attempts <= max_retries {
+            assert!(!delivery.dead);
+            delivery = delivery.mark_failed("timeout".to_string(), &context)?;
+        }
+        assert!(delivery.dead);
+        assert_eq!(max_retries + 1, delivery.attempts);
+        assert!(
+            QueuedDelivery::read_due(&context)?
+                .iter()
+                .all(|d| d.id != delivery.id)
+        );
+
+        QueuedDelivery::delete(delivery.id, &context)?;
+        Ok(())
+    }
+}
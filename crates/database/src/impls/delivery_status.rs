@@ -0,0 +1,116 @@
+use crate::{
+    common::{
+        instance::{DeliveryStatus, PeerDeliveryStatus},
+        newtypes::InstanceId,
+        utils::extract_domain,
+    },
+    error::BackendResult,
+    impls::IbisContext,
+};
+use chrono::Utc;
+use diesel::{
+    ExpressionMethods,
+    NullableExpressionMethods,
+    OptionalExtension,
+    QueryDsl,
+    RunQueryDsl,
+    dsl::{not, sum},
+    insert_into,
+};
+use ibis_database_schema::{delivery_status, instance};
+use std::ops::DerefMut;
+use url::Url;
+
+impl DeliveryStatus {
+    /// Store the result of sending an activity to the given inboxes. Inboxes which don't belong
+    /// to a known remote instance are ignored.
+    pub fn update_for_inboxes(
+        inboxes: &[Url],
+        success: bool,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        let mut domains: Vec<_> = inboxes.iter().map(extract_domain).collect();
+        domains.sort();
+        domains.dedup();
+        for domain in domains {
+            let Some(instance_id) = Self::read_instance_id(&domain, context)? else {
+                continue;
+            };
+            if success {
+                Self::mark_success(instance_id, context)?;
+            } else {
+                Self::mark_failure(instance_id, context)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn read_instance_id(domain: &str, context: &IbisContext) -> BackendResult<Option<InstanceId>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(instance::table
+            .filter(instance::domain.eq(domain))
+            .filter(not(instance::local))
+            .select(instance::id)
+            .first(conn.deref_mut())
+            .optional()?)
+    }
+
+    fn mark_success(instance_id: InstanceId, context: &IbisContext) -> BackendResult<()> {
+        use delivery_status::dsl::{failures, last_success};
+        let mut conn = context.db_pool.get()?;
+        let form = (last_success.eq(Utc::now()), failures.eq(0));
+        insert_into(delivery_status::table)
+            .values((delivery_status::instance_id.eq(instance_id), form))
+            .on_conflict(delivery_status::instance_id)
+            .do_update()
+            .set(form)
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    fn mark_failure(instance_id: InstanceId, context: &IbisContext) -> BackendResult<()> {
+        use delivery_status::dsl::{failures, last_failure};
+        let mut conn = context.db_pool.get()?;
+        insert_into(delivery_status::table)
+            .values((
+                delivery_status::instance_id.eq(instance_id),
+                last_failure.eq(Utc::now()),
+                failures.eq(1),
+            ))
+            .on_conflict(delivery_status::instance_id)
+            .do_update()
+            .set((last_failure.eq(Utc::now()), failures.eq(failures + 1)))
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    /// Total number of failed deliveries since the last success, across all instances.
+    pub fn count_failures(context: &IbisContext) -> BackendResult<i64> {
+        let mut conn = context.db_pool.get()?;
+        let count: Option<i64> = delivery_status::table
+            .select(sum(delivery_status::failures))
+            .first(conn.deref_mut())?;
+        Ok(count.unwrap_or_default())
+    }
+
+    /// List delivery status for all remote instances, including those which never received
+    /// any activity.
+    pub fn list_peers(
+        limit: i64,
+        offset: i64,
+        context: &IbisContext,
+    ) -> BackendResult<Vec<PeerDeliveryStatus>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(instance::table
+            .left_join(delivery_status::table)
+            .filter(not(instance::local))
+            .order_by(instance::id)
+            .limit(limit)
+            .offset(offset)
+            .select((
+                instance::all_columns,
+                delivery_status::all_columns.nullable(),
+            ))
+            .get_results(conn.deref_mut())?)
+    }
+}
@@ -1,9 +1,9 @@
-use super::{is_conflict, notifications::Notification};
+use super::{is_conflict, notifications::Notification, read_jwt_secret};
 use crate::{
     DbUrl,
     common::{
-        article::{Article, Edit, EditVersion, EditView},
-        newtypes::{ArticleId, PersonId},
+        article::{AnonymousEdit, Article, Edit, EditVersion, EditView, LatestEdit},
+        newtypes::{ArticleId, InstanceId, PersonId},
         user::LocalUserView,
     },
     error::BackendResult,
@@ -22,7 +22,8 @@ use diesel::{
 };
 use diffy::create_patch;
 use ibis_database_schema::{article, edit, person};
-use std::ops::DerefMut;
+use sha2::{Digest, Sha256};
+use std::{collections::HashMap, ops::DerefMut};
 use url::Url;
 
 #[derive(Debug, Clone, Insertable, AsChangeset)]
@@ -37,6 +38,8 @@ pub struct DbEditForm {
     pub previous_version_id: EditVersion,
     pub published: DateTime<Utc>,
     pub pending: bool,
+    pub minor: bool,
+    pub anonymous_id: Option<String>,
 }
 
 impl DbEditForm {
@@ -45,6 +48,7 @@ impl DbEditForm {
         creator_id: PersonId,
         updated_text: &str,
         summary: String,
+        minor: bool,
         previous_version_id: EditVersion,
         pending: bool,
     ) -> BackendResult<Self> {
@@ -61,6 +65,8 @@ impl DbEditForm {
             summary,
             published: Utc::now(),
             pending,
+            minor,
+            anonymous_id: None,
         })
     }
 
@@ -116,35 +122,190 @@ impl Edit {
             .get_result(conn.deref_mut())?)
     }
 
+    /// Number of edits made by the user since the given time, used for rate limiting.
+    pub fn count_since(
+        person_id: PersonId,
+        since: DateTime<Utc>,
+        context: &IbisContext,
+    ) -> BackendResult<i64> {
+        let mut conn = context.db_pool.get()?;
+        Ok(edit::table
+            .filter(edit::creator_id.eq(person_id))
+            .filter(edit::published.gt(since))
+            .count()
+            .get_result(conn.deref_mut())?)
+    }
+
+    /// Number of edits with the given anonymous identifier since the given time.
+    pub fn count_since_anonymous(
+        anonymous_id: &str,
+        since: DateTime<Utc>,
+        context: &IbisContext,
+    ) -> BackendResult<i64> {
+        let mut conn = context.db_pool.get()?;
+        Ok(edit::table
+            .filter(edit::anonymous_id.eq(anonymous_id))
+            .filter(edit::published.gt(since))
+            .count()
+            .get_result(conn.deref_mut())?)
+    }
+
+    /// Edits of the article which were made without login, in the same order as the history
+    pub fn list_anonymous(
+        article_id: ArticleId,
+        context: &IbisContext,
+    ) -> BackendResult<Vec<AnonymousEdit>> {
+        let mut conn = context.db_pool.get()?;
+        let edits: Vec<Edit> = edit::table
+            .filter(edit::article_id.eq(article_id))
+            .filter(edit::anonymous_id.is_not_null())
+            .order(edit::sequence)
+            .get_results(conn.deref_mut())?;
+        Ok(edits
+            .into_iter()
+            .filter_map(|edit| {
+                let anonymous_id = edit.anonymous_id.clone()?;
+                Some(AnonymousEdit { edit, anonymous_id })
+            })
+            .collect())
+    }
+
+    /// Identifier for a client which edits without login. The IP is hashed together with the
+    /// instance secret so that it can't be recovered from the database.
+    pub fn anonymous_id(client_ip: &str, context: &IbisContext) -> BackendResult<String> {
+        let mut sha256 = Sha256::new();
+        sha256.update(read_jwt_secret(context)?);
+        sha256.update(client_ip);
+        Ok(hex::encode(sha256.finalize()))
+    }
+
     pub fn list_for_article(id: ArticleId, context: &IbisContext) -> BackendResult<Vec<Self>> {
         let mut conn = context.db_pool.get()?;
         Ok(edit::table
             .filter(edit::article_id.eq(id))
             .filter(edit::pending.eq(false))
-            .order(edit::published)
+            .order(edit::sequence)
             .get_results(conn.deref_mut())?)
     }
 
+    /// Most recent edit of each of the given articles
+    pub fn read_latest(
+        article_ids: &[ArticleId],
+        context: &IbisContext,
+    ) -> BackendResult<HashMap<ArticleId, LatestEdit>> {
+        let mut conn = context.db_pool.get()?;
+        let latest: Vec<(ArticleId, LatestEdit)> = edit::table
+            .inner_join(person::table)
+            .filter(edit::article_id.eq_any(article_ids))
+            .filter(edit::pending.eq(false))
+            .distinct_on(edit::article_id)
+            .order_by((edit::article_id, edit::sequence.desc()))
+            .select((
+                edit::article_id,
+                (edit::summary, edit::published, person::all_columns),
+            ))
+            .get_results(conn.deref_mut())?;
+        Ok(latest.into_iter().collect())
+    }
+
     pub fn list_views(
         params: ViewEditParams,
+        hide_minor: bool,
         user: &Option<LocalUserView>,
         context: &IbisContext,
     ) -> BackendResult<Vec<EditView>> {
         let mut conn = context.db_pool.get()?;
         let person_id = user.as_ref().map(|u| u.person.id).unwrap_or(PersonId(-1));
-        let query = edit::table
+        let mut query = edit::table
             .inner_join(article::table)
             .inner_join(person::table)
             // only the creator can view pending edits
             .filter(not(edit::pending).or(edit::creator_id.eq(person_id)))
             .into_boxed();
+        if hide_minor {
+            query = query.filter(not(edit::minor));
+        }
 
         let query = match params {
             ViewEditParams::PersonId(person_id) => query.filter(edit::creator_id.eq(person_id)),
             ViewEditParams::ArticleId(article_id) => query.filter(edit::article_id.eq(article_id)),
         };
 
-        Ok(query.order(edit::published).get_results(conn.deref_mut())?)
+        Ok(query.order(edit::sequence).get_results(conn.deref_mut())?)
+    }
+
+    /// Edits by the given person across all articles, newest first.
+    pub fn list_for_person(
+        creator_id: PersonId,
+        limit: i64,
+        offset: i64,
+        user: &Option<LocalUserView>,
+        context: &IbisContext,
+    ) -> BackendResult<Vec<EditView>> {
+        let mut conn = context.db_pool.get()?;
+        let person_id = user.as_ref().map(|u| u.person.id).unwrap_or(PersonId(-1));
+        Ok(edit::table
+            .inner_join(article::table)
+            .inner_join(person::table)
+            .filter(edit::creator_id.eq(creator_id))
+            // only the creator can view pending edits
+            .filter(not(edit::pending).or(edit::creator_id.eq(person_id)))
+            .order((edit::published.desc(), edit::id.desc()))
+            .limit(limit)
+            .offset(offset)
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Recent edits to articles which belong to the given instance, newest first. Only includes
+    /// data which was already received, nothing is fetched. Use `before` to get the next page.
+    pub fn list_for_instance(
+        instance_id: InstanceId,
+        before: Option<DateTime<Utc>>,
+        limit: i64,
+        context: &IbisContext,
+    ) -> BackendResult<Vec<EditView>> {
+        let mut conn = context.db_pool.get()?;
+        let mut query = edit::table
+            .inner_join(article::table)
+            .inner_join(person::table)
+            .filter(article::instance_id.eq(instance_id))
+            .filter(not(edit::pending))
+            .filter(not(article::removed))
+            .into_boxed();
+        if let Some(before) = before {
+            query = query.filter(edit::published.lt(before));
+        }
+        Ok(query
+            .order((edit::published.desc(), edit::id.desc()))
+            .limit(limit)
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Recent edits to all articles known to this instance, newest first. Use `since` to only
+    /// get edits which are newer than the given time.
+    pub fn list_recent(
+        only_local: bool,
+        since: Option<DateTime<Utc>>,
+        limit: i64,
+        context: &IbisContext,
+    ) -> BackendResult<Vec<EditView>> {
+        let mut conn = context.db_pool.get()?;
+        let mut query = edit::table
+            .inner_join(article::table)
+            .inner_join(person::table)
+            .filter(not(edit::pending))
+            .filter(not(article::removed))
+            .into_boxed();
+        if only_local {
+            query = query.filter(article::local);
+        }
+        if let Some(since) = since {
+            query = query.filter(edit::published.gt(since));
+        }
+        Ok(query
+            .order((edit::published.desc(), edit::id.desc()))
+            .limit(limit)
+            .get_results(conn.deref_mut())?)
     }
 }
 
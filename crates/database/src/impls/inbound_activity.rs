@@ -0,0 +1,86 @@
+use crate::{
+    DbUrl,
+    common::{instance::InboundActivity, newtypes::InboundActivityId},
+    error::BackendResult,
+    impls::{IbisContext, retry_backoff},
+};
+use chrono::Utc;
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl, delete, dsl::not, insert_into, update};
+use ibis_database_schema::inbound_activity;
+use std::ops::DerefMut;
+
+impl InboundActivity {
+    /// Store an activity after processing failed for the first time. If the same activity is
+    /// already stored it is left unchanged.
+    pub fn create(
+        ap_id: DbUrl,
+        json: String,
+        error: String,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        use inbound_activity::dsl;
+        let mut conn = context.db_pool.get()?;
+        let max_retries = context.conf.options.inbound_activity_retries;
+        insert_into(inbound_activity::table)
+            .values((
+                dsl::ap_id.eq(ap_id),
+                dsl::json.eq(json),
+                dsl::error.eq(error),
+                dsl::attempts.eq(1),
+                dsl::next_retry.eq(Utc::now() + retry_backoff(1)),
+                dsl::dead.eq(max_retries == 0),
+            ))
+            .on_conflict(dsl::ap_id)
+            .do_nothing()
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    pub fn read(id: InboundActivityId, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(inbound_activity::table
+            .find(id)
+            .get_result(conn.deref_mut())?)
+    }
+
+    /// Activities which should be retried now
+    pub fn read_due(context: &IbisContext) -> BackendResult<Vec<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(inbound_activity::table
+            .filter(not(inbound_activity::dead))
+            .filter(inbound_activity::next_retry.le(Utc::now()))
+            .order_by(inbound_activity::next_retry)
+            .get_results(conn.deref_mut())?)
+    }
+
+    pub fn list_dead(context: &IbisContext) -> BackendResult<Vec<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(inbound_activity::table
+            .filter(inbound_activity::dead)
+            .order_by(inbound_activity::published.desc())
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Schedule another retry, or mark as dead letter if there are no retries left.
+    pub fn mark_failed(&self, error: String, context: &IbisContext) -> BackendResult<()> {
+        use inbound_activity::dsl;
+        let mut conn = context.db_pool.get()?;
+        let max_retries = context.conf.options.inbound_activity_retries as i32;
+        let attempts = self.attempts + 1;
+        update(inbound_activity::table.find(self.id))
+            .set((
+                dsl::error.eq(error),
+                dsl::attempts.eq(attempts),
+                dsl::next_retry.eq(Utc::now() + retry_backoff(attempts)),
+                dsl::dead.eq(attempts > max_retries),
+            ))
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    pub fn delete(id: InboundActivityId, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        delete(inbound_activity::table.find(id)).execute(conn.deref_mut())?;
+        Ok(())
+    }
+}
@@ -10,7 +10,7 @@ use crate::{
 };
 use chrono::{DateTime, Utc};
 use diesel::{
-    dsl::{count, max, not},
+    dsl::{count, count_distinct, count_star, max, not},
     *,
 };
 use ibis_database_schema::{article, comment, edit, instance, instance_follow};
@@ -30,6 +30,7 @@ pub struct DbInstanceForm {
     pub local: bool,
     pub instances_url: DbUrl,
     pub name: Option<String>,
+    pub icon_url: Option<DbUrl>,
 }
 
 #[derive(Debug, Clone, AsChangeset)]
@@ -37,6 +38,7 @@ pub struct DbInstanceForm {
 pub struct DbInstanceUpdateForm {
     pub topic: Option<String>,
     pub name: Option<String>,
+    pub icon_url: Option<DbUrl>,
 }
 
 #[derive(Debug)]
@@ -69,6 +71,21 @@ impl Instance {
             .get_result(conn.deref_mut())?)
     }
 
+    /// Whether new users can register, as set by the admin or otherwise from the config
+    pub fn registration_open(context: &IbisContext) -> BackendResult<bool> {
+        Ok(Self::read_local(context)?
+            .registration_open
+            .unwrap_or(context.conf.options.registration_open))
+    }
+
+    pub fn set_registration_open(open: bool, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(update(instance::table)
+            .filter(instance::local)
+            .set(instance::registration_open.eq(open))
+            .get_result(conn.deref_mut())?)
+    }
+
     pub fn read_from_ap_id(ap_id: &DbUrl, context: &IbisContext) -> BackendResult<Instance> {
         let mut conn = context.db_pool.get()?;
         Ok(instance::table
@@ -76,6 +93,16 @@ impl Instance {
             .get_result(conn.deref_mut())?)
     }
 
+    /// Update the refresh time without changing anything else, for example after fetching the
+    /// remote instance failed.
+    pub fn mark_refreshed(ap_id: &DbUrl, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        update(instance::table.filter(instance::ap_id.eq(ap_id)))
+            .set(instance::last_refreshed_at.eq(Utc::now()))
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
     pub fn read_local(context: &IbisContext) -> BackendResult<Self> {
         let mut conn = context.db_pool.get()?;
         Ok(instance::table
@@ -118,11 +145,16 @@ impl Instance {
             .limit(5)
             .select(article::all_columns)
             .get_results(conn.deref_mut())?;
+        let (follower_count, following_count) = instance.follow_counts(context)?;
 
         Ok(InstanceView {
             instance,
             articles,
             following: following.unwrap_or_default(),
+            follower_count,
+            following_count,
+            default_sort: context.conf.options.default_article_sort,
+            default_page_size: context.conf.options.default_page_size,
         })
     }
 
@@ -173,6 +205,49 @@ impl Instance {
             .get_results(conn.deref_mut())?)
     }
 
+    /// Followers of the instance, ordered by username
+    pub fn list_followers(
+        id_: InstanceId,
+        limit: i64,
+        offset: i64,
+        context: &IbisContext,
+    ) -> BackendResult<Vec<Person>> {
+        use ibis_database_schema::person;
+        use instance_follow::dsl::{follower_id, instance_id, pending};
+        let mut conn = context.db_pool.get()?;
+        Ok(instance_follow::table
+            .inner_join(person::table.on(follower_id.eq(person::id)))
+            .filter(instance_id.eq(id_))
+            .filter(not(pending))
+            .order_by((person::username, person::id))
+            .limit(limit)
+            .offset(offset)
+            .select(person::all_columns)
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Number of users following this instance, and number of instances followed by users of
+    /// this instance. Pending follows are not counted. Users are matched to the instance by the
+    /// domain of their id, so for remote instances only follows known to this instance are
+    /// counted.
+    pub fn follow_counts(&self, context: &IbisContext) -> BackendResult<(i64, i64)> {
+        use ibis_database_schema::person;
+        let mut conn = context.db_pool.get()?;
+        let followers = instance_follow::table
+            .filter(instance_follow::instance_id.eq(self.id))
+            .filter(not(instance_follow::pending))
+            .select(count_star())
+            .first(conn.deref_mut())?;
+        let users = format!("{}%", self.ap_id.inner().join("/")?);
+        let following = instance_follow::table
+            .inner_join(person::table)
+            .filter(person::ap_id.like(users))
+            .filter(not(instance_follow::pending))
+            .select(count_distinct(instance_follow::instance_id))
+            .first(conn.deref_mut())?;
+        Ok((followers, following))
+    }
+
     pub fn read_local_followers_count(context: &IbisContext) -> BackendResult<i64> {
         let mut conn = context.db_pool.get()?;
         Ok(instance_follow::table
@@ -182,6 +257,17 @@ impl Instance {
             .first(conn.deref_mut())?)
     }
 
+    /// Number of remote instances which are followed by at least one local user.
+    pub fn read_following_count(context: &IbisContext) -> BackendResult<i64> {
+        let mut conn = context.db_pool.get()?;
+        Ok(instance_follow::table
+            .inner_join(instance::table)
+            .filter(not(instance::local))
+            .filter(not(instance_follow::pending))
+            .select(count_distinct(instance_follow::instance_id))
+            .first(conn.deref_mut())?)
+    }
+
     pub fn list(context: &IbisContext) -> BackendResult<Vec<Instance>> {
         let mut conn = context.db_pool.get()?;
         Ok(instance::table
@@ -215,10 +301,15 @@ impl Instance {
                 .limit(5)
                 .select(article::all_columns)
                 .get_results(conn.deref_mut())?;
+            let (follower_count, following_count) = instance.follow_counts(context)?;
             res.push(InstanceView {
                 instance,
                 articles,
                 following: false,
+                follower_count,
+                following_count,
+                default_sort: context.conf.options.default_article_sort,
+                default_page_size: context.conf.options.default_page_size,
             });
         }
 
@@ -1,4 +1,5 @@
-use crate::{config::IbisConfig, error::BackendResult};
+use crate::{common::newtypes::ArticleId, config::IbisConfig, error::BackendResult};
+use blocked_instance::DomainCache;
 use diesel::{
     PgConnection,
     QueryDsl,
@@ -11,15 +12,30 @@ use diesel::{
 use diesel_migrations::{EmbeddedMigrations, MigrationHarness, embed_migrations};
 use ibis_database_schema::jwt_secret;
 use reqwest::Client;
-use std::{env::var, ops::DerefMut};
+use std::{
+    collections::HashMap,
+    env::var,
+    ops::DerefMut,
+    sync::{Arc, Mutex},
+};
 
+pub mod allowed_instance;
 pub mod article;
+pub mod article_move;
+pub mod attachment;
+pub mod auth_token;
+pub mod blocked_instance;
+pub mod category;
 pub mod comment;
 pub mod conflict;
+pub mod delivery_queue;
+pub mod delivery_status;
 pub mod edit;
+pub mod inbound_activity;
 pub mod instance;
 pub mod instance_stats;
 pub mod notifications;
+pub mod revision_tag;
 pub mod sent_activity;
 pub mod user;
 
@@ -32,6 +48,12 @@ pub struct IbisContext {
     pub db_pool: DbPool,
     pub conf: IbisConfig,
     pub client: Client,
+    pub(crate) blocklist: DomainCache,
+    pub(crate) allowlist: DomainCache,
+    /// Article views which are not written to the database yet, see [Article::add_view]
+    ///
+    /// [Article::add_view]: crate::common::article::Article::add_view
+    pub(crate) pending_views: Arc<Mutex<HashMap<ArticleId, i64>>>,
 }
 
 impl IbisContext {
@@ -57,6 +79,9 @@ impl IbisContext {
             db_pool,
             conf: config,
             client,
+            blocklist: Default::default(),
+            allowlist: Default::default(),
+            pending_views: Default::default(),
         })
     }
 }
@@ -79,3 +104,9 @@ fn is_conflict<T>(val: &Result<T, Error>) -> bool {
         Some(Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _))
     )
 }
+
+/// Exponential backoff for retrying failed activities, starting at one minute up to about
+/// 17 hours
+fn retry_backoff(attempts: i32) -> chrono::Duration {
+    chrono::Duration::minutes(1 << attempts.clamp(1, 11).saturating_sub(1))
+}
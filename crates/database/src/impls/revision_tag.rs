@@ -0,0 +1,46 @@
+use crate::{
+    common::{
+        article::{EditVersion, RevisionTag},
+        newtypes::{ArticleId, PersonId},
+    },
+    error::BackendResult,
+    impls::IbisContext,
+};
+use diesel::{ExpressionMethods, Insertable, QueryDsl, RunQueryDsl, insert_into, upsert::excluded};
+use ibis_database_schema::revision_tag;
+use std::ops::DerefMut;
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = revision_tag, check_for_backend(diesel::pg::Pg))]
+pub struct DbRevisionTagForm {
+    pub article_id: ArticleId,
+    pub version: EditVersion,
+    pub tag: String,
+    pub creator_id: PersonId,
+}
+
+impl RevisionTag {
+    /// Tag the given version. If the tag already exists for this article it is moved to the
+    /// new version.
+    pub fn create(form: &DbRevisionTagForm, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(insert_into(revision_tag::table)
+            .values(form)
+            .on_conflict((revision_tag::article_id, revision_tag::tag))
+            .do_update()
+            .set((
+                revision_tag::version.eq(excluded(revision_tag::version)),
+                revision_tag::creator_id.eq(excluded(revision_tag::creator_id)),
+                revision_tag::published.eq(excluded(revision_tag::published)),
+            ))
+            .get_result(conn.deref_mut())?)
+    }
+
+    pub fn list_for_article(id: ArticleId, context: &IbisContext) -> BackendResult<Vec<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(revision_tag::table
+            .filter(revision_tag::article_id.eq(id))
+            .order_by(revision_tag::published)
+            .get_results(conn.deref_mut())?)
+    }
+}
@@ -3,10 +3,10 @@ use crate::{
     common::{
         instance::InstanceFollow,
         newtypes::{LocalUserId, PersonId},
-        user::{LocalUser, LocalUserView, Person},
+        user::{LocalUser, LocalUserView, Person, UserRole},
         utils::http_protocol_str,
     },
-    error::BackendResult,
+    error::{BackendError, BackendResult},
     impls::{IbisContext, coalesce, lower},
     utils::generate_keypair,
 };
@@ -16,6 +16,7 @@ use chrono::{DateTime, Utc};
 use diesel::{
     AsChangeset,
     BoolExpressionMethods,
+    Connection,
     ExpressionMethods,
     Insertable,
     JoinOnDsl,
@@ -38,12 +39,18 @@ use ibis_database_schema::{
 use std::ops::DerefMut;
 use url::Url;
 
+/// Username of the placeholder for deleted accounts, see [Person::ghost]
+const GHOST_USERNAME: &str = "ghost";
+
+/// Username of the shared user for edits without login, see [Person::anonymous]
+const ANONYMOUS_USERNAME: &str = "anonymous";
+
 #[derive(Debug, Clone, Insertable, AsChangeset)]
 #[diesel(table_name = local_user, check_for_backend(diesel::pg::Pg))]
 pub struct LocalUserInsertForm {
     pub password_encrypted: Option<String>,
     pub person_id: PersonId,
-    pub admin: bool,
+    pub role: UserRole,
     pub email: Option<String>,
     pub email_verified: bool,
 }
@@ -112,15 +119,40 @@ impl Person {
         Ok(person::table.find(id).get_result(conn.deref_mut())?)
     }
 
+    /// Ban or unban the user. If `expires` is given the ban is temporary.
+    pub fn update_ban(
+        id: PersonId,
+        banned: bool,
+        expires: Option<DateTime<Utc>>,
+        context: &IbisContext,
+    ) -> BackendResult<Person> {
+        let mut conn = context.db_pool.get()?;
+        let expires = expires.filter(|_| banned);
+        Ok(diesel::update(person::table.find(id))
+            .set((person::banned.eq(banned), person::ban_expires.eq(expires)))
+            .get_result(conn.deref_mut())?)
+    }
+
     pub fn read_admin(context: &IbisContext) -> BackendResult<Person> {
         let mut conn = context.db_pool.get()?;
         Ok(person::table
             .inner_join(local_user::table)
-            .filter(local_user::admin)
+            .filter(local_user::role.eq(UserRole::Admin))
             .select(person::all_columns)
             .get_result(conn.deref_mut())?)
     }
 
+    /// All local users with admin role, oldest first
+    pub fn list_admins(context: &IbisContext) -> BackendResult<Vec<Person>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(person::table
+            .inner_join(local_user::table)
+            .filter(local_user::role.eq(UserRole::Admin))
+            .order_by(person::id)
+            .select(person::all_columns)
+            .get_results(conn.deref_mut())?)
+    }
+
     pub fn read_from_ap_id(ap_id: &DbUrl, context: &IbisContext) -> BackendResult<Person> {
         let mut conn = context.db_pool.get()?;
         Ok(person::table
@@ -128,6 +160,16 @@ impl Person {
             .get_result(conn.deref_mut())?)
     }
 
+    /// Update the refresh time without changing anything else, for example after fetching the
+    /// remote user failed.
+    pub fn mark_refreshed(ap_id: &DbUrl, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        diesel::update(person::table.filter(person::ap_id.eq(ap_id)))
+            .set(person::last_refreshed_at.eq(Utc::now()))
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
     pub fn read_from_name(
         username: &str,
         domain: &Option<String>,
@@ -186,7 +228,27 @@ impl Person {
 
     /// Ghost user serves as placeholder for deleted accounts
     pub fn ghost(context: &IbisContext) -> BackendResult<Person> {
-        Self::get_or_create_person_with_name("ghost", context)
+        Self::get_or_create_person_with_name(GHOST_USERNAME, context)
+    }
+
+    /// Shared user for edits made without login, if `allow_anonymous_edits` is enabled
+    pub fn anonymous(context: &IbisContext) -> BackendResult<Person> {
+        use diesel::dsl::{exists, select};
+        let person = Self::get_or_create_person_with_name(ANONYMOUS_USERNAME, context)?;
+        // The name is reserved, but an account with this name may have been registered before.
+        // Anonymous edits must never be attributed to it.
+        let mut conn = context.db_pool.get()?;
+        let is_account = select(exists(
+            local_user::table.filter(local_user::person_id.eq(person.id)),
+        ))
+        .get_result::<bool>(conn.deref_mut())?;
+        if is_account {
+            return Err(anyhow!(
+                "Anonymous edits are unavailable because there is an account named {ANONYMOUS_USERNAME}"
+            )
+            .into());
+        }
+        Ok(person)
     }
 
     pub fn wikibot(context: &IbisContext) -> BackendResult<Person> {
@@ -251,7 +313,7 @@ impl LocalUserView {
     pub fn create(
         username: String,
         password: Option<String>,
-        admin: bool,
+        role: UserRole,
         email: Option<String>,
         context: &IbisContext,
     ) -> BackendResult<Self> {
@@ -283,7 +345,7 @@ impl LocalUserView {
         let local_user_form = LocalUserInsertForm {
             password_encrypted: password.map(|p| hash(p, DEFAULT_COST)).transpose()?,
             person_id: person.id,
-            admin,
+            role,
             email,
             email_verified: false,
         };
@@ -322,11 +384,20 @@ impl LocalUserView {
 impl LocalUser {
     pub fn check_username_taken(username: &str, context: &IbisContext) -> BackendResult<()> {
         use diesel::dsl::{exists, select};
+        let username = username.to_lowercase();
+        let reserved = [
+            GHOST_USERNAME,
+            ANONYMOUS_USERNAME,
+            &context.conf.setup.wiki_bot_name,
+        ];
+        if reserved.iter().any(|r| r.to_lowercase() == username) {
+            return Err(anyhow!("Username is reserved").into());
+        }
         let mut conn = context.db_pool.get()?;
         select(not(exists(
             person::table
                 .filter(person::local)
-                .filter(lower(person::username).eq(username.to_lowercase())),
+                .filter(lower(person::username).eq(username)),
         )))
         .get_result::<bool>(conn.deref_mut())?
         .then_some(())
@@ -344,6 +415,32 @@ impl LocalUser {
         .ok_or(anyhow!("Email is taken").into())
     }
 
+    /// Change the role of the user. Fails if this would demote the last remaining admin.
+    pub fn update_role(
+        person_id: PersonId,
+        role: UserRole,
+        context: &IbisContext,
+    ) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        conn.transaction::<_, BackendError, _>(|conn| {
+            // Lock the admin rows, so that concurrent demotions wait for each other and the
+            // second one sees the result of the first.
+            let admins: Vec<PersonId> = local_user::table
+                .filter(local_user::role.eq(UserRole::Admin))
+                .select(local_user::person_id)
+                .for_update()
+                .get_results(conn)?;
+            if role != UserRole::Admin && admins.iter().all(|a| *a == person_id) {
+                return Err(anyhow!("Can't remove the last admin").into());
+            }
+            Ok(
+                diesel::update(local_user::table.filter(local_user::person_id.eq(person_id)))
+                    .set(local_user::role.eq(role))
+                    .get_result(conn)?,
+            )
+        })
+    }
+
     pub fn update_password(
         password: String,
         id: LocalUserId,
@@ -1,4 +1,8 @@
-use crate::{error::BackendResult, impls::DbPool};
+use crate::{
+    common::article::{Article, Conflict},
+    error::BackendResult,
+    impls::{DbPool, IbisContext, auth_token::AuthToken},
+};
 use clokwerk::{Scheduler, TimeUnits};
 use diesel::{
     ExpressionMethods,
@@ -12,18 +16,37 @@ use ibis_database_schema::sent_activity;
 use log::{error, info};
 use std::time::Duration;
 
-pub fn start(pool: DbPool) {
+pub fn start(context: IbisContext) {
     let mut scheduler = Scheduler::new();
+    let pool = context.db_pool.clone();
+    let options = context.conf.options.clone();
 
     active_counts(&pool).inspect_err(|e| error!("{e}")).ok();
     cleanup_sent_activities(&pool)
         .inspect_err(|e| error!("{e}"))
         .ok();
+    cleanup_conflicts(&pool, options.conflict_retention_days)
+        .inspect_err(|e| error!("{e}"))
+        .ok();
+    cleanup_auth_tokens(&pool)
+        .inspect_err(|e| error!("{e}"))
+        .ok();
+    scheduler.every(1.minute()).run(move || {
+        Article::flush_views(&context)
+            .inspect_err(|e| error!("{e}"))
+            .ok();
+    });
     scheduler.every(1.hour()).run(move || {
         active_counts(&pool).inspect_err(|e| error!("{e}")).ok();
         cleanup_sent_activities(&pool)
             .inspect_err(|e| error!("{e}"))
             .ok();
+        cleanup_conflicts(&pool, options.conflict_retention_days)
+            .inspect_err(|e| error!("{e}"))
+            .ok();
+        cleanup_auth_tokens(&pool)
+            .inspect_err(|e| error!("{e}"))
+            .ok();
     });
 
     let _ = scheduler.watch_thread(Duration::from_secs(60));
@@ -59,16 +82,36 @@ fn cleanup_sent_activities(pool: &DbPool) -> BackendResult<()> {
     Ok(())
 }
 
+fn cleanup_conflicts(pool: &DbPool, retention_days: u32) -> BackendResult<()> {
+    if retention_days == 0 {
+        return Ok(());
+    }
+    info!("Cleanup stale edit conflicts");
+    let count = Conflict::delete_stale(retention_days, pool)?;
+    info!("Done with cleaning up {count} edit conflicts");
+    Ok(())
+}
+
+fn cleanup_auth_tokens(pool: &DbPool) -> BackendResult<()> {
+    info!("Cleanup expired login sessions");
+    let count = AuthToken::delete_expired(pool)?;
+    info!("Done with cleaning up {count} login sessions");
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{config::IbisConfig, impls::IbisContext};
+    use crate::config::IbisConfig;
 
     #[test]
     fn test_scheduled_tasks() -> BackendResult<()> {
         let context = IbisContext::init(IbisConfig::read()?, false)?;
         active_counts(&context.db_pool)?;
         cleanup_sent_activities(&context.db_pool)?;
+        cleanup_conflicts(&context.db_pool, 30)?;
+        cleanup_auth_tokens(&context.db_pool)?;
+        Article::flush_views(&context)?;
         Ok(())
     }
 }
@@ -1,4 +1,8 @@
-use crate::{DbUrl, error::BackendResult};
+use crate::{
+    DbUrl,
+    common::{article::ProtectionLevel, user::UserRole},
+    error::BackendResult,
+};
 use activitypub_federation::{
     fetch::{collection_id::CollectionId, object_id::ObjectId},
     http_signatures::{Keypair, generate_actor_keypair},
@@ -31,6 +35,36 @@ where
     }
 }
 
+impl ToSql<Text, Pg> for UserRole {
+    fn to_sql(&self, out: &mut Output<Pg>) -> diesel::serialize::Result {
+        <str as ToSql<Text, Pg>>::to_sql(self.as_str(), &mut out.reborrow())
+    }
+}
+
+impl<DB: Backend> FromSql<Text, DB> for UserRole
+where
+    String: FromSql<Text, DB>,
+{
+    fn from_sql(value: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+        Ok(String::from_sql(value)?.parse()?)
+    }
+}
+
+impl ToSql<Text, Pg> for ProtectionLevel {
+    fn to_sql(&self, out: &mut Output<Pg>) -> diesel::serialize::Result {
+        <str as ToSql<Text, Pg>>::to_sql(self.as_str(), &mut out.reborrow())
+    }
+}
+
+impl<DB: Backend> FromSql<Text, DB> for ProtectionLevel
+where
+    String: FromSql<Text, DB>,
+{
+    fn from_sql(value: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+        Ok(String::from_sql(value)?.parse()?)
+    }
+}
+
 #[expect(clippy::from_over_into)]
 impl Into<DbUrl> for Url {
     fn into(self) -> DbUrl {
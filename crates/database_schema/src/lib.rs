@@ -1,5 +1,12 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    allowed_instance (domain) {
+        domain -> Text,
+        published -> Timestamptz,
+    }
+}
+
 diesel::table! {
     article (id) {
         id -> Int4,
@@ -9,11 +16,24 @@ diesel::table! {
         ap_id -> Varchar,
         instance_id -> Int4,
         local -> Bool,
-        protected -> Bool,
         published -> Timestamptz,
         removed -> Bool,
         updated -> Timestamptz,
         pending -> Bool,
+        forked_by -> Nullable<Int4>,
+        redirect_to -> Nullable<Int4>,
+        protection -> Varchar,
+        removed_at -> Nullable<Timestamptz>,
+        last_refreshed_at -> Timestamptz,
+        signature -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    article_category (article_id, category_id) {
+        article_id -> Int4,
+        category_id -> Int4,
+        from_marker -> Bool,
     }
 }
 
@@ -24,6 +44,66 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    article_move (id) {
+        id -> Int4,
+        article_id -> Int4,
+        creator_id -> Int4,
+        old_title -> Text,
+        new_title -> Text,
+        published -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    article_subscriber (article_id, person_id) {
+        article_id -> Int4,
+        person_id -> Int4,
+        published -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    article_views (article_id) {
+        article_id -> Int4,
+        views -> Int8,
+    }
+}
+
+diesel::table! {
+    attachment (id) {
+        id -> Int4,
+        article_id -> Int4,
+        url -> Text,
+        alt_text -> Nullable<Text>,
+        media_type -> Nullable<Text>,
+        published -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    auth_token (token) {
+        token -> Text,
+        person_id -> Int4,
+        published -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    blocked_instance (domain) {
+        domain -> Text,
+        published -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    category (id) {
+        id -> Int4,
+        name -> Text,
+        published -> Timestamptz,
+    }
+}
+
 diesel::table! {
     comment (id) {
         id -> Int4,
@@ -51,6 +131,45 @@ diesel::table! {
         article_id -> Int4,
         previous_version_id -> Uuid,
         published -> Timestamptz,
+        attempts -> Int4,
+        updated -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    conflict_log (id) {
+        id -> Int4,
+        conflict_id -> Int4,
+        article_id -> Int4,
+        published -> Timestamptz,
+        resolved -> Nullable<Timestamptz>,
+        abandoned -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    delivery_queue (id) {
+        id -> Int4,
+        #[max_length = 255]
+        activity_id -> Varchar,
+        #[max_length = 255]
+        actor_id -> Varchar,
+        inbox -> Text,
+        json -> Text,
+        error -> Text,
+        attempts -> Int4,
+        next_retry -> Timestamptz,
+        dead -> Bool,
+        published -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    delivery_status (instance_id) {
+        instance_id -> Int4,
+        last_success -> Nullable<Timestamptz>,
+        last_failure -> Nullable<Timestamptz>,
+        failures -> Int4,
     }
 }
 
@@ -67,6 +186,9 @@ diesel::table! {
         previous_version_id -> Uuid,
         published -> Timestamptz,
         pending -> Bool,
+        sequence -> Int8,
+        minor -> Bool,
+        anonymous_id -> Nullable<Text>,
     }
 }
 
@@ -80,6 +202,20 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    inbound_activity (id) {
+        id -> Int4,
+        #[max_length = 255]
+        ap_id -> Varchar,
+        json -> Text,
+        error -> Text,
+        attempts -> Int4,
+        next_retry -> Timestamptz,
+        dead -> Bool,
+        published -> Timestamptz,
+    }
+}
+
 diesel::table! {
     instance (id) {
         id -> Int4,
@@ -98,6 +234,9 @@ diesel::table! {
         #[max_length = 255]
         instances_url -> Varchar,
         name -> Nullable<Text>,
+        registration_open -> Nullable<Bool>,
+        #[max_length = 255]
+        icon_url -> Nullable<Varchar>,
     }
 }
 
@@ -132,10 +271,12 @@ diesel::table! {
         id -> Int4,
         password_encrypted -> Nullable<Text>,
         person_id -> Int4,
-        admin -> Bool,
         email -> Nullable<Text>,
         email_verified -> Bool,
         email_notifications -> Bool,
+        published -> Timestamptz,
+        #[max_length = 20]
+        role -> Varchar,
     }
 }
 
@@ -187,6 +328,8 @@ diesel::table! {
         display_name -> Nullable<Varchar>,
         #[max_length = 1000]
         bio -> Nullable<Varchar>,
+        banned -> Bool,
+        ban_expires -> Nullable<Timestamptz>,
     }
 }
 
@@ -197,6 +340,17 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    revision_tag (id) {
+        id -> Int4,
+        article_id -> Int4,
+        version -> Uuid,
+        tag -> Text,
+        creator_id -> Int4,
+        published -> Timestamptz,
+    }
+}
+
 diesel::table! {
     sent_activity (id) {
         #[max_length = 255]
@@ -207,12 +361,24 @@ diesel::table! {
 }
 
 diesel::joinable!(article -> instance (instance_id));
+diesel::joinable!(article -> person (forked_by));
+diesel::joinable!(article_category -> article (article_id));
+diesel::joinable!(article_category -> category (category_id));
 diesel::joinable!(article_follow -> article (article_id));
 diesel::joinable!(article_follow -> local_user (local_user_id));
+diesel::joinable!(article_move -> article (article_id));
+diesel::joinable!(article_move -> person (creator_id));
+diesel::joinable!(article_subscriber -> article (article_id));
+diesel::joinable!(article_subscriber -> person (person_id));
+diesel::joinable!(article_views -> article (article_id));
+diesel::joinable!(attachment -> article (article_id));
+diesel::joinable!(auth_token -> person (person_id));
 diesel::joinable!(comment -> article (article_id));
 diesel::joinable!(comment -> person (creator_id));
 diesel::joinable!(conflict -> article (article_id));
 diesel::joinable!(conflict -> person (creator_id));
+diesel::joinable!(conflict_log -> article (article_id));
+diesel::joinable!(delivery_status -> instance (instance_id));
 diesel::joinable!(edit -> article (article_id));
 diesel::joinable!(edit -> person (creator_id));
 diesel::joinable!(email_verification -> local_user (local_user_id));
@@ -227,14 +393,29 @@ diesel::joinable!(notification -> local_user (local_user_id));
 diesel::joinable!(notification -> person (creator_id));
 diesel::joinable!(oauth_account -> local_user (local_user_id));
 diesel::joinable!(password_reset_request -> local_user (local_user_id));
+diesel::joinable!(revision_tag -> article (article_id));
+diesel::joinable!(revision_tag -> person (creator_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    allowed_instance,
     article,
+    article_category,
     article_follow,
+    article_move,
+    article_subscriber,
+    article_views,
+    attachment,
+    auth_token,
+    blocked_instance,
+    category,
     comment,
     conflict,
+    conflict_log,
+    delivery_queue,
+    delivery_status,
     edit,
     email_verification,
+    inbound_activity,
     instance,
     instance_follow,
     instance_stats,
@@ -245,5 +426,6 @@ diesel::allow_tables_to_appear_in_same_query!(
     password_reset_request,
     person,
     person_follow,
+    revision_tag,
     sent_activity,
 );
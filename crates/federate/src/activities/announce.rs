@@ -12,7 +12,7 @@ use activitypub_federation::{
     traits::{Activity, Object},
 };
 use ibis_database::{
-    common::instance::Instance,
+    common::{article::Article, instance::Instance},
     error::{BackendError, BackendResult},
     impls::IbisContext,
 };
@@ -49,12 +49,25 @@ impl AnnounceActivity {
             id,
         };
 
-        // Send to followers of local instance
-        let follower_inboxes: Vec<_> = Instance::read_followers(instance.id, context)?
+        // Send to followers of local instance. Followers which share an inbox are collapsed by
+        // `send_ibis_activity`.
+        let mut inboxes: Vec<_> = Instance::read_followers(instance.id, context)?
             .into_iter()
             .map(|f| f.inbox_url())
             .collect();
-        send_ibis_activity(&instance, announce, follower_inboxes, context).await?;
+        // and to remote users who only subscribed to the affected article
+        let article = announce
+            .object
+            .article_ap_id()
+            .and_then(|id| Article::read_from_ap_id(&id.clone().into(), context).ok());
+        if let Some(article) = article {
+            inboxes.extend(
+                Article::read_subscribers(article.id, context)?
+                    .into_iter()
+                    .map(|p| p.inbox_url()),
+            );
+        }
+        send_ibis_activity(&instance, announce, inboxes, context).await?;
 
         Ok(())
     }
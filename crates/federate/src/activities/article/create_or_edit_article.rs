@@ -10,6 +10,7 @@ use crate::{
     },
     routes::AnnouncableActivities,
     send_ibis_activity,
+    validate::validate_article_size,
 };
 use activitypub_federation::{
     config::Data,
@@ -23,8 +24,9 @@ use chrono::Utc;
 use diffy::{Patch, apply};
 use ibis_database::{
     common::{
-        article::{Article, Edit, can_edit_article},
+        article::{Article, Edit, ProtectionLevel, can_edit_article},
         instance::Instance,
+        user::UserRole,
     },
     error::{BackendError, BackendResult},
     impls::{IbisContext, article::DbArticleForm},
@@ -104,12 +106,25 @@ impl Activity for CreateOrEditArticle {
 
     async fn verify(&self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
         let article = Article::read_from_ap_id(&self.object.object.clone().into(), context);
+        let creator = self.actor.dereference(context).await?;
         if self.kind == CreateOrEditType::Create {
             if article.is_ok() {
                 return Err(anyhow!("Article already exists").into());
             }
+            if creator.is_banned() {
+                return Err(anyhow!("User is banned").into());
+            }
         } else {
-            can_edit_article(&article?, false)?;
+            // Account age of remote users is unknown, so they are treated as new accounts. Email
+            // verification is up to their home instance.
+            can_edit_article(
+                &article?,
+                UserRole::Editor,
+                creator.is_banned(),
+                Utc::now(),
+                true,
+                &context.conf.options,
+            )?;
         }
         Ok(())
     }
@@ -131,9 +146,10 @@ impl Activity for CreateOrEditArticle {
                 ap_id: self.object.object.clone().into(),
                 instance_id: instance.id,
                 local: instance.local,
-                protected: false,
+                protection: ProtectionLevel::Unprotected,
                 updated: Utc::now(),
                 pending: false,
+                forked_by: None,
             };
             let creator = self.actor.dereference(context).await?;
             Article::create_or_update(form, creator.id, context).await?
@@ -152,6 +168,7 @@ impl Activity for CreateOrEditArticle {
 
         match apply(&article.text, &patch) {
             Ok(applied) => {
+                validate_article_size(&applied, &context.conf.options)?;
                 let edit = EditWrapper::from_json(self.object.clone(), context).await?;
                 let article = Article::update_text(edit.article_id, &applied, context)?;
                 if article.local {
@@ -47,7 +47,7 @@ impl RemoveArticle {
             object,
             kind: Default::default(),
             id,
-            summary: "Removed by admin".to_string(),
+            summary: "Removed by moderator".to_string(),
         })
     }
     pub async fn send(
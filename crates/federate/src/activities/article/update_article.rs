@@ -56,6 +56,8 @@ impl UpdateArticle {
     }
 
     pub async fn send(article: ArticleWrapper, context: &Data<IbisContext>) -> BackendResult<()> {
+        // the article was changed, so it needs a new signature
+        let article = article.sign(context)?;
         let update = Self::new(article, context).await?;
         Self::send_update(update, context).await?;
         Ok(())
@@ -83,6 +83,10 @@ impl Activity for Accept {
     async fn receive(self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
         match self.actor.dereference(context).await? {
             Either::Left(instance) => {
+                // follow of a single article, nothing to store
+                if self.object.object.inner() != instance.ap_id.inner() {
+                    return Ok(());
+                }
                 // add to follows
                 let person = self.object.actor.dereference_local(context).await?;
                 Instance::follow(&person, &instance, false, context)?;
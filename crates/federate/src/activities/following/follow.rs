@@ -1,6 +1,6 @@
 use super::InstanceOrPerson;
 use crate::{
-    activities::following::accept::Accept,
+    activities::following::{accept::Accept, reject::RejectFollow},
     generate_activity_id,
     objects::{instance::InstanceWrapper, user::PersonWrapper},
     send_ibis_activity,
@@ -15,7 +15,7 @@ use activitypub_federation::{
 use anyhow::anyhow;
 use either::Either;
 use ibis_database::{
-    common::{instance::Instance, user::Person},
+    common::{article::Article, instance::Instance, user::Person},
     error::{BackendError, BackendResult},
     impls::IbisContext,
 };
@@ -52,6 +52,19 @@ impl Follow {
         })
     }
 
+    /// Follow a single article on the given instance, to receive its updates without following
+    /// the whole instance.
+    pub fn new_for_article(
+        actor: &PersonWrapper,
+        article: &Article,
+        to: &InstanceWrapper,
+        context: &Data<IbisContext>,
+    ) -> BackendResult<Self> {
+        let mut follow = Self::new(actor, to, context)?;
+        follow.object = article.ap_id.clone().into();
+        Ok(follow)
+    }
+
     pub async fn send(
         actor: &PersonWrapper,
         to: &InstanceWrapper,
@@ -61,6 +74,17 @@ impl Follow {
         send_ibis_activity(actor, follow, vec![to.shared_inbox_or_inbox()], context).await?;
         Ok(())
     }
+
+    pub async fn send_for_article(
+        actor: &PersonWrapper,
+        article: &Article,
+        to: &InstanceWrapper,
+        context: &Data<IbisContext>,
+    ) -> BackendResult<()> {
+        let follow = Self::new_for_article(actor, article, to, context)?;
+        send_ibis_activity(actor, follow, vec![to.shared_inbox_or_inbox()], context).await?;
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -82,6 +106,20 @@ impl Activity for Follow {
 
     async fn receive(self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
         let actor = self.actor.dereference(context).await?;
+        if let Ok(article) = Article::read_from_ap_id(&self.object.inner().clone().into(), context)
+        {
+            if !article.local {
+                return Err(anyhow!("invalid follow").into());
+            }
+            let instance: InstanceWrapper = Instance::read_local(context)?.into();
+            if !context.conf.options.accept_follows {
+                RejectFollow::send(instance, self, context).await?;
+                return Ok(());
+            }
+            Article::add_subscriber(article.id, actor.id, context)?;
+            Accept::send(Either::Left(instance), self, context).await?;
+            return Ok(());
+        }
         let object = self.object.dereference_local(context).await?;
         match object {
             Either::Left(instance) => {
@@ -89,6 +127,10 @@ impl Activity for Follow {
                     return Err(anyhow!("invalid follow").into());
                 }
                 verify_urls_match(self.object.inner(), instance.ap_id.inner())?;
+                if !context.conf.options.accept_follows {
+                    RejectFollow::send(instance, self, context).await?;
+                    return Ok(());
+                }
                 Instance::follow(&actor, &instance, false, context)?;
                 Accept::send(Either::Left(instance), self, context).await?;
             }
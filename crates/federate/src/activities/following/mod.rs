@@ -3,6 +3,7 @@ use either::Either;
 
 pub mod accept;
 pub mod follow;
+pub mod reject;
 pub mod undo_follow;
 
 type InstanceOrPerson = Either<InstanceWrapper, PersonWrapper>;
@@ -0,0 +1,81 @@
+use crate::{
+    activities::following::follow::Follow,
+    generate_activity_id,
+    objects::{instance::InstanceWrapper, user::PersonWrapper},
+    send_ibis_activity,
+};
+use activitypub_federation::{
+    config::Data,
+    fetch::object_id::ObjectId,
+    kinds::activity::RejectType,
+    protocol::helpers::deserialize_skip_error,
+    traits::{Activity, Actor},
+};
+use ibis_database::{
+    common::instance::Instance,
+    error::{BackendError, BackendResult},
+    impls::IbisContext,
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Sent instead of [Accept](super::accept::Accept) if the local instance doesn't accept follows.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RejectFollow {
+    actor: ObjectId<InstanceWrapper>,
+    /// Optional, for compatibility with platforms that always expect recipient field
+    #[serde(deserialize_with = "deserialize_skip_error", default)]
+    pub(crate) to: Option<[ObjectId<PersonWrapper>; 1]>,
+    object: Follow,
+    #[serde(rename = "type")]
+    kind: RejectType,
+    id: Url,
+}
+
+impl RejectFollow {
+    pub async fn send(
+        actor: InstanceWrapper,
+        object: Follow,
+        context: &Data<IbisContext>,
+    ) -> BackendResult<()> {
+        let id = generate_activity_id(context)?;
+        let follower = object.actor.dereference(context).await?;
+        let reject = RejectFollow {
+            actor: actor.ap_id.clone().into(),
+            to: Some([follower.ap_id.clone().into()]),
+            object,
+            kind: Default::default(),
+            id,
+        };
+        let inboxes = vec![follower.shared_inbox_or_inbox()];
+        send_ibis_activity(&actor, reject, inboxes, context).await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Activity for RejectFollow {
+    type DataType = IbisContext;
+    type Error = BackendError;
+
+    fn id(&self) -> &Url {
+        &self.id
+    }
+
+    fn actor(&self) -> &Url {
+        self.actor.inner()
+    }
+
+    async fn verify(&self, _context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn receive(self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        // remove the pending follow
+        let instance = self.actor.dereference(context).await?;
+        let person = self.object.actor.dereference_local(context).await?;
+        Instance::unfollow(&person, &instance, context)?;
+        Ok(())
+    }
+}
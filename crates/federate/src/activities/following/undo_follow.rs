@@ -12,7 +12,7 @@ use activitypub_federation::{
     traits::{Activity, Actor},
 };
 use ibis_database::{
-    common::instance::Instance,
+    common::{article::Article, instance::Instance},
     error::{BackendError, BackendResult},
     impls::IbisContext,
 };
@@ -38,8 +38,27 @@ impl UndoFollow {
         to: &InstanceWrapper,
         context: &Data<IbisContext>,
     ) -> BackendResult<()> {
-        let id = generate_activity_id(context)?;
         let object = Follow::new(actor, to, context)?;
+        Self::send_undo(actor, object, to, context).await
+    }
+
+    pub async fn send_for_article(
+        actor: &PersonWrapper,
+        article: &Article,
+        to: &InstanceWrapper,
+        context: &Data<IbisContext>,
+    ) -> BackendResult<()> {
+        let object = Follow::new_for_article(actor, article, to, context)?;
+        Self::send_undo(actor, object, to, context).await
+    }
+
+    async fn send_undo(
+        actor: &PersonWrapper,
+        object: Follow,
+        to: &InstanceWrapper,
+        context: &Data<IbisContext>,
+    ) -> BackendResult<()> {
+        let id = generate_activity_id(context)?;
         let undo_follow = UndoFollow {
             actor: actor.ap_id.clone().into(),
             to: Some([to.ap_id.clone().into()]),
@@ -79,6 +98,11 @@ impl Activity for UndoFollow {
 
     async fn receive(self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
         let actor = self.actor.dereference(context).await?;
+        let object_id = self.object.object.inner().clone().into();
+        if let Ok(article) = Article::read_from_ap_id(&object_id, context) {
+            Article::remove_subscriber(article.id, actor.id, context)?;
+            return Ok(());
+        }
         let local_instance: InstanceWrapper = Instance::read_local(context)?.into();
         verify_urls_match(self.object.object.inner(), local_instance.ap_id.inner())?;
         Instance::unfollow(&actor, &local_instance, context)?;
@@ -21,12 +21,18 @@ pub mod comment;
 pub mod following;
 pub mod reject;
 
+/// Store a new edit and federate it. Edits to local articles are sent to all instance followers,
+/// together with an `Update` of the article. Edits to remote articles are stored as pending and
+/// sent to the home instance of the article, which then announces them. `anonymous_id` is set for
+/// edits made without login.
 pub async fn submit_article_update(
     new_text: String,
     summary: String,
+    minor: bool,
     previous_version: EditVersion,
     article: &Article,
     person: PersonWrapper,
+    anonymous_id: Option<String>,
     is_create: bool,
     context: &Data<IbisContext>,
 ) -> BackendResult<()> {
@@ -35,10 +41,12 @@ pub async fn submit_article_update(
         person.id,
         &new_text,
         summary,
+        minor,
         previous_version,
         false,
     )?;
 
+    form.anonymous_id = anonymous_id;
     // insert edit to remote instance as pending, so only the creator can see it
     form.pending = !article.local;
     let edit = Edit::create_or_update(&form, true, context).await?;
@@ -87,6 +87,7 @@ impl Activity for RejectEdit {
             creator_id: creator.id,
             article_id: article.id,
             previous_version_id: self.object.previous_version,
+            attempts: 0,
         };
         Conflict::create(&form, context)?;
         Ok(())
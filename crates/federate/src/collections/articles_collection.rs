@@ -1,14 +1,15 @@
 use crate::{activities::article::update_article::UpdateArticle, objects::article::ArticleWrapper};
 use activitypub_federation::{
     config::Data,
-    fetch::collection_id::CollectionId,
+    fetch::{collection_id::CollectionId, fetch_object_http},
     kinds::collection::CollectionType,
     protocol::verification::verify_domains_match,
     traits::{Activity, Collection},
 };
+use anyhow::anyhow;
 use futures::future::{join_all, try_join_all};
 use ibis_database::{
-    common::{article::Article, utils::http_protocol_str},
+    common::{article::Article, instance::Instance, utils::http_protocol_str},
     error::{BackendError, BackendResult},
     impls::IbisContext,
 };
@@ -35,6 +36,59 @@ pub fn local_articles_url(domain: &str) -> BackendResult<CollectionId<ArticleCol
     ))?)
 }
 
+/// Import all articles of a remote instance including their edit history. Existing articles are
+/// updated in place, so syncing again doesn't create duplicates and retries articles which
+/// failed before. The import runs in the background, as it may take a long time.
+pub async fn sync_articles(instance: &Instance, context: &Data<IbisContext>) -> BackendResult<()> {
+    if instance.local {
+        return Err(anyhow!("Cannot sync articles of the local instance").into());
+    }
+    let Some(articles_url) = instance.articles_url.clone() else {
+        return Err(anyhow!("Instance {} doesn't publish its articles", instance.domain).into());
+    };
+    let context = context.reset_request_count();
+    let join = tokio::spawn(async move {
+        let url = articles_url.inner().clone();
+        if let Err(e) = fetch_and_sync_articles(&url, &context).await {
+            warn!("Failed to synchronize articles from {url}: {e}");
+        }
+    });
+
+    // In production sync in background to avoid slow api calls. For tests wait until the
+    // sync is finished.
+    if cfg!(debug_assertions) {
+        join.await?;
+    }
+    Ok(())
+}
+
+async fn fetch_and_sync_articles(url: &Url, context: &Data<IbisContext>) -> BackendResult<()> {
+    let collection: ApubArticleCollection = fetch_object_http(url, context).await?.object;
+    ArticleCollection::verify(&collection, url, context).await?;
+    let articles = collection
+        .items
+        .into_iter()
+        .filter(|i| !i.object.id.is_local(context))
+        .map(|update| async move {
+            // each article is fetched independently, so large instances don't run into the
+            // request limit. This is only done for syncs started by an admin, not for
+            // collections received from other instances.
+            receive_article(update, &context.reset_request_count()).await
+        });
+    join_all(articles).await;
+    Ok(())
+}
+
+async fn receive_article(update: UpdateArticle, context: &Data<IbisContext>) -> BackendResult<()> {
+    let id = update.object.id.clone();
+    UpdateArticle::verify(&update, context).await?;
+    let res = UpdateArticle::receive(update, context).await;
+    if let Err(e) = &res {
+        warn!("Failed to synchronize article {id}: {e}");
+    }
+    res
+}
+
 #[async_trait::async_trait]
 impl Collection for ArticleCollection {
     type Owner = ();
@@ -46,7 +100,15 @@ impl Collection for ArticleCollection {
         _owner: &Self::Owner,
         context: &Data<Self::DataType>,
     ) -> Result<Self::Kind, Self::Error> {
-        let local_articles = Article::read_all(Some(true), None, false, context)?;
+        let local_articles = Article::read_all(
+            Some(true),
+            None,
+            false,
+            Default::default(),
+            None,
+            0,
+            context,
+        )?;
         let articles = try_join_all(
             local_articles
                 .into_iter()
@@ -82,15 +144,7 @@ impl Collection for ArticleCollection {
             .items
             .into_iter()
             .filter(|i| !i.object.id.is_local(context))
-            .map(|update| async {
-                let id = update.object.id.clone();
-                UpdateArticle::verify(&update, context).await?;
-                let res = UpdateArticle::receive(update, context).await;
-                if let Err(e) = &res {
-                    warn!("Failed to synchronize article {id}: {e}");
-                }
-                res
-            });
+            .map(|update| receive_article(update, context));
         join_all(articles).await;
 
         Ok(ArticleCollection(()))
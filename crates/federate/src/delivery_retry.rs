@@ -0,0 +1,238 @@
+//! Outgoing activities are delivered by the activity queue of the federation library. If a
+//! delivery fails temporarily, for example because the remote instance is down, it is stored and
+//! retried with backoff. After all retries are used up, the delivery is marked as dead and kept
+//! for admins to inspect. Activities which are rejected by the remote instance are never retried.
+//!
+//! Retries are only handled here. The library is told that a failed delivery succeeded once it is
+//! queued, otherwise it would retry the same delivery in parallel.
+
+use crate::objects::{instance::InstanceWrapper, user::PersonWrapper};
+use activitypub_federation::{
+    activity_sending::SendActivityTask,
+    config::Data,
+    traits::{Activity, Actor},
+};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use http::Extensions;
+use ibis_database::{
+    DbUrl,
+    common::{
+        instance::{DeliveryStatus, Instance, QueuedDelivery},
+        newtypes::QueuedDeliveryId,
+        user::Person,
+    },
+    error::{BackendError, BackendResult},
+    impls::IbisContext,
+};
+use log::warn;
+use reqwest::{Method, Request, Response, StatusCode};
+use reqwest_middleware::{ClientBuilder, ClientWithMiddleware, Middleware, Next};
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::Value;
+use std::fmt::Debug;
+use url::Url;
+
+/// Http client for the federation library, which keeps track of activity deliveries.
+pub fn federation_client(context: &IbisContext) -> ClientWithMiddleware {
+    ClientBuilder::new(context.client.clone())
+        .with(DeliveryTracker(context.clone()))
+        .build()
+}
+
+/// Stores the outcome of each activity delivery once the request is finished, both for the
+/// delivery status of the remote instance and for the retry queue. The federation library sends
+/// activities from its own worker pool and doesn't report failures, so this is done on the level
+/// of http requests.
+struct DeliveryTracker(IbisContext);
+
+#[async_trait]
+impl Middleware for DeliveryTracker {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        // activities are sent with post, other requests are for fetching objects
+        if req.method() != Method::POST {
+            return next.run(req, extensions).await;
+        }
+        let inbox = req.url().clone();
+        let json = req
+            .body()
+            .and_then(|b| b.as_bytes())
+            .map(|b| String::from_utf8_lossy(b).to_string());
+        let res = next.run(req, extensions).await;
+        let outcome = match &res {
+            Ok(r) if r.status().is_success() => DeliveryOutcome::Success,
+            Ok(r) if is_temporary(r.status()) => DeliveryOutcome::Temporary(r.status().to_string()),
+            Ok(r) => DeliveryOutcome::Rejected(r.status().to_string()),
+            Err(e) => DeliveryOutcome::Temporary(e.to_string()),
+        };
+        let Some(json) = json else {
+            return res;
+        };
+        let newly_queued = record_delivery(inbox, json, outcome, &self.0)
+            .inspect_err(|e| warn!("Failed to store delivery: {e}"))
+            .unwrap_or(false);
+        if newly_queued {
+            return Ok(http::Response::new(Vec::<u8>::new()).into());
+        }
+        res
+    }
+}
+
+enum DeliveryOutcome {
+    Success,
+    /// Remote instance is down or overloaded, try again later
+    Temporary(String),
+    /// Remote instance doesn't accept the activity, so retrying won't help
+    Rejected(String),
+}
+
+/// Client errors other than rate limiting mean that the activity was rejected.
+fn is_temporary(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Queue the delivery for retry after a temporary failure, and remove it from the queue once it
+/// succeeds. Returns true if the delivery was added to the queue.
+fn record_delivery(
+    inbox: Url,
+    json: String,
+    outcome: DeliveryOutcome,
+    context: &IbisContext,
+) -> BackendResult<bool> {
+    #[derive(Deserialize)]
+    struct ActivityIds {
+        id: Url,
+        actor: Url,
+    }
+    let success = matches!(outcome, DeliveryOutcome::Success);
+    DeliveryStatus::update_for_inboxes(&[inbox.clone()], success, context)?;
+    let ids: ActivityIds = serde_json::from_str(&json)?;
+    let activity_id: DbUrl = ids.id.into();
+    let inbox: DbUrl = inbox.into();
+    let queued = QueuedDelivery::read_for_inbox(&activity_id, &inbox, context)?;
+    match (outcome, queued) {
+        (DeliveryOutcome::Success, Some(queued)) => QueuedDelivery::delete(queued.id, context)?,
+        (DeliveryOutcome::Temporary(error), Some(queued)) => {
+            queued.mark_failed(error, context)?;
+        }
+        (DeliveryOutcome::Temporary(error), None) => {
+            QueuedDelivery::create(activity_id, ids.actor.into(), inbox, json, error, context)?;
+            return Ok(true);
+        }
+        (DeliveryOutcome::Rejected(error), Some(queued)) => {
+            queued.mark_dead(error, context)?;
+        }
+        (DeliveryOutcome::Success | DeliveryOutcome::Rejected(_), None) => {}
+    }
+    Ok(false)
+}
+
+/// Sign and send an activity to a single inbox.
+async fn deliver<A, ActorType>(
+    activity: &A,
+    actor: &ActorType,
+    inbox: Url,
+    context: &Data<IbisContext>,
+) -> BackendResult<()>
+where
+    A: Activity + Serialize + Debug,
+    ActorType: Actor,
+{
+    for task in SendActivityTask::prepare(activity, actor, vec![inbox], context).await? {
+        task.sign_and_send(context).await?;
+    }
+    Ok(())
+}
+
+/// Activity which was already serialized when it was first sent, so that it can be sent again
+/// without knowing its type.
+#[derive(Debug)]
+struct StoredActivity {
+    id: Url,
+    actor: Url,
+    json: Value,
+}
+
+impl Serialize for StoredActivity {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.json.serialize(serializer)
+    }
+}
+
+#[async_trait::async_trait]
+impl Activity for StoredActivity {
+    type DataType = IbisContext;
+    type Error = BackendError;
+
+    fn id(&self) -> &Url {
+        &self.id
+    }
+
+    fn actor(&self) -> &Url {
+        &self.actor
+    }
+
+    async fn verify(&self, _context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        Err(anyhow!("Stored activities are only sent, not received").into())
+    }
+
+    async fn receive(self, _context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        Err(anyhow!("Stored activities are only sent, not received").into())
+    }
+}
+
+pub(crate) async fn retry_due(context: &Data<IbisContext>) -> BackendResult<()> {
+    for delivery in QueuedDelivery::read_due(context)? {
+        // failures are stored by `DeliveryTracker`
+        resend(&delivery, &context.reset_request_count()).await.ok();
+    }
+    Ok(())
+}
+
+/// Send a queued delivery immediately, including dead ones, for example after the remote
+/// instance is back online.
+pub async fn retry_delivery(
+    id: QueuedDeliveryId,
+    context: &Data<IbisContext>,
+) -> BackendResult<()> {
+    let delivery = QueuedDelivery::read(id, context)?;
+    resend(&delivery, context).await
+}
+
+/// Send the delivery again. The queue is updated by [DeliveryTracker] depending on the result.
+async fn resend(delivery: &QueuedDelivery, context: &Data<IbisContext>) -> BackendResult<()> {
+    let activity = StoredActivity {
+        id: delivery.activity_id.clone().into(),
+        actor: delivery.actor_id.clone().into(),
+        json: serde_json::from_str(&delivery.json)?,
+    };
+    let inbox: Url = delivery.inbox.clone().into();
+    // activities are signed either by a local user or by the local instance
+    match Person::read_from_ap_id(&delivery.actor_id, context) {
+        Ok(person) => deliver(&activity, &PersonWrapper::from(person), inbox, context).await,
+        Err(_) => {
+            let instance = Instance::read_from_ap_id(&delivery.actor_id, context)?;
+            deliver(&activity, &InstanceWrapper(instance), inbox, context).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_temporary() {
+        assert!(is_temporary(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_temporary(StatusCode::BAD_GATEWAY));
+        assert!(is_temporary(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!is_temporary(StatusCode::BAD_REQUEST));
+        assert!(!is_temporary(StatusCode::UNAUTHORIZED));
+        assert!(!is_temporary(StatusCode::GONE));
+    }
+}
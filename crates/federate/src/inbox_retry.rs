@@ -0,0 +1,122 @@
+//! Incoming activities which fail to process because of a temporary error are not dropped, but
+//! stored and retried with backoff. This helps for example if a referenced object can't be fetched
+//! yet because its instance is down. After all retries are used up, the activity is kept as dead
+//! letter which admins can inspect and replay. Other errors are returned to the sender as before.
+//!
+//! Reprocessing is safe because receive handlers upsert objects by their ap_id, so an edit which
+//! was partially applied is not applied twice.
+
+use crate::routes::InboxActivities;
+use activitypub_federation::{config::Data, protocol::context::WithContext, traits::Activity};
+use anyhow::anyhow;
+use ibis_database::{
+    common::{instance::InboundActivity, newtypes::InboundActivityId},
+    error::{BackendError, BackendResult},
+    impls::IbisContext,
+};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Wrapper around inbox activities, which stores them for retry if processing fails temporarily.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(transparent)]
+pub struct RetryableActivity(WithContext<InboxActivities>);
+
+#[async_trait::async_trait]
+impl Activity for RetryableActivity {
+    type DataType = IbisContext;
+    type Error = BackendError;
+
+    fn id(&self) -> &Url {
+        self.0.id()
+    }
+
+    fn actor(&self) -> &Url {
+        self.0.actor()
+    }
+
+    async fn verify(&self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        self.0.verify(context).await
+    }
+
+    async fn receive(self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        let id = self.id().clone();
+        let json = serde_json::to_string(&self.0)?;
+        match self.0.receive(context).await {
+            Err(e) if is_temporary(&e) => {
+                warn!("Failed to process activity {id}, retrying later: {e}");
+                InboundActivity::create(id.into(), json, e.to_string(), context)?;
+                Ok(())
+            }
+            res => res,
+        }
+    }
+}
+
+/// Network errors, for example while fetching a referenced object, may go away later. Anything
+/// else like an invalid activity fails the same way on every retry.
+fn is_temporary(error: &BackendError) -> bool {
+    error
+        .0
+        .chain()
+        .any(|e| e.is::<reqwest::Error>() || e.is::<reqwest_middleware::Error>())
+}
+
+pub(crate) async fn retry_due(context: &Data<IbisContext>) -> BackendResult<()> {
+    for activity in InboundActivity::read_due(context)? {
+        match process(&activity, &context.reset_request_count()).await {
+            Ok(()) => InboundActivity::delete(activity.id, context)?,
+            Err(e) => activity.mark_failed(e.to_string(), context)?,
+        }
+    }
+    Ok(())
+}
+
+/// Process a dead letter again, and remove it if successful.
+pub async fn replay_dead_letter(
+    id: InboundActivityId,
+    context: &Data<IbisContext>,
+) -> BackendResult<()> {
+    let activity = InboundActivity::read(id, context)?;
+    if !activity.dead {
+        return Err(anyhow!("Activity is still scheduled for retry").into());
+    }
+    process(&activity, context).await?;
+    InboundActivity::delete(activity.id, context)?;
+    Ok(())
+}
+
+/// The http signature was already checked when the activity was first received, so only
+/// activity verification is repeated here.
+async fn process(activity: &InboundActivity, context: &Data<IbisContext>) -> BackendResult<()> {
+    let activity: WithContext<InboxActivities> = serde_json::from_str(&activity.json)?;
+    activity.verify(context).await?;
+    activity.receive(context).await
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_is_temporary() -> BackendResult<()> {
+        // nothing listens on this port
+        let Err(e) = reqwest::get("http://127.0.0.1:1/").await else {
+            return Err(anyhow!("request should fail").into());
+        };
+        let error = anyhow::Error::from(e).context("Failed to fetch article");
+        assert!(is_temporary(&error.into()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_not_temporary() -> BackendResult<()> {
+        assert!(!is_temporary(&anyhow!("Edit has invalid diff").into()));
+        let Err(e) = serde_json::from_str::<Url>("{}") else {
+            return Err(anyhow!("parsing should fail").into());
+        };
+        assert!(!is_temporary(&e.into()));
+        Ok(())
+    }
+}
@@ -6,32 +6,57 @@ use activitypub_federation::{
     protocol::context::WithContext,
     traits::{Activity, Actor},
 };
+use anyhow::anyhow;
 use async_trait::async_trait;
 use ibis_database::{
-    common::utils::http_protocol_str,
-    config::IbisConfig,
-    error::BackendResult,
+    common::{
+        instance::{AllowedInstance, BlockedInstance, FederationMode},
+        utils::http_protocol_str,
+    },
+    error::{BackendError, BackendResult},
     impls::{
         IbisContext,
         sent_activity::{SentActivity, SentActivityInsertForm},
     },
 };
+use itertools::Itertools;
 use log::{info, warn};
 use objects::{instance::InstanceWrapper, user::PersonWrapper};
 use rand::{Rng, distr::Alphanumeric, rng};
 use routes::AnnouncableActivities;
 use serde::Serialize;
-use std::fmt::Debug;
+use std::{fmt::Debug, time::Duration};
+use tokio::time::interval;
 use url::Url;
 
 pub mod activities;
 pub mod collections;
+pub mod delivery_retry;
+pub mod inbox_retry;
 pub mod nodeinfo;
 pub mod objects;
 pub mod routes;
 pub mod validate;
 pub mod webfinger;
 
+/// Periodically retry failed inbound activities and outgoing deliveries in the background.
+pub fn start_retry_task(context: Data<IbisContext>) {
+    tokio::spawn(async move {
+        let mut interval = interval(Duration::from_secs(60));
+        loop {
+            interval.tick().await;
+            inbox_retry::retry_due(&context)
+                .await
+                .inspect_err(|e| warn!("Failed to retry inbound activities: {e}"))
+                .ok();
+            delivery_retry::retry_due(&context)
+                .await
+                .inspect_err(|e| warn!("Failed to retry outgoing deliveries: {e}"))
+                .ok();
+        }
+    });
+}
+
 pub async fn send_ibis_activity<A, ActorType>(
     actor: &ActorType,
     activity: A,
@@ -50,6 +75,7 @@ where
     SentActivity::create(form, context)?;
     info!("Sending activity {}", activity.id());
 
+    let recipients = unique_inboxes(recipients);
     let actor = actor.clone();
     let context = context.reset_request_count();
     let join = tokio::spawn(async move {
@@ -68,6 +94,13 @@ where
     Ok(())
 }
 
+/// Remote users store the shared inbox of their instance if it has one, so followers from the
+/// same instance result in identical urls. Collapse them so that each inbox receives the
+/// activity only once.
+fn unique_inboxes(recipients: Vec<Url>) -> Vec<Url> {
+    recipients.into_iter().unique().collect()
+}
+
 pub async fn send_activity_to_instance(
     actor: &PersonWrapper,
     activity: AnnouncableActivities,
@@ -84,33 +117,54 @@ pub async fn send_activity_to_instance(
 }
 
 #[derive(Clone)]
-pub struct VerifyUrlData(pub IbisConfig);
+pub struct VerifyUrlData(pub IbisContext);
 
 #[async_trait]
 impl UrlVerifier for VerifyUrlData {
-    /// Check domain against allowlist and blocklist from config file.
+    /// Check domain against the federation mode, allowlist and blocklist. This applies to fetched
+    /// objects, incoming activities and delivery.
+    ///
+    /// `Closed` mode rejects everything. Otherwise blocked domains are always rejected, even if
+    /// they are also allowed. Only allowed domains are accepted in `AllowList` mode, or in `Open`
+    /// mode if the config file has an allowlist. Domains in the config allowlist and those
+    /// allowed by admins are treated the same, see [AllowedInstance::is_allowed].
     async fn verify(&self, url: &Url) -> Result<(), ActivityPubError> {
         let domain = url.domain().expect("url has domain");
-        if let Some(allowlist) = &self.0.federation.allowlist {
-            let allowlist = allowlist.split(',').collect::<Vec<_>>();
-            if !allowlist.contains(&domain) {
-                return Err(ActivityPubError::Other(format!(
-                    "Domain {domain} is not allowed"
-                )));
-            }
-        }
-        if let Some(blocklist) = &self.0.federation.blocklist {
-            let blocklist = blocklist.split(',').collect::<Vec<_>>();
-            if blocklist.contains(&domain) {
-                return Err(ActivityPubError::Other(format!(
-                    "Domain {domain} is blocked"
-                )));
+        let to_error = |e: BackendError| ActivityPubError::Other(e.to_string());
+        let federation = &self.0.conf.federation;
+        let allowlist_only = match self.0.conf.options.federation_mode {
+            FederationMode::Open => federation.allowlist.is_some(),
+            FederationMode::AllowList => true,
+            FederationMode::Closed => {
+                return Err(ActivityPubError::Other(
+                    "Federation is disabled".to_string(),
+                ));
             }
+        };
+        if allowlist_only && !AllowedInstance::is_allowed(domain, &self.0).map_err(to_error)? {
+            return Err(ActivityPubError::Other(format!(
+                "Domain {domain} is not allowed"
+            )));
         }
+        check_not_blocked(domain, &self.0).map_err(to_error)?;
         Ok(())
     }
 }
 
+/// Reject domains which are blocked in the config file or by admins.
+pub(crate) fn check_not_blocked(domain: &str, context: &IbisContext) -> BackendResult<()> {
+    let blocked_in_config = context
+        .conf
+        .federation
+        .blocklist
+        .as_ref()
+        .is_some_and(|b| b.split(',').any(|d| d == domain));
+    if blocked_in_config || BlockedInstance::is_blocked(domain, context)? {
+        return Err(anyhow!("Domain {domain} is blocked").into());
+    }
+    Ok(())
+}
+
 pub(crate) fn generate_activity_id(context: &Data<IbisContext>) -> BackendResult<Url> {
     let domain = &context.conf.federation.domain;
     let id: String = rng()
@@ -125,3 +179,23 @@ pub(crate) fn generate_activity_id(context: &Data<IbisContext>) -> BackendResult
         id
     ))?)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_unique_inboxes() -> BackendResult<()> {
+        let shared: Url = "https://example.com/inbox".parse()?;
+        let other: Url = "https://other.com/inbox".parse()?;
+        // three followers on the same instance and one elsewhere
+        let recipients = vec![
+            shared.clone(),
+            other.clone(),
+            shared.clone(),
+            shared.clone(),
+        ];
+        assert_eq!(vec![shared, other], unique_inboxes(recipients));
+        Ok(())
+    }
+}
@@ -1,11 +1,13 @@
 use activitypub_federation::config::Data;
 use axum::{Json, Router, routing::get};
 use ibis_database::{
-    common::utils::http_protocol_str,
+    common::{instance::Instance, utils::http_protocol_str},
     error::BackendResult,
     impls::{IbisContext, instance_stats::InstanceStats},
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use serde_with::skip_serializing_none;
 use url::Url;
 
 pub fn config() -> Router<()> {
@@ -30,7 +32,26 @@ async fn node_info_well_known(
 }
 
 async fn node_info(context: Data<IbisContext>) -> BackendResult<Json<NodeInfo>> {
-    let stats = InstanceStats::read(&context)?;
+    let usage = if context.conf.options.nodeinfo_usage_stats {
+        let stats = InstanceStats::read(&context)?;
+        NodeInfoUsage {
+            users: NodeInfoUsers {
+                total: Some(stats.users),
+                active_month: Some(stats.users_active_month),
+                active_halfyear: Some(stats.users_active_half_year),
+            },
+            local_posts: Some(stats.articles),
+            local_comments: Some(stats.comments),
+        }
+    } else {
+        // Counts are optional in the schema, only the users object is required
+        NodeInfoUsage::default()
+    };
+    // name of the instance as used by fediverse statistics sites
+    let mut metadata = Map::new();
+    if let Some(name) = Instance::read_local(&context)?.name {
+        metadata.insert("nodeName".to_string(), Value::String(name));
+    }
     Ok(Json(NodeInfo {
         version: "2.1".to_string(),
         software: NodeInfoSoftware {
@@ -40,18 +61,10 @@ async fn node_info(context: Data<IbisContext>) -> BackendResult<Json<NodeInfo>>
             homepage: "https://ibis.wiki/".to_string(),
         },
         protocols: vec!["activitypub".to_string()],
-        usage: NodeInfoUsage {
-            users: NodeInfoUsers {
-                total: stats.users,
-                active_month: stats.users_active_month,
-                active_halfyear: stats.users_active_half_year,
-            },
-            local_posts: stats.articles,
-            local_comments: stats.comments,
-        },
-        open_registrations: context.conf.options.registration_open,
+        usage,
+        open_registrations: Instance::registration_open(&context)?,
         services: Default::default(),
-        metadata: vec![],
+        metadata,
     }))
 }
 
@@ -66,7 +79,7 @@ struct NodeInfoWellKnownLinks {
     pub href: Url,
 }
 
-#[derive(Serialize)]
+#[derive(Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NodeInfo {
     pub version: String,
@@ -76,10 +89,10 @@ pub struct NodeInfo {
     pub open_registrations: bool,
     /// These fields are required by the spec for no reason
     pub services: NodeInfoServices,
-    pub metadata: Vec<String>,
+    pub metadata: Map<String, Value>,
 }
 
-#[derive(Serialize)]
+#[derive(Deserialize, Serialize)]
 pub struct NodeInfoSoftware {
     pub name: String,
     pub version: String,
@@ -87,23 +100,25 @@ pub struct NodeInfoSoftware {
     pub homepage: String,
 }
 
-#[derive(Serialize)]
+#[skip_serializing_none]
+#[derive(Deserialize, Serialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct NodeInfoUsage {
     pub users: NodeInfoUsers,
-    pub local_posts: i32,
-    pub local_comments: i32,
+    pub local_posts: Option<i32>,
+    pub local_comments: Option<i32>,
 }
 
-#[derive(Serialize)]
+#[skip_serializing_none]
+#[derive(Deserialize, Serialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct NodeInfoUsers {
-    pub total: i32,
-    pub active_month: i32,
-    pub active_halfyear: i32,
+    pub total: Option<i32>,
+    pub active_month: Option<i32>,
+    pub active_halfyear: Option<i32>,
 }
 
-#[derive(Serialize, Default)]
+#[derive(Deserialize, Serialize, Default)]
 pub struct NodeInfoServices {
     pub inbound: Vec<String>,
     pub outbound: Vec<String>,
@@ -1,13 +1,26 @@
-use super::{Source, read_from_string_or_source, user::PersonWrapper};
+use super::{
+    Source,
+    read_from_string_or_source,
+    signature::{sign_data, verify_data},
+    user::PersonWrapper,
+};
 use crate::{
     collections::edits_collection::EditCollection,
     objects::instance::InstanceWrapper,
-    validate::validate_article_title,
+    validate::{
+        MAX_ARTICLE_ATTACHMENTS,
+        validate_article_size,
+        validate_article_title,
+        validate_attachment_url,
+    },
 };
 use activitypub_federation::{
     config::Data,
     fetch::{collection_id::CollectionId, object_id::ObjectId},
-    kinds::{object::ArticleType, public},
+    kinds::{
+        object::{ArticleType, DocumentType},
+        public,
+    },
     protocol::{
         helpers::{deserialize_one_or_many, deserialize_skip_error},
         values::MediaTypeMarkdownOrHtml,
@@ -19,12 +32,12 @@ use anyhow::anyhow;
 use chrono::{DateTime, Utc};
 use ibis_database::{
     common::{
-        article::{Article, EditVersion},
+        article::{Article, Attachment, EditVersion, ProtectionLevel},
         instance::Instance,
         user::Person,
     },
-    error::BackendError,
-    impls::{IbisContext, article::DbArticleForm},
+    error::{BackendError, BackendResult},
+    impls::{IbisContext, article::DbArticleForm, attachment::DbAttachmentForm},
 };
 use ibis_markdown::render_article_markdown;
 use itertools::Itertools;
@@ -49,12 +62,80 @@ pub struct ApubArticle {
     latest_version: EditVersion,
     content: String,
     name: String,
+    /// Set for [ProtectionLevel::AdminOnly], kept for compatibility with older versions
     protected: bool,
+    #[serde(deserialize_with = "deserialize_skip_error", default)]
+    protection_level: Option<ProtectionLevel>,
     pub(crate) media_type: Option<MediaTypeMarkdownOrHtml>,
     #[serde(deserialize_with = "deserialize_skip_error", default)]
     pub(crate) source: Option<Source>,
     published: Option<DateTime<Utc>>,
     updated: Option<DateTime<Utc>>,
+    /// Optional signature by the home instance, so that the article can be passed on by other
+    /// instances without being altered.
+    #[serde(deserialize_with = "deserialize_skip_error", default)]
+    signature: Option<ArticleSignature>,
+    #[serde(deserialize_with = "deserialize_skip_error", default)]
+    attachment: Vec<ApubAttachment>,
+}
+
+#[skip_serializing_none]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ApubAttachment {
+    #[serde(rename = "type")]
+    kind: DocumentType,
+    url: Url,
+    /// Alt text
+    name: Option<String>,
+    media_type: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ArticleSignature {
+    /// Instance whose key was used for signing
+    creator: ObjectId<InstanceWrapper>,
+    signature_value: String,
+}
+
+/// Stable representation of the article content which is covered by the signature. This
+/// includes all fields which other instances rely on, so that none of them can be altered in
+/// transit.
+#[derive(Serialize)]
+struct CanonicalArticle<'a> {
+    id: &'a Url,
+    name: &'a str,
+    text: String,
+    latest_version: &'a EditVersion,
+    protected: bool,
+    protection_level: Option<ProtectionLevel>,
+    attachment: &'a [ApubAttachment],
+}
+
+impl<'a> CanonicalArticle<'a> {
+    fn new(
+        id: &'a Url,
+        name: &'a str,
+        text: &str,
+        latest_version: &'a EditVersion,
+        protected: bool,
+        protection_level: Option<ProtectionLevel>,
+        attachment: &'a [ApubAttachment],
+    ) -> BackendResult<String> {
+        // Normalize line endings and trailing whitespace which may be changed in transit
+        let text = text.replace("\r\n", "\n").trim_end().to_string();
+        let canonical = CanonicalArticle {
+            id,
+            name,
+            text,
+            latest_version,
+            protected,
+            protection_level,
+            attachment,
+        };
+        Ok(serde_json::to_string(&canonical)?)
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -74,6 +155,46 @@ impl From<Article> for ArticleWrapper {
     }
 }
 
+impl ArticleWrapper {
+    /// Sign a local article with the key of the local instance and store the signature, so that
+    /// it is created only once and not every time the article is federated. Needs to be called
+    /// after any of the signed fields has changed.
+    pub(crate) fn sign(self, context: &Data<IbisContext>) -> BackendResult<Self> {
+        let local_instance = Instance::read_local(context)?;
+        let (true, Some(private_key)) = (self.local, &local_instance.private_key) else {
+            return Ok(self);
+        };
+        let canonical = CanonicalArticle::new(
+            self.ap_id.inner(),
+            &self.title,
+            &self.text,
+            &self.latest_edit_version(context)?,
+            self.protection == ProtectionLevel::AdminOnly,
+            Some(self.protection),
+            &apub_attachments(&self, context)?,
+        )?;
+        let signature = sign_data(&canonical, private_key)?;
+        Ok(Article::update_signature(self.id, &signature, context)?.into())
+    }
+}
+
+fn apub_attachments(
+    article: &Article,
+    context: &Data<IbisContext>,
+) -> BackendResult<Vec<ApubAttachment>> {
+    Attachment::list_for_article(article.id, context)?
+        .into_iter()
+        .map(|a| {
+            Ok(ApubAttachment {
+                kind: Default::default(),
+                url: Url::parse(&a.url)?,
+                name: a.alt_text,
+                media_type: a.media_type,
+            })
+        })
+        .collect()
+}
+
 #[async_trait::async_trait]
 impl Object for ArticleWrapper {
     type DataType = IbisContext;
@@ -84,6 +205,12 @@ impl Object for ArticleWrapper {
         self.ap_id.inner()
     }
 
+    /// Remote articles are served from the local copy, and only fetched again once this is
+    /// older than the refresh interval of the federation library
+    fn last_refreshed_at(&self) -> Option<DateTime<Utc>> {
+        Some(self.last_refreshed_at)
+    }
+
     async fn read_from_id(
         object_id: Url,
         context: &Data<Self::DataType>,
@@ -96,21 +223,43 @@ impl Object for ArticleWrapper {
         let latest_version = self.latest_edit_version(context)?;
         let wikibot = Person::wikibot(context)?;
         let local_instance: InstanceWrapper = Instance::read_local(context)?.into();
+        // articles which were stored before signing was added are signed on first use
+        let article = match (self.local, &self.signature) {
+            (true, None) => self.sign(context)?,
+            _ => self,
+        };
+        let signature = article
+            .signature
+            .clone()
+            .map(|signature_value| ArticleSignature {
+                creator: local_instance.ap_id.clone().into(),
+                signature_value,
+            });
+        let attachment = apub_attachments(&article, context)?;
         Ok(ApubArticle {
             kind: Default::default(),
-            id: self.ap_id.clone().into(),
+            id: article.ap_id.clone().into(),
             attributed_to: wikibot.ap_id.into(),
             to: vec![public(), local_instance.ap_id.clone().into()],
             cc: vec![],
-            edits: self.edits_id()?.into(),
+            edits: article.edits_id()?.into(),
             latest_version,
-            content: render_article_markdown(&self.text),
-            name: self.title.clone(),
-            protected: self.protected,
+            // line break hints are left to the rendering on other instances
+            content: render_article_markdown(
+                &article.text,
+                context.conf.options.math_rendering,
+                0,
+                false,
+            ),
+            name: article.title.clone(),
+            protected: article.protection == ProtectionLevel::AdminOnly,
+            protection_level: Some(article.protection),
             media_type: Some(MediaTypeMarkdownOrHtml::Html),
-            source: Some(Source::new(self.text.clone())),
-            published: Some(self.published),
-            updated: Some(self.updated),
+            source: Some(Source::new(article.text.clone())),
+            published: Some(article.published),
+            updated: Some(article.updated),
+            signature,
+            attachment,
         })
     }
 
@@ -140,23 +289,103 @@ impl Object for ArticleWrapper {
             }
         };
         let text = read_from_string_or_source(&json.content, &json.media_type, &json.source);
+        if let Some(signature) = &json.signature {
+            verify_domains_match(signature.creator.inner(), json.id.inner())?;
+            let creator = signature.creator.dereference(context).await?;
+            let canonical = CanonicalArticle::new(
+                json.id.inner(),
+                &json.name,
+                &text,
+                &json.latest_version,
+                json.protected,
+                json.protection_level,
+                &json.attachment,
+            )?;
+            verify_data(&canonical, &signature.signature_value, &creator.public_key).map_err(
+                |e| {
+                    anyhow!(
+                        "Signature of article {} is invalid, it may have been altered: {e}",
+                        json.id
+                    )
+                },
+            )?;
+        }
         let form = DbArticleForm {
             title: json.name,
             text,
             ap_id: json.id.into(),
             local: false,
             instance_id: instance.id,
-            protected: json.protected,
+            protection: protection_level(json.protected, json.protection_level),
             updated: json.updated.or(json.published).unwrap_or_default(),
             pending: false,
+            forked_by: None,
         };
         validate_article_title(&form.title)?;
+        validate_article_size(&form.text, &context.conf.options)?;
         let creator = json.attributed_to.dereference(context).await?;
         let article = Article::create_or_update(form, creator.id, context).await?;
 
+        let mut attachments = vec![];
+        for a in json.attachment.into_iter().take(MAX_ARTICLE_ATTACHMENTS) {
+            // skip attachments from blocked domains
+            if validate_attachment_url(&a.url, context).is_ok() {
+                attachments.push(DbAttachmentForm {
+                    article_id: article.id,
+                    url: a.url.to_string(),
+                    alt_text: a.name,
+                    media_type: a.media_type,
+                });
+            }
+        }
+        Attachment::replace_for_article(article.id, &attachments, context)?;
+
         let mut edits = json.edits.dereference(&article, context).await?.0;
         edits.sort_by_key(|e| Reverse(e.published));
 
         Ok(article.into())
     }
 }
+
+/// The signed `protected` flag takes precedence, so that a relaying instance cannot lift the
+/// protection of an article by changing the unsigned level.
+fn protection_level(protected: bool, level: Option<ProtectionLevel>) -> ProtectionLevel {
+    match (protected, level) {
+        (true, _) => ProtectionLevel::AdminOnly,
+        (false, Some(ProtectionLevel::AutoConfirmed)) => ProtectionLevel::AutoConfirmed,
+        _ => ProtectionLevel::Unprotected,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use activitypub_federation::http_signatures::generate_actor_keypair;
+
+    #[test]
+    fn test_signature_covers_all_fields() -> BackendResult<()> {
+        let keypair = generate_actor_keypair()?;
+        let id: Url = "https://example.com/article/Test".parse()?;
+        let version = EditVersion::default();
+        let mut attachment = vec![ApubAttachment {
+            kind: Default::default(),
+            url: "https://example.com/diagram.png".parse()?,
+            name: None,
+            media_type: None,
+        }];
+        let canonical = |attachment: &[ApubAttachment], level| {
+            CanonicalArticle::new(&id, "Test", "text", &version, false, level, attachment)
+        };
+        let unprotected = Some(ProtectionLevel::Unprotected);
+        let signature = sign_data(&canonical(&attachment, unprotected)?, &keypair.private_key)?;
+        let verify = |canonical: String| verify_data(&canonical, &signature, &keypair.public_key);
+        assert!(verify(canonical(&attachment, unprotected)?).is_ok());
+
+        // changing the protection level or an attachment invalidates the signature
+        let auto_confirmed = Some(ProtectionLevel::AutoConfirmed);
+        assert!(verify(canonical(&attachment, auto_confirmed)?).is_err());
+        attachment[0].url = "https://evil.example/diagram.png".parse()?;
+        assert!(verify(canonical(&attachment, unprotected)?).is_err());
+        Ok(())
+    }
+}
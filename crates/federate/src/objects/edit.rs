@@ -41,6 +41,9 @@ pub struct ApubEdit {
     pub object: ObjectId<ArticleWrapper>,
     pub attributed_to: ObjectId<PersonWrapper>,
     pub published: DateTime<Utc>,
+    /// Optional for compatibility with older versions
+    #[serde(default)]
+    pub minor: bool,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -92,6 +95,7 @@ impl Object for EditWrapper {
             object: article.ap_id.into(),
             attributed_to: creator.ap_id.into(),
             published: self.published,
+            minor: self.minor,
         })
     }
 
@@ -128,6 +132,8 @@ impl Object for EditWrapper {
             previous_version_id: json.previous_version,
             published: json.published,
             pending: false,
+            minor: json.minor,
+            anonymous_id: None,
         };
         let edit = Edit::create_or_update(&form, true, context).await?;
         Ok(edit.into())
@@ -1,4 +1,4 @@
-use super::Endpoints;
+use super::{Endpoints, ImageObject, RefreshableActor};
 use crate::collections::{
     articles_collection::ArticleCollection,
     instance_collection::InstanceCollection,
@@ -15,6 +15,7 @@ use activitypub_federation::{
 };
 use chrono::{DateTime, Utc};
 use ibis_database::{
+    DbUrl,
     common::{instance::Instance, utils::extract_domain},
     error::{BackendError, BackendResult},
     impls::{IbisContext, instance::DbInstanceForm},
@@ -35,6 +36,7 @@ pub struct ApubInstance {
     /// displayname
     name: Option<String>,
     summary: Option<String>,
+    icon: Option<ImageObject>,
     outbox: Option<CollectionId<ArticleCollection>>,
     // This field is mandatory to prevent fetching communities from Lemmy etc
     instances: CollectionId<InstanceCollection>,
@@ -102,6 +104,10 @@ impl Object for InstanceWrapper {
             kind: Default::default(),
             id: self.ap_id.clone().into(),
             summary: self.topic.clone(),
+            icon: self
+                .icon_url
+                .clone()
+                .map(|url| ImageObject::new(url.into())),
             outbox: self.articles_url.clone().map(Into::into),
             instances: self.instances_url.clone().into(),
             inbox: Url::parse(&self.inbox_url)?,
@@ -140,6 +146,7 @@ impl Object for InstanceWrapper {
             last_refreshed_at: Utc::now(),
             local: false,
             name: json.name,
+            icon_url: json.icon.map(|i| i.url.into()),
         };
         let instance = Instance::create(&form, context)?;
 
@@ -165,6 +172,12 @@ impl Object for InstanceWrapper {
     }
 }
 
+impl RefreshableActor for InstanceWrapper {
+    fn mark_refreshed(ap_id: &DbUrl, context: &IbisContext) -> BackendResult<()> {
+        Instance::mark_refreshed(ap_id, context)
+    }
+}
+
 impl Actor for InstanceWrapper {
     fn public_key_pem(&self) -> &str {
         &self.public_key
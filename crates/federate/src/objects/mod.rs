@@ -1,15 +1,30 @@
-use activitypub_federation::protocol::values::{MediaTypeMarkdown, MediaTypeMarkdownOrHtml};
+use activitypub_federation::{
+    config::Data,
+    fetch::object_id::ObjectId,
+    kinds::object::ImageType,
+    protocol::values::{MediaTypeMarkdown, MediaTypeMarkdownOrHtml},
+    traits::Object,
+};
 use article::ArticleWrapper;
+use chrono::{Duration, Utc};
 use comment::CommentWrapper;
 use either::Either;
 use html2md::parse_html;
+use ibis_database::{
+    DbUrl,
+    error::{BackendError, BackendResult},
+    impls::IbisContext,
+};
+use log::warn;
 use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
 use url::Url;
 
 pub mod article;
 pub mod comment;
 pub mod edit;
 pub mod instance;
+mod signature;
 pub mod user;
 
 type DbArticleOrComment = Either<ArticleWrapper, CommentWrapper>;
@@ -30,6 +45,47 @@ impl Source {
     }
 }
 
+/// Remote actor which can be refreshed with [refresh_stale_actor].
+pub trait RefreshableActor: Object<DataType = IbisContext, Error = BackendError> {
+    /// Update the refresh time of the stored actor, without changing anything else.
+    fn mark_refreshed(ap_id: &DbUrl, context: &IbisContext) -> BackendResult<()>;
+}
+
+/// Fetch a remote user or instance again in the background if the stored copy is older than
+/// `remote_actor_refresh_seconds`, so that a new public key or inbox is picked up. The stored copy
+/// is returned right away. If fetching fails, the refresh time is updated anyway so that an
+/// unreachable instance isn't contacted on every read.
+pub async fn refresh_stale_actor<T>(actor: T, context: &Data<IbisContext>) -> BackendResult<T>
+where
+    T: RefreshableActor + Debug + Send + 'static,
+    for<'de> T::Kind: Deserialize<'de>,
+{
+    let ttl = context.conf.options.remote_actor_refresh_seconds;
+    let id = ObjectId::<T>::from(actor.id().clone());
+    let stale = actor
+        .last_refreshed_at()
+        .is_some_and(|t| t < Utc::now() - Duration::seconds(ttl.into()));
+    if ttl == 0 || !stale || id.is_local(context) {
+        return Ok(actor);
+    }
+    let context = context.reset_request_count();
+    let join = tokio::spawn(async move {
+        if let Err(e) = id.dereference_forced(&context).await {
+            warn!("Failed to refresh {id}: {e}");
+            T::mark_refreshed(&id.inner().clone().into(), &context)
+                .inspect_err(|e| warn!("Failed to update refresh time of {id}: {e}"))
+                .ok();
+        }
+    });
+
+    // In production refresh in background to avoid slow api calls. For tests wait until the
+    // refresh is finished.
+    if cfg!(debug_assertions) {
+        join.await?;
+    }
+    Ok(actor)
+}
+
 pub(crate) fn read_from_string_or_source(
     content: &str,
     media_type: &Option<MediaTypeMarkdownOrHtml>,
@@ -62,3 +118,19 @@ pub(crate) fn read_from_string_or_source_opt(
 pub(crate) struct Endpoints {
     pub shared_inbox: Url,
 }
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub(crate) struct ImageObject {
+    #[serde(rename = "type")]
+    kind: ImageType,
+    pub url: Url,
+}
+
+impl ImageObject {
+    pub(crate) fn new(url: Url) -> Self {
+        ImageObject {
+            kind: Default::default(),
+            url,
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use anyhow::anyhow;
+use base64::{Engine, engine::general_purpose::STANDARD};
+use ibis_database::error::BackendResult;
+use rsa::{
+    RsaPrivateKey,
+    RsaPublicKey,
+    pkcs1v15::{Signature, SigningKey, VerifyingKey},
+    pkcs8::{DecodePrivateKey, DecodePublicKey},
+    sha2::Sha256,
+    signature::{SignatureEncoding, Signer, Verifier},
+};
+
+/// Sign data with an actor's private key (PEM encoded), returning a base64 encoded signature.
+pub(crate) fn sign_data(data: &str, private_key: &str) -> BackendResult<String> {
+    let private_key = RsaPrivateKey::from_pkcs8_pem(private_key)?;
+    let signature = SigningKey::<Sha256>::new(private_key).sign(data.as_bytes());
+    Ok(STANDARD.encode(signature.to_bytes()))
+}
+
+/// Verify a signature created by [sign_data] with the actor's public key (PEM encoded).
+pub(crate) fn verify_data(data: &str, signature: &str, public_key: &str) -> BackendResult<()> {
+    let public_key = RsaPublicKey::from_public_key_pem(public_key)?;
+    let signature = Signature::try_from(STANDARD.decode(signature)?.as_slice())?;
+    VerifyingKey::<Sha256>::new(public_key)
+        .verify(data.as_bytes(), &signature)
+        .map_err(|_| anyhow!("Invalid signature"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use activitypub_federation::http_signatures::generate_actor_keypair;
+
+    #[test]
+    fn test_sign_and_verify() -> BackendResult<()> {
+        let keypair = generate_actor_keypair()?;
+        let signature = sign_data("some data", &keypair.private_key)?;
+        assert!(verify_data("some data", &signature, &keypair.public_key).is_ok());
+        assert!(verify_data("other data", &signature, &keypair.public_key).is_err());
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use super::{Endpoints, Source, read_from_string_or_source_opt};
+use super::{Endpoints, RefreshableActor, Source, read_from_string_or_source_opt};
 use activitypub_federation::{
     config::Data,
     fetch::object_id::ObjectId,
@@ -13,8 +13,9 @@ use activitypub_federation::{
 };
 use chrono::{DateTime, Utc};
 use ibis_database::{
+    DbUrl,
     common::user::Person,
-    error::BackendError,
+    error::{BackendError, BackendResult},
     impls::{IbisContext, user::PersonInsertForm},
 };
 use ibis_markdown::render_article_markdown;
@@ -84,7 +85,7 @@ impl Object for PersonWrapper {
             .map(Into::into))
     }
 
-    async fn into_json(self, _context: &Data<Self::DataType>) -> Result<Self::Kind, Self::Error> {
+    async fn into_json(self, context: &Data<Self::DataType>) -> Result<Self::Kind, Self::Error> {
         Ok(ApubUser {
             kind: Default::default(),
             id: self.ap_id.clone().into(),
@@ -92,7 +93,10 @@ impl Object for PersonWrapper {
             inbox: Url::parse(&self.inbox_url)?,
             public_key: self.public_key(),
             name: self.display_name.clone(),
-            summary: self.bio.as_ref().map(|b| render_article_markdown(b)),
+            summary: self
+                .bio
+                .as_ref()
+                .map(|b| render_article_markdown(b, context.conf.options.math_rendering, 0, false)),
             outbox: format!("{}/outbox", &self.ap_id),
             media_type: Some(MediaTypeMarkdownOrHtml::Html),
             source: self.bio.clone().map(Source::new),
@@ -130,6 +134,12 @@ impl Object for PersonWrapper {
     }
 }
 
+impl RefreshableActor for PersonWrapper {
+    fn mark_refreshed(ap_id: &DbUrl, context: &IbisContext) -> BackendResult<()> {
+        Person::mark_refreshed(ap_id, context)
+    }
+}
+
 impl Actor for PersonWrapper {
     fn public_key_pem(&self) -> &str {
         &self.public_key
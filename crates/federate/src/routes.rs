@@ -12,7 +12,12 @@ use crate::{
             delete_comment::DeleteComment,
             undo_delete_comment::UndoDeleteComment,
         },
-        following::{accept::Accept, follow::Follow, undo_follow::UndoFollow},
+        following::{
+            accept::Accept,
+            follow::Follow,
+            reject::RejectFollow,
+            undo_follow::UndoFollow,
+        },
         reject::RejectEdit,
     },
     collections::{
@@ -22,9 +27,11 @@ use crate::{
         instance_collection::InstanceCollection,
         instance_follower::InstanceFollower,
     },
+    inbox_retry::RetryableActivity,
     objects::{
         article::ArticleWrapper,
         comment::CommentWrapper,
+        edit::EditWrapper,
         instance::InstanceWrapper,
         user::PersonWrapper,
     },
@@ -38,6 +45,7 @@ use activitypub_federation::{
     protocol::context::WithContext,
     traits::{Activity, Collection, Object},
 };
+use anyhow::anyhow;
 use axum::{
     Router,
     extract::Path,
@@ -48,7 +56,7 @@ use axum_macros::debug_handler;
 use either::Either;
 use ibis_database::{
     common::{
-        article::Article,
+        article::{Article, Edit},
         comment::Comment,
         instance::Instance,
         newtypes::CommentId,
@@ -74,6 +82,7 @@ pub fn federation_routes() -> Router<()> {
         .route("/linked_instances", get(http_get_linked_instances))
         .route("/article/{title}", get(http_get_article))
         .route("/article/{title}/edits", get(http_get_article_edits))
+        .route("/article/{title}/{version}", get(http_get_edit))
         .route("/comment/{id}", get(http_get_comment))
         .route("/inbox", post(http_post_inbox))
 }
@@ -153,10 +162,7 @@ async fn http_get_article(
     Path(title): Path<String>,
     context: Data<IbisContext>,
 ) -> BackendResult<impl IntoResponse> {
-    let title = title.replace("_", " ");
-    let article: ArticleWrapper = Article::read_view((&title, None), None, &context)?
-        .article
-        .into();
+    let article: ArticleWrapper = read_local_article(&title, &context)?.into();
     let json = article.into_json(&context).await?;
     Ok(FederationJson(WithContext::new_default(json)))
 }
@@ -166,12 +172,43 @@ async fn http_get_article_edits(
     Path(title): Path<String>,
     context: Data<IbisContext>,
 ) -> BackendResult<impl IntoResponse> {
-    let title = title.replace("_", " ");
-    let article = Article::read_view((&title, None), None, &context)?;
-    let json = EditCollection::read_local(&article.article, &context).await?;
+    let article = read_local_article(&title, &context)?;
+    let json = EditCollection::read_local(&article, &context).await?;
     Ok(FederationJson(WithContext::new_default(json)))
 }
 
+/// Single edit of a local article, the id is formed by appending the version hash to the article id.
+#[debug_handler]
+async fn http_get_edit(
+    Path((title, version)): Path<(String, String)>,
+    context: Data<IbisContext>,
+) -> BackendResult<impl IntoResponse> {
+    let article = read_local_article(&title, &context)?;
+    let ap_id = Url::parse(&format!("{}/{version}", article.ap_id))?;
+    let edit = Edit::read_from_ap_id(&ap_id.into(), &context)?;
+    if edit.pending {
+        return Err(anyhow!("Edit not found").into());
+    }
+    let edit: EditWrapper = edit.into();
+    let json = edit.into_json(&context).await?;
+    Ok(FederationJson(WithContext::new_default(json)))
+}
+
+/// Moved articles keep their original ap_id, so look up by ap_id first and only fall back to
+/// the title.
+fn read_local_article(title: &str, context: &IbisContext) -> BackendResult<Article> {
+    let ap_id = Url::parse(&format!(
+        "{}://{}/article/{}",
+        http_protocol_str(),
+        context.conf.federation.domain,
+        title
+    ))?;
+    match Article::read_from_ap_id(&ap_id.into(), context) {
+        Ok(article) if article.local && !article.removed => Ok(article),
+        _ => Ok(Article::read_view((&title.replace("_", " "), None), None, context)?.article),
+    }
+}
+
 #[debug_handler]
 async fn http_get_comment(
     Path(id): Path<i32>,
@@ -190,6 +227,7 @@ pub enum InboxActivities {
     Follow(Follow),
     UndoFollow(UndoFollow),
     Accept(Accept),
+    RejectFollow(RejectFollow),
     RejectEdit(RejectEdit),
     AnnounceActivity(AnnounceActivity),
     AnnouncableActivities(AnnouncableActivities),
@@ -208,12 +246,25 @@ pub enum AnnouncableActivities {
     UndoDeleteComment(UndoDeleteComment),
 }
 
+impl AnnouncableActivities {
+    /// Article which this activity is about, so it can also be sent to subscribers of the article
+    pub(crate) fn article_ap_id(&self) -> Option<&Url> {
+        match self {
+            AnnouncableActivities::EditArticle(a) => Some(a.object.object.inner()),
+            AnnouncableActivities::UpdateArticle(a) => Some(a.object.id.inner()),
+            AnnouncableActivities::RemoveArticle(a) => Some(a.object.inner()),
+            AnnouncableActivities::UndoRemoveArticle(a) => Some(a.object.object.inner()),
+            _ => None,
+        }
+    }
+}
+
 #[debug_handler]
 pub async fn http_post_inbox(
     context: Data<IbisContext>,
     activity_data: ActivityData,
 ) -> impl IntoResponse {
-    receive_activity::<WithContext<InboxActivities>, Either<PersonWrapper, InstanceWrapper>, _>(
+    receive_activity::<RetryableActivity, Either<PersonWrapper, InstanceWrapper>, _>(
         activity_data,
         &context,
     )
@@ -1,14 +1,32 @@
+use crate::check_not_blocked;
 use anyhow::anyhow;
-use ibis_database::error::BackendResult;
+use ibis_database::{common::instance::Options, error::BackendResult, impls::IbisContext};
 use regex::Regex;
 use std::sync::LazyLock;
+use url::Url;
 
+/// Maximum number of media attachments per article
+pub const MAX_ARTICLE_ATTACHMENTS: usize = 20;
+
+/// Article titles are stored with spaces, which are replaced by underscores in urls. Only
+/// letters, digits, spaces, `-` and `.` are allowed so that titles always form a valid path.
 pub fn validate_article_title(title: &str) -> BackendResult<()> {
-    #[expect(clippy::expect_used)]
-    static TITLE_REGEX: LazyLock<Regex> =
-        LazyLock::new(|| Regex::new(r"^[a-zA-Z0-9-. ]{3,100}$").expect("compile regex"));
-    if !TITLE_REGEX.is_match(title) {
-        return Err(anyhow!("Invalid title").into());
+    const MIN_LENGTH: usize = 3;
+    const MAX_LENGTH: usize = 100;
+    if title.len() < MIN_LENGTH {
+        return Err(anyhow!("Title must be at least {MIN_LENGTH} characters").into());
+    }
+    if title.len() > MAX_LENGTH {
+        return Err(anyhow!("Title must be at most {MAX_LENGTH} characters").into());
+    }
+    if let Some(c) = title
+        .chars()
+        .find(|c| !c.is_ascii_alphanumeric() && !matches!(c, '-' | '.' | ' '))
+    {
+        return Err(anyhow!("Title contains invalid character '{c}'").into());
+    }
+    if title.starts_with(' ') || title.ends_with(' ') || title.contains("  ") {
+        return Err(anyhow!("Title contains leading, trailing or repeated spaces").into());
     }
     Ok(())
 }
@@ -47,6 +65,18 @@ pub fn validate_comment_max_depth(depth: i32) -> BackendResult<()> {
     Ok(())
 }
 
+/// Attachments must be http(s) links to a domain which is not blocked. They are only linked,
+/// so federation mode and allowlist don't apply.
+pub fn validate_attachment_url(url: &Url, context: &IbisContext) -> BackendResult<()> {
+    let Some(domain) = url
+        .domain()
+        .filter(|_| matches!(url.scheme(), "http" | "https"))
+    else {
+        return Err(anyhow!("Invalid attachment url").into());
+    };
+    check_not_blocked(domain, context)
+}
+
 pub fn validate_not_empty(text: &str) -> BackendResult<()> {
     if text.trim().len() < 2 {
         return Err(anyhow!("Empty text submitted").into());
@@ -54,9 +84,168 @@ pub fn validate_not_empty(text: &str) -> BackendResult<()> {
     Ok(())
 }
 
+/// Reject article text which exceeds the configured `max_article_bytes`
+pub fn validate_article_size(text: &str, options: &Options) -> BackendResult<()> {
+    let max = options.max_article_bytes as usize;
+    if max > 0 && text.len() > max {
+        return Err(anyhow!(
+            "Article text is {} bytes, which exceeds the maximum of {max} bytes",
+            text.len()
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Reject edit summaries which are too long, or empty if `require_edit_summary` is enabled
+pub fn validate_edit_summary(summary: &str, options: &Options) -> BackendResult<()> {
+    const MAX_LENGTH: usize = 500;
+    if options.require_edit_summary && summary.trim().is_empty() {
+        return Err(anyhow!("Edit summary is required").into());
+    }
+    let len = summary.chars().count();
+    if len > MAX_LENGTH {
+        return Err(anyhow!(
+            "Edit summary is {len} characters, which exceeds the maximum of {MAX_LENGTH} characters"
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Reject new passwords which are shorter than `min_password_length`, or which consist only of
+/// letters or only of digits and symbols
+pub fn validate_password(password: &str, options: &Options) -> BackendResult<()> {
+    let min = options.min_password_length as usize;
+    if password.chars().count() < min {
+        return Err(anyhow!("Passwords must have at least {min} characters").into());
+    }
+    let letters = password.chars().filter(|c| c.is_alphabetic()).count();
+    if letters == 0 || letters == password.chars().count() {
+        return Err(
+            anyhow!("Passwords must contain at least one letter and one digit or symbol").into(),
+        );
+    }
+    Ok(())
+}
+
+/// Reject binary or control-heavy content which breaks diffing and rendering. Text arrives as
+/// `&str` so it is already valid UTF-8, but replacement characters from lossy decoding are
+/// treated the same as control characters. Newlines, tabs and any printable unicode are allowed.
+pub fn validate_text_content(text: &str) -> BackendResult<()> {
+    if text.contains('\0') {
+        return Err(anyhow!("Text contains binary content").into());
+    }
+    let total = text.chars().count();
+    let non_printable = text
+        .chars()
+        .filter(|c| {
+            (c.is_control() && !matches!(c, '\n' | '\r' | '\t'))
+                || *c == char::REPLACEMENT_CHARACTER
+        })
+        .count();
+    // Allow up to 1% of non-printable characters, eg from copy-pasting
+    if non_printable * 100 > total {
+        return Err(anyhow!("Text contains too many non-printable characters").into());
+    }
+    Ok(())
+}
+
 #[test]
 fn test_validate_article_title() {
     assert!(validate_article_title("With space 123").is_ok());
+    assert!(validate_article_title("Version 1.0-beta").is_ok());
     assert!(validate_article_title(&"long".to_string().repeat(100)).is_err());
+    assert!(validate_article_title(&"a".repeat(100)).is_ok());
     assert!(validate_article_title("a").is_err());
 }
+
+#[test]
+fn test_validate_article_title_characters() {
+    for title in [
+        "With/slash",
+        "Under_score",
+        "Query?",
+        "Hash#tag",
+        "Ünïcödé",
+        "Tab\tTitle",
+    ] {
+        assert!(validate_article_title(title).is_err(), "{title}");
+    }
+    assert!(validate_article_title("With/slash").is_err_and(|e| e.to_string().contains("'/'")));
+    assert!(validate_article_title(" Leading").is_err());
+    assert!(validate_article_title("Trailing ").is_err());
+    assert!(validate_article_title("Double  space").is_err());
+}
+
+#[test]
+fn test_normalize_article_title() {
+    use ibis_database::common::{MAIN_PAGE_NAME, utils::normalize_article_title};
+    for title in [
+        " Main_Page ",
+        "Main Page",
+        "Main__Page",
+        "Main \t Page",
+        "_Main_Page_",
+    ] {
+        assert_eq!(MAIN_PAGE_NAME, normalize_article_title(title));
+    }
+    assert!(validate_article_title(&normalize_article_title("Under_score")).is_ok());
+}
+
+#[test]
+fn test_validate_article_size() {
+    let options = Options {
+        max_article_bytes: 10,
+        ..Default::default()
+    };
+    assert!(validate_article_size("short", &options).is_ok());
+    assert!(validate_article_size(&"a".repeat(10), &options).is_ok());
+    assert!(validate_article_size(&"a".repeat(11), &options).is_err());
+    let unlimited = Options {
+        max_article_bytes: 0,
+        ..Default::default()
+    };
+    assert!(validate_article_size(&"a".repeat(100), &unlimited).is_ok());
+}
+
+#[test]
+fn test_validate_edit_summary() {
+    let options = Options::default();
+    assert!(validate_edit_summary("fix typo", &options).is_ok());
+    assert!(validate_edit_summary(" ", &options).is_err());
+    assert!(validate_edit_summary(&"ü".repeat(500), &options).is_ok());
+    assert!(validate_edit_summary(&"ü".repeat(501), &options).is_err());
+    let optional = Options {
+        require_edit_summary: false,
+        ..Default::default()
+    };
+    assert!(validate_edit_summary("", &optional).is_ok());
+    assert!(validate_edit_summary(&"a".repeat(501), &optional).is_err());
+}
+
+#[test]
+fn test_validate_password() {
+    let options = Options::default();
+    assert!(validate_password("hunter22", &options).is_ok());
+    assert!(validate_password("correct horse battery staple", &options).is_ok());
+    assert!(validate_password("hunt2", &options).is_err());
+    assert!(validate_password("onlyletters", &options).is_err());
+    assert!(validate_password("12345678", &options).is_err());
+    let long = Options {
+        min_password_length: 12,
+        ..Default::default()
+    };
+    assert!(validate_password("hunter22", &long).is_err());
+    assert!(validate_password("hunter2hunter2", &long).is_ok());
+}
+
+#[test]
+fn test_validate_text_content() {
+    assert!(validate_text_content("Regular text\nwith\ttabs and ünïcödé 日本語 🦩\n").is_ok());
+    assert!(validate_text_content("null\0byte").is_err());
+    assert!(validate_text_content(&"\u{1}\u{2}\u{7}\u{1b}text".repeat(10)).is_err());
+    assert!(validate_text_content(&"\u{FFFD}".repeat(5)).is_err());
+    let mostly_text = format!("{}\u{c}", "a".repeat(200));
+    assert!(validate_text_content(&mostly_text).is_ok());
+}
@@ -6,8 +6,8 @@ use axum::{
     Json,
     Router,
     extract::Query,
-    http::header::CONTENT_TYPE,
-    response::IntoResponse,
+    http::{StatusCode, header::CONTENT_TYPE},
+    response::{IntoResponse, Response},
     routing::get,
 };
 use axum_macros::debug_handler;
@@ -32,7 +32,7 @@ struct Params {
 async fn get_webfinger_response(
     info: Query<Params>,
     context: Data<IbisContext>,
-) -> BackendResult<impl IntoResponse> {
+) -> BackendResult<Response> {
     let name = extract_webfinger_name(&info.resource, &context)?;
 
     let links = if name == context.conf.setup.group_name {
@@ -44,8 +44,10 @@ async fn get_webfinger_response(
         ))?;
         webfinger_link_for_actor(url)?
     } else {
-        let user_id: Url = Person::read_from_name(name, &None, &context)?.ap_id.into();
-        webfinger_link_for_actor(user_id)?
+        let Ok(user) = Person::read_from_name(name, &None, &context) else {
+            return Ok((StatusCode::NOT_FOUND, "User not found").into_response());
+        };
+        webfinger_link_for_actor(user.ap_id.into())?
     };
 
     let webfinger = Webfinger {
@@ -57,7 +59,8 @@ async fn get_webfinger_response(
     Ok((
         [(CONTENT_TYPE, WEBFINGER_CONTENT_TYPE.clone())],
         Json(webfinger),
-    ))
+    )
+        .into_response())
 }
 
 fn webfinger_link_for_actor(url: Url) -> BackendResult<Vec<WebfingerLink>> {
@@ -1,9 +1,10 @@
 use crate::pages::{
     article::{
         actions::ArticleActions,
+        category::CategoryPage,
         comment_redirect::CommentRedirect,
         create::CreateArticle,
-        diff::EditDiff,
+        diff::{CompareRevisions, EditDiff},
         discussion::ArticleDiscussion,
         edit::EditArticle,
         history::ArticleHistory,
@@ -13,10 +14,12 @@ use crate::pages::{
         about::About,
         details::InstanceDetails,
         explore::Explore,
+        recent_changes::RecentChanges,
         search::Search,
         settings::AdminSettings,
     },
     user::{
+        contributions::UserContributions,
         edit_profile::UserEditProfile,
         login::Login,
         notifications::Notifications,
@@ -117,16 +120,26 @@ pub fn App() -> impl IntoView {
                                 view=ArticleActions
                             />
                             <Route path=path!("/article/:title/diff/:hash") view=EditDiff />
+                            <Route
+                                path=path!("/article/:title/compare/:from/:to")
+                                view=CompareRevisions
+                            />
                             <Route path=path!("/comment/:id") view=CommentRedirect />
+                            <Route path=path!("/category/:name") view=CategoryPage />
                             <IbisProtectedRoute path=path!("/create-article") view=CreateArticle />
 
                             <Route path=path!("/explore") view=Explore />
+                            <Route path=path!("/recent_changes") view=RecentChanges />
                             <Route path=path!("/instance/:hostname") view=InstanceDetails />
                             <IbisProtectedRoute path=path!("/admin") view=AdminSettings />
                             <Route path=path!("/about") view=About />
                             <Route path=path!("/search") view=Search />
 
                             <Route path=path!("/user/:name") view=UserProfile />
+                            <Route
+                                path=path!("/user/:name/contributions")
+                                view=UserContributions
+                            />
                             <Route path=path!("/login") view=Login />
                             <Route path=path!("/register") view=Register />
                             <Route path=path!("/account/verify_email") view=VerifyEmail />
@@ -1,27 +1,33 @@
 use crate::pages::article_resource;
 use ibis_api_client::{
     CLIENT,
-    article::{ForkArticleParams, ProtectArticleParams},
+    article::{ForkArticleParams, MoveArticleParams, SetProtectionParams},
     errors::FrontendResultExt,
 };
-use ibis_database::common::{article::Article, newtypes::ArticleId};
+use ibis_database::common::{
+    article::{Article, ProtectionLevel},
+    newtypes::ArticleId,
+};
 use ibis_frontend_components::{
     article_nav::{ActiveTab, ArticleNav},
     suspense_error::SuspenseError,
-    utils::{formatting::article_path, resources::is_admin},
+    utils::{
+        formatting::article_path,
+        resources::{is_logged_in, is_moderator},
+    },
 };
 use leptos::{ev::KeyboardEvent, prelude::*};
 use leptos_router::components::Redirect;
 
 #[component]
 pub fn ArticleActions() -> impl IntoView {
-    let article = article_resource();
+    let article = article_resource(false);
     let (new_title, set_new_title) = signal(String::new());
     let (fork_response, set_fork_response) = signal(Option::<Article>::None);
     let fork_action = Action::new(move |(article_id, new_title): &(ArticleId, String)| {
         let params = ForkArticleParams {
             article_id: *article_id,
-            new_title: new_title.to_string(),
+            new_title: Some(new_title.to_string()).filter(|t| !t.is_empty()),
         };
         async move {
             CLIENT
@@ -30,10 +36,24 @@ pub fn ArticleActions() -> impl IntoView {
                 .error_popup(|res| set_fork_response.set(Some(res.article)));
         }
     });
-    let protect_action = Action::new(move |(id, protected): &(ArticleId, bool)| {
-        let params = ProtectArticleParams {
+    let (move_title, set_move_title) = signal(String::new());
+    let (move_response, set_move_response) = signal(Option::<Article>::None);
+    let move_action = Action::new(move |(article_id, new_title): &(ArticleId, String)| {
+        let params = MoveArticleParams {
+            article_id: *article_id,
+            new_title: new_title.to_string(),
+        };
+        async move {
+            CLIENT
+                .move_article(&params)
+                .await
+                .error_popup(|res| set_move_response.set(Some(res.article)));
+        }
+    });
+    let protect_action = Action::new(move |(id, level): &(ArticleId, ProtectionLevel)| {
+        let params = SetProtectionParams {
             article_id: *id,
-            protected: !protected,
+            level: *level,
         };
         async move {
             CLIENT
@@ -51,6 +71,15 @@ pub fn ArticleActions() -> impl IntoView {
                 .error_popup(|_| article.refetch());
         }
     });
+    let watch_action = Action::new(move |(id, following): &(ArticleId, bool)| {
+        let (id, following) = (*id, *following);
+        async move {
+            CLIENT
+                .follow_article(id, !following)
+                .await
+                .error_popup(|_| article.refetch());
+        }
+    });
     view! {
         <ArticleNav article=article active_tab=ActiveTab::Actions />
         <SuspenseError result=article>
@@ -60,19 +89,77 @@ pub fn ArticleActions() -> impl IntoView {
                     .map(|article| {
                         view! {
                             <div>
-                                <Show when=move || { is_admin() && article.article.local }>
+                                <Show when=is_logged_in>
                                     <div class="m-4">
                                         <button
                                             class="btn btn-secondary"
-                                            title="Protect a local article so that only admins can edit it"
+                                            title="Get notified about changes to this article"
+                                            on:click=move |_| {
+                                                watch_action.dispatch((article.article.id, article.following));
+                                            }
+                                        >
+                                            {if article.following {
+                                                "Remove from watchlist"
+                                            } else {
+                                                "Add to watchlist"
+                                            }}
+                                        </button>
+                                    </div>
+                                </Show>
+                                <Show when=move || {
+                                    is_logged_in() && article.article.local
+                                        && article.article.redirect_to.is_none()
+                                }>
+                                    <div class="flex gap-2 items-center m-4">
+                                        <input
+                                            class="input"
+                                            placeholder="New Title"
+                                            on:keyup=move |ev: KeyboardEvent| {
+                                                let val = event_target_value(&ev);
+                                                set_move_title.update(|v| *v = val);
+                                            }
+                                        />
+                                        <button
+                                            class="btn btn-secondary"
+                                            title="Rename the article and leave a redirect at the old title"
+                                            disabled=move || move_title.get().is_empty()
                                             on:click=move |_| {
-                                                protect_action
-                                                    .dispatch((article.article.id, article.article.protected));
+                                                move_action.dispatch((article.article.id, move_title.get()));
                                             }
                                         >
-                                            Toggle Article Protection
+                                            Move Article
                                         </button>
                                     </div>
+                                </Show>
+                                <Show when=move || { is_moderator() && article.article.local }>
+                                    <div class="flex gap-2 items-center m-4">
+                                        <span>Protection level</span>
+                                        <select
+                                            class="select select-bordered"
+                                            title="Restrict who can edit this article"
+                                            on:change=move |ev| {
+                                                if let Ok(level) = event_target_value(&ev).parse() {
+                                                    protect_action.dispatch((article.article.id, level));
+                                                }
+                                            }
+                                        >
+                                            {[
+                                                ProtectionLevel::Unprotected,
+                                                ProtectionLevel::AutoConfirmed,
+                                                ProtectionLevel::AdminOnly,
+                                            ]
+                                                .map(|level| {
+                                                    view! {
+                                                        <option
+                                                            value=level.as_str()
+                                                            selected=level == article.article.protection
+                                                        >
+                                                            {level.as_str()}
+                                                        </option>
+                                                    }
+                                                })}
+                                        </select>
+                                    </div>
                                     <div class="m-4">
                                         <button
                                             class="btn btn-secondary"
@@ -113,6 +200,7 @@ pub fn ArticleActions() -> impl IntoView {
                     })
             })}
             {fork_response.get().map(|article| view! { <Redirect path=article_path(&article) /> })}
+            {move_response.get().map(|article| view! { <Redirect path=article_path(&article) /> })}
         </SuspenseError>
     }
 }
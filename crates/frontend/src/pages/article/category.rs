@@ -0,0 +1,48 @@
+use ibis_api_client::{CLIENT, article::GetCategoryParams, errors::FrontendError};
+use ibis_frontend_components::{suspense_error::SuspenseError, utils::formatting::article_path};
+use leptos::prelude::*;
+use leptos_meta::Title;
+use leptos_router::hooks::use_params_map;
+
+#[component]
+pub fn CategoryPage() -> impl IntoView {
+    let params = use_params_map();
+    let name = move || params.get().get("name").clone();
+    let category = Resource::new(name, move |name| async move {
+        let name = name.ok_or(FrontendError::new("No category given"))?;
+        CLIENT.get_category(&GetCategoryParams { name }).await
+    });
+
+    view! {
+        <SuspenseError result=category>
+            {move || Suspend::new(async move {
+                category
+                    .await
+                    .map(|category| {
+                        let title = format!("Category: {}", category.category.name);
+                        view! {
+                            <Title text=title.clone() />
+                            <h1 class="flex-auto my-6 font-serif text-4xl font-bold grow">
+                                {title}
+                            </h1>
+                            <ul class="list-disc list-inside">
+                                {category
+                                    .articles
+                                    .iter()
+                                    .map(|a| {
+                                        view! {
+                                            <li>
+                                                <a class="text-lg link" href=article_path(a)>
+                                                    {a.title()}
+                                                </a>
+                                            </li>
+                                        }
+                                    })
+                                    .collect::<Vec<_>>()}
+                            </ul>
+                        }
+                    })
+            })}
+        </SuspenseError>
+    }
+}
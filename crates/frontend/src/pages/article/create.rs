@@ -1,5 +1,9 @@
 use ibis_api_client::{CLIENT, article::CreateArticleParams};
-use ibis_database::common::{article::ArticleView, newtypes::InstanceId};
+use ibis_database::common::{
+    article::ArticleView,
+    newtypes::InstanceId,
+    utils::normalize_article_title,
+};
 use ibis_frontend_components::{
     article_editor::EditorView,
     suspense_error::SuspenseError,
@@ -13,13 +17,13 @@ use leptos_use::{UseTextareaAutosizeReturn, use_textarea_autosize};
 
 #[component]
 pub fn CreateArticle() -> impl IntoView {
+    // prefill the title, for example when following a red link or after an article wasn't found
     let title = use_query_map()
         .get_untracked()
         .get("title")
-        .unwrap_or_default()
-        .replace('_', " ");
+        .unwrap_or_default();
     let title = title.split_once('@').map(|(t, _)| t).unwrap_or(&title);
-    let title = signal(title.to_string());
+    let title = signal(normalize_article_title(title));
 
     let textarea_ref = NodeRef::<Textarea>::new();
     let UseTextareaAutosizeReturn {
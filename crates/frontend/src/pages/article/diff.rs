@@ -1,7 +1,10 @@
 use crate::pages::{article_edits_resource, article_resource};
+use ibis_api_client::{CLIENT, article::CompareRevisionsParams};
+use ibis_database::common::article::EditVersion;
 use ibis_frontend_components::{
     Pending,
     article_nav::{ActiveTab, ArticleNav},
+    diff_view::DiffView,
     suspense_error::SuspenseError,
     utils::formatting::{edit_time, user_link},
 };
@@ -12,7 +15,7 @@ use leptos_router::hooks::use_params_map;
 #[component]
 pub fn EditDiff() -> impl IntoView {
     let params = use_params_map();
-    let article = article_resource();
+    let article = article_resource(false);
 
     view! {
         <ArticleNav article=article active_tab=ActiveTab::History />
@@ -43,11 +46,7 @@ pub fn EditDiff() -> impl IntoView {
                                         <Pending pending />
                                     </div>
                                     <p>"by " {user_link(&edit.creator)}</p>
-                                    <div class="max-w-full prose prose-slate">
-                                        <pre class="text-wrap">
-                                            <code>{edit.edit.diff.clone()}</code>
-                                        </pre>
-                                    </div>
+                                    <DiffView diff=edit.edit.diff.clone() />
                                 },
                             )
                         } else {
@@ -65,3 +64,52 @@ pub fn EditDiff() -> impl IntoView {
         </SuspenseError>
     }
 }
+
+/// Combined diff between two versions of the article, given by the `from` and `to` hashes
+#[component]
+pub fn CompareRevisions() -> impl IntoView {
+    let params = use_params_map();
+    let article = article_resource(false);
+    let version = move |name: &str| {
+        params
+            .get()
+            .get(name)
+            .and_then(|h| h.parse().ok())
+            .map(EditVersion)
+            .unwrap_or_default()
+    };
+    let diff = Resource::new(
+        move || (article.get(), version("from"), version("to")),
+        move |(article, from, to)| async move {
+            let article_id = article.and_then(|a| a.ok()).map(|a| a.article.id);
+            match article_id {
+                Some(article_id) => {
+                    let params = CompareRevisionsParams {
+                        article_id,
+                        from,
+                        to,
+                    };
+                    CLIENT.compare_revisions(&params).await
+                }
+                None => Ok(String::new()),
+            }
+        },
+    );
+
+    view! {
+        <ArticleNav article=article active_tab=ActiveTab::History />
+        <SuspenseError result=diff>
+            {move || Suspend::new(async move {
+                let title = article.await.map(|a| a.article.title()).unwrap_or_default();
+                diff.await
+                    .map(|diff| {
+                        view! {
+                            <Title text=format!("Compare versions — {title}") />
+                            <h2 class="my-2 font-serif text-xl font-bold">Compare versions</h2>
+                            <DiffView diff />
+                        }
+                    })
+            })}
+        </SuspenseError>
+    }
+}
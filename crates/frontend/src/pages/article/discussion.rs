@@ -11,7 +11,7 @@ use std::collections::HashMap;
 
 #[component]
 pub fn ArticleDiscussion() -> impl IntoView {
-    let article = article_resource();
+    let article = article_resource(false);
 
     let show_editor = signal(CommentId(-1));
 
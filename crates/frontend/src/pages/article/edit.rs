@@ -29,7 +29,7 @@ const CONFLICT_MESSAGE: &str = "There was an edit conflict. Resolve it manually
 
 #[component]
 pub fn EditArticle() -> impl IntoView {
-    let article = article_resource();
+    let article = article_resource(false);
 
     let (edit_response, set_edit_response) = signal(EditResponse::None);
     let (edit_error, set_edit_error) = signal(None::<String>);
@@ -54,6 +54,7 @@ pub fn EditArticle() -> impl IntoView {
         trigger_resize: _,
     } = use_textarea_autosize(textarea_ref);
     let (summary, set_summary) = signal(String::new());
+    let (minor, set_minor) = signal(false);
     let (wait_for_response, set_wait_for_response) = signal(false);
     let button_is_disabled =
         Signal::derive(move || wait_for_response.get() || summary.get().is_empty());
@@ -75,6 +76,7 @@ pub fn EditArticle() -> impl IntoView {
                 EditResponse::Conflict(conflict) => conflict.previous_version_id.clone(),
                 _ => article.latest_version,
             };
+            let minor = minor.get_untracked();
             async move {
                 set_edit_error.update(|e| *e = None);
                 let params = EditArticleParams {
@@ -83,6 +85,7 @@ pub fn EditArticle() -> impl IntoView {
                     summary,
                     previous_version_id,
                     resolve_conflict_id,
+                    minor: Some(minor),
                 };
                 set_wait_for_response.update(|w| *w = true);
                 let res = CLIENT.edit_article(&params).await;
@@ -155,6 +158,20 @@ pub fn EditArticle() -> impl IntoView {
                                                         set_summary.update(|p| *p = val);
                                                     }
                                                 />
+                                                <label
+                                                    class="flex gap-2 items-center me-4 label"
+                                                    title="Small change like a typo fix, can be hidden in the history"
+                                                >
+                                                    <input
+                                                        type="checkbox"
+                                                        class="checkbox"
+                                                        prop:checked=minor
+                                                        on:change=move |ev| {
+                                                            set_minor.set(event_target_checked(&ev));
+                                                        }
+                                                    />
+                                                    "Minor edit"
+                                                </label>
 
                                                 <button
                                                     class="btn btn-primary"
@@ -1,26 +1,120 @@
 use crate::pages::{article_edits_resource, article_resource};
+use ibis_api_client::{
+    CLIENT,
+    article::{RevertArticleParams, TagRevisionParams},
+    errors::FrontendResultExt,
+};
+use ibis_database::common::article::EditVersion;
 use ibis_frontend_components::{
     article_nav::{ActiveTab, ArticleNav},
     edit_list::EditList,
     suspense_error::SuspenseError,
+    utils::{
+        formatting::{edit_time, user_link},
+        resources::user_role,
+    },
 };
 use leptos::prelude::*;
 
 #[component]
 pub fn ArticleHistory() -> impl IntoView {
-    let article = article_resource();
+    let article = article_resource(false);
+    let revert_action = Action::new(move |version: &EditVersion| {
+        let version = version.clone();
+        async move {
+            let Ok(article_id) = article.await.map(|a| a.article.id) else {
+                return;
+            };
+            let params = RevertArticleParams {
+                article_id,
+                revert_to_version: version,
+            };
+            CLIENT
+                .revert_article(&params)
+                .await
+                .error_popup(|_| article.refetch());
+        }
+    });
+
+    let tag_action = Action::new(move |version: &EditVersion| {
+        let version = version.clone();
+        async move {
+            let Ok(article_id) = article.await.map(|a| a.article.id) else {
+                return;
+            };
+            let tag = window()
+                .prompt_with_message("Tag name, eg v1.0 or reviewed")
+                .ok()
+                .flatten()
+                .filter(|t| !t.trim().is_empty());
+            let Some(tag) = tag else {
+                return;
+            };
+            let params = TagRevisionParams {
+                article_id,
+                version,
+                tag,
+            };
+            CLIENT
+                .tag_revision(&params)
+                .await
+                .error_popup(|_| article.refetch());
+        }
+    });
 
     view! {
         <ArticleNav article=article active_tab=ActiveTab::History />
         <SuspenseError result=article>
             {move || Suspend::new(async move {
+                let moves = article.await.map(|a| a.moves).unwrap_or_default();
+                let contributors = match article.await {
+                    Ok(a) => CLIENT.get_contributors(a.article.id).await.unwrap_or_default(),
+                    Err(_) => vec![],
+                };
+                let tags = match article.await {
+                    Ok(a) => CLIENT.get_tagged_revisions(a.article.id).await.unwrap_or_default(),
+                    Err(_) => vec![],
+                };
                 let edits = article_edits_resource(article).await;
                 edits
                     .await
                     .map(|edits| {
+                        let revert_action = user_role().can_edit().then_some(revert_action);
+                        let tag_action = user_role().can_edit().then_some(tag_action);
                         view! {
+                            <ul class="m-2">
+                                {moves
+                                    .into_iter()
+                                    .rev()
+                                    .map(|m| {
+                                        view! {
+                                            <li>
+                                                "Moved from " {m.article_move.old_title} " to "
+                                                {m.article_move.new_title} " "
+                                                {edit_time(m.article_move.published)} " by "
+                                                {user_link(&m.creator)}
+                                            </li>
+                                        }
+                                    })
+                                    .collect::<Vec<_>>()}
+                            </ul>
+                            <h2 class="m-2 text-lg font-bold">"Contributors"</h2>
+                            <ul class="m-2">
+                                {contributors
+                                    .into_iter()
+                                    .map(|c| {
+                                        view! {
+                                            <li>
+                                                {user_link(&c.person)} ": " {c.edits}
+                                                {if c.edits == 1 { " edit, " } else { " edits, " }}
+                                                {format!("{:+}", c.byte_delta)} " bytes"
+                                            </li>
+                                        }
+                                    })
+                                    .collect::<Vec<_>>()}
+                            </ul>
                             // TODO: move edits resource here? but leads to strange crash
-                            <EditList edits=edits for_article=true />
+                            <EditList edits=edits for_article=true revert_action tags tag_action />
                         }
                     })
             })}
@@ -1,41 +1,142 @@
-use crate::pages::article_resource;
+use crate::pages::{article_resource, disambiguation_resource};
+use ibis_database::common::article::ArticleDisambiguation;
 use ibis_frontend_components::{
     article_nav::{ActiveTab, ArticleNav},
     suspense_error::SuspenseError,
+    utils::{formatting::article_path, resources::config},
 };
-use ibis_markdown::render_article_markdown;
+use ibis_markdown::render_article_markdown_with_links;
 use leptos::{either::Either, prelude::*};
+use leptos_meta::Link;
 use leptos_router::hooks::use_query_map;
 
 #[component]
 pub fn ReadArticle() -> impl IntoView {
-    let article = article_resource();
+    let article = article_resource(true);
+    let disambiguation = disambiguation_resource(article);
     let query = use_query_map();
     let edit_successful = query.get_untracked().get("edit_successful").is_some();
 
+    let article_view = move || {
+        view! {
+            <ArticleNav article=article active_tab=ActiveTab::Read />
+            <SuspenseError result=article>
+                {move || Suspend::new(async move {
+                    let article = article.await;
+                    let markdown = article.map(|a| {
+                        let config = config();
+                        let markdown = render_article_markdown_with_links(
+                            &a.article.text,
+                            config.math_rendering,
+                            config.break_hint_length,
+                            true,
+                            &a.missing_links,
+                        );
+                        (markdown, a.redirected_from, a.categories, a.article.id)
+                    });
+                    if let Ok((markdown, redirected_from, categories, id)) = markdown {
+                        Either::Right(
+                            view! {
+                                <Link
+                                    rel="alternate"
+                                    type_="application/atom+xml"
+                                    title="Article history"
+                                    href=format!("/feeds/article/{}", id.0)
+                                />
+                                {redirected_from
+                                    .map(|from| {
+                                        // link to edit page, as reading the redirect would only redirect again
+                                        view! {
+                                            <div class="mb-2 text-sm italic">
+                                                "Redirected from "
+                                                <a
+                                                    class="link"
+                                                    href=format!("{}/edit", article_path(&from))
+                                                >
+                                                    {from.title()}
+                                                </a>
+                                            </div>
+                                        }
+                                    })}
+                                <div
+                                    class="max-w-full prose prose-slate text-ellipsis overflow-x-hidden"
+                                    inner_html=markdown
+                                ></div>
+                                {(!categories.is_empty())
+                                    .then(|| {
+                                        view! {
+                                            <div class="pt-2 mt-4 text-sm border-t border-slate-400">
+                                                "Categories: "
+                                                {categories
+                                                    .iter()
+                                                    .map(|c| {
+                                                        let path = format!(
+                                                            "/category/{}",
+                                                            c.name.replace(' ', "_"),
+                                                        );
+                                                        view! {
+                                                            <a class="mr-2 link" href=path>
+                                                                {c.name.clone()}
+                                                            </a>
+                                                        }
+                                                    })
+                                                    .collect::<Vec<_>>()}
+                                            </div>
+                                        }
+                                    })}
+                            },
+                        )
+                    } else {
+                        Either::Left(markdown.map(|_| ()))
+                    }
+                })} <Show when=move || edit_successful>
+                    <div class="toast toast-center">
+                        <div class="alert alert-success">Edit successful</div>
+                    </div>
+                </Show>
+            </SuspenseError>
+        }
+    };
+
     view! {
-        <ArticleNav article=article active_tab=ActiveTab::Read />
-        <SuspenseError result=article>
+        <Suspense fallback=|| {
+            view! { "Loading..." }
+        }>
             {move || Suspend::new(async move {
-                let article = article.await;
-                let markdown = article.map(|a| render_article_markdown(&a.article.text));
-                if let Ok(markdown) = markdown {
-                    Either::Right(
-                        view! {
-                            <div
-                                class="max-w-full prose prose-slate text-ellipsis overflow-x-hidden"
-                                inner_html=markdown
-                            ></div>
-                        },
-                    )
-                } else {
-                    Either::Left(markdown)
+                match disambiguation.await {
+                    Some(disambiguation) => {
+                        Either::Left(view! { <Disambiguation disambiguation /> })
+                    }
+                    None => Either::Right(article_view()),
                 }
-            })} <Show when=move || edit_successful>
-                <div class="toast toast-center">
-                    <div class="alert alert-success">Edit successful</div>
-                </div>
-            </Show>
-        </SuspenseError>
+            })}
+        </Suspense>
+    }
+}
+
+/// List of articles on different instances which have the requested title
+#[component]
+fn Disambiguation(disambiguation: ArticleDisambiguation) -> impl IntoView {
+    view! {
+        <h1 class="my-4 font-serif text-4xl font-bold">{disambiguation.title}</h1>
+        <div class="mb-2">"This title exists on multiple instances:"</div>
+        <ul class="list-disc list-inside">
+            {disambiguation
+                .candidates
+                .into_iter()
+                .map(|c| {
+                    view! {
+                        <li class="mb-2">
+                            <a class="link" href=article_path(&c.article)>
+                                {c.article.title()}
+                            </a>
+                            " on "
+                            {c.instance_domain}
+                            <div class="ml-5 text-sm">{c.excerpt}</div>
+                        </li>
+                    }
+                })
+                .collect::<Vec<_>>()}
+        </ul>
     }
 }
@@ -2,12 +2,20 @@ use ibis_api_client::{
     CLIENT,
     article::ListArticlesParams,
     errors::FrontendError,
-    instance::GetInstanceParams,
+    instance::{GetInstanceFollowers, GetInstanceParams},
 };
+use ibis_database::common::newtypes::InstanceId;
 use ibis_frontend_components::{
     instance_follow_button::InstanceFollowButton,
+    pager::{Pager, page_count},
     suspense_error::SuspenseError,
-    utils::formatting::{article_path, instance_title_with_domain, instance_updated},
+    utils::formatting::{
+        article_path,
+        instance_title_with_domain,
+        instance_updated,
+        time_ago,
+        user_link,
+    },
 };
 use leptos::prelude::*;
 use leptos_meta::Title;
@@ -33,18 +41,26 @@ pub fn InstanceDetails() -> impl IntoView {
                 instance
                     .await
                     .map(|instance_| {
+                        let sort = instance_.default_sort;
+                        let limit = i64::from(instance_.default_page_size);
+                        let page = RwSignal::new(1i64);
                         let articles = Resource::new(
-                            move || instance_.instance.id,
-                            |instance_id| async move {
+                            move || (instance_.instance.id, page.get()),
+                            move |(instance_id, page)| async move {
                                 CLIENT
                                     .list_articles(ListArticlesParams {
                                         only_local: None,
                                         instance_id: Some(instance_id),
                                         include_removed: None,
+                                        sort: Some(sort),
+                                        page: Some(page),
+                                        limit: Some(limit),
+                                        offset: None,
                                     })
                                     .await
                             },
                         );
+                        let instance_id = instance_.instance.id;
                         let title = instance_title_with_domain(&instance_.instance);
                         let local = !instance_.instance.local;
                         let ap_id = instance_.instance.ap_id.to_string();
@@ -72,28 +88,60 @@ pub fn InstanceDetails() -> impl IntoView {
 
                                 <div class="divider"></div>
                                 <div>{instance_.instance.topic}</div>
+                                <div class="text-sm opacity-70">
+                                    {format!(
+                                        "{} followers, {} following",
+                                        instance_.follower_count,
+                                        instance_.following_count,
+                                    )}
+                                </div>
                                 <h2 class="font-serif text-xl font-bold">Articles</h2>
                                 <ul class="list-none">
                                     <SuspenseError result=articles>
                                         {move || Suspend::new(async move {
                                             articles
                                                 .await
-                                                .map(|a| {
-                                                    a.into_iter()
+                                                .map(|list| {
+                                                    let pages = page_count(list.total, limit);
+                                                    let articles = list
+                                                        .articles
+                                                        .into_iter()
                                                         .map(|a| {
                                                             view! {
                                                                 <li>
-                                                                    <a class="text-lg link" href=article_path(&a)>
-                                                                        {a.title()}
+                                                                    <a
+                                                                        class="text-lg link"
+                                                                        href=article_path(&a.article)
+                                                                    >
+                                                                        {a.article.title()}
                                                                     </a>
+                                                                    {a
+                                                                        .latest_edit
+                                                                        .map(|e| {
+                                                                            view! {
+                                                                                <span class="ml-2 text-sm opacity-70">
+                                                                                    {format!(
+                                                                                        "{} by {}, {}",
+                                                                                        e.summary,
+                                                                                        e.creator.title(),
+                                                                                        time_ago(e.published),
+                                                                                    )}
+                                                                                </span>
+                                                                            }
+                                                                        })}
                                                                 </li>
                                                             }
                                                         })
-                                                        .collect::<Vec<_>>()
+                                                        .collect::<Vec<_>>();
+                                                    view! {
+                                                        {articles}
+                                                        <Pager page pages />
+                                                    }
                                                 })
                                         })}
                                     </SuspenseError>
                                 </ul>
+                                <InstanceFollowers instance_id />
                             </div>
                         }
                     })
@@ -102,3 +150,48 @@ pub fn InstanceDetails() -> impl IntoView {
         </SuspenseError>
     }
 }
+
+/// Users following the instance, loaded one page at a time
+#[component]
+fn InstanceFollowers(instance_id: InstanceId) -> impl IntoView {
+    const LIMIT: i64 = 20;
+    let page = RwSignal::new(1i64);
+    let followers = Resource::new(
+        move || page.get(),
+        move |page| async move {
+            CLIENT
+                .get_instance_followers(&GetInstanceFollowers {
+                    instance_id,
+                    limit: Some(LIMIT),
+                    offset: Some((page - 1) * LIMIT),
+                })
+                .await
+        },
+    );
+    view! {
+        <SuspenseError result=followers>
+            {move || Suspend::new(async move {
+                followers
+                    .await
+                    .map(|list| {
+                        let total = list.total;
+                        let pages = page_count(total, LIMIT);
+                        let followers = list
+                            .followers
+                            .iter()
+                            .map(|p| view! { <li>{user_link(p)}</li> })
+                            .collect::<Vec<_>>();
+                        view! {
+                            <Show when=move || { pages > 0 }>
+                                <h2 class="font-serif text-xl font-bold">
+                                    {format!("Followers ({total})")}
+                                </h2>
+                            </Show>
+                            <ul class="list-none">{followers}</ul>
+                            <Pager page pages />
+                        }
+                    })
+            })}
+        </SuspenseError>
+    }
+}
@@ -0,0 +1,59 @@
+use ibis_api_client::{CLIENT, article::GetRecentChanges};
+use ibis_frontend_components::{
+    edit_list::EditList,
+    suspense_error::SuspenseError,
+    utils::i18n::IbisTitle,
+};
+use leptos::prelude::*;
+use leptos_fluent::tr;
+use leptos_meta::Link;
+
+#[component]
+pub fn RecentChanges() -> impl IntoView {
+    let only_local = RwSignal::new(false);
+    let edits = Resource::new(
+        move || only_local.get(),
+        |only_local| async move {
+            let params = GetRecentChanges {
+                only_local: Some(only_local),
+                ..Default::default()
+            };
+            CLIENT.get_recent_changes(&params).await
+        },
+    );
+
+    view! {
+        <IbisTitle key="recent-changes" />
+        <Link
+            rel="alternate"
+            type_="application/atom+xml"
+            title="Recent changes"
+            href="/feeds/recent_changes"
+        />
+        <div class="flex items-center">
+            <h1 class="my-4 font-serif text-4xl font-bold grow">
+                {move || tr!("recent-changes")}
+            </h1>
+            <label class="label cursor-pointer">
+                <span class="mr-2">Only local articles</span>
+                <input
+                    type="checkbox"
+                    class="toggle"
+                    prop:checked=only_local
+                    on:change=move |ev| only_local.set(event_target_checked(&ev))
+                />
+            </label>
+        </div>
+        <SuspenseError result=edits>
+            {move || Suspend::new(async move {
+                edits
+                    .await
+                    .map(|edits| {
+                        // edit list shows the last item first
+                        let edits = edits.into_iter().rev().collect();
+                        view! { <EditList edits=edits for_article=false /> }
+                    })
+            })}
+        </SuspenseError>
+    }
+}
@@ -1,5 +1,8 @@
 use ibis_api_client::{CLIENT, instance::SearchArticleParams};
-use ibis_database::common::{article::Article, instance::Instance};
+use ibis_database::common::{
+    article::{SEARCH_HIGHLIGHT_END, SEARCH_HIGHLIGHT_START, SearchResult},
+    instance::Instance,
+};
 use ibis_frontend_components::utils::formatting::article_path;
 use leptos::prelude::*;
 use leptos_meta::Title;
@@ -9,7 +12,7 @@ use url::Url;
 
 #[derive(Default, Clone, Deserialize, Serialize, Debug)]
 struct SearchResults {
-    articles: Vec<Article>,
+    articles: Vec<SearchResult>,
     instance: Option<Instance>,
 }
 
@@ -29,7 +32,11 @@ pub fn Search() -> impl IntoView {
             set_error.set(None);
             let mut search_results = SearchResults::default();
             let url = Url::parse(&query);
-            let search_data = SearchArticleParams { query };
+            let search_data = SearchArticleParams {
+                query,
+                search_mode: None,
+                fuzzy: Some(true),
+            };
             let search = CLIENT.search(&search_data);
 
             match search.await {
@@ -40,7 +47,10 @@ pub fn Search() -> impl IntoView {
             // If its a valid url, also attempt to resolve as federation object
             if let Ok(url) = url {
                 match CLIENT.resolve_article(url.clone()).await {
-                    Ok(a) => search_results.articles.push(a.article),
+                    Ok(a) => search_results.articles.push(SearchResult {
+                        article: a.article,
+                        snippet: String::new(),
+                    }),
                     Err(e) => set_error.set(Some(e.to_string())),
                 }
                 match CLIENT.resolve_instance(url).await {
@@ -103,12 +113,13 @@ pub fn Search() -> impl IntoView {
                                     {search_results
                                         .articles
                                         .iter()
-                                        .map(|a| {
+                                        .map(|r| {
                                             view! {
-                                                <li>
-                                                    <a class="text-lg link" href=article_path(a)>
-                                                        {a.title()}
+                                                <li class="mb-2">
+                                                    <a class="text-lg link" href=article_path(&r.article)>
+                                                        {r.article.title()}
                                                     </a>
+                                                    <p class="text-sm">{highlight_snippet(&r.snippet)}</p>
                                                 </li>
                                             }
                                         })
@@ -123,3 +134,16 @@ pub fn Search() -> impl IntoView {
         </Suspense>
     }
 }
+
+/// Render the snippet as text, with matched terms in bold. Splitting on the markers instead of
+/// using inner html means that markup in the article text is not interpreted.
+fn highlight_snippet(snippet: &str) -> Vec<AnyView> {
+    let mut parts = snippet.split(SEARCH_HIGHLIGHT_START);
+    let mut views = vec![parts.next().unwrap_or_default().to_string().into_any()];
+    for part in parts {
+        let (matched, rest) = part.split_once(SEARCH_HIGHLIGHT_END).unwrap_or((part, ""));
+        views.push(view! { <b>{matched.to_string()}</b> }.into_any());
+        views.push(rest.to_string().into_any());
+    }
+    views
+}
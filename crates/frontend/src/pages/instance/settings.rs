@@ -10,18 +10,21 @@ pub fn AdminSettings() -> impl IntoView {
     let (saved, set_saved) = signal(false);
     let site = site();
 
-    let submit_action = Action::new(move |params: &UpdateInstanceParams| {
-        let params = params.clone();
-        async move {
-            CLIENT
-                .update_local_instance(&params)
-                .await
-                .error_popup(|_| {
+    let submit_action = Action::new(
+        move |(params, registration_open): &(UpdateInstanceParams, bool)| {
+            let (params, registration_open) = (params.clone(), *registration_open);
+            async move {
+                let res = match CLIENT.update_local_instance(&params).await {
+                    Ok(_) => CLIENT.set_registration_open(registration_open).await,
+                    Err(e) => Err(e),
+                };
+                res.error_popup(|_| {
                     site.refetch();
                     set_saved.set(true);
                 });
-        }
-    });
+            }
+        },
+    );
 
     // TODO: It would make sense to use a table for the labels and inputs, but for some reason
     //       that completely breaks reactivity.
@@ -33,6 +36,10 @@ pub fn AdminSettings() -> impl IntoView {
                     .map(|site| {
                         let (name, set_name) = signal(site.instance.name.unwrap_or_default());
                         let (topic, set_topic) = signal(site.instance.topic.unwrap_or_default());
+                        let (icon_url, set_icon_url) = signal(
+                            site.instance.icon_url.map(|u| u.to_string()).unwrap_or_default(),
+                        );
+                        let registration_open = signal(site.config.registration_open);
                         view! {
                             <h1 class="flex-auto my-6 font-serif text-4xl font-bold grow">
                                 "Admin Settings"
@@ -59,14 +66,38 @@ pub fn AdminSettings() -> impl IntoView {
                                     bind:value=(topic, set_topic)
                                 />
                             </div>
+                            <div class="flex flex-row mb-2">
+                                <label class="block w-20" for="icon_url">
+                                    "Icon"
+                                </label>
+                                <input
+                                    type="url"
+                                    id="icon_url"
+                                    class="w-80 input input-secondary input-bordered"
+                                    placeholder="https://example.com/icon.png"
+                                    bind:value=(icon_url, set_icon_url)
+                                />
+                            </div>
+                            <div class="flex flex-row mb-2">
+                                <label class="block w-40" for="registration_open">
+                                    "Registration open"
+                                </label>
+                                <input
+                                    type="checkbox"
+                                    id="registration_open"
+                                    class="checkbox"
+                                    bind:checked=registration_open
+                                />
+                            </div>
                             <button
                                 class="btn btn-primary"
                                 on:click=move |_| {
                                     let form = UpdateInstanceParams {
                                         name: Some(name.get()),
                                         topic: Some(topic.get()),
+                                        icon_url: Some(icon_url.get()),
                                     };
-                                    submit_action.dispatch(form);
+                                    submit_action.dispatch((form, registration_open.0.get()));
                                 }
                             >
                                 Submit
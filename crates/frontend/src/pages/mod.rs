@@ -1,7 +1,8 @@
 use ibis_api_client::{CLIENT, article::GetArticleParams, errors::FrontendResult};
 use ibis_database::common::{
     MAIN_PAGE_NAME,
-    article::{ArticleView, EditView},
+    article::{ArticleDisambiguation, ArticleLookup, ArticleView, EditView},
+    utils::normalize_article_title,
 };
 use ibis_frontend_components::suspense_error::article_title_param;
 use leptos::prelude::*;
@@ -10,26 +11,51 @@ pub mod article;
 pub mod instance;
 pub mod user;
 
-fn article_resource() -> Resource<FrontendResult<ArticleView>> {
+/// Load the article from the title in the url. Only the read page follows redirects, so that
+/// other pages like edit can be used for the redirect article itself.
+fn article_resource(follow_redirect: bool) -> Resource<FrontendResult<ArticleView>> {
     Resource::new(article_title_param, move |title| async move {
-        let mut title = title
-            .unwrap_or(MAIN_PAGE_NAME.to_string())
-            .replace("_", " ");
-        let mut domain = None;
-        if let Some((title_, domain_)) = title.clone().split_once('@') {
-            title = title_.to_string();
-            domain = Some(domain_.to_string());
-        }
         CLIENT
-            .get_article(GetArticleParams {
-                title: Some(title),
-                domain,
-                id: None,
-            })
+            .get_article(article_params(title, follow_redirect))
             .await
     })
 }
 
+/// If loading the article failed because its title exists on multiple instances, load the
+/// candidates so that the user can choose one.
+fn disambiguation_resource(
+    article: Resource<FrontendResult<ArticleView>>,
+) -> Resource<Option<ArticleDisambiguation>> {
+    Resource::new(
+        move || (article_title_param(), article.get().map(|a| a.is_err())),
+        move |(title, failed)| async move {
+            let params = article_params(title, true);
+            if failed != Some(true) || params.domain.is_some() {
+                return None;
+            }
+            match CLIENT.lookup_article(params).await {
+                Ok(ArticleLookup::Disambiguation(d)) => Some(d),
+                _ => None,
+            }
+        },
+    )
+}
+
+fn article_params(title: Option<String>, follow_redirect: bool) -> GetArticleParams {
+    let mut title = normalize_article_title(&title.unwrap_or(MAIN_PAGE_NAME.to_string()));
+    let mut domain = None;
+    if let Some((title_, domain_)) = title.clone().split_once('@') {
+        title = title_.to_string();
+        domain = Some(domain_.to_string());
+    }
+    GetArticleParams {
+        title: Some(title),
+        domain,
+        id: None,
+        follow_redirect: Some(follow_redirect),
+    }
+}
+
 async fn article_edits_resource(
     article: Resource<FrontendResult<ArticleView>>,
 ) -> Resource<FrontendResult<Vec<EditView>>> {
@@ -0,0 +1,75 @@
+use ibis_api_client::{CLIENT, user::GetUserContributions};
+use ibis_frontend_components::{edit_list::EditList, suspense_error::SuspenseError};
+use leptos::prelude::*;
+use leptos_meta::Title;
+use leptos_router::hooks::use_params_map;
+
+/// Number of edits shown on each page
+const PAGE_SIZE: i64 = 20;
+
+#[component]
+pub fn UserContributions() -> impl IntoView {
+    let params = use_params_map();
+    let name = move || params.get().get("name").clone().unwrap_or_default();
+    let page = RwSignal::new(1i64);
+    let edits = Resource::new(
+        move || (name(), page.get()),
+        move |(mut name, page)| async move {
+            let mut domain = None;
+            if let Some((name_, domain_)) = name.clone().split_once('@') {
+                name = name_.to_string();
+                domain = Some(domain_.to_string());
+            }
+            let params = GetUserContributions {
+                name: Some(name),
+                domain,
+                page: Some(page),
+                limit: Some(PAGE_SIZE),
+                ..Default::default()
+            };
+            CLIENT.get_user_contributions(&params).await
+        },
+    );
+
+    view! {
+        <Title text=move || format!("Contributions of {}", name()) />
+        <h1 class="my-6 font-serif text-4xl font-bold">
+            "Contributions of " <a class="link" href=move || format!("/user/{}", name())>
+                {name}
+            </a>
+        </h1>
+        <SuspenseError result=edits>
+            {move || Suspend::new(async move {
+                edits
+                    .await
+                    .map(|edits| {
+                        let is_last_page = (edits.len() as i64) < PAGE_SIZE;
+                        // edit list shows the last item first
+                        let edits = edits.into_iter().rev().collect();
+                        view! {
+                            <EditList edits=edits for_article=false />
+                            <div class="join mt-2">
+                                <button
+                                    class="join-item btn btn-sm"
+                                    disabled=move || page.get() <= 1
+                                    on:click=move |_| page.update(|p| *p -= 1)
+                                >
+                                    "«"
+                                </button>
+                                <span class="join-item btn btn-sm btn-disabled">
+                                    {move || format!("Page {}", page.get())}
+                                </span>
+                                <button
+                                    class="join-item btn btn-sm"
+                                    disabled=is_last_page
+                                    on:click=move |_| page.update(|p| *p += 1)
+                                >
+                                    "»"
+                                </button>
+                            </div>
+                        }
+                    })
+            })}
+        </SuspenseError>
+    }
+}
@@ -29,6 +29,9 @@ pub fn UserEditProfile() -> impl IntoView {
             });
         }
     });
+    let logout_all_action = Action::new(move |_| async move {
+        CLIENT.logout_all().await.error_popup(|_| site().refetch());
+    });
     let site = site();
 
     // TODO: It would make sense to use a table for the labels and inputs, but for some reason
@@ -166,6 +169,18 @@ pub fn UserEditProfile() -> impl IntoView {
                                 Save
                             </button>
 
+                            <h2 class="flex-auto my-6 font-serif text-2xl font-bold grow">
+                                Sessions
+                            </h2>
+                            <button
+                                class="btn btn-secondary"
+                                on:click=move |_| {
+                                    logout_all_action.dispatch(());
+                                }
+                            >
+                                Logout on all devices
+                            </button>
+
                             <Show when=move || saved.get()>
                                 <div class="toast">
                                     <div class="alert alert-info">
@@ -1,5 +1,9 @@
 use ibis_api_client::{CLIENT, user::GetUserParams};
-use ibis_frontend_components::{edit_list::EditList, suspense_error::SuspenseError};
+use ibis_frontend_components::{
+    edit_list::EditList,
+    suspense_error::SuspenseError,
+    utils::resources::config,
+};
 use ibis_markdown::render_article_markdown;
 use leptos::prelude::*;
 use leptos_meta::Title;
@@ -37,7 +41,12 @@ pub fn UserProfile() -> impl IntoView {
 
                             <div
                                 class="mb-2 max-w-full prose prose-slate text-ellipsis overflow-x-hidden"
-                                inner_html=render_article_markdown(&person.bio.unwrap_or_default())
+                                inner_html=render_article_markdown(
+                                    &person.bio.unwrap_or_default(),
+                                    config().math_rendering,
+                                    config().break_hint_length,
+                                    true,
+                                )
                             ></div>
 
                             <SuspenseError result=user_profile>
@@ -46,7 +55,17 @@ pub fn UserProfile() -> impl IntoView {
                                         .await
                                         .map(|edits| {
                                             view! {
-                                                <h2 class="font-serif text-xl font-bold">Edits</h2>
+                                                <div class="flex items-center">
+                                                    <h2 class="font-serif text-xl font-bold grow">
+                                                        Edits
+                                                    </h2>
+                                                    <a
+                                                        class="link"
+                                                        href=format!("/user/{}/contributions", name())
+                                                    >
+                                                        All contributions
+                                                    </a>
+                                                </div>
                                                 <EditList edits=edits for_article=false />
                                             }
                                         })
@@ -1,4 +1,8 @@
-use crate::{prevent_navigation, utils::use_cookie};
+use crate::{
+    prevent_navigation,
+    utils::{resources::config, use_cookie},
+};
+use ibis_api_client::{CLIENT, errors::FrontendResultExt};
 use ibis_markdown::render_article_markdown;
 use leptos::{html::Textarea, prelude::*};
 
@@ -8,9 +12,22 @@ pub fn EditorView(
     content: Signal<String>,
     set_content: WriteSignal<String>,
 ) -> impl IntoView {
-    let (preview, set_preview) = signal(render_article_markdown(&content.get_untracked()));
+    let (math, break_hint_length) = (config().math_rendering, config().break_hint_length);
+    let (preview, set_preview) = signal(render_article_markdown(
+        &content.get_untracked(),
+        math,
+        break_hint_length,
+        true,
+    ));
     let cookie = use_cookie("editor_preview");
     let show_preview = Signal::derive(move || cookie.0.get().unwrap_or(true));
+    // Render on the server, which also marks links to missing articles
+    let preview_action = Action::new(move |_: &()| async move {
+        CLIENT
+            .preview_article(content.get_untracked())
+            .await
+            .error_popup(|p| set_preview.set(p.html));
+    });
 
     prevent_navigation(content);
 
@@ -23,7 +40,7 @@ pub fn EditorView(
                     class="text-base resize-none grow textarea textarea-primary min-h-80"
                     on:input=move |evt| {
                         let val = event_target_value(&evt);
-                        set_preview.set(render_article_markdown(&val));
+                        set_preview.set(render_article_markdown(&val, math, break_hint_length, true));
                         set_content.set(val);
                     }
                     node_ref=textarea_ref
@@ -40,7 +57,11 @@ pub fn EditorView(
                 <button
                     class="btn btn-secondary btn-sm"
                     on:click=move |_| {
-                        cookie.1.set(Some(!show_preview.get_untracked()));
+                        let show = !show_preview.get_untracked();
+                        cookie.1.set(Some(show));
+                        if show {
+                            preview_action.dispatch(());
+                        }
                     }
                 >
                     Preview
@@ -2,14 +2,14 @@ use crate::{
     Pending,
     utils::{
         formatting::article_path,
-        resources::{is_admin, is_logged_in},
+        resources::{config, is_moderator, my_profile},
     },
 };
 use ibis_api_client::{
     CLIENT,
     errors::{FrontendResult, FrontendResultExt},
 };
-use ibis_database::common::article::{ArticleView, can_edit_article};
+use ibis_database::common::article::{ArticleView, ProtectionLevel, can_edit_article};
 use leptos::prelude::*;
 use leptos_meta::Title;
 use leptos_router::components::A;
@@ -54,7 +54,7 @@ pub fn ArticleNav(
                         let article_link_ = article_link.clone();
                         let ap_id = article_.article.ap_id.to_string();
                         let removed = article_.article.removed;
-                        let protected = article_.article.protected;
+                        let protection = protection_label(article_.article.protection);
                         let pending = article_.article.pending;
                         let follow_article_action = Action::new(move |_: &()| async move {
                             CLIENT
@@ -91,8 +91,18 @@ pub fn ArticleNav(
                                     "History"
                                 </A>
                                 <Show when=move || {
-                                    is_logged_in()
-                                        && can_edit_article(&article_.article, is_admin()).is_ok()
+                                    my_profile()
+                                        .is_some_and(|p| {
+                                            can_edit_article(
+                                                    &article_.article,
+                                                    p.local_user.role,
+                                                    p.person.is_banned(),
+                                                    p.local_user.published,
+                                                    p.local_user.email_verified,
+                                                    &config(),
+                                                )
+                                                .is_ok()
+                                        })
                                 }>
                                     <A
                                         href=format!("{article_link}/edit")
@@ -104,7 +114,7 @@ pub fn ArticleNav(
                                     </A>
                                 </Show>
                                 <Suspense>
-                                    <Show when=is_admin>
+                                    <Show when=is_moderator>
                                         <A
                                             href=format!("{article_link_}/actions")
                                             {..}
@@ -129,11 +139,15 @@ pub fn ArticleNav(
                                         <Icon icon=TRASH size="24px" />
                                     </span>
                                 </Show>
-                                <Show when=move || protected>
-                                    <span title="Article can only be edited by local admins">
-                                        <Icon icon=LOCK_SIMPLE size="24px" />
-                                    </span>
-                                </Show>
+                                {protection
+                                    .map(|(label, title)| {
+                                        view! {
+                                            <span class="gap-1 badge badge-outline" title=title>
+                                                <Icon icon=LOCK_SIMPLE size="16px" />
+                                                {label}
+                                            </span>
+                                        }
+                                    })}
                                 <button
                                     class="btn btn-sm btn-outline"
                                     on:click=move |_| {
@@ -197,3 +211,18 @@ fn page_title(active_tab: ActiveTab, article_title: &str) -> String {
     };
     format!("{active} — {article_title}")
 }
+
+/// Short label and explanation for the protection badge, or none if the article is unprotected
+fn protection_label(level: ProtectionLevel) -> Option<(&'static str, &'static str)> {
+    match level {
+        ProtectionLevel::Unprotected => None,
+        ProtectionLevel::AutoConfirmed => Some((
+            "Semi-protected",
+            "Article can only be edited by established accounts",
+        )),
+        ProtectionLevel::AdminOnly => Some((
+            "Protected",
+            "Article can only be edited by moderators of its instance",
+        )),
+    }
+}
@@ -0,0 +1,235 @@
+use leptos::{either::Either, prelude::*};
+
+/// Lines with more words than this are highlighted as a whole, to avoid slow comparison.
+const MAX_WORD_DIFF_TOKENS: usize = 500;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffLineKind {
+    /// Hunk header like `@@ -1,3 +1,3 @@`, file header or `\ No newline at end of file`
+    Header,
+    Context,
+    Added,
+    Removed,
+}
+
+/// Part of a diff line. Changed words within an otherwise modified line are highlighted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiffSpan {
+    pub text: String,
+    pub highlight: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    pub spans: Vec<DiffSpan>,
+}
+
+impl DiffLine {
+    fn new(kind: DiffLineKind, text: &str) -> Self {
+        DiffLine {
+            kind,
+            spans: vec![DiffSpan {
+                text: text.to_string(),
+                highlight: false,
+            }],
+        }
+    }
+}
+
+/// Parse a unified diff into lines for rendering. Removed lines which are directly followed by
+/// added lines are compared word by word, so that the changed words can be highlighted.
+pub fn parse_unified_diff(diff: &str) -> Vec<DiffLine> {
+    let mut res = vec![];
+    let mut removed: Vec<&str> = vec![];
+    let mut added: Vec<&str> = vec![];
+    let mut in_hunk = false;
+    for line in diff.lines() {
+        // file headers only appear before the first hunk, afterwards `---` is a removed line
+        let file_header = !in_hunk && (line.starts_with("---") || line.starts_with("+++"));
+        if file_header || line.starts_with("@@") || line.starts_with('\\') {
+            in_hunk |= line.starts_with("@@");
+            flush_changes(&mut removed, &mut added, &mut res);
+            res.push(DiffLine::new(DiffLineKind::Header, line));
+        } else if let Some(line) = line.strip_prefix('-') {
+            if !added.is_empty() {
+                flush_changes(&mut removed, &mut added, &mut res);
+            }
+            removed.push(line);
+        } else if let Some(line) = line.strip_prefix('+') {
+            added.push(line);
+        } else {
+            flush_changes(&mut removed, &mut added, &mut res);
+            let line = line.strip_prefix(' ').unwrap_or(line);
+            res.push(DiffLine::new(DiffLineKind::Context, line));
+        }
+    }
+    flush_changes(&mut removed, &mut added, &mut res);
+    res
+}
+
+/// Pair up removed and added lines in order, and add them to the result with word highlights.
+fn flush_changes(removed: &mut Vec<&str>, added: &mut Vec<&str>, res: &mut Vec<DiffLine>) {
+    let mut removed_lines = vec![];
+    let mut added_lines = vec![];
+    for i in 0..removed.len().max(added.len()) {
+        match (removed.get(i), added.get(i)) {
+            (Some(old), Some(new)) => {
+                let (old_spans, new_spans) = word_diff(old, new);
+                removed_lines.push(DiffLine {
+                    kind: DiffLineKind::Removed,
+                    spans: old_spans,
+                });
+                added_lines.push(DiffLine {
+                    kind: DiffLineKind::Added,
+                    spans: new_spans,
+                });
+            }
+            (Some(old), None) => removed_lines.push(DiffLine::new(DiffLineKind::Removed, old)),
+            (None, Some(new)) => added_lines.push(DiffLine::new(DiffLineKind::Added, new)),
+            (None, None) => {}
+        }
+    }
+    res.append(&mut removed_lines);
+    res.append(&mut added_lines);
+    removed.clear();
+    added.clear();
+}
+
+/// Split text into words and whitespace, so that joining the tokens gives the original text.
+fn tokenize(text: &str) -> Vec<&str> {
+    let mut tokens = vec![];
+    let mut start = 0;
+    let mut prev_whitespace = None;
+    for (i, c) in text.char_indices() {
+        let whitespace = c.is_whitespace();
+        if prev_whitespace.is_some_and(|p| p != whitespace) {
+            tokens.push(&text[start..i]);
+            start = i;
+        }
+        prev_whitespace = Some(whitespace);
+    }
+    if start < text.len() {
+        tokens.push(&text[start..]);
+    }
+    tokens
+}
+
+/// Compare two lines word by word using longest common subsequence, and highlight the words
+/// which are only present on one side.
+fn word_diff(old: &str, new: &str) -> (Vec<DiffSpan>, Vec<DiffSpan>) {
+    let old_tokens = tokenize(old);
+    let new_tokens = tokenize(new);
+    let (n, m) = (old_tokens.len(), new_tokens.len());
+    if n > MAX_WORD_DIFF_TOKENS || m > MAX_WORD_DIFF_TOKENS {
+        let highlight = |text: &str| {
+            vec![DiffSpan {
+                text: text.to_string(),
+                highlight: true,
+            }]
+        };
+        return (highlight(old), highlight(new));
+    }
+
+    // lcs[i][j] is the length of the longest common subsequence of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old_tokens[i] == new_tokens[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut old_spans = vec![];
+    let mut new_spans = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old_tokens[i] == new_tokens[j] {
+            push_token(&mut old_spans, old_tokens[i], false);
+            push_token(&mut new_spans, new_tokens[j], false);
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            push_token(&mut new_spans, new_tokens[j], true);
+            j += 1;
+        } else {
+            push_token(&mut old_spans, old_tokens[i], true);
+            i += 1;
+        }
+    }
+    (old_spans, new_spans)
+}
+
+/// Append token to the last span if it has the same highlight, otherwise start a new span.
+fn push_token(spans: &mut Vec<DiffSpan>, token: &str, highlight: bool) {
+    match spans.last_mut() {
+        Some(last) if last.highlight == highlight => last.text.push_str(token),
+        _ => spans.push(DiffSpan {
+            text: token.to_string(),
+            highlight,
+        }),
+    }
+}
+
+/// Render a unified diff with colored lines and highlighted words. The raw patch can be shown
+/// instead using a toggle.
+#[component]
+pub fn DiffView(diff: String) -> impl IntoView {
+    let lines = parse_unified_diff(&diff);
+    let show_raw = RwSignal::new(false);
+    view! {
+        <label class="flex gap-2 my-2 cursor-pointer w-fit">
+            <span class="label-text">Raw patch</span>
+            <input type="checkbox" class="toggle toggle-sm" bind:checked=show_raw />
+        </label>
+        <div class="max-w-full prose prose-slate">
+            {move || {
+                if show_raw.get() {
+                    Either::Left(
+                        view! {
+                            <pre class="text-wrap">
+                                <code>{diff.clone()}</code>
+                            </pre>
+                        },
+                    )
+                } else {
+                    Either::Right(
+                        view! {
+                            <pre class="text-wrap">
+                                <code>
+                                    {lines.iter().map(render_line).collect::<Vec<_>>()}
+                                </code>
+                            </pre>
+                        },
+                    )
+                }
+            }}
+        </div>
+    }
+}
+
+fn render_line(line: &DiffLine) -> impl IntoView {
+    let (prefix, line_class, highlight_class) = match line.kind {
+        DiffLineKind::Header => ("", "block text-info", ""),
+        DiffLineKind::Context => (" ", "block", ""),
+        DiffLineKind::Added => ("+", "block bg-success/20", "bg-success/50"),
+        DiffLineKind::Removed => ("-", "block bg-error/20", "bg-error/50"),
+    };
+    let spans = line
+        .spans
+        .iter()
+        .map(|span| {
+            let class = if span.highlight { highlight_class } else { "" };
+            view! { <span class=class>{span.text.clone()}</span> }
+        })
+        .collect::<Vec<_>>();
+    view! {
+        <span class=line_class>
+            {prefix}
+            {spans}
+        </span>
+    }
+}
@@ -1,22 +1,64 @@
 use crate::{
     Pending,
-    utils::formatting::{article_link, edit_path, edit_time, user_link},
+    utils::formatting::{article_link, compare_path, edit_path, edit_time, user_link},
 };
-use ibis_database::common::article::EditView;
+use ibis_database::common::article::{EditVersion, EditView, RevisionTag};
 use leptos::{either::Either, prelude::*};
 
 // If `for_article` is true, edit entries link to the respective user account. Otherwise
 // if edits for a user is rendered, entries link to the respective article.
 #[component]
-pub fn EditList(edits: Vec<EditView>, for_article: bool) -> impl IntoView {
+pub fn EditList(
+    edits: Vec<EditView>,
+    for_article: bool,
+    /// If this is present, a button to revert to each previous version is shown
+    #[prop(optional_no_strip)]
+    revert_action: Option<Action<EditVersion, ()>>,
+    /// Tagged versions are highlighted and the tag links directly to the version
+    #[prop(optional)]
+    tags: Vec<RevisionTag>,
+    /// If this is present, a button to tag each version is shown
+    #[prop(optional_no_strip)]
+    tag_action: Option<Action<EditVersion, ()>>,
+) -> impl IntoView {
+    let latest = edits.last().map(|e| e.edit.hash.clone());
     view! {
         <div>
             <ul class="list-disc">
                 {edits
                     .into_iter()
                     .rev()
-                    .map(|edit: EditView| {
+                    .enumerate()
+                    .map(|(i, edit): (usize, EditView)| {
+                        // the latest version can't be reverted to
+                        let revert_action = revert_action.filter(|_| i > 0 && !edit.edit.pending);
+                        let hash = edit.edit.hash.clone();
+                        let tag_hash = edit.edit.hash.clone();
+                        let edit_tags: Vec<_> = tags
+                            .iter()
+                            .filter(|t| t.version == edit.edit.hash)
+                            .map(|t| t.tag.clone())
+                            .collect();
+                        let tagged = !edit_tags.is_empty();
+                        let minor = edit.edit.minor;
+                        let stats = edit.edit.diff_stats();
+                        // compare with the previous version, and older versions with the latest one
+                        let prev_path = (for_article
+                            && edit.edit.previous_version_id != EditVersion::default())
+                            .then(|| {
+                                compare_path(
+                                    &edit.article,
+                                    &edit.edit.previous_version_id,
+                                    &edit.edit.hash,
+                                )
+                            });
+                        let cur_path = latest
+                            .clone()
+                            .filter(|_| for_article && i > 0)
+                            .map(|latest| compare_path(&edit.article, &edit.edit.hash, &latest));
                         let path = edit_path(&edit.edit, &edit.article);
+                        let tag_path = path.clone();
+                        let tag_action = tag_action.filter(|_| !edit.edit.pending);
                         let edit_time = edit_time(edit.edit.published);
                         let second_line = if for_article {
                             Either::Left(
@@ -36,13 +78,103 @@ pub fn EditList(edits: Vec<EditView>, for_article: bool) -> impl IntoView {
                             )
                         };
                         view! {
-                            <li class="m-2 card card-compact bg-base-100 card-bordered rounded-s">
+                            <li
+                                class="m-2 card card-compact bg-base-100 card-bordered rounded-s"
+                                class=("border-primary", tagged)
+                            >
                                 <div class="card-body">
                                     <div class="flex w-full">
                                         <a class="text-lg grow link link-primary" href=path>
                                             {edit.edit.summary}
                                         </a>
+                                        <span
+                                            class="mx-2 font-mono text-sm whitespace-nowrap"
+                                            title="Lines added and removed"
+                                        >
+                                            <span class="text-success">
+                                                {format!("+{}", stats.added)}
+                                            </span>
+                                            " "
+                                            <span class="text-error">
+                                                {format!("−{}", stats.removed)}
+                                            </span>
+                                        </span>
+                                        <Show when=move || minor>
+                                            <span
+                                                class="mx-2 badge badge-ghost badge-sm"
+                                                title="Minor edit"
+                                            >
+                                                m
+                                            </span>
+                                        </Show>
+                                        {edit_tags
+                                            .into_iter()
+                                            .map(|tag| {
+                                                view! {
+                                                    <a
+                                                        class="mx-1 badge badge-primary"
+                                                        id=format!("tag-{tag}")
+                                                        title="Tagged version"
+                                                        href=tag_path.clone()
+                                                    >
+                                                        {tag}
+                                                    </a>
+                                                }
+                                            })
+                                            .collect::<Vec<_>>()}
                                         <Pending pending=edit.edit.pending />
+                                        {prev_path
+                                            .map(|path| {
+                                                view! {
+                                                    <a
+                                                        class="mx-1 btn btn-xs btn-outline"
+                                                        title="Compare with the previous version"
+                                                        href=path
+                                                    >
+                                                        prev
+                                                    </a>
+                                                }
+                                            })}
+                                        {cur_path
+                                            .map(|path| {
+                                                view! {
+                                                    <a
+                                                        class="mx-1 btn btn-xs btn-outline"
+                                                        title="Compare this version with the latest one"
+                                                        href=path
+                                                    >
+                                                        cur
+                                                    </a>
+                                                }
+                                            })}
+                                        {revert_action
+                                            .map(|action| {
+                                                view! {
+                                                    <button
+                                                        class="btn btn-xs btn-outline"
+                                                        title="Restore the article text from this version"
+                                                        on:click=move |_| {
+                                                            action.dispatch(hash.clone());
+                                                        }
+                                                    >
+                                                        Revert
+                                                    </button>
+                                                }
+                                            })}
+                                        {tag_action
+                                            .map(|action| {
+                                                view! {
+                                                    <button
+                                                        class="mx-2 btn btn-xs btn-outline"
+                                                        title="Mark this version with a named tag"
+                                                        on:click=move |_| {
+                                                            action.dispatch(tag_hash.clone());
+                                                        }
+                                                    >
+                                                        Tag
+                                                    </button>
+                                                }
+                                            })}
                                     </div>
                                     <p>{second_line}</p>
                                 </div>
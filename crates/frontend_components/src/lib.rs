@@ -5,10 +5,12 @@ pub mod article_editor;
 pub mod article_nav;
 pub mod comment;
 pub mod comment_editor;
+pub mod diff_view;
 pub mod edit_list;
 pub mod instance_follow_button;
 pub mod nav;
 pub mod oauth_login_button;
+pub mod pager;
 pub mod protected_route;
 pub mod suspense_error;
 pub mod utils;
@@ -1,6 +1,6 @@
 use crate::utils::{
     dark_mode::DarkMode,
-    formatting::instance_title,
+    formatting::{instance_icon, instance_title},
     i18n::LanguageSelector,
     resources::{config, is_admin, is_logged_in, my_profile, site},
 };
@@ -11,6 +11,7 @@ use leptos_router::hooks::use_navigate;
 use phosphor_leptos::{
     BELL_RINGING,
     CARDS,
+    CLOCK_COUNTER_CLOCKWISE,
     EXCLAMATION_MARK,
     GEAR,
     HOUSE,
@@ -38,7 +39,13 @@ pub fn Nav() -> impl IntoView {
                 class="md:h-full max-sm:navbar-start max-sm:flex max-sm:dropdown max-sm:dropdown-bottom max-sm:dropdown-end max-sm:w-full"
             >
                 <a href="/" class="flex flex-row md:hidden">
-                    <img src="/logo.png" class="m-auto size-8" />
+                    {move || Suspend::new(async move {
+                        site()
+                            .await
+                            .map(|s| {
+                                view! { <img src=instance_icon(&s.instance) class="m-auto size-8" /> }
+                            })
+                    })}
                     <h2 class="m-2 font-serif text-xl font-bold">
                         {move || Suspend::new(async move {
                             site().await.map(|s| instance_title(&s.instance))
@@ -50,7 +57,11 @@ pub fn Nav() -> impl IntoView {
                 <div class="md:h-full menu dropdown-content max-sm:rounded-box max-sm:z-[1] max-sm:shadow">
                     <Transition>
                         <a href="/" class="max-sm:hidden">
-                            <img src="/logo.png" class="m-auto" />
+                            {move || Suspend::new(async move {
+                                site()
+                                    .await
+                                    .map(|s| view! { <img src=instance_icon(&s.instance) class="m-auto" /> })
+                            })}
                             <h2 class="m-4 font-serif text-xl font-bold">
                                 {move || Suspend::new(async move {
                                     site().await.map(|s| instance_title(&s.instance))
@@ -70,6 +81,12 @@ pub fn Nav() -> impl IntoView {
                                     {move || tr!("explore")}
                                 </a>
                             </li>
+                            <li>
+                                <a href="/recent_changes">
+                                    <Icon icon=CLOCK_COUNTER_CLOCKWISE />
+                                    {move || tr!("recent-changes")}
+                                </a>
+                            </li>
                             <li>
                                 <a href="/about">
                                     <Icon icon=EXCLAMATION_MARK />
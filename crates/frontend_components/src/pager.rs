@@ -0,0 +1,34 @@
+use leptos::prelude::*;
+
+/// Number of pages needed to show `total` items with `limit` items per page
+pub fn page_count(total: i64, limit: i64) -> i64 {
+    (total + limit - 1) / limit
+}
+
+/// Buttons to go to the previous or next page of a list, hidden if there is only one page
+#[component]
+pub fn Pager(page: RwSignal<i64>, pages: i64) -> impl IntoView {
+    view! {
+        <Show when=move || { pages > 1 }>
+            <div class="join mt-2">
+                <button
+                    class="join-item btn btn-sm"
+                    disabled=move || page.get() <= 1
+                    on:click=move |_| page.update(|p| *p -= 1)
+                >
+                    "«"
+                </button>
+                <span class="join-item btn btn-sm btn-disabled">
+                    {move || format!("Page {} of {pages}", page.get())}
+                </span>
+                <button
+                    class="join-item btn btn-sm"
+                    disabled=move || page.get() >= pages
+                    on:click=move |_| page.update(|p| *p += 1)
+                >
+                    "»"
+                </button>
+            </div>
+        </Show>
+    }
+}
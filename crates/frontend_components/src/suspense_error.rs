@@ -19,6 +19,8 @@ where
         }>
             {move || {
                 if let Some(Err(e)) = result.get() {
+                    // only offer to create the article if it doesn't exist, not for other errors
+                    let not_found = e.is_not_found();
                     let article_title = article_title_param();
                     let href = format!(
                         "/create-article?title={}",
@@ -29,7 +31,9 @@ where
                             <div class="grid place-items-center h-screen">
                                 <div>
                                     <div class="alert alert-error w-fit">{e.message()}</div>
-                                    <Show when=move || article_title.is_some() && is_logged_in()>
+                                    <Show when=move || {
+                                        not_found && article_title.is_some() && is_logged_in()
+                                    }>
                                         <a class="mt-4 btn" href=href.clone()>
                                             Create Article
                                         </a>
@@ -1,6 +1,6 @@
 use chrono::{DateTime, Local, Utc};
 use ibis_database::common::{
-    article::{Article, Edit},
+    article::{Article, Edit, EditVersion},
     comment::Comment,
     instance::{Instance, InstanceView},
     user::Person,
@@ -79,6 +79,15 @@ pub fn instance_title(instance: &Instance) -> String {
     instance.name.clone().unwrap_or(instance.domain.clone())
 }
 
+/// Icon configured by the admin, or the default Ibis logo
+pub fn instance_icon(instance: &Instance) -> String {
+    instance
+        .icon_url
+        .as_ref()
+        .map(|u| u.to_string())
+        .unwrap_or("/logo.png".to_string())
+}
+
 pub fn instance_updated(instance: &InstanceView) -> String {
     if instance.instance.local {
         "Local".to_string()
@@ -99,6 +108,11 @@ pub fn comment_path(comment: &Comment, article: &Article) -> String {
     format!("{}/discussion#comment-{}", article_path, comment.id.0)
 }
 
+/// Page showing the changes between two versions of the article
+pub fn compare_path(article: &Article, from: &EditVersion, to: &EditVersion) -> String {
+    format!("{}/compare/{}/{}", article_path(article), from.0, to.0)
+}
+
 pub fn edit_path(edit: &Edit, article: &Article) -> String {
     format!(
         "/article/{}@{}/diff/{}",
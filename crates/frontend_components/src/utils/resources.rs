@@ -1,7 +1,7 @@
 use ibis_api_client::{CLIENT, errors::FrontendResult};
 use ibis_database::common::{
     instance::{Options, SiteView},
-    user::LocalUserView,
+    user::{LocalUserView, UserRole},
 };
 use leptos::prelude::*;
 
@@ -35,5 +35,17 @@ pub fn is_logged_in() -> bool {
 }
 
 pub fn is_admin() -> bool {
-    my_profile().map(|p| p.local_user.admin).unwrap_or(false)
+    my_profile()
+        .map(|p| p.local_user.is_admin())
+        .unwrap_or(false)
+}
+
+pub fn user_role() -> UserRole {
+    my_profile()
+        .map(|p| p.local_user.role)
+        .unwrap_or(UserRole::Reader)
+}
+
+pub fn is_moderator() -> bool {
+    user_role().can_moderate()
 }
@@ -4,12 +4,24 @@ use markdown_it::{
     Renderer,
     parser::inline::{InlineRule, InlineState},
 };
+use url::form_urlencoded::byte_serialize;
 
 #[derive(Debug)]
-struct ArticleLink {
+pub(crate) struct ArticleLink {
     label: String,
-    title: String,
-    domain: String,
+    pub(crate) title: String,
+    /// Links without domain point to an article on the current instance
+    pub(crate) domain: Option<String>,
+    /// Set for local links if the target article doesn't exist, rendered as red link
+    pub(crate) missing: bool,
+}
+
+/// Target of a `[[Title]]` or `[[Title@example.com]]` link in article text
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ArticleLinkTarget {
+    /// Title with underscores replaced by spaces, as stored in the database
+    pub title: String,
+    pub domain: Option<String>,
 }
 
 // This defines how your custom node should be rendered.
@@ -17,8 +29,21 @@ impl NodeValue for ArticleLink {
     fn render(&self, node: &Node, fmt: &mut dyn Renderer) {
         let mut attrs = node.attrs.clone();
 
-        let link = format!("/article/{}@{}", self.title, self.domain);
+        let title = self.title.replace(' ', "_");
+        let link = match &self.domain {
+            Some(domain) => format!("/article/{title}@{domain}"),
+            // red links go directly to the create form, with the title filled in
+            None if self.missing => {
+                let title: String = byte_serialize(title.as_bytes()).collect();
+                format!("/create-article?title={title}")
+            }
+            None => format!("/article/{title}"),
+        };
         attrs.push(("href", link));
+        if self.missing {
+            attrs.push(("class", "text-error".to_string()));
+            attrs.push(("title", "Article doesn't exist yet".to_string()));
+        }
 
         fmt.open("a", &attrs);
         fmt.text(&self.label);
@@ -31,7 +56,7 @@ pub struct ArticleLinkScanner;
 impl InlineRule for ArticleLinkScanner {
     const MARKER: char = '[';
 
-    /// Find `[[Title@example.com]], return the position and split title/domain.
+    /// Find `[[Title@example.com]]` or `[[Title]]`, return the position and split title/domain.
     fn run(state: &mut InlineState) -> Option<(Node, usize)> {
         let input = &state.src[state.pos..state.pos_max];
         if !input.starts_with("[[") {
@@ -43,16 +68,26 @@ impl InlineRule for ArticleLinkScanner {
             let start = state.pos + SEPARATOR_LENGTH;
             let i = start + length - SEPARATOR_LENGTH;
             let content = &state.src[start..i];
-            content.split_once('@').map(|(title, domain)| {
-                // Handle custom link label if provided, otherwise use title as label
-                let (domain, label) = domain.split_once('|').unwrap_or((domain, title));
-                let node = Node::new(ArticleLink {
-                    label: label.to_string(),
-                    title: title.to_string(),
-                    domain: domain.to_string(),
-                });
-                (node, length + SEPARATOR_LENGTH)
-            })
+            // Handle custom link label if provided, otherwise use title as label
+            let (target, label) = match content.split_once('|') {
+                Some((target, label)) => (target, Some(label)),
+                None => (content, None),
+            };
+            let (title, domain) = match target.split_once('@') {
+                Some((title, domain)) => (title, Some(domain.to_string())),
+                None => (target, None),
+            };
+            if title.trim().is_empty() {
+                return None;
+            }
+            let label = label.unwrap_or(title);
+            let node = Node::new(ArticleLink {
+                label: label.to_string(),
+                title: title.to_string(),
+                domain,
+                missing: false,
+            });
+            Some((node, length + SEPARATOR_LENGTH))
         })
     }
 }
@@ -63,16 +98,35 @@ mod test {
 
     #[test]
     fn test_markdown_article_link() {
-        let plain = render_article_markdown("[[Title@example.com]]");
+        let plain = render_article_markdown("[[Title@example.com]]", true, 0, false);
         assert_eq!(
             "<p><a href=\"/article/Title@example.com\">Title</a></p>\n",
             plain
         );
 
-        let with_label = render_article_markdown("[[Title@example.com|Example Article]]");
+        let with_label =
+            render_article_markdown("[[Title@example.com|Example Article]]", true, 0, false);
         assert_eq!(
             "<p><a href=\"/article/Title@example.com\">Example Article</a></p>\n",
             with_label
         );
     }
+
+    #[test]
+    fn test_markdown_local_article_link() {
+        let plain = render_article_markdown("[[Article_Title]]", true, 0, false);
+        assert_eq!(
+            "<p><a href=\"/article/Article_Title\">Article_Title</a></p>\n",
+            plain
+        );
+
+        let with_label = render_article_markdown("[[Main Page|home]]", true, 0, false);
+        assert_eq!(
+            "<p><a href=\"/article/Main_Page\">home</a></p>\n",
+            with_label
+        );
+
+        let empty = render_article_markdown("[[]]", true, 0, false);
+        assert_eq!("<p>[[]]</p>\n", empty);
+    }
 }
@@ -0,0 +1,93 @@
+use crate::external_link::ExternalLink;
+use markdown_it::{
+    Node,
+    NodeValue,
+    Renderer,
+    parser::inline::Text,
+    plugins::cmark::inline::{autolink::Autolink, backticks::CodeInline},
+};
+
+/// Number of characters between break hints within a long word
+const BREAK_INTERVAL: usize = 10;
+
+/// Text containing long words, which is rendered with `<wbr>` tags so that browsers can wrap
+/// it on narrow screens.
+#[derive(Debug)]
+struct BreakableText {
+    content: String,
+    threshold: usize,
+}
+
+impl NodeValue for BreakableText {
+    fn render(&self, _node: &Node, fmt: &mut dyn Renderer) {
+        for word in self.content.split_inclusive(char::is_whitespace) {
+            if word.trim_end().chars().count() <= self.threshold {
+                fmt.text(word);
+                continue;
+            }
+            let chars: Vec<_> = word.chars().collect();
+            for (i, chunk) in chars.chunks(BREAK_INTERVAL).enumerate() {
+                if i > 0 {
+                    fmt.text_raw("<wbr>");
+                }
+                fmt.text(&chunk.iter().collect::<String>());
+            }
+        }
+    }
+}
+
+/// Insert line break hints into words longer than `threshold` characters. Text inside code
+/// spans and links is left unchanged.
+pub(crate) fn add_break_hints(node: &mut Node, threshold: usize) {
+    if node.is::<CodeInline>() || node.is::<Autolink>() || node.is::<ExternalLink>() {
+        return;
+    }
+    if let Some(text) = node.cast::<Text>() {
+        if text
+            .content
+            .split_whitespace()
+            .any(|w| w.chars().count() > threshold)
+        {
+            let content = text.content.clone();
+            node.replace(BreakableText { content, threshold });
+        }
+        return;
+    }
+    for child in node.children.iter_mut() {
+        add_break_hints(child, threshold);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::render_article_markdown;
+
+    #[test]
+    fn test_break_hints() {
+        let rendered = render_article_markdown(
+            "a Pneumonoultramicroscopicsilicovolcanoconiosis b",
+            false,
+            20,
+            false,
+        );
+        assert_eq!(
+            "<p>a Pneumonoul<wbr>tramicrosc<wbr>opicsilico<wbr>volcanocon<wbr>iosis b</p>\n",
+            rendered
+        );
+
+        // code spans and links are unchanged
+        let text = "`Pneumonoultramicroscopicsilicovolcanoconiosis` \
+            <https://example.com/Pneumonoultramicroscopicsilicovolcanoconiosis>";
+        let rendered = render_article_markdown(text, false, 20, false);
+        assert!(!rendered.contains("<wbr>"));
+
+        // disabled
+        let rendered = render_article_markdown(
+            "Pneumonoultramicroscopicsilicovolcanoconiosis",
+            false,
+            0,
+            false,
+        );
+        assert!(!rendered.contains("<wbr>"));
+    }
+}
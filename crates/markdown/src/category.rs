@@ -0,0 +1,61 @@
+use markdown_it::{
+    Node,
+    NodeValue,
+    Renderer,
+    parser::inline::{InlineRule, InlineState},
+};
+
+const CATEGORY_PREFIX: &str = "[[Category:";
+
+/// A `[[Category:Name]]` marker which adds the article to a category. It is not rendered inline,
+/// category membership is shown separately.
+#[derive(Debug)]
+pub(crate) struct CategoryMarker {
+    pub(crate) name: String,
+}
+
+impl NodeValue for CategoryMarker {
+    fn render(&self, _node: &Node, _fmt: &mut dyn Renderer) {}
+}
+
+pub struct CategoryScanner;
+
+impl InlineRule for CategoryScanner {
+    const MARKER: char = '[';
+
+    fn run(state: &mut InlineState) -> Option<(Node, usize)> {
+        let input = &state.src[state.pos..state.pos_max];
+        if !input.starts_with(CATEGORY_PREFIX) {
+            return None;
+        }
+        let end = input.find("]]")?;
+        let name = input[CATEGORY_PREFIX.len()..end].trim().replace('_', " ");
+        if name.is_empty() {
+            return None;
+        }
+        Some((Node::new(CategoryMarker { name }), end + 2))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{extract_article_links, extract_categories, render_article_markdown};
+
+    #[test]
+    fn test_category_marker() {
+        let text = "Text [[Category:Fruit]] more [[Category:Red_things]] [[Category:Fruit]]";
+        assert_eq!(
+            vec!["Fruit".to_string(), "Red things".to_string()],
+            extract_categories(text)
+        );
+        // markers are not rendered or treated as article links
+        assert_eq!(
+            "<p>Text  more  </p>\n",
+            render_article_markdown(text, true, 0, false)
+        );
+        assert!(extract_article_links(text).is_empty());
+
+        // empty or code markers are ignored
+        assert!(extract_categories("[[Category:]] `[[Category:Code]]`").is_empty());
+    }
+}
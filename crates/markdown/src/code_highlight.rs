@@ -0,0 +1,99 @@
+use markdown_it::{Node, NodeValue, Renderer, plugins::cmark::block::fence::CodeFence};
+use std::sync::OnceLock;
+use syntect::{
+    html::{ClassStyle, ClassedHTMLGenerator},
+    parsing::SyntaxSet,
+    util::LinesWithEndings,
+};
+
+/// Prefix for syntax highlighting classes, to avoid collisions with other css classes
+const CLASS_PREFIX: &str = "hl-";
+
+/// Fenced code block with syntax highlighting. Tokens are wrapped in spans with theme classes
+/// based on the syntect scope names, eg `hl-keyword` or `hl-string`.
+#[derive(Debug)]
+struct HighlightedCode {
+    lang: String,
+    html: String,
+}
+
+impl NodeValue for HighlightedCode {
+    fn render(&self, node: &Node, fmt: &mut dyn Renderer) {
+        let mut attrs = node.attrs.clone();
+        attrs.push(("class", format!("language-{}", self.lang)));
+
+        fmt.cr();
+        fmt.open("pre", &[]);
+        fmt.open("code", &attrs);
+        fmt.text_raw(&self.html);
+        fmt.close("code");
+        fmt.close("pre");
+        fmt.cr();
+    }
+}
+
+/// Replace fenced code blocks with a known language by syntax highlighted html. Code blocks
+/// with unknown or missing language are left unchanged, and rendered as plain escaped text.
+pub(crate) fn highlight_code_blocks(node: &mut Node) {
+    node.walk_mut(|node, _| {
+        let Some(fence) = node.cast::<CodeFence>() else {
+            return;
+        };
+        let Some(lang) = fence.info.split_whitespace().next() else {
+            return;
+        };
+        if let Some(html) = highlight(&fence.content, lang) {
+            let lang = lang.to_string();
+            node.replace(HighlightedCode { lang, html });
+        }
+    });
+}
+
+fn highlight(code: &str, lang: &str) -> Option<String> {
+    static SYNTAXES: OnceLock<SyntaxSet> = OnceLock::new();
+    let syntaxes = SYNTAXES.get_or_init(SyntaxSet::load_defaults_newlines);
+    let syntax = syntaxes.find_syntax_by_token(lang)?;
+    let mut generator = ClassedHTMLGenerator::new_with_class_style(
+        syntax,
+        syntaxes,
+        ClassStyle::SpacedPrefixed {
+            prefix: CLASS_PREFIX,
+        },
+    );
+    for line in LinesWithEndings::from(code) {
+        generator
+            .parse_html_for_line_which_includes_newline(line)
+            .ok()?;
+    }
+    Some(generator.finalize())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::render_article_markdown;
+
+    #[test]
+    fn test_markdown_highlight_rust() {
+        let text = "```rust\nfn main() {\n    let x = \"<b>\";\n}\n```";
+        let rendered = render_article_markdown(text, false, 0, true);
+        assert!(rendered.starts_with("<pre><code class=\"language-rust\">"));
+        assert!(rendered.contains("<span class=\"hl-source hl-rust\">"));
+        assert!(rendered.contains("<span class=\"hl-storage"));
+        assert!(rendered.contains("&lt;b&gt;"));
+        assert!(!rendered.contains("<b>"));
+
+        // without highlighting the code is shown as plain text
+        let plain = render_article_markdown(text, false, 0, false);
+        assert!(!plain.contains("<span"));
+    }
+
+    #[test]
+    fn test_markdown_highlight_unknown_language() {
+        let text = "```notalanguage\nlet x = <b>;\n```";
+        let rendered = render_article_markdown(text, false, 0, true);
+        assert_eq!(
+            "<pre><code class=\"language-notalanguage\">let x = &lt;b&gt;;\n</code></pre>\n",
+            rendered
+        );
+    }
+}
@@ -1,4 +1,7 @@
-use article_link::ArticleLinkScanner;
+use article_link::{ArticleLink, ArticleLinkScanner};
+use break_hints::add_break_hints;
+use category::{CategoryMarker, CategoryScanner};
+use code_highlight::highlight_code_blocks;
 use fmtm_ytmimi_markdown_fmt::{Config, OrderedListMarker, UnorderedListMarker};
 use markdown_it::{
     MarkdownIt,
@@ -9,13 +12,38 @@ use std::{borrow::Cow, sync::OnceLock};
 use table_of_contents::{TocMarkerScanner, TocScanner};
 
 mod article_link;
+mod break_hints;
+mod category;
+mod code_highlight;
 mod external_link;
 mod math_equation;
 mod table_of_contents;
 
-pub fn render_article_markdown(text: &str) -> String {
-    static INSTANCE: OnceLock<MarkdownIt> = OnceLock::new();
-    let mut parsed = INSTANCE.get_or_init(article_markdown).parse(text);
+pub use article_link::ArticleLinkTarget;
+
+/// Render article markdown to html. Math equations are only rendered if `math` is enabled,
+/// otherwise they are shown as plain text. Words longer than `break_hint_length` get line break
+/// hints so that they can wrap, zero disables this. Fenced code blocks are syntax highlighted
+/// if `highlight` is enabled.
+pub fn render_article_markdown(
+    text: &str,
+    math: bool,
+    break_hint_length: u32,
+    highlight: bool,
+) -> String {
+    render_article_markdown_with_links(text, math, break_hint_length, highlight, &[])
+}
+
+/// Same as [render_article_markdown], but links to local articles with the given titles are
+/// shown as red links, to indicate that the article doesn't exist yet.
+pub fn render_article_markdown_with_links(
+    text: &str,
+    math: bool,
+    break_hint_length: u32,
+    highlight: bool,
+    missing_links: &[String],
+) -> String {
+    let mut parsed = article_parser(math).parse(text);
 
     // Make markdown headings one level smaller, so that h1 becomes h2 etc, and markdown titles
     // are smaller than page title.
@@ -26,16 +54,67 @@ pub fn render_article_markdown(text: &str) -> String {
         if let Some(heading) = node.cast_mut::<SetextHeader>() {
             heading.level += 1;
         }
+        if let Some(link) = node.cast_mut::<ArticleLink>() {
+            link.missing =
+                link.domain.is_none() && missing_links.contains(&link.title.replace('_', " "));
+        }
     });
+    if break_hint_length > 0 {
+        add_break_hints(&mut parsed, break_hint_length as usize);
+    }
+    if highlight {
+        highlight_code_blocks(&mut parsed);
+    }
     parsed.render()
 }
 
+/// List all distinct article links in the text, in order of first occurrence.
+pub fn extract_article_links(text: &str) -> Vec<ArticleLinkTarget> {
+    let mut links: Vec<ArticleLinkTarget> = vec![];
+    article_parser(false).parse(text).walk(|node, _| {
+        if let Some(link) = node.cast::<ArticleLink>() {
+            let target = ArticleLinkTarget {
+                title: link.title.replace('_', " "),
+                domain: link.domain.clone(),
+            };
+            if !links.contains(&target) {
+                links.push(target);
+            }
+        }
+    });
+    links
+}
+
+/// List all distinct categories from `[[Category:Name]]` markers in the text, in order of first
+/// occurrence.
+pub fn extract_categories(text: &str) -> Vec<String> {
+    let mut categories: Vec<String> = vec![];
+    article_parser(false).parse(text).walk(|node, _| {
+        if let Some(marker) = node.cast::<CategoryMarker>() {
+            if !categories.contains(&marker.name) {
+                categories.push(marker.name.clone());
+            }
+        }
+    });
+    categories
+}
+
+fn article_parser(math: bool) -> &'static MarkdownIt {
+    static INSTANCE: OnceLock<MarkdownIt> = OnceLock::new();
+    static INSTANCE_NO_MATH: OnceLock<MarkdownIt> = OnceLock::new();
+    if math {
+        INSTANCE.get_or_init(|| article_markdown(true))
+    } else {
+        INSTANCE_NO_MATH.get_or_init(|| article_markdown(false))
+    }
+}
+
 pub fn render_comment_markdown(text: &str) -> String {
     static INSTANCE: OnceLock<MarkdownIt> = OnceLock::new();
     INSTANCE.get_or_init(common_markdown).parse(text).render()
 }
 
-fn article_markdown() -> MarkdownIt {
+fn article_markdown(math: bool) -> MarkdownIt {
     let mut parser = common_markdown();
     let p = &mut parser;
     markdown_it::plugins::cmark::inline::image::add(p);
@@ -48,8 +127,11 @@ fn article_markdown() -> MarkdownIt {
     markdown_it_sup::add(p);
 
     // Ibis custom extensions
+    parser.inline.add_rule::<CategoryScanner>();
     parser.inline.add_rule::<ArticleLinkScanner>();
-    parser.inline.add_rule::<MathEquationScanner>();
+    if math {
+        parser.inline.add_rule::<MathEquationScanner>();
+    }
     parser.inline.add_rule::<TocMarkerScanner>();
     parser.add_rule::<TocScanner>();
 
@@ -112,3 +194,75 @@ pub fn format_markdown(text: &str) -> Result<String, std::fmt::Error> {
     };
     fmtm::format_with_config(text, CONFIG)
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        ArticleLinkTarget,
+        extract_article_links,
+        render_article_markdown,
+        render_article_markdown_with_links,
+        render_comment_markdown,
+    };
+
+    #[test]
+    fn test_markdown_escapes_html() {
+        let script = "<script>alert(1)</script>";
+        let expected = "<p>&lt;script&gt;alert(1)&lt;/script&gt;</p>\n";
+        assert_eq!(expected, render_article_markdown(script, true, 0, false));
+        assert_eq!(expected, render_comment_markdown(script));
+
+        let link = render_article_markdown("[[<b>Title</b>]]", true, 0, false);
+        assert!(!link.contains("<b>"));
+    }
+
+    #[test]
+    fn test_extract_article_links() {
+        let text = "See [[Main_Page|home]], [[Rust@example.com]] and [[Main Page]].\n\n\
+            `[[Code]]` is not a link.";
+        let links = extract_article_links(text);
+        assert_eq!(
+            vec![
+                ArticleLinkTarget {
+                    title: "Main Page".to_string(),
+                    domain: None,
+                },
+                ArticleLinkTarget {
+                    title: "Rust".to_string(),
+                    domain: Some("example.com".to_string()),
+                },
+            ],
+            links
+        );
+    }
+
+    #[test]
+    fn test_markdown_missing_link() {
+        let missing = vec!["New Article".to_string()];
+        let rendered = render_article_markdown_with_links(
+            "[[New_Article]] [[Existing]]",
+            true,
+            0,
+            false,
+            &missing,
+        );
+        assert_eq!(
+            "<p><a href=\"/create-article?title=New_Article\" class=\"text-error\" title=\"Article doesn't exist yet\">New_Article</a> <a href=\"/article/Existing\">Existing</a></p>\n",
+            rendered
+        );
+
+        let missing = vec!["Q&A #1?".to_string()];
+        let rendered = render_article_markdown_with_links("[[Q&A #1?]]", true, 0, false, &missing);
+        assert_eq!(
+            "<p><a href=\"/create-article?title=Q%26A_%231%3F\" class=\"text-error\" title=\"Article doesn't exist yet\">Q&amp;A #1?</a></p>\n",
+            rendered
+        );
+    }
+
+    #[test]
+    fn test_markdown_formatting() {
+        let rendered = render_article_markdown("# Heading\n\n- **bold** item", true, 0, false);
+        assert!(rendered.contains("<h2"));
+        assert!(rendered.contains("<li><strong>bold</strong> item</li>"));
+    }
+}
@@ -9,17 +9,22 @@ use markdown_it::{
 struct MathEquation {
     equation: String,
     display_mode: bool,
+    /// Original markdown including delimiters, shown if the equation can't be rendered
+    source: String,
 }
 
 impl NodeValue for MathEquation {
     fn render(&self, _node: &Node, fmt: &mut dyn Renderer) {
         let opts = katex::Opts::builder()
-            .throw_on_error(false)
+            .throw_on_error(true)
             .display_mode(self.display_mode)
             .build()
             .ok();
-        let katex_equation = opts.and_then(|o| katex::render_with_opts(&self.equation, o).ok());
-        fmt.text_raw(katex_equation.as_ref().unwrap_or(&self.equation))
+        match opts.and_then(|o| katex::render_with_opts(&self.equation, o).ok()) {
+            Some(katex_equation) => fmt.text_raw(&katex_equation),
+            // Malformed equation, show it as escaped plain text
+            None => fmt.text(&self.source),
+        }
     }
 }
 
@@ -30,9 +35,18 @@ impl InlineRule for MathEquationScanner {
 
     fn run(state: &mut InlineState) -> Option<(Node, usize)> {
         let input = &state.src[state.pos..state.pos_max];
-        if !input.starts_with("$$") {
-            return None;
+        if input.starts_with("$$") {
+            Self::run_double(state)
+        } else {
+            Self::run_single(state)
         }
+    }
+}
+
+impl MathEquationScanner {
+    /// Equation in the form `$$E=mc^2$$`, or `$$ E=mc^2 $$` for display mode
+    fn run_double(state: &mut InlineState) -> Option<(Node, usize)> {
+        let input = &state.src[state.pos..state.pos_max];
         let mut display_mode = false;
         if input.starts_with("$$\n") || input.starts_with("$$ ") {
             display_mode = true;
@@ -46,13 +60,38 @@ impl InlineRule for MathEquationScanner {
                 return None;
             }
             let content = &state.src[start..i];
+            let consumed = length + SEPARATOR_LENGTH + 1;
             let node = Node::new(MathEquation {
                 equation: content.to_string(),
                 display_mode,
+                source: state.src[state.pos..state.pos + consumed].to_string(),
             });
-            Some((node, length + SEPARATOR_LENGTH + 1))
+            Some((node, consumed))
         })?
     }
+
+    /// Inline equation in the form `$E=mc^2$`. To avoid matching prices like `$5 or $10`, the
+    /// content may not start or end with whitespace, and closing `$` may not be followed by a digit.
+    fn run_single(state: &mut InlineState) -> Option<(Node, usize)> {
+        let input = &state.src[state.pos + 1..state.pos_max];
+        if input.starts_with(char::is_whitespace) {
+            return None;
+        }
+        let end = input.find('$')?;
+        let content = &input[..end];
+        if content.is_empty()
+            || content.ends_with(char::is_whitespace)
+            || input[end + 1..].starts_with(|c: char| c.is_ascii_digit())
+        {
+            return None;
+        }
+        let node = Node::new(MathEquation {
+            equation: content.to_string(),
+            display_mode: false,
+            source: format!("${content}$"),
+        });
+        Some((node, end + 2))
+    }
 }
 
 #[cfg(test)]
@@ -62,8 +101,12 @@ mod test {
     #[test]
     #[expect(clippy::unwrap_used)]
     fn test_markdown_equation_katex() {
-        let rendered =
-            render_article_markdown("here is a math equation: $$E=mc^2$$. Pretty cool, right?");
+        let rendered = render_article_markdown(
+            "here is a math equation: $$E=mc^2$$. Pretty cool, right?",
+            true,
+            0,
+            false,
+        );
         assert_eq!(
             "<p>here is a math equation: ".to_owned()
                 + &katex::render("E=mc^2").unwrap()
@@ -71,4 +114,29 @@ mod test {
             rendered
         );
     }
+
+    #[test]
+    #[expect(clippy::unwrap_used)]
+    fn test_markdown_equation_single_dollar() {
+        let rendered = render_article_markdown("inline $E=mc^2$ equation", true, 0, false);
+        assert_eq!(
+            "<p>inline ".to_owned() + &katex::render("E=mc^2").unwrap() + " equation</p>\n",
+            rendered
+        );
+
+        let prices = render_article_markdown("costs $5 or $10", true, 0, false);
+        assert_eq!("<p>costs $5 or $10</p>\n", prices);
+    }
+
+    #[test]
+    fn test_markdown_equation_malformed() {
+        let rendered = render_article_markdown("broken $\\frac{<b>$ equation", true, 0, false);
+        assert_eq!("<p>broken $\\frac{&lt;b&gt;$ equation</p>\n", rendered);
+    }
+
+    #[test]
+    fn test_markdown_equation_disabled() {
+        let rendered = render_article_markdown("no math $$E=mc^2$$ here", false, 0, false);
+        assert_eq!("<p>no math $$E=mc^2$$ here</p>\n", rendered);
+    }
 }
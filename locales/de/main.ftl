@@ -1,5 +1,6 @@
 main-page = Hauptseite
 explore = Erkunden
+recent-changes = Letzte Änderungen
 about = Über
 create-article = Artikel Erstellen
 admin-settings = Admin Einstellungen
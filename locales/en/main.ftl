@@ -1,5 +1,6 @@
 main-page = Main Page
 explore = Explore
+recent-changes = Recent Changes
 about = About
 create-article = Create Article
 admin-settings = Admin Settings
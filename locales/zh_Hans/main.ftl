@@ -1,5 +1,6 @@
 main-page = 主页
 explore = 探索
+recent-changes = 最近更改
 about = 关于
 create-article = 创建文章
 admin-settings = 管理员设置